@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vs_alloy_calculator::parse;
+
+fuzz_target!(|input: &str| {
+    if let Some(alloy) = parse::alloy(input) {
+        // Anything that parses must round trip through its own game code
+        assert_eq!(Some(alloy), parse::alloy(alloy.game_code()));
+    }
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vs_alloy_calculator::parse;
+
+fuzz_target!(|input: &str| {
+    if let Some(value) = parse::percent(input) {
+        assert!(value.is_finite() && (0.0..=1.0).contains(&value));
+    }
+});
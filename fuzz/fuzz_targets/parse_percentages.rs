@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vs_alloy_calculator::parse;
+use vs_alloy_calculator::prelude::*;
+
+fuzz_target!(|input: &str| {
+    if let Ok(percentages) = parse::percentages(input) {
+        // Parsed input goes straight into alloy creation, which must reject it cleanly rather than panic
+        for alloy in Alloys::ALL {
+            let _ = alloy.try_new(percentages.as_ref(), 1);
+        }
+        let _ = Alloys::infer(percentages.as_ref());
+    }
+});
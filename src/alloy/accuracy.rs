@@ -0,0 +1,190 @@
+//! # Accuracy
+//! Comparing the percentages the rounded nugget amounts actually produce against the requested percentages.
+use super::*;
+
+/// How closely a number of ingots can follow a mix once the nuggets are rounded
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct IngotEfficiency {
+    pub num_ingots: i32,
+    /// Total nuggets of every constituent
+    pub total_nuggets: i32,
+    /// Units the rounded nuggets are off by in total from the exact requested mix
+    pub leftover_units: f32,
+    pub nuggets_per_ingot: f32,
+}
+
+impl<T: AlloyType> AlloyData<T> {
+    /// Gets the percentages the nugget amounts actually produce, which can differ from the requested ones after rounding
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = AlloyData::<TinBronze>::default();
+    ///
+    /// assert_eq!(vec![Copper(0.90), Tin(0.10)], alloy.actual_percentages());
+    /// ```
+    pub fn actual_percentages(&self) -> Vec<BaseMetal<f32>> {
+        actual_percentages(self.nuggets())
+    }
+
+    /// Gets the signed difference of each actual percentage from the requested one
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = AlloyData::<TinBronze>::default();
+    /// let accuracy = alloy.accuracy();
+    ///
+    /// assert_eq!(Metal::Tin, accuracy[1].0);
+    /// assert!((accuracy[1].1 - 0.02).abs() < 1e-6);
+    /// ```
+    pub fn accuracy(&self) -> Vec<(Metal, f32)> {
+        accuracy(self.percentages(), self.nuggets())
+    }
+
+    /// Checks whether every actual percentage is within the tolerance of the requested one
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = AlloyData::<TinBronze>::default();
+    ///
+    /// assert!(!alloy.is_faithful(0.01));
+    /// assert!(alloy.is_faithful(0.025));
+    /// ```
+    pub fn is_faithful(&self, tolerance: f32) -> bool {
+        is_faithful(self.percentages(), self.nuggets(), tolerance)
+    }
+}
+
+impl<T: AlloyType> AlloyData<T> {
+    /// Lists how closely each achievable number of ingots follows the supplied percentages
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let table = AlloyData::<TinBronze>::efficiency_table([Copper(0.92), Tin(0.08)]).expect("should be valid");
+    ///
+    /// assert_eq!(20, table.len());
+    /// assert_eq!(4.0, table[0].leftover_units);
+    /// assert_eq!(0.0, table[4].leftover_units);
+    /// ```
+    pub fn efficiency_table(
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+    ) -> Result<Vec<IngotEfficiency>, AlloyError> {
+        use unit_constants::*;
+        let percentages = T::check_valid_percentages(percentages)?;
+        let percentages = percentages.as_ref();
+        let max_ingots = Self::validated_max_ingots(percentages);
+        (1..=max_ingots)
+            .map(|num_ingots| {
+                let alloy_type = Self::validated_nuggets(percentages, num_ingots, max_ingots)?;
+                let nuggets = alloy_type.nuggets();
+                let total_nuggets = nuggets.iter().map(|n| **n).sum::<i32>();
+                // Both sides are scaled by basis points so the difference is exact
+                let needed_units = ingot_units(num_ingots);
+                let off = percentages
+                    .iter()
+                    .zip(nuggets)
+                    .map(|(p, n)| {
+                        let requested = to_basis_points(**p) * needed_units;
+                        let actual = nugget_units(**n) * BASIS_POINTS;
+                        (requested - actual).abs()
+                    })
+                    .sum::<i64>();
+                Ok(IngotEfficiency {
+                    num_ingots,
+                    total_nuggets,
+                    leftover_units: (off as f64 / BASIS_POINTS as f64) as f32,
+                    nuggets_per_ingot: total_nuggets as f32 / num_ingots as f32,
+                })
+            })
+            .collect()
+    }
+
+    /// Gets the numbers of ingots whose rounded nuggets follow the supplied percentages exactly
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let counts = AlloyData::<TinBronze>::most_efficient_counts([Copper(0.92), Tin(0.08)]).expect("should be valid");
+    ///
+    /// assert_eq!(vec![5, 10, 15, 20], counts);
+    /// ```
+    pub fn most_efficient_counts(
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+    ) -> Result<Vec<i32>, AlloyError> {
+        Ok(Self::efficiency_table(percentages)?
+            .iter()
+            .filter(|e| e.leftover_units == 0.0)
+            .map(|e| e.num_ingots)
+            .collect())
+    }
+
+    /// Gets the smallest number of ingots at which every constituent needs a whole number of nuggets,
+    /// so the nuggets match the supplied percentages exactly, or `None` if no count that fits in the crucible does
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(Ok(Some(5)), AlloyData::<TinBronze>::min_ingots_for_exact([Copper(0.92), Tin(0.08)]));
+    /// assert_eq!(Ok(Some(1)), AlloyData::<TinBronze>::min_ingots_for_exact([Copper(0.9), Tin(0.1)]));
+    /// ```
+    pub fn min_ingots_for_exact(
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+    ) -> Result<Option<i32>, AlloyError> {
+        use unit_constants::*;
+        let percentages = T::check_valid_percentages(percentages)?;
+        let percentages = percentages.as_ref();
+        let basis_points: Vec<i64> = percentages.iter().map(|p| to_basis_points(**p)).collect();
+        // A constituent is exact when its units, scaled by basis points, divide into whole nuggets
+        let nugget = i64::from(NUGGET_UNIT_AMOUNT) * BASIS_POINTS;
+        Ok(
+            (1..=Self::validated_max_ingots(percentages)).find(|&num_ingots| {
+                let needed_units = ingot_units(num_ingots);
+                basis_points
+                    .iter()
+                    .all(|bp| (bp * needed_units) % nugget == 0)
+            }),
+        )
+    }
+}
+
+impl Alloy {
+    /// Gets the percentages the nugget amounts actually produce, which can differ from the requested ones after rounding
+    pub fn actual_percentages(&self) -> Vec<BaseMetal<f32>> {
+        actual_percentages(self.nuggets())
+    }
+
+    /// Gets the signed difference of each actual percentage from the requested one
+    pub fn accuracy(&self) -> Vec<(Metal, f32)> {
+        accuracy(self.percentages(), self.nuggets())
+    }
+
+    /// Checks whether every actual percentage is within the tolerance of the requested one
+    pub fn is_faithful(&self, tolerance: f32) -> bool {
+        is_faithful(self.percentages(), self.nuggets(), tolerance)
+    }
+}
+
+fn actual_percentages(nuggets: &[BaseMetal<i32>]) -> Vec<BaseMetal<f32>> {
+    let total = nuggets.iter().map(|n| **n).sum::<i32>() as f32;
+    nuggets
+        .iter()
+        .map(|n| n.update_inner_value(**n as f32 / total))
+        .collect()
+}
+
+fn accuracy(percentages: &[BaseMetal<f32>], nuggets: &[BaseMetal<i32>]) -> Vec<(Metal, f32)> {
+    actual_percentages(nuggets)
+        .iter()
+        .zip(percentages)
+        .map(|(actual, requested)| (actual.metal(), **actual - **requested))
+        .collect()
+}
+
+fn is_faithful(percentages: &[BaseMetal<f32>], nuggets: &[BaseMetal<i32>], tolerance: f32) -> bool {
+    accuracy(percentages, nuggets)
+        .iter()
+        .all(|(_, deviation)| deviation.abs() <= tolerance)
+}
@@ -0,0 +1,81 @@
+//! # Nugget Adjustments
+//! Reporting the nuggets that are moved between constituents after rounding, which are otherwise applied silently.
+use super::*;
+
+/// Why a nugget was moved between constituents
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AdjustmentReason {
+    /// Rounding left the nuggets short of a whole number of ingots
+    SumCorrection,
+    /// Rounding left the last constituent above its maximum
+    RangeClamp,
+}
+
+/// A single nugget moved to or from a constituent
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Adjustment {
+    pub metal: Metal,
+    /// Nuggets gained, or lost when negative
+    pub change: i32,
+    pub reason: AdjustmentReason,
+}
+
+/// Every correction made to the rounded nuggets, in the order they were applied
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct AdjustmentReport {
+    pub adjustments: Vec<Adjustment>,
+}
+
+impl AdjustmentReport {
+    /// Whether the rounded nuggets were used as is
+    pub fn is_empty(&self) -> bool {
+        self.adjustments.is_empty()
+    }
+}
+
+impl<T: AlloyType> AlloyData<T> {
+    /// Tries to create a new instance of an alloy like [`AlloyData::try_new`], also reporting any nuggets moved between constituents after rounding
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let (alloy, report) = AlloyData::<TinBronze>::try_new_reporting([Copper(0.92), Tin(0.08)], 5).expect("should be valid");
+    ///
+    /// assert_eq!(&[Copper(92), Tin(8)], alloy.nuggets());
+    /// assert!(report.is_empty());
+    /// ```
+    pub fn try_new_reporting(
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+        num_ingots: i32,
+    ) -> Result<(Self, AdjustmentReport), AlloyError> {
+        let alloy = Self::try_new(percentages, num_ingots)?;
+        let report = alloy.adjustments();
+        Ok((alloy, report))
+    }
+
+    /// Gets the nuggets that were moved between constituents after rounding to produce the current nugget amounts
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::{Adjustment, AdjustmentReason};
+    ///
+    /// let alloy = AlloyData::<TinBronze>::try_new([Copper(0.88), Tin(0.12)], 1).expect("should be valid");
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         Adjustment { metal: Metal::Tin, change: -1, reason: AdjustmentReason::RangeClamp },
+    ///         Adjustment { metal: Metal::Copper, change: 1, reason: AdjustmentReason::RangeClamp },
+    ///     ],
+    ///     alloy.adjustments().adjustments
+    /// );
+    /// ```
+    pub fn adjustments(&self) -> AdjustmentReport {
+        Self::nuggets_with_adjustments_for(
+            self.percentages.as_ref(),
+            self.num_ingots,
+            self.max_ingots,
+        )
+        .map(|(_, report)| report)
+        .unwrap_or_default()
+    }
+}
@@ -2,9 +2,23 @@
 //! This module contains the [AlloyType] trait which is used to give shared functionality to the alloy types also located in this module's child modules.
 use super::*;
 
-pub trait AlloyType: private::AlloyType {
+pub trait AlloyType: private::AlloyType + AsRef<[BaseMetal<i32>]> {
+    /// Number of constituents in the alloy, taken from the length of its percentage ranges
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let percentages = [0.0_f32; BismuthBronze::N_CONSTITUENTS];
+    ///
+    /// assert_eq!(3, percentages.len());
+    /// ```
+    const N_CONSTITUENTS: usize = {
+        assert!(Self::RANGES.len() == Self::METALS.len());
+        Self::RANGES.len()
+    };
+
     /// Checks if the supplied percentages are valid (eg. total to 1.0 and are within the ranges) \
-    /// Returns the validated percentages in a reordered boxed slice or an error if invalid
+    /// Returns the validated percentages reordered into the alloy's inline storage or an error if invalid
     /// ### Example
     /// ```rust
     /// use vs_alloy_calculator::prelude::*;
@@ -12,17 +26,13 @@ pub trait AlloyType: private::AlloyType {
     /// let percentages = [Tin(0.08), Copper(0.92)];
     /// assert!(
     ///     TinBronze::check_valid_percentages(percentages)
-    ///         .is_ok_and(|p| p == Box::from([Copper(0.92), Tin(0.08)]))
+    ///         .is_ok_and(|p| p == [Copper(0.92), Tin(0.08)])
     /// );
     /// ```
     fn check_valid_percentages(
         percentages: impl AsRef<[BaseMetal<f32>]>,
-    ) -> Result<Box<[BaseMetal<f32>]>, AlloyError> {
-        if Self::check_percentages_total(&percentages) {
-            Self::check_ranges_contains(&percentages)
-        } else {
-            Err(InvalidPercentages)
-        }
+    ) -> Result<Self::Percentages<f32>, AlloyError> {
+        validated_percentages::<Self>(percentages.as_ref())
     }
 
     /// Checks if the supplied percentages total to 1.0
@@ -37,36 +47,33 @@ pub trait AlloyType: private::AlloyType {
     /// ```
     fn check_percentages_total(percentages: impl AsRef<[BaseMetal<f32>]>) -> bool {
         let sum = percentages.as_ref().iter().map(|p| **p).sum::<f32>();
-        (sum - 1.0).abs() < 0.01
+        (sum - 1.0).abs() < TOTAL_TOLERANCE
     }
 
-    /// Checks if the supplied percentages are within the ranges for the alloy
+    /// Checks if the supplied percentages are within the ranges for the alloy, with each constituent supplied exactly once
     /// ### Example
     /// ```rust
     /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::AlloyError;
     ///
     /// let percentages = [Copper(0.92), Tin(0.08)];
     /// let validation = TinBronze::check_ranges_contains(percentages).is_ok();
     ///
     /// assert!(validation);
+    /// assert_eq!(Err(AlloyError::MissingConstituent(Metal::Tin)), TinBronze::check_ranges_contains([Copper(0.92)]));
+    /// assert_eq!(Err(AlloyError::UnknownConstituent(Metal::Lead)), TinBronze::check_ranges_contains([Copper(0.92), Lead(0.08)]));
     /// ```
     fn check_ranges_contains(
         percentages: impl AsRef<[BaseMetal<f32>]>,
-    ) -> Result<Box<[BaseMetal<f32>]>, AlloyError> {
-        let percentages = percentages.as_ref();
-        match percentages.len() {
-            l if l == 2 || l == 3 => {
-                let perc_1 = percentages[0];
-                let perc_2 = percentages[1];
-                let percentages = if let Some(perc_3) = percentages.get(2) {
-                    Self::check_own_ranges_contains(&[perc_1, perc_2, *perc_3])?
-                } else {
-                    Self::check_own_ranges_contains(&[perc_1, perc_2])?
-                };
-                Ok(percentages)
-            }
-            _ => Err(InvalidPercentages),
-        }
+    ) -> Result<Self::Percentages<f32>, AlloyError> {
+        let mut reordered = Self::percentages_from_fn(|_| 0.0);
+        reorder_constituents_into(
+            percentages.as_ref(),
+            Self::METALS,
+            |i, p| Self::RANGES[i].contains(p),
+            reordered.as_mut(),
+        )?;
+        Ok(reordered)
     }
 
     /// Returns the name of the alloy as a string
@@ -96,6 +103,66 @@ pub trait AlloyType: private::AlloyType {
         Self::RANGES
     }
 
+    /// Returns the allowed percentage range of the supplied metal or `None` if the alloy does not use it
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::ConstituentRange;
+    ///
+    /// assert_eq!(Some(ConstituentRange::new(0.08, 0.12)), TinBronze::range_of(Metal::Tin));
+    /// assert_eq!(None, TinBronze::range_of(Metal::Zinc));
+    /// ```
+    fn range_of(metal: Metal) -> Option<Range> {
+        let index = Self::METALS.iter().position(|m| *m == metal)?;
+        Some(*Self::RANGES[index])
+    }
+
+    /// Returns the metals of the constituents in the same order as [`percentage_ranges`](AlloyType::percentage_ranges)
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(&[Metal::Copper, Metal::Tin], TinBronze::constituent_metals());
+    /// ```
+    fn constituent_metals() -> &'static [Metal] {
+        Self::METALS
+    }
+
+    /// Returns the number of constituents in the alloy, the same as [`N_CONSTITUENTS`](AlloyType::N_CONSTITUENTS)
+    /// and [`Alloys::num_constituents`] at runtime
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(3, BismuthBronze::constituent_count());
+    /// assert_eq!(Alloys::BismuthBronze.num_constituents(), BismuthBronze::constituent_count());
+    /// ```
+    fn constituent_count() -> usize {
+        Self::N_CONSTITUENTS
+    }
+
+    /// Returns the percentages the alloy defaults to
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(&[Copper(0.92), Tin(0.08)], TinBronze::default_percentages());
+    /// ```
+    fn default_percentages() -> &'static [BaseMetal<f32>] {
+        Self::DEFAULT_PERCENTAGES
+    }
+
+    /// Returns the number of ingots the alloy defaults to
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(1, TinBronze::default_ingots());
+    /// ```
+    fn default_ingots() -> i32 {
+        Self::DEFAULT_INGOTS
+    }
+
     /// Returns the array of constituent nugget amounts
     /// ### Example
     /// ```rust
@@ -109,31 +176,183 @@ pub trait AlloyType: private::AlloyType {
     fn nuggets(&self) -> &[BaseMetal<i32>];
 }
 
-mod private {
+/// Validates and reorders supplied constituents into the canonical order of `metals`, checking each value with `in_range`. \
+/// More constituents than metals gives [`WrongConstituentCount`]. Otherwise constituents are checked in the order supplied,
+/// so the first problem found decides the error: an unknown metal gives [`UnknownConstituent`], a repeated one gives
+/// [`DuplicateConstituent`], and a value outside its range gives [`InvalidPercentages`].
+/// The first metal that is never supplied then gives [`MissingConstituent`].
+pub(crate) fn reorder_constituents<V: Clone>(
+    constituents: &[BaseMetal<V>],
+    metals: &[Metal],
+    in_range: impl Fn(usize, &BaseMetal<V>) -> bool,
+) -> Result<Box<[BaseMetal<V>]>, AlloyError> {
+    // On success there is exactly one constituent per metal, so every slot is overwritten
+    let mut reorder = constituents.to_vec();
+    reorder_constituents_into(constituents, metals, in_range, &mut reorder)?;
+    Ok(reorder.into())
+}
+
+/// Validates constituents like [`reorder_constituents`] but writes them into the canonical slots of `out` instead of allocating. \
+/// `out` is only fully overwritten on success.
+pub(crate) fn reorder_constituents_into<V: Clone>(
+    constituents: &[BaseMetal<V>],
+    metals: &[Metal],
+    in_range: impl Fn(usize, &BaseMetal<V>) -> bool,
+    out: &mut [BaseMetal<V>],
+) -> Result<(), AlloyError> {
+    if constituents.len() > metals.len() {
+        return Err(WrongConstituentCount {
+            expected: metals.len(),
+            got: constituents.len(),
+        });
+    }
+    for (i, constituent) in constituents.iter().enumerate() {
+        let metal = constituent.metal();
+        let index = metals
+            .iter()
+            .position(|m| *m == metal)
+            .ok_or(UnknownConstituent(metal))?;
+        if constituents[..i].iter().any(|c| c.metal() == metal) {
+            return Err(DuplicateConstituent(metal));
+        } else if !in_range(index, constituent) {
+            return Err(InvalidPercentages);
+        }
+        if let Some(slot) = out.get_mut(index) {
+            *slot = constituent.clone();
+        }
+    }
+    match metals
+        .iter()
+        .find(|metal| !constituents.iter().any(|c| c.metal() == **metal))
+    {
+        Some(metal) => Err(MissingConstituent(*metal)),
+        None => Ok(()),
+    }
+}
+
+/// Checks percentages like [`AlloyType::check_valid_percentages`] but returns them in the alloy's inline storage without allocating
+pub(crate) fn validated_percentages<T: AlloyType>(
+    percentages: &[BaseMetal<f32>],
+) -> Result<T::Percentages<f32>, AlloyError> {
+    if T::check_percentages_total(percentages) {
+        T::check_ranges_contains(percentages)
+    } else {
+        Err(InvalidPercentages)
+    }
+}
+
+/// Finds the corners of the valid region for the ranges in basis points, from the most of the first constituent to the least
+pub(crate) fn boundary_points(ranges: &[BaseMetal<Range>]) -> Vec<Vec<i64>> {
+    let bounds: Vec<(i64, i64)> = ranges
+        .iter()
+        .map(|r| (to_basis_points(r.min), to_basis_points(r.max)))
+        .collect();
+    let mut corners: Vec<Vec<i64>> = Vec::new();
+    for free in 0..bounds.len() {
+        for pins in 0..1u32 << (bounds.len() - 1) {
+            let mut values = vec![0; bounds.len()];
+            for (bit, i) in (0..bounds.len()).filter(|&i| i != free).enumerate() {
+                values[i] = if pins >> bit & 1 == 0 {
+                    bounds[i].0
+                } else {
+                    bounds[i].1
+                };
+            }
+            let rest = BASIS_POINTS - values.iter().sum::<i64>();
+            if (bounds[free].0..=bounds[free].1).contains(&rest) {
+                values[free] = rest;
+                if !corners.contains(&values) {
+                    corners.push(values);
+                }
+            }
+        }
+    }
+    corners.sort_unstable_by(|a, b| b.cmp(a));
+    corners
+}
+
+/// Counts the mixes of whole percents that fall within the ranges and add up to 100%
+pub(crate) fn count_whole_percent_mixes(ranges: &[BaseMetal<Range>]) -> usize {
+    let percent = BASIS_POINTS / 100;
+    let bounds: Vec<(usize, usize)> = ranges
+        .iter()
+        .map(|r| {
+            let (min, max) = (to_basis_points(r.min), to_basis_points(r.max));
+            let whole = |bp: i64| usize::try_from(bp / percent).unwrap_or(0).min(100);
+            (whole(min + percent - 1), whole(max))
+        })
+        .collect();
+    let Some(((last_min, last_max), rest)) = bounds.split_last() else {
+        return 0;
+    };
+    // Number of ways to pick the leading constituents, indexed by the whole percents they use up
+    let mut ways = [0usize; 101];
+    ways[0] = 1;
+    for (min, max) in rest {
+        let mut next = [0usize; 101];
+        for used in 0..=100 {
+            for p in *min..=(*max).min(100 - used) {
+                next[used + p] += ways[used];
+            }
+        }
+        ways = next;
+    }
+    (100 - last_max..=100 - last_min)
+        .map(|used| ways[used])
+        .sum()
+}
+
+/// The constants behind an [`AlloyType`], implemented by [`define_alloy!`](crate::define_alloy) \
+/// Public as [`AlloyTypeDefinition`] with the `unstable-custom-alloys` feature so other crates can declare their own alloys.
+pub(crate) mod private {
     use super::*;
     pub trait AlloyType: Sized {
+        /// Name of the alloy
         const NAME: &str;
+        /// Percentage range of each constituent in canonical order, which is also the order of the nuggets and percentages. \
+        /// [`validate_definition`](crate::definition::validate_definition) checks these against [`DEFAULT_PERCENTAGES`](Self::DEFAULT_PERCENTAGES).
         const RANGES: &[BaseMetal<Range>];
+        /// Must list the metals of [`RANGES`](Self::RANGES) in the same order
+        const METALS: &[Metal];
+        /// Percentages used by the [`Default`] implementation, in the same order as [`RANGES`](Self::RANGES)
+        const DEFAULT_PERCENTAGES: &[BaseMetal<f32>];
+        /// Number of ingots used by the [`Default`] implementation, which the nuggets from the type's own [`Default`] must match
+        const DEFAULT_INGOTS: i32 = 1;
 
-        fn check_base_metal(value: &f32, index: usize, seen: bool) -> Result<(), AlloyError> {
-            if seen {
-                Err(InvalidBaseMetals)
-            } else if !Self::RANGES[index].contains(value) {
-                Err(InvalidPercentages)
-            } else {
-                Ok(())
-            }
-        }
+        /// Inline storage for one percentage per constituent at either precision, an array as long as [`RANGES`](Self::RANGES)
+        type Percentages<F: Copy + std::fmt::Debug>: Copy
+            + std::fmt::Debug
+            + AsRef<[BaseMetal<F>]>
+            + AsMut<[BaseMetal<F>]>;
+
+        /// Builds inline percentages for the metals of [`METALS`](Self::METALS) from the value for each index
+        fn percentages_from_fn<F: Copy + std::fmt::Debug>(
+            value: impl FnMut(usize) -> F,
+        ) -> Self::Percentages<F>;
 
-        fn try_from_vec(value: Vec<i32>) -> Result<Self, AlloyError>;
-        fn check_own_ranges_contains(
-            percentages: &[BaseMetal<f32>],
-        ) -> Result<Box<[BaseMetal<f32>]>, AlloyError>;
+        /// Builds the nuggets from amounts in the same order as [`RANGES`](Self::RANGES), or returns [`InvalidConstituentAmounts`](AlloyError::InvalidConstituentAmounts) if there are too few
+        fn try_from_amounts(value: &[i32]) -> Result<Self, AlloyError>;
+
+        /// Checks the definition with [`validate_definition`](crate::definition::validate_definition) before alloy data is created from it. \
+        /// [`define_alloy!`](crate::define_alloy) caches the result in a static so the check only runs the first time the alloy is used,
+        /// while this default checks every time.
+        fn checked_definition() -> Result<(), crate::definition::AlloyDefinitionError> {
+            crate::definition::validate_definition(Self::RANGES, Self::DEFAULT_PERCENTAGES)
+        }
     }
 }
 
+/// Implementing this along with [`AlloyType`] adds an alloy from outside the crate, usually through [`define_alloy!`](crate::define_alloy). \
+/// Unstable: requires the `unstable-custom-alloys` feature, and the items may change in any release.
+#[cfg(feature = "unstable-custom-alloys")]
+pub use private::AlloyType as AlloyTypeDefinition;
+#[cfg(not(feature = "unstable-custom-alloys"))]
+pub(crate) use private::AlloyType as AlloyTypeDefinition;
+
 /// Enum of the available alloys
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum Alloys {
     TinBronze,
     BismuthBronze,
@@ -146,7 +365,41 @@ pub enum Alloys {
     Cupronickel,
 }
 
+impl std::fmt::Display for Alloys {
+    /// Writes the name of the alloy
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 impl Alloys {
+    /// All of the available alloys in the same order as the variants are declared. \
+    /// New alloys are added to the end, so the order of existing alloys is stable.
+    pub const ALL: [Alloys; 9] = [
+        Alloys::TinBronze,
+        Alloys::BismuthBronze,
+        Alloys::BlackBronze,
+        Alloys::Brass,
+        Alloys::Molybdochalkos,
+        Alloys::LeadSolder,
+        Alloys::SilverSolder,
+        Alloys::Electrum,
+        Alloys::Cupronickel,
+    ];
+
+    /// Iterates over every available alloy in the same order as [`ALL`](Alloys::ALL), which is the order the variants are declared
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let names: Vec<&str> = Alloys::iter().map(|a| a.name()).take(3).collect();
+    ///
+    /// assert_eq!(vec!["Tin Bronze", "Bismuth Bronze", "Black Bronze"], names);
+    /// ```
+    pub fn iter() -> impl Iterator<Item = Alloys> {
+        Self::ALL.into_iter()
+    }
+
     /// Tries to create a new instance of an alloy. Checks if the input values are valid and tries to calculate valid values for the given alloy.
     /// ### Example
     /// ```rust
@@ -162,8 +415,9 @@ impl Alloys {
     pub fn try_new(
         &self,
         percentages: impl AsRef<[BaseMetal<f32>]>,
-        num_ingots: i32,
+        num_ingots: impl TryInto<IngotCount, Error: Into<AlloyError>>,
     ) -> Result<Alloy, AlloyError> {
+        let num_ingots: IngotCount = num_ingots.try_into().map_err(Into::into)?;
         Ok(match self {
             Alloys::TinBronze => {
                 Alloy::TinBronze(AlloyData::<TinBronze>::try_new(percentages, num_ingots)?)
@@ -195,6 +449,57 @@ impl Alloys {
         })
     }
 
+    /// Calculates the nuggets needed for the alloy without creating it, the same as [`AlloyData::nuggets_for`]. \
+    /// The alloy is picked at runtime, so the nuggets are returned inline in an [`AlloyNuggets`] that derefs to a slice.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let nuggets = Alloys::TinBronze.nuggets_for([Copper(0.92), Tin(0.08)], 7).expect("should be valid");
+    ///
+    /// assert_eq!(&[Copper(128), Tin(12)], &*nuggets);
+    /// ```
+    pub fn nuggets_for(
+        &self,
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+        num_ingots: impl TryInto<IngotCount, Error: Into<AlloyError>>,
+    ) -> Result<AlloyNuggets, AlloyError> {
+        match self {
+            Alloys::TinBronze => AlloyData::<TinBronze>::nuggets_for(percentages, num_ingots)
+                .map(|n| AlloyNuggets::new(n.nuggets())),
+            Alloys::BismuthBronze => {
+                AlloyData::<BismuthBronze>::nuggets_for(percentages, num_ingots)
+                    .map(|n| AlloyNuggets::new(n.nuggets()))
+            }
+            Alloys::BlackBronze => AlloyData::<BlackBronze>::nuggets_for(percentages, num_ingots)
+                .map(|n| AlloyNuggets::new(n.nuggets())),
+            Alloys::Brass => AlloyData::<Brass>::nuggets_for(percentages, num_ingots)
+                .map(|n| AlloyNuggets::new(n.nuggets())),
+            Alloys::Molybdochalkos => {
+                AlloyData::<Molybdochalkos>::nuggets_for(percentages, num_ingots)
+                    .map(|n| AlloyNuggets::new(n.nuggets()))
+            }
+            Alloys::LeadSolder => AlloyData::<LeadSolder>::nuggets_for(percentages, num_ingots)
+                .map(|n| AlloyNuggets::new(n.nuggets())),
+            Alloys::SilverSolder => AlloyData::<SilverSolder>::nuggets_for(percentages, num_ingots)
+                .map(|n| AlloyNuggets::new(n.nuggets())),
+            Alloys::Electrum => AlloyData::<Electrum>::nuggets_for(percentages, num_ingots)
+                .map(|n| AlloyNuggets::new(n.nuggets())),
+            Alloys::Cupronickel => AlloyData::<Cupronickel>::nuggets_for(percentages, num_ingots)
+                .map(|n| AlloyNuggets::new(n.nuggets())),
+        }
+    }
+
+    /// Creates the alloy with its default percentages and number of ingots, wrapped in the matching [`Alloy`] variant
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = Alloys::Brass.get_default();
+    ///
+    /// assert_eq!("Brass", alloy.name());
+    /// assert_eq!(Alloy::Brass(AlloyData::<Brass>::default()), alloy);
+    /// ```
     pub fn get_default(&self) -> Alloy {
         match self {
             Alloys::TinBronze => Alloy::TinBronze(AlloyData::<TinBronze>::default()),
@@ -209,6 +514,14 @@ impl Alloys {
         }
     }
 
+    /// Returns the name of the alloy as a string without creating it, the same as [`AlloyType::name`](crate::AlloyType::name) and [`alloy_names`](crate::alloy_names)
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!("Bismuth Bronze", Alloys::BismuthBronze.name());
+    /// assert_eq!("Lead Solder", format!("{}", Alloys::LeadSolder));
+    /// ```
     pub fn name(&self) -> &'static str {
         use private::AlloyType;
         match self {
@@ -224,6 +537,19 @@ impl Alloys {
         }
     }
 
+    /// Returns the constituent percentage ranges of the alloy chosen at runtime, the same as [`AlloyData::percentage_ranges`]
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let ranges: Vec<String> = Alloys::BismuthBronze
+    ///     .percentage_ranges()
+    ///     .iter()
+    ///     .map(|r| format!("{} {:.0}-{:.0}%", r.name(), r.min * 100.0, r.max * 100.0))
+    ///     .collect();
+    ///
+    /// assert_eq!("Copper 50-70%, Zinc 20-30%, Bismuth 10-20%", ranges.join(", "));
+    /// ```
     pub fn percentage_ranges(&self) -> &'static [BaseMetal<Range>] {
         use private::AlloyType;
         match self {
@@ -238,42 +564,312 @@ impl Alloys {
             Alloys::Cupronickel => Cupronickel::RANGES,
         }
     }
-}
 
-impl<T: AlloyType> From<&AlloyData<T>> for Alloys {
-    fn from(_: &AlloyData<T>) -> Self {
+    /// Returns the allowed percentage range of the supplied metal or `None` if the alloy does not use it
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::ConstituentRange;
+    ///
+    /// assert_eq!(Some(ConstituentRange::new(0.40, 0.50)), Alloys::SilverSolder.range_of(Metal::Silver));
+    /// assert_eq!(None, Alloys::SilverSolder.range_of(Metal::Gold));
+    /// ```
+    pub fn range_of(&self, metal: Metal) -> Option<Range> {
+        let index = self.constituent_metals().iter().position(|m| *m == metal)?;
+        Some(*self.percentage_ranges()[index])
+    }
+
+    /// Returns the percentages the alloy defaults to without constructing it
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(&[Gold(0.40), Silver(0.60)], Alloys::Electrum.default_percentages());
+    /// ```
+    pub fn default_percentages(&self) -> &'static [BaseMetal<f32>] {
         use private::AlloyType;
-        match T::NAME {
-            TinBronze::NAME => Self::TinBronze,
-            BismuthBronze::NAME => Self::BismuthBronze,
-            BlackBronze::NAME => Self::BlackBronze,
-            Brass::NAME => Self::Brass,
-            Molybdochalkos::NAME => Self::Molybdochalkos,
-            LeadSolder::NAME => Self::LeadSolder,
-            SilverSolder::NAME => Self::SilverSolder,
-            Electrum::NAME => Self::Electrum,
-            _ => Self::Cupronickel,
+        match self {
+            Alloys::TinBronze => TinBronze::DEFAULT_PERCENTAGES,
+            Alloys::BismuthBronze => BismuthBronze::DEFAULT_PERCENTAGES,
+            Alloys::BlackBronze => BlackBronze::DEFAULT_PERCENTAGES,
+            Alloys::Brass => Brass::DEFAULT_PERCENTAGES,
+            Alloys::Molybdochalkos => Molybdochalkos::DEFAULT_PERCENTAGES,
+            Alloys::LeadSolder => LeadSolder::DEFAULT_PERCENTAGES,
+            Alloys::SilverSolder => SilverSolder::DEFAULT_PERCENTAGES,
+            Alloys::Electrum => Electrum::DEFAULT_PERCENTAGES,
+            Alloys::Cupronickel => Cupronickel::DEFAULT_PERCENTAGES,
         }
     }
-}
-impl<T: AlloyType> From<&T> for Alloys {
-    fn from(_: &T) -> Self {
+
+    /// Lists the corner mixes of the alloy, where every constituent but one is pinned to its min or max and the remaining one,
+    /// still within its own range, makes up the rest of the total. \
+    /// For two-metal alloys these are the two ends of the range. For three-metal alloys they are the vertices of the valid region,
+    /// so any mix with all three metals at a bound is included along with the points where one metal is forced off its bounds.
+    /// Every mix is in canonical order and passes validation, and mixes are listed from the most of the first constituent to the least.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let corners = Alloys::TinBronze.boundary_mixes();
+    ///
+    /// assert_eq!(vec![Box::from([Copper(0.92), Tin(0.08)]), Box::from([Copper(0.88), Tin(0.12)])], corners);
+    /// ```
+    pub fn boundary_mixes(&self) -> Vec<Box<[BaseMetal<f32>]>> {
+        let ranges = self.percentage_ranges();
+        boundary_points(ranges)
+            .iter()
+            .map(|values| {
+                ranges
+                    .iter()
+                    .zip(values)
+                    .map(|(r, bp)| r.update_inner_value(*bp as f32 / BASIS_POINTS as f32))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the number of ingots the alloy defaults to without constructing it
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(1, Alloys::Electrum.default_ingots());
+    /// ```
+    pub fn default_ingots(&self) -> i32 {
         use private::AlloyType;
-        match T::NAME {
-            TinBronze::NAME => Self::TinBronze,
-            BismuthBronze::NAME => Self::BismuthBronze,
-            BlackBronze::NAME => Self::BlackBronze,
-            Brass::NAME => Self::Brass,
-            Molybdochalkos::NAME => Self::Molybdochalkos,
-            LeadSolder::NAME => Self::LeadSolder,
-            SilverSolder::NAME => Self::SilverSolder,
-            Electrum::NAME => Self::Electrum,
-            _ => Self::Cupronickel,
+        match self {
+            Alloys::TinBronze => TinBronze::DEFAULT_INGOTS,
+            Alloys::BismuthBronze => BismuthBronze::DEFAULT_INGOTS,
+            Alloys::BlackBronze => BlackBronze::DEFAULT_INGOTS,
+            Alloys::Brass => Brass::DEFAULT_INGOTS,
+            Alloys::Molybdochalkos => Molybdochalkos::DEFAULT_INGOTS,
+            Alloys::LeadSolder => LeadSolder::DEFAULT_INGOTS,
+            Alloys::SilverSolder => SilverSolder::DEFAULT_INGOTS,
+            Alloys::Electrum => Electrum::DEFAULT_INGOTS,
+            Alloys::Cupronickel => Cupronickel::DEFAULT_INGOTS,
+        }
+    }
+
+    /// Returns the number of constituents in the alloy
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(2, Alloys::TinBronze.num_constituents());
+    /// assert_eq!(3, Alloys::BlackBronze.num_constituents());
+    /// ```
+    pub fn num_constituents(&self) -> usize {
+        self.percentage_ranges().len()
+    }
+
+    /// Returns whether the alloy is made of exactly two constituents
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert!(Alloys::Electrum.is_binary());
+    /// assert!(!Alloys::BismuthBronze.is_binary());
+    /// ```
+    pub fn is_binary(&self) -> bool {
+        self.num_constituents() == 2
+    }
+
+    /// Returns whether the alloy is made of exactly three constituents
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert!(Alloys::BismuthBronze.is_ternary());
+    /// assert!(!Alloys::Brass.is_ternary());
+    /// ```
+    pub fn is_ternary(&self) -> bool {
+        self.num_constituents() == 3
+    }
+
+    /// Returns the metals of the constituents in the same order as [`percentage_ranges`](Alloys::percentage_ranges)
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(&[Metal::Lead, Metal::Copper], Alloys::Molybdochalkos.constituent_metals());
+    /// ```
+    pub fn constituent_metals(&self) -> &'static [Metal] {
+        use private::AlloyType;
+        match self {
+            Alloys::TinBronze => TinBronze::METALS,
+            Alloys::BismuthBronze => BismuthBronze::METALS,
+            Alloys::BlackBronze => BlackBronze::METALS,
+            Alloys::Brass => Brass::METALS,
+            Alloys::Molybdochalkos => Molybdochalkos::METALS,
+            Alloys::LeadSolder => LeadSolder::METALS,
+            Alloys::SilverSolder => SilverSolder::METALS,
+            Alloys::Electrum => Electrum::METALS,
+            Alloys::Cupronickel => Cupronickel::METALS,
+        }
+    }
+}
+
+/// Largest number of constituents among the built-in alloys
+const MAX_BUILT_IN_CONSTITUENTS: usize = {
+    let counts = [
+        TinBronze::N_CONSTITUENTS,
+        BismuthBronze::N_CONSTITUENTS,
+        BlackBronze::N_CONSTITUENTS,
+        Brass::N_CONSTITUENTS,
+        Molybdochalkos::N_CONSTITUENTS,
+        LeadSolder::N_CONSTITUENTS,
+        SilverSolder::N_CONSTITUENTS,
+        Electrum::N_CONSTITUENTS,
+        Cupronickel::N_CONSTITUENTS,
+    ];
+    let mut max = 0;
+    let mut i = 0;
+    while i < counts.len() {
+        if counts[i] > max {
+            max = counts[i];
+        }
+        i += 1;
+    }
+    max
+};
+
+/// Nuggets of an alloy picked at runtime, returned by [`Alloys::nuggets_for`]. \
+/// Held inline in an array as long as the largest built-in alloy along with the number of constituents used,
+/// and derefs to a slice of just those constituents.
+/// ### Example
+/// ```rust
+/// use vs_alloy_calculator::prelude::*;
+///
+/// let kind = Alloys::BismuthBronze;
+/// let nuggets = kind.nuggets_for(kind.default_percentages(), 1).expect("should be valid");
+///
+/// assert_eq!(3, nuggets.len());
+/// assert_eq!(20, nuggets.iter().map(|n| **n).sum::<i32>());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct AlloyNuggets {
+    nuggets: [BaseMetal<i32>; MAX_BUILT_IN_CONSTITUENTS],
+    len: usize,
+}
+
+impl AlloyNuggets {
+    /// Copies the nuggets into inline storage, the built-in alloys never have more than fit
+    fn new(nuggets: &[BaseMetal<i32>]) -> Self {
+        let mut inline = [Copper(0); MAX_BUILT_IN_CONSTITUENTS];
+        for (slot, nugget) in inline.iter_mut().zip(nuggets) {
+            *slot = *nugget;
+        }
+        Self {
+            nuggets: inline,
+            len: nuggets.len().min(MAX_BUILT_IN_CONSTITUENTS),
         }
     }
 }
 
+impl std::ops::Deref for AlloyNuggets {
+    type Target = [BaseMetal<i32>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.nuggets[..self.len]
+    }
+}
+
+impl AsRef<[BaseMetal<i32>]> for AlloyNuggets {
+    fn as_ref(&self) -> &[BaseMetal<i32>] {
+        self
+    }
+}
+
 // Modules
+/// Declares an alloy type, generating the struct that holds its nuggets along with its [`Default`], [`AsRef`], and [`AlloyType`] implementations. \
+/// Takes the name of the alloy followed by one line per constituent in canonical order, giving the metal, its percentage range,
+/// its default percentage, and its share of the nuggets for the default single ingot.
+/// Other crates can use it with the `unstable-custom-alloys` feature.
+/// The nuggets must be the ones calculated for the default percentages, which [`validate_alloy_type`](crate::definition::validate_alloy_type) checks.
+/// ### Example
+/// ```rust
+/// # #[cfg(feature = "unstable-custom-alloys")]
+/// # {
+/// use vs_alloy_calculator::define_alloy;
+/// use vs_alloy_calculator::prelude::*;
+///
+/// define_alloy! {
+///     pub struct RoseGold = "Rose Gold";
+///     Gold: 0.70..=0.80, default 0.75 with 15 nuggets;
+///     Copper: 0.20..=0.30, default 0.25 with 5 nuggets;
+/// }
+///
+/// let alloy = AlloyData::<RoseGold>::try_new([Copper(0.30), Gold(0.70)], 2).expect("should be valid");
+///
+/// assert_eq!("Rose Gold", RoseGold::name());
+/// assert_eq!(&[Gold(28), Copper(12)], alloy.nuggets());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! define_alloy {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $alloy:ident = $name:expr;
+        $($metal:ident: $min:literal..=$max:literal, default $percentage:literal with $nuggets:literal nuggets;)+
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+        $vis struct $alloy([$crate::BaseMetal<i32>; [$(stringify!($metal)),+].len()]);
+
+        impl Default for $alloy {
+            fn default() -> Self {
+                Self([$($crate::BaseMetal::$metal($nuggets)),+])
+            }
+        }
+
+        impl $crate::AlloyType for $alloy {
+            fn nuggets(&self) -> &[$crate::BaseMetal<i32>] {
+                &self.0
+            }
+        }
+
+        impl AsRef<[$crate::BaseMetal<i32>]> for $alloy {
+            fn as_ref(&self) -> &[$crate::BaseMetal<i32>] {
+                &self.0
+            }
+        }
+
+        impl $crate::alloy_types::AlloyTypeDefinition for $alloy {
+            const NAME: &str = $name;
+            const RANGES: &[$crate::BaseMetal<$crate::ConstituentRange<f32>>] =
+                &[$($crate::BaseMetal::$metal($crate::ConstituentRange::new($min, $max))),+];
+            const METALS: &[$crate::Metal] = &[$($crate::Metal::$metal),+];
+            const DEFAULT_PERCENTAGES: &[$crate::BaseMetal<f32>] =
+                &[$($crate::BaseMetal::$metal($percentage)),+];
+
+            type Percentages<F: Copy + ::core::fmt::Debug> =
+                [$crate::BaseMetal<F>; [$(stringify!($metal)),+].len()];
+
+            fn percentages_from_fn<F: Copy + ::core::fmt::Debug>(
+                mut value: impl FnMut(usize) -> F,
+            ) -> Self::Percentages<F> {
+                ::core::array::from_fn(|i| Self::METALS[i].with_value(value(i)))
+            }
+
+            fn try_from_amounts(value: &[i32]) -> Result<Self, $crate::AlloyError> {
+                let mut value = value.iter().copied();
+                Ok(Self([$($crate::BaseMetal::$metal(
+                    value.next().ok_or($crate::AlloyError::InvalidConstituentAmounts)?,
+                )),+]))
+            }
+
+            fn checked_definition() -> Result<(), $crate::definition::AlloyDefinitionError> {
+                static CHECKED: ::std::sync::OnceLock<
+                    Result<(), $crate::definition::AlloyDefinitionError>,
+                > = ::std::sync::OnceLock::new();
+                *CHECKED.get_or_init(|| {
+                    $crate::definition::validate_definition(Self::RANGES, Self::DEFAULT_PERCENTAGES)
+                })
+            }
+        }
+    };
+}
+
 mod bismuth_bronze;
 mod black_bronze;
 mod brass;
@@ -0,0 +1,118 @@
+//! # Builder
+//! Step by step construction of an alloy, starting from its defaults and overriding only what is supplied.
+use super::*;
+
+/// Builder for [`AlloyData`], created with [`AlloyData::builder`]
+///
+/// Anything left unset falls back to the alloy's defaults when the alloy is built:
+/// the default number of ingots, and the default percentages for any metal without its own.
+#[derive(Clone, PartialEq, Debug)]
+pub struct AlloyBuilder<T: AlloyType> {
+    percentages: Vec<BaseMetal<f32>>,
+    num_ingots: Option<i32>,
+    alloy_type: std::marker::PhantomData<T>,
+}
+
+impl<T: AlloyType> AlloyData<T> {
+    /// Starts building an alloy from its defaults
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = AlloyData::<TinBronze>::builder()
+    ///     .default_ratio()
+    ///     .ingots(12)
+    ///     .build()
+    ///     .expect("should be valid");
+    ///
+    /// assert_eq!(&[Copper(0.92), Tin(0.08)], alloy.percentages());
+    /// assert_eq!(12, alloy.num_ingots());
+    /// ```
+    pub fn builder() -> AlloyBuilder<T> {
+        AlloyBuilder {
+            percentages: Vec::new(),
+            num_ingots: None,
+            alloy_type: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: AlloyType> AlloyBuilder<T> {
+    /// Sets the percentage of a single metal, replacing any earlier value for it
+    pub fn percentage(mut self, metal: Metal, value: f32) -> Self {
+        self.percentages.retain(|p| p.metal() != metal);
+        self.percentages.push(metal.with_value(value));
+        self
+    }
+
+    /// Sets the percentages of several metals, the same as calling [`percentage`](Self::percentage) for each
+    pub fn percentages(self, percentages: impl IntoIterator<Item = BaseMetal<f32>>) -> Self {
+        percentages
+            .into_iter()
+            .fold(self, |builder, p| builder.percentage(p.metal(), *p))
+    }
+
+    /// Sets the number of ingots to make
+    pub fn ingots(mut self, num_ingots: i32) -> Self {
+        self.num_ingots = Some(num_ingots);
+        self
+    }
+
+    /// Clears any percentages set so far, going back to the alloy's default ratio
+    pub fn default_ratio(mut self) -> Self {
+        self.percentages.clear();
+        self
+    }
+
+    /// Tries to create the alloy, validating it the same as [`AlloyData::try_new`]. \
+    /// When only some metals have percentages, the rest share what remains in proportion to their defaults, each staying within its range.
+    ///
+    /// Returns [`UnknownConstituent`](AlloyError::UnknownConstituent) for a metal that is not part of the alloy,
+    /// and [`InvalidPercentages`](AlloyError::InvalidPercentages) when the remaining metals cannot make up the difference.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::AlloyError;
+    ///
+    /// let alloy = AlloyData::<BismuthBronze>::builder()
+    ///     .percentage(Metal::Copper, 0.5)
+    ///     .ingots(3)
+    ///     .build()
+    ///     .expect("should be valid");
+    ///
+    /// assert_eq!(&[Copper(0.5), Zinc(0.3), Bismuth(0.2)], alloy.percentages());
+    ///
+    /// let builder = AlloyData::<BismuthBronze>::builder().percentage(Metal::Tin, 0.1);
+    /// assert_eq!(Err(AlloyError::UnknownConstituent(Metal::Tin)), builder.build());
+    /// ```
+    pub fn build(&self) -> Result<AlloyData<T>, AlloyError> {
+        let num_ingots = self.num_ingots.unwrap_or_else(T::default_ingots);
+        let metals = T::constituent_metals();
+        if self.percentages.len() == metals.len() {
+            return AlloyData::try_new(&self.percentages, num_ingots);
+        }
+
+        let ranges = percent::basis_point_ranges::<T>();
+        let mut values: Vec<i64> = T::default_percentages()
+            .iter()
+            .map(|p| to_basis_points(**p))
+            .collect();
+        let mut fixed = vec![false; values.len()];
+        for p in &self.percentages {
+            let index = metals
+                .iter()
+                .position(|m| *m == p.metal())
+                .ok_or(UnknownConstituent(p.metal()))?;
+            if !p.is_finite() {
+                return Err(InvalidPercentages);
+            }
+            values[index] = to_basis_points(**p);
+            fixed[index] = true;
+        }
+        let mut values = slider::rebalance(&values, &ranges, &fixed);
+        if distribute_difference(&mut values, &ranges, &fixed) != 0 {
+            return Err(InvalidPercentages);
+        }
+        AlloyData::try_new(percent::from_basis_points::<T>(&values), num_ingots)
+    }
+}
@@ -0,0 +1,105 @@
+//! # Clamped Percentages
+//! Snapping out-of-range percentages to the nearest legal value instead of rejecting them.
+use super::*;
+
+/// A constituent whose percentage was changed from what was requested
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ClampedValue {
+    pub metal: Metal,
+    /// The percentage that was supplied
+    pub requested: f32,
+    /// The percentage that was stored
+    pub value: f32,
+    /// Whether the requested percentage was outside its range, rather than moved to make up the total
+    pub clamped: bool,
+}
+
+impl ClampedValue {
+    /// How much the percentage was moved, negative when it was lowered
+    pub fn change(&self) -> f32 {
+        self.value - self.requested
+    }
+}
+
+/// Every constituent changed by [`AlloyData::set_percentages_clamped`], in canonical order
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct ClampReport {
+    pub adjustments: Vec<ClampedValue>,
+}
+
+impl ClampReport {
+    /// Whether the percentages were stored as requested
+    pub fn is_empty(&self) -> bool {
+        self.adjustments.is_empty()
+    }
+}
+
+impl<T: AlloyType> AlloyData<T> {
+    /// Tries to update the percentages like [`AlloyData::set_percentages`], clamping any value outside its range to the nearest bound. \
+    /// Clamped values are then held at their bound while the difference from 100% is shared among the rest in proportion to their values,
+    /// with any that would leave their range held at its edge and the rest shared among the others.
+    ///
+    /// Returns [`UnknownConstituent`](AlloyError::UnknownConstituent), [`DuplicateConstituent`](AlloyError::DuplicateConstituent),
+    /// [`MissingConstituent`](AlloyError::MissingConstituent), or [`WrongConstituentCount`](AlloyError::WrongConstituentCount)
+    /// unless every constituent is supplied exactly once,
+    /// and [`InvalidPercentages`](AlloyError::InvalidPercentages) for a value that is not finite
+    /// or when the unclamped constituents cannot make up the difference. \
+    /// Nothing is updated on error.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let mut alloy = AlloyData::<TinBronze>::default();
+    ///
+    /// let report = alloy.set_percentages_clamped([Copper(0.9), Tin(0.14)]).expect("should be valid");
+    /// assert_eq!(&[Copper(0.88), Tin(0.12)], alloy.percentages());
+    ///
+    /// assert_eq!(Metal::Copper, report.adjustments[0].metal);
+    /// assert!(!report.adjustments[0].clamped);
+    /// assert_eq!(Metal::Tin, report.adjustments[1].metal);
+    /// assert!(report.adjustments[1].clamped);
+    /// ```
+    pub fn set_percentages_clamped(
+        &mut self,
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+    ) -> Result<ClampReport, AlloyError> {
+        let requested: Vec<f32> =
+            reorder_constituents(percentages.as_ref(), T::constituent_metals(), |_, _| true)?
+                .iter()
+                .map(|p| **p)
+                .collect();
+        if requested.iter().any(|value| !value.is_finite()) {
+            return Err(InvalidPercentages);
+        }
+
+        let ranges = percent::basis_point_ranges::<T>();
+        let values: Vec<i64> = requested.iter().map(|v| to_basis_points(*v)).collect();
+        let clamped: Vec<i64> = values
+            .iter()
+            .zip(&ranges)
+            .map(|(v, (min, max))| (*v).clamp(*min, *max))
+            .collect();
+        let fixed: Vec<bool> = values.iter().zip(&clamped).map(|(v, c)| v != c).collect();
+        let mut values = slider::rebalance(&clamped, &ranges, &fixed);
+        if distribute_difference(&mut values, &ranges, &fixed) != 0 {
+            return Err(InvalidPercentages);
+        }
+
+        let stored = percent::from_basis_points::<T>(&values);
+        let adjustments = stored
+            .as_ref()
+            .iter()
+            .zip(&requested)
+            .zip(&fixed)
+            .filter(|((p, requested), _)| ***p != **requested)
+            .map(|((p, requested), clamped)| ClampedValue {
+                metal: p.metal(),
+                requested: *requested,
+                value: **p,
+                clamped: *clamped,
+            })
+            .collect();
+        self.update_values(Some(stored), None)?;
+        Ok(ClampReport { adjustments })
+    }
+}
@@ -0,0 +1,110 @@
+//! # Crucible Contents
+//! Checking an arbitrary pile of nuggets, such as what is already sitting in a crucible, against the alloys.
+use super::*;
+
+/// Report on whether a pile of nuggets will combine into an alloy
+#[derive(Clone, PartialEq, Debug)]
+pub struct ContentsReport {
+    /// Share of the total of each metal in the pile, with the alloy's constituents first in canonical order followed by any other metals
+    pub ratio: Box<[BaseMetal<f32>]>,
+    /// Whether the pile only contains the alloy's constituents and each one is within its range
+    pub in_range: bool,
+    /// Number of whole ingots the pile will produce, which is zero when it is not in range
+    pub ingots: i32,
+    /// Units left in the crucible after the whole ingots are poured, which is everything when it is not in range
+    pub leftover_units: i32,
+}
+
+impl<T: AlloyType> AlloyData<T> {
+    /// Checks whether a pile of nuggets will combine into the alloy and how many ingots it will produce
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let report = AlloyData::<TinBronze>::classify_contents(&[Copper(45), Tin(4), Copper(1)]);
+    ///
+    /// assert!(report.in_range);
+    /// assert_eq!(2, report.ingots);
+    /// assert_eq!(50, report.leftover_units);
+    /// ```
+    pub fn classify_contents(nuggets: &[BaseMetal<i32>]) -> ContentsReport {
+        classify(T::percentage_ranges(), nuggets)
+    }
+}
+
+impl Alloys {
+    /// Checks whether a pile of nuggets will combine into the alloy and how many ingots it will produce
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let report = Alloys::Brass.classify_contents(&[Copper(30), Zinc(10)]);
+    ///
+    /// assert!(!report.in_range);
+    /// assert_eq!(&[Copper(0.75), Zinc(0.25)], &*report.ratio);
+    /// ```
+    pub fn classify_contents(&self, nuggets: &[BaseMetal<i32>]) -> ContentsReport {
+        classify(self.percentage_ranges(), nuggets)
+    }
+
+    /// Checks a pile of nuggets against every alloy and returns each alloy it will combine into
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let matches = Alloys::matching_contents(&[Gold(10), Silver(10)]);
+    ///
+    /// assert_eq!(1, matches.len());
+    /// assert_eq!(Alloys::Electrum, matches[0].0);
+    /// ```
+    pub fn matching_contents(nuggets: &[BaseMetal<i32>]) -> Box<[(Alloys, ContentsReport)]> {
+        Alloys::ALL
+            .into_iter()
+            .map(|alloy| (alloy, alloy.classify_contents(nuggets)))
+            .filter(|(_, report)| report.in_range)
+            .collect()
+    }
+}
+
+/// Classifies a pile of nuggets against the supplied ranges using exact integer math
+fn classify(ranges: &[BaseMetal<Range>], nuggets: &[BaseMetal<i32>]) -> ContentsReport {
+    use unit_constants::*;
+    let mut totals: Vec<BaseMetal<i64>> = ranges.iter().map(|r| r.update_inner_value(0)).collect();
+    for n in nuggets {
+        match totals.iter_mut().find(|t| t.metal() == n.metal()) {
+            Some(t) => **t += i64::from(**n),
+            None => totals.push(n.update_inner_value(i64::from(**n))),
+        }
+    }
+    let total: i64 = totals.iter().map(|t| **t).sum();
+    let units = total * i64::from(NUGGET_UNIT_AMOUNT);
+
+    let in_range = total > 0
+        && totals.len() == ranges.len()
+        && totals.iter().zip(ranges).all(|(t, r)| {
+            let share = **t * BASIS_POINTS;
+            **t >= 0
+                && to_basis_points(r.min) * total <= share
+                && share <= to_basis_points(r.max) * total
+        });
+    let ingots = if in_range {
+        units / i64::from(INGOT_UNIT_AMOUNT)
+    } else {
+        0
+    };
+    ContentsReport {
+        ratio: totals
+            .iter()
+            .map(|t| {
+                t.update_inner_value(if total > 0 {
+                    **t as f32 / total as f32
+                } else {
+                    0.0
+                })
+            })
+            .collect(),
+        in_range,
+        ingots: ingots as i32,
+        leftover_units: (units - ingots * i64::from(INGOT_UNIT_AMOUNT)) as i32,
+    }
+}
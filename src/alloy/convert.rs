@@ -0,0 +1,87 @@
+//! # Converting Alloys
+//! Switching an [`Alloy`] to a different kind while carrying over as much of its settings as the target allows.
+use super::*;
+
+/// Result of converting an [`Alloy`] to a different kind of alloy
+#[derive(PartialEq, Debug)]
+pub struct Conversion {
+    /// The newly created alloy
+    pub alloy: Alloy,
+    /// The original number of ingots if it was more than the new alloy can make and had to be clamped
+    pub clamped_from: Option<i32>,
+}
+
+impl Alloy {
+    /// Converts the alloy into a different kind of alloy. \
+    /// The percentage of any metal shared by both alloys is kept if it is within the target's range, with the remaining
+    /// constituents rebalanced from the target's defaults. The number of ingots is kept, clamped to the target's max.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = Alloys::BismuthBronze.try_new([Copper(0.65), Zinc(0.20), Bismuth(0.15)], 10).expect("should be valid");
+    ///
+    /// let conversion = alloy.convert_to(Alloys::Brass);
+    ///
+    /// assert_eq!(&[Copper(0.65), Zinc(0.35)], conversion.alloy.percentages());
+    /// assert_eq!(10, conversion.alloy.num_ingots());
+    /// assert_eq!(None, conversion.clamped_from);
+    /// ```
+    pub fn convert_to(&self, target: Alloys) -> Conversion {
+        let percentages = rebalance(self.percentages(), target);
+        let alloy = target
+            .try_new(&percentages, 1)
+            .unwrap_or_else(|_| target.get_default());
+        let num_ingots = self.num_ingots().min(alloy.max_ingots());
+        let percentages = alloy.percentages().to_vec();
+        Conversion {
+            alloy: target.try_new(percentages, num_ingots).unwrap_or(alloy),
+            clamped_from: (num_ingots < self.num_ingots()).then_some(self.num_ingots()),
+        }
+    }
+}
+
+/// Builds percentages for the target that keep every shared metal possible and rebalance the rest to total 1.0. \
+/// Shared metals are dropped from the end until the others can make up the difference, falling back to the target's defaults.
+fn rebalance(source: &[BaseMetal<f32>], target: Alloys) -> Vec<BaseMetal<f32>> {
+    let ranges: Vec<(i64, i64)> = target
+        .percentage_ranges()
+        .iter()
+        .map(|r| (to_basis_points(r.min), to_basis_points(r.max)))
+        .collect();
+    let defaults: Vec<i64> = target
+        .default_percentages()
+        .iter()
+        .map(|p| to_basis_points(**p))
+        .collect();
+    let mut kept: Vec<Option<i64>> = target
+        .constituent_metals()
+        .iter()
+        .zip(&ranges)
+        .map(|(metal, (min, max))| {
+            let p = source.iter().find(|p| p.metal() == *metal)?;
+            Some(to_basis_points(**p)).filter(|bp| (min..=max).contains(&bp))
+        })
+        .collect();
+
+    loop {
+        let mut values: Vec<i64> = kept
+            .iter()
+            .zip(&defaults)
+            .map(|(k, d)| k.unwrap_or(*d))
+            .collect();
+        let fixed: Vec<bool> = kept.iter().map(Option::is_some).collect();
+        if distribute_difference(&mut values, &ranges, &fixed) == 0 {
+            return target
+                .constituent_metals()
+                .iter()
+                .zip(values)
+                .map(|(metal, bp)| metal.with_value(bp as f32 / BASIS_POINTS as f32))
+                .collect();
+        }
+        match kept.iter_mut().rev().find(|k| k.is_some()) {
+            Some(k) => *k = None,
+            None => return target.default_percentages().to_vec(),
+        }
+    }
+}
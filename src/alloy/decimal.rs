@@ -0,0 +1,116 @@
+//! # Decimal Percentages
+//! Fixed-point [`Decimal`] support for alloy percentages, enabled with the `decimal` feature.
+//! Validation is done on the exact decimal values, which are then converted exactly to basis points for the unit math.
+use super::*;
+use rust_decimal::Decimal;
+
+type DecimalRange = ConstituentRange<Decimal>;
+
+impl<T: AlloyType> AlloyData<T> {
+    /// Tries to create a new instance of an alloy from decimal percentages. \
+    /// Percentages must total exactly 1.0, fall within the alloy's ranges, and be exact to a basis point (0.01%).
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use rust_decimal::Decimal;
+    ///
+    /// let percentages = [Copper(Decimal::new(915, 3)), Tin(Decimal::new(85, 3))];
+    ///
+    /// let alloy = AlloyData::<TinBronze>::try_new_decimal(percentages, 2).expect("should be valid");
+    ///
+    /// assert_eq!(&[Copper(36), Tin(4)], alloy.nuggets());
+    /// ```
+    pub fn try_new_decimal(
+        percentages: impl AsRef<[BaseMetal<Decimal>]>,
+        num_ingots: i32,
+    ) -> Result<Self, AlloyError> {
+        if num_ingots > unit_constants::MAX_POSSIBLE_INGOTS {
+            return Err(TooManyIngots);
+        } else if num_ingots <= 0 {
+            return Err(TooFewIngots);
+        }
+        check_definition::<T>()?;
+        let decimals = Self::check_valid_decimal_percentages(percentages)?;
+        let basis_points = decimals
+            .iter()
+            .map(|p| {
+                let scaled = p
+                    .checked_mul(Decimal::from(BASIS_POINTS))
+                    .ok_or(InvalidPercentages)?;
+                if scaled.fract().is_zero() {
+                    i64::try_from(scaled).map_err(|_| InvalidPercentages)
+                } else {
+                    Err(InvalidPercentages)
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (alloy_type, max_ingots) =
+            Self::get_updated_values_basis_points(&basis_points, num_ingots)?;
+        Ok(Self {
+            alloy_type,
+            percentages_f64: T::percentages_from_fn(|i| {
+                basis_points
+                    .get(i)
+                    .map_or(0.0, |bp| *bp as f64 / BASIS_POINTS as f64)
+            }),
+            percentages: percent::from_basis_points::<T>(&basis_points),
+            num_ingots,
+            max_ingots,
+        })
+    }
+
+    /// Gets the ranges of percentages of the constituents for the given alloy as decimals
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::ConstituentRange;
+    /// use rust_decimal::Decimal;
+    ///
+    /// let ranges = AlloyData::<TinBronze>::decimal_percentage_ranges();
+    ///
+    /// assert_eq!(Tin(ConstituentRange::new(Decimal::new(8, 2), Decimal::new(12, 2))), ranges[1]);
+    /// ```
+    pub fn decimal_percentage_ranges() -> Box<[BaseMetal<DecimalRange>]> {
+        T::percentage_ranges()
+            .iter()
+            .map(|r| r.update_inner_value(DecimalRange::new(to_decimal(r.min), to_decimal(r.max))))
+            .collect()
+    }
+
+    /// Checks whether the supplied decimal percentages are valid for the given alloy \
+    /// Returns the validated percentages in a reordered boxed slice or an error if invalid
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use rust_decimal::Decimal;
+    ///
+    /// let percentages = [Tin(Decimal::new(8, 2)), Copper(Decimal::new(92, 2))];
+    /// assert!(
+    ///     AlloyData::<TinBronze>::check_valid_decimal_percentages(percentages)
+    ///         .is_ok_and(|p| p == Box::from([Copper(Decimal::new(92, 2)), Tin(Decimal::new(8, 2))]))
+    /// );
+    /// ```
+    pub fn check_valid_decimal_percentages(
+        percentages: impl AsRef<[BaseMetal<Decimal>]>,
+    ) -> Result<Box<[BaseMetal<Decimal>]>, AlloyError> {
+        let percentages = percentages.as_ref();
+        let sum = percentages
+            .iter()
+            .try_fold(Decimal::ZERO, |acc, p| acc.checked_add(**p))
+            .ok_or(InvalidPercentages)?;
+        if sum != Decimal::ONE {
+            return Err(InvalidPercentages);
+        }
+
+        let ranges = Self::decimal_percentage_ranges();
+        reorder_constituents(percentages, T::constituent_metals(), |i, p| {
+            ranges[i].contains(p)
+        })
+    }
+}
+
+/// Converts a whole-percent float range bound into an exact decimal
+fn to_decimal(value: f32) -> Decimal {
+    Decimal::new((value * 100.0).round() as i64, 2)
+}
@@ -0,0 +1,100 @@
+//! # Alloy Documentation
+//! Short descriptions of each alloy for tooltips and help screens, kept in one table so translations can be added alongside it.
+use super::*;
+
+/// Documentation for a single alloy
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AlloyDoc {
+    /// One sentence describing the alloy
+    pub description: &'static str,
+    /// Where its constituents come from
+    pub acquisition: &'static str,
+    /// What the alloy is mostly used for
+    pub uses: &'static str,
+}
+
+/// English documentation in the same order as [`Alloys::ALL`]
+const DOCS: [AlloyDoc; 9] = [
+    AlloyDoc {
+        description: "The classic bronze of copper with a little tin.",
+        acquisition: "Tin is smelted from cassiterite, which is rarer than the copper ores.",
+        uses: "Bronze tools, weapons, and armor.",
+    },
+    AlloyDoc {
+        description: "A bronze of copper, zinc, and bismuth that is often the easiest bronze to obtain.",
+        acquisition: "Zinc comes from sphalerite and bismuth from bismuthinite, which are often found near each other.",
+        uses: "Bronze tools, weapons, and armor.",
+    },
+    AlloyDoc {
+        description: "The strongest bronze, alloying copper with gold and silver.",
+        acquisition: "Gold and silver come from native nuggets, so it is usually the last bronze to be made.",
+        uses: "The most durable bronze tools and weapons.",
+    },
+    AlloyDoc {
+        description: "A golden alloy of copper and zinc.",
+        acquisition: "Zinc is smelted from sphalerite.",
+        uses: "Decorative and mechanical crafting ingredients.",
+    },
+    AlloyDoc {
+        description: "A heavy alloy that is mostly lead with a little copper.",
+        acquisition: "Lead is smelted from galena.",
+        uses: "Crafting ingredients that call for a lead alloy.",
+    },
+    AlloyDoc {
+        description: "A soft, low melting solder of equal parts tin and lead.",
+        acquisition: "Tin is smelted from cassiterite and lead from galena.",
+        uses: "Joining metal parts together.",
+    },
+    AlloyDoc {
+        description: "A solder of tin and silver.",
+        acquisition: "Tin is smelted from cassiterite and silver comes from native silver nuggets.",
+        uses: "Joining metal parts together.",
+    },
+    AlloyDoc {
+        description: "A pale gold alloy of roughly equal parts gold and silver.",
+        acquisition: "Both metals come from native nuggets.",
+        uses: "Jewelry and decorative items.",
+    },
+    AlloyDoc {
+        description: "A silvery alloy of copper and nickel.",
+        acquisition: "Nickel is smelted from pentlandite.",
+        uses: "Decorative and mechanical crafting ingredients.",
+    },
+];
+
+impl Alloys {
+    /// Gets the documentation for the alloy
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert!(Alloys::Electrum.doc().acquisition.contains("native nuggets"));
+    /// ```
+    pub fn doc(&self) -> &'static AlloyDoc {
+        &DOCS[*self as usize]
+    }
+
+    /// Gets a one sentence description of the alloy
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!("A golden alloy of copper and zinc.", Alloys::Brass.description());
+    /// ```
+    pub fn description(&self) -> &'static str {
+        self.doc().description
+    }
+}
+
+impl Alloy {
+    /// Gets the documentation for the alloy
+    pub fn doc(&self) -> &'static AlloyDoc {
+        self.kind().doc()
+    }
+
+    /// Gets a one sentence description of the alloy
+    pub fn description(&self) -> &'static str {
+        self.kind().description()
+    }
+}
@@ -0,0 +1,169 @@
+//! # Dynamic Alloys
+//! An object-safe view of any [`AlloyData`], so code can work with alloys through `dyn AnyAlloy` instead of matching on [`Alloy`].
+use super::*;
+
+/// The instance-level operations of an alloy, usable as a trait object \
+/// Implemented for every [`AlloyData`] and for [`PureMetal`]; see [`Alloy::as_dyn`] and [`Alloy::as_dyn_mut`] to get one from the enum.
+/// ### Example
+/// ```rust
+/// use vs_alloy_calculator::prelude::*;
+/// use vs_alloy_calculator::AnyAlloy;
+///
+/// let mut alloys: Vec<Box<dyn AnyAlloy>> = vec![
+///     Box::new(AlloyData::<TinBronze>::default()),
+///     Box::new(AlloyData::<Brass>::default()),
+/// ];
+/// for alloy in &mut alloys {
+///     alloy.set_num_ingots(2).expect("should be valid");
+/// }
+///
+/// assert_eq!("Tin Bronze", alloys[0].name());
+/// assert_eq!(&[Copper(36), Tin(4)], alloys[0].nuggets());
+/// assert_eq!(2, alloys[1].num_ingots());
+/// ```
+pub trait AnyAlloy {
+    /// Gets the name of the alloy, the same as [`AlloyType::name`]
+    fn name(&self) -> &'static str;
+
+    /// Gets the ranges of percentages of the constituents, the same as [`AlloyData::percentage_ranges`]
+    fn percentage_ranges(&self) -> &'static [BaseMetal<Range>];
+
+    /// Gets the number of nuggets of each constituent, the same as [`AlloyData::nuggets`]
+    fn nuggets(&self) -> &[BaseMetal<i32>];
+
+    /// Gets the percentages of each constituent, the same as [`AlloyData::percentages`]
+    fn percentages(&self) -> &[BaseMetal<f32>];
+
+    /// Gets the number of ingots, the same as [`AlloyData::num_ingots`]
+    fn num_ingots(&self) -> i32;
+
+    /// Gets the max number of ingots, the same as [`AlloyData::max_ingots`]
+    fn max_ingots(&self) -> i32;
+
+    /// Tries to update the number of ingots, the same as [`AlloyData::set_num_ingots`]
+    fn set_num_ingots(&mut self, num_ingots: i32) -> Result<(), AlloyError>;
+
+    /// Tries to update the percentages, the same as [`AlloyData::set_percentages`]
+    fn set_percentages(&mut self, percentages: &[BaseMetal<f32>]) -> Result<(), AlloyError>;
+}
+
+impl<T: AlloyType> AnyAlloy for AlloyData<T> {
+    fn name(&self) -> &'static str {
+        T::name()
+    }
+
+    fn percentage_ranges(&self) -> &'static [BaseMetal<Range>] {
+        T::percentage_ranges()
+    }
+
+    fn nuggets(&self) -> &[BaseMetal<i32>] {
+        AlloyData::nuggets(self)
+    }
+
+    fn percentages(&self) -> &[BaseMetal<f32>] {
+        AlloyData::percentages(self)
+    }
+
+    fn num_ingots(&self) -> i32 {
+        AlloyData::num_ingots(self)
+    }
+
+    fn max_ingots(&self) -> i32 {
+        AlloyData::max_ingots(self)
+    }
+
+    fn set_num_ingots(&mut self, num_ingots: i32) -> Result<(), AlloyError> {
+        AlloyData::set_num_ingots(self, num_ingots)
+    }
+
+    fn set_percentages(&mut self, percentages: &[BaseMetal<f32>]) -> Result<(), AlloyError> {
+        AlloyData::set_percentages(self, percentages)
+    }
+}
+
+impl AnyAlloy for PureMetal {
+    fn name(&self) -> &'static str {
+        PureMetal::name(self)
+    }
+
+    fn percentage_ranges(&self) -> &'static [BaseMetal<Range>] {
+        PureMetal::percentage_ranges(self)
+    }
+
+    fn nuggets(&self) -> &[BaseMetal<i32>] {
+        PureMetal::nuggets(self)
+    }
+
+    fn percentages(&self) -> &[BaseMetal<f32>] {
+        PureMetal::percentages(self)
+    }
+
+    fn num_ingots(&self) -> i32 {
+        PureMetal::num_ingots(self)
+    }
+
+    fn max_ingots(&self) -> i32 {
+        PureMetal::max_ingots(self)
+    }
+
+    fn set_num_ingots(&mut self, num_ingots: i32) -> Result<(), AlloyError> {
+        PureMetal::set_num_ingots(self, num_ingots)
+    }
+
+    /// Only accepts 100% of the metal being smelted, which leaves the recipe unchanged
+    fn set_percentages(&mut self, percentages: &[BaseMetal<f32>]) -> Result<(), AlloyError> {
+        self.check_percentages(percentages)
+    }
+}
+
+impl Alloy {
+    /// Borrows the alloy's data as a trait object
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = Alloys::BismuthBronze.get_default();
+    /// let any = alloy.as_dyn();
+    ///
+    /// assert_eq!(alloy.name(), any.name());
+    /// assert_eq!(alloy.nuggets(), any.nuggets());
+    /// ```
+    pub fn as_dyn(&self) -> &dyn AnyAlloy {
+        match self {
+            Alloy::TinBronze(alloy_data) => alloy_data,
+            Alloy::BismuthBronze(alloy_data) => alloy_data,
+            Alloy::BlackBronze(alloy_data) => alloy_data,
+            Alloy::Brass(alloy_data) => alloy_data,
+            Alloy::Molybdochalkos(alloy_data) => alloy_data,
+            Alloy::LeadSolder(alloy_data) => alloy_data,
+            Alloy::SilverSolder(alloy_data) => alloy_data,
+            Alloy::Electrum(alloy_data) => alloy_data,
+            Alloy::Cupronickel(alloy_data) => alloy_data,
+        }
+    }
+
+    /// Mutably borrows the alloy's data as a trait object
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let mut alloy = Alloys::TinBronze.get_default();
+    /// alloy.as_dyn_mut().set_num_ingots(5).expect("should be valid");
+    ///
+    /// assert_eq!(5, alloy.num_ingots());
+    /// assert_eq!(&[Copper(92), Tin(8)], alloy.nuggets());
+    /// ```
+    pub fn as_dyn_mut(&mut self) -> &mut dyn AnyAlloy {
+        match self {
+            Alloy::TinBronze(alloy_data) => alloy_data,
+            Alloy::BismuthBronze(alloy_data) => alloy_data,
+            Alloy::BlackBronze(alloy_data) => alloy_data,
+            Alloy::Brass(alloy_data) => alloy_data,
+            Alloy::Molybdochalkos(alloy_data) => alloy_data,
+            Alloy::LeadSolder(alloy_data) => alloy_data,
+            Alloy::SilverSolder(alloy_data) => alloy_data,
+            Alloy::Electrum(alloy_data) => alloy_data,
+            Alloy::Cupronickel(alloy_data) => alloy_data,
+        }
+    }
+}
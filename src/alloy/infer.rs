@@ -0,0 +1,116 @@
+//! # Inferring Alloys
+//! Working out which alloys a mix of percentages is valid for, so a rejected mix can point the user at the alloy they likely meant.
+use super::*;
+
+/// Error from setting percentages that also lists the alloys the percentages would have been valid for
+#[derive(Clone, PartialEq, Debug)]
+pub struct Suggestion {
+    /// Why the percentages were rejected by the alloy they were set on
+    pub error: AlloyError,
+    /// Every other alloy whose ranges accept the percentages
+    pub valid_for: Vec<Alloys>,
+}
+
+impl std::fmt::Display for Suggestion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error)?;
+        if !self.valid_for.is_empty() {
+            let names: Vec<&str> = self.valid_for.iter().map(|a| a.name()).collect();
+            write!(f, "; these percentages are valid for {}", names.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Suggestion {}
+
+impl Alloys {
+    /// Checks whether the supplied percentages are valid for the alloy \
+    /// Returns the validated percentages in a reordered boxed slice or an error if invalid
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert!(Alloys::Brass.check_valid_percentages([Zinc(0.3), Copper(0.7)]).is_ok());
+    /// assert!(Alloys::Brass.check_valid_percentages([Copper(0.92), Tin(0.08)]).is_err());
+    /// ```
+    pub fn check_valid_percentages(
+        &self,
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+    ) -> Result<Box<[BaseMetal<f32>]>, AlloyError> {
+        match self {
+            Alloys::TinBronze => AlloyData::<TinBronze>::check_valid_percentages(percentages)
+                .map(|p| p.as_ref().into()),
+            Alloys::BismuthBronze => {
+                AlloyData::<BismuthBronze>::check_valid_percentages(percentages)
+                    .map(|p| p.as_ref().into())
+            }
+            Alloys::BlackBronze => AlloyData::<BlackBronze>::check_valid_percentages(percentages)
+                .map(|p| p.as_ref().into()),
+            Alloys::Brass => {
+                AlloyData::<Brass>::check_valid_percentages(percentages).map(|p| p.as_ref().into())
+            }
+            Alloys::Molybdochalkos => {
+                AlloyData::<Molybdochalkos>::check_valid_percentages(percentages)
+                    .map(|p| p.as_ref().into())
+            }
+            Alloys::LeadSolder => AlloyData::<LeadSolder>::check_valid_percentages(percentages)
+                .map(|p| p.as_ref().into()),
+            Alloys::SilverSolder => AlloyData::<SilverSolder>::check_valid_percentages(percentages)
+                .map(|p| p.as_ref().into()),
+            Alloys::Electrum => AlloyData::<Electrum>::check_valid_percentages(percentages)
+                .map(|p| p.as_ref().into()),
+            Alloys::Cupronickel => AlloyData::<Cupronickel>::check_valid_percentages(percentages)
+                .map(|p| p.as_ref().into()),
+        }
+    }
+
+    /// Finds every alloy whose ranges accept the supplied percentages
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(vec![Alloys::TinBronze], Alloys::infer(&[Copper(0.92), Tin(0.08)]));
+    /// assert!(Alloys::infer(&[Copper(0.5), Tin(0.5)]).is_empty());
+    /// ```
+    pub fn infer(percentages: &[BaseMetal<f32>]) -> Vec<Alloys> {
+        Alloys::ALL
+            .into_iter()
+            .filter(|alloy| alloy.check_valid_percentages(percentages).is_ok())
+            .collect()
+    }
+}
+
+impl Alloy {
+    /// Tries to update the percentages of the alloy like [`Alloy::set_percentages`],
+    /// but on failure also reports which other alloys the percentages are valid for
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let mut alloy = Alloys::Brass.get_default();
+    ///
+    /// let suggestion = alloy
+    ///     .set_percentages_or_suggest([Copper(0.92), Tin(0.08)])
+    ///     .expect_err("should be invalid for brass");
+    ///
+    /// assert_eq!(vec![Alloys::TinBronze], suggestion.valid_for);
+    /// assert!(suggestion.to_string().ends_with("these percentages are valid for Tin Bronze"));
+    /// ```
+    pub fn set_percentages_or_suggest(
+        &mut self,
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+    ) -> Result<(), Suggestion> {
+        let percentages = percentages.as_ref();
+        self.set_percentages(percentages).map_err(|error| {
+            let kind = self.kind();
+            Suggestion {
+                error,
+                valid_for: Alloys::infer(percentages)
+                    .into_iter()
+                    .filter(|alloy| *alloy != kind)
+                    .collect(),
+            }
+        })
+    }
+}
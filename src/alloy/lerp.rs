@@ -0,0 +1,49 @@
+//! # Interpolated Recipes
+//! Picking a recipe from a single slider position across the alloy's valid mixes.
+use super::alloy_types::boundary_points;
+use super::percent::{basis_point_ranges, from_basis_points};
+use super::*;
+
+impl<T: AlloyType> AlloyData<T> {
+    /// Tries to create a new instance of an alloy from a single position `t` between 0 and 1. \
+    /// The path runs in a straight line from the mix with the most of the first constituent at `t = 0`
+    /// to the mix with the least at `t = 1`, which are the first and last of [`Alloys::boundary_mixes`].
+    /// For two-metal alloys this is simply the range from one end to the other. Since the valid mixes form a convex region
+    /// every point on the line is valid. Percentages are snapped to whole percents, with any rounding difference taken up
+    /// by the constituents in order within their ranges.
+    ///
+    /// Returns [`InvalidValues`](AlloyError::InvalidValues) if `t` is outside `0..=1` or not a number.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = AlloyData::<TinBronze>::from_lerp(0.5, 5).expect("should be valid");
+    /// assert_eq!(&[Copper(0.90), Tin(0.10)], alloy.percentages());
+    ///
+    /// let alloy = AlloyData::<BismuthBronze>::from_lerp(0.5, 1).expect("should be valid");
+    /// assert_eq!(&[Copper(0.60), Zinc(0.25), Bismuth(0.15)], alloy.percentages());
+    /// ```
+    pub fn from_lerp(t: f32, num_ingots: i32) -> Result<Self, AlloyError> {
+        if !(0.0..=1.0).contains(&t) {
+            return Err(InvalidValues);
+        }
+        let corners = boundary_points(T::percentage_ranges());
+        let (start, end) = corners
+            .first()
+            .zip(corners.last())
+            .ok_or(InvalidPercentages)?;
+        let mut values: Vec<i64> = start
+            .iter()
+            .zip(end)
+            .map(|(a, b)| {
+                let bp = *a as f64 + (b - a) as f64 * f64::from(t);
+                (bp / 100.0).round() as i64 * 100
+            })
+            .collect();
+        let fixed = vec![false; values.len()];
+        if distribute_difference(&mut values, &basis_point_ranges::<T>(), &fixed) != 0 {
+            return Err(InvalidPercentages);
+        }
+        Self::try_new(from_basis_points::<T>(&values), num_ingots)
+    }
+}
@@ -1,6 +1,199 @@
 use super::*;
+use crate::crucible::Capacity;
 use alloy_types::*;
+mod accuracy;
+mod adjustments;
 pub mod alloy_types;
+mod builder;
+mod clamp;
+mod contents;
+mod convert;
+#[cfg(feature = "decimal")]
+mod decimal;
+mod docs;
+mod dynamic;
+mod infer;
+mod lerp;
+mod options;
+mod percent;
+mod precision;
+mod pure_metal;
+#[cfg(feature = "rational")]
+mod rational;
+#[cfg(feature = "serde")]
+mod serialization;
+mod slider;
+mod solve;
+mod split;
+mod trace;
+mod validation;
+
+/// Number of basis points (hundredths of a percent) in a whole
+const BASIS_POINTS: i64 = 10_000;
+/// Largest difference from 1.0 allowed in the total of f32 percentages, a few roundings at f32 precision. \
+/// Anything further off is a near miss rather than float error, and the nuggets could not be split from it without one constituent
+/// taking up the difference, so it has to be normalized first.
+pub(crate) const TOTAL_TOLERANCE: f32 = 4.0 * f32::EPSILON;
+/// Most constituents an alloy can have, one of each metal
+const MAX_CONSTITUENTS: usize = Metal::ALL.len();
+
+/// Widens percentages to f64 for the full precision copy, rounded to the 7 decimal places an f32 percentage is good for
+/// so that `0.92_f32` becomes `0.92`. The last constituent takes whatever is left so the widened values total 1.0 at f64 precision
+/// even when the f32 ones are only within [`TOTAL_TOLERANCE`] of it
+fn widened<T: AlloyType>(percentages: &T::Percentages<f32>) -> T::Percentages<f64> {
+    let percentages = percentages.as_ref();
+    let mut scaled = [0; MAX_CONSTITUENTS];
+    if let Some((last, rest)) = scaled[..percentages.len()].split_last_mut() {
+        for (share, p) in rest.iter_mut().zip(percentages) {
+            *share = to_fixed_point(**p);
+        }
+        *last = PERCENT_SCALE - rest.iter().sum::<i64>();
+    }
+    T::percentages_from_fn(|i| scaled[i] as f64 / PERCENT_SCALE as f64)
+}
+
+/// Fixed point scale f32 percentages are converted to for exact unit math, the 7 decimal places an f32 percentage is good for
+const PERCENT_SCALE: i64 = 10_000_000;
+/// Units left over after splitting the ingots between constituents that are treated as float error rather than a real remainder,
+/// scaled by [`PERCENT_SCALE`] to a thousandth of a unit
+const MAX_REMAINING_UNITS_ERROR: i64 = PERCENT_SCALE / 1000;
+
+/// Converts an f32 percentage to fixed point at [`PERCENT_SCALE`], dropping the float noise past 7 decimal places
+fn to_fixed_point(percentage: f32) -> i64 {
+    (f64::from(percentage) * PERCENT_SCALE as f64).round() as i64
+}
+
+/// Divides rounding towards positive infinity, for a positive divisor
+fn div_ceil(value: i64, divisor: i64) -> i64 {
+    -(-value).div_euclid(divisor)
+}
+
+/// The most ingots worth searching for in the capacity, since no [`IngotCount`] can hold more than [`IngotCount::MAX`]
+fn max_search_ingots(capacity: Capacity) -> i32 {
+    capacity.max_possible_ingots().min(IngotCount::MAX.get())
+}
+
+/// Counts how many times the max ingots search runs on the current thread so tests can check it is cached
+#[cfg(test)]
+pub(crate) mod max_ingots_counter {
+    use std::cell::Cell;
+
+    thread_local! {
+        static COUNT: Cell<usize> = const { Cell::new(0) };
+    }
+
+    pub(crate) fn increment() {
+        COUNT.with(|c| c.set(c.get() + 1));
+    }
+
+    pub(crate) fn get() -> usize {
+        COUNT.with(|c| c.get())
+    }
+}
+
+/// Rounds a float percentage to basis points
+fn to_basis_points(value: f32) -> i64 {
+    (f64::from(value) * BASIS_POINTS as f64).round() as i64
+}
+
+/// Wraps an alloy's validated percentages as [`Percent`] values
+fn typed(percentages: &[BaseMetal<f32>]) -> Box<[BaseMetal<Percent>]> {
+    percentages
+        .iter()
+        .map(|p| p.map(Percent::from_validated))
+        .collect()
+}
+
+/// Checks that the alloy type's ranges and defaults are consistent,
+/// which catches an invalid alloy declared in another crate before any alloy data is created from it
+fn check_definition<T: AlloyType>() -> Result<(), AlloyError> {
+    T::checked_definition().map_err(InvalidDefinition)
+}
+
+impl<T: AlloyType + Default> Default for AlloyData<T> {
+    /// Creates the default alloy data for an alloy type, calculating its max ingots from the default percentages. \
+    /// The definition is not checked, so an alloy declared in another crate should be checked with [`definition::validate_alloy_type`].
+    fn default() -> Self {
+        let percentages =
+            T::percentages_from_fn(|i| T::default_percentages().get(i).map_or(0.0, |p| **p));
+        AlloyData {
+            alloy_type: T::default(),
+            max_ingots: AlloyData::<T>::validated_max_ingots(percentages.as_ref()),
+            num_ingots: T::default_ingots(),
+            percentages_f64: widened::<T>(&percentages),
+            percentages,
+        }
+    }
+}
+
+impl<T: AlloyType> AlloyData<T>
+where
+    Self: Default,
+{
+    /// Resets the percentages, number of ingots, nuggets, and max ingots to the alloy's defaults, the same values as [`Default::default`]
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let mut alloy = AlloyData::<TinBronze>::try_new([Copper(0.88), Tin(0.12)], 12).expect("should be valid");
+    /// alloy.reset_to_default();
+    ///
+    /// assert_eq!(AlloyData::<TinBronze>::default(), alloy);
+    /// assert_eq!(&[Copper(18), Tin(2)], alloy.nuggets());
+    /// ```
+    pub fn reset_to_default(&mut self) {
+        let Self {
+            alloy_type,
+            percentages,
+            percentages_f64,
+            num_ingots,
+            max_ingots,
+        } = Self::default();
+        self.alloy_type = alloy_type;
+        self.percentages = percentages;
+        self.percentages_f64 = percentages_f64;
+        self.num_ingots = num_ingots;
+        self.max_ingots = max_ingots;
+    }
+}
+
+/// Spreads the difference between the values and a whole across the constituents that are not fixed, in order and clamped to their ranges. \
+/// Returns whatever difference could not be made up, which is zero on success.
+fn distribute_difference(values: &mut [i64], ranges: &[(i64, i64)], fixed: &[bool]) -> i64 {
+    let mut difference = BASIS_POINTS - values.iter().sum::<i64>();
+    for (i, (min, max)) in ranges.iter().enumerate() {
+        if !fixed[i] {
+            let value = (values[i] + difference).clamp(*min, *max);
+            difference -= value - values[i];
+            values[i] = value;
+        }
+    }
+    difference
+}
+
+pub use accuracy::IngotEfficiency;
+pub use adjustments::Adjustment;
+pub use adjustments::AdjustmentReason;
+pub use adjustments::AdjustmentReport;
+pub use builder::AlloyBuilder;
+pub use clamp::ClampReport;
+pub use clamp::ClampedValue;
+pub use contents::ContentsReport;
+pub use convert::Conversion;
+pub use docs::AlloyDoc;
+pub use dynamic::AnyAlloy;
+pub use infer::Suggestion;
+pub use options::AdjustOutcome;
+pub use options::Adjusted;
+pub use options::CreateOptions;
+pub use options::normalize_sum;
+pub use pure_metal::PureMetal;
+pub use slider::SliderBinding;
+pub use solve::AchievableMix;
+pub use trace::CalculationTrace;
+pub use trace::TraceStep;
+pub use validation::ValidationIssue;
+pub use validation::ValidationReport;
 
 pub mod alloy_names {
     pub const TIN_BRONZE: &str = "Tin Bronze";
@@ -15,6 +208,9 @@ pub mod alloy_names {
 }
 
 /// Unified alloy enum
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum Alloy {
     TinBronze(AlloyData<TinBronze>),
     BismuthBronze(AlloyData<BismuthBronze>),
@@ -28,17 +224,126 @@ pub enum Alloy {
 }
 
 /// Struct for modeling all of the alloys in Vintage Story
-#[derive(PartialEq, PartialOrd, Debug)]
+///
+/// Equality, ordering, and hashing use a canonical integer form of the percentages rounded to basis points (hundredths of a percent) plus the number of ingots.
+/// Two alloys built from floats that round to the same basis points, such as `0.90` and `0.9000001`, therefore compare equal.
+#[derive(Clone, Debug)]
 pub struct AlloyData<T: AlloyType> {
     /// Also stores number of nuggets of each constituent
     alloy_type: T,
-    percentages: Box<[BaseMetal<f32>]>,
+    percentages: T::Percentages<f32>,
+    /// Full precision copy of the percentages, widened from `percentages` unless set through the f64 methods
+    percentages_f64: T::Percentages<f64>,
     num_ingots: i32,
     max_ingots: i32,
 }
 
+/// Struct for viewing all of the data for a single constituent of an alloy at once
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ConstituentView {
+    pub metal: Metal,
+    pub range: Range,
+    pub percent: f32,
+    pub nuggets: i32,
+}
+
+/// Iterator over the [`ConstituentView`]s of an alloy in canonical order
+#[derive(Clone, Debug)]
+pub struct Constituents<'a> {
+    ranges: &'static [BaseMetal<Range>],
+    percentages: &'a [BaseMetal<f32>],
+    nuggets: &'a [BaseMetal<i32>],
+    index: usize,
+}
+
+impl ConstituentView {
+    /// Gets the constituent's amount in crucible units, which is its nuggets times [`NUGGET_UNIT_AMOUNT`](unit_constants::NUGGET_UNIT_AMOUNT)
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = AlloyData::<TinBronze>::default();
+    /// let tin = alloy.constituents().nth(1).expect("should have tin");
+    ///
+    /// assert_eq!(10, tin.units());
+    /// ```
+    pub fn units(&self) -> i32 {
+        self.nuggets
+            .saturating_mul(unit_constants::NUGGET_UNIT_AMOUNT)
+    }
+}
+
+impl Iterator for Constituents<'_> {
+    type Item = ConstituentView;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let range = self.ranges.get(self.index)?;
+        let percent = self.percentages.get(self.index)?;
+        let nuggets = self.nuggets.get(self.index)?;
+        debug_assert!(range.metal() == percent.metal() && range.metal() == nuggets.metal());
+        self.index += 1;
+        Some(ConstituentView {
+            metal: range.metal(),
+            range: **range,
+            percent: **percent,
+            nuggets: **nuggets,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.ranges.len().saturating_sub(self.index);
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Constituents<'_> {}
+
+impl<T: AlloyType> PartialEq for AlloyData<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.num_ingots == other.num_ingots && self.basis_points().eq(other.basis_points())
+    }
+}
+
+impl<T: AlloyType> Eq for AlloyData<T> {}
+
+impl<T: AlloyType> PartialOrd for AlloyData<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: AlloyType> Ord for AlloyData<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.basis_points()
+            .cmp(other.basis_points())
+            .then(self.num_ingots.cmp(&other.num_ingots))
+    }
+}
+
+impl<T: AlloyType> std::hash::Hash for AlloyData<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for bp in self.basis_points() {
+            bp.hash(state);
+        }
+        self.num_ingots.hash(state);
+    }
+}
+
 impl<T: AlloyType> AlloyData<T> {
     /// Tries to create a new instance of an alloy. Checks if the input values are valid and tries to calculate valid values for the given alloy.
+    ///
+    /// Nuggets are rounded by flooring every constituent except the last, which takes the remaining units rounded up.
+    /// The nuggets are then corrected one at a time, as many as needed, in two passes:
+    /// - If they do not total a whole number of ingots, nuggets are added to the latest constituent below its maximum
+    ///   or taken from the latest constituent above its minimum until they do.
+    /// - Any constituent still over its maximum gives nuggets to the latest other constituent with room,
+    ///   and any constituent under its minimum takes them from the latest other constituent with some to spare.
+    ///
+    /// This has not been verified against the in-game crucible, which may spread the rounding differently for some mixes.
+    ///
+    /// Percentages must total 1.0 up to f32 rounding, and are stored as given.
+    /// A total that misses by more, such as `0.915 + 0.08`, returns [`InvalidPercentages`](AlloyError::InvalidPercentages);
+    /// use [`AlloyData::try_new_normalized`] to rescale it to exactly 1.0 first.
     /// ### Example
     /// ```rust
     /// use vs_alloy_calculator::prelude::*;
@@ -52,30 +357,48 @@ impl<T: AlloyType> AlloyData<T> {
     /// ```
     pub fn try_new(
         percentages: impl AsRef<[BaseMetal<f32>]>,
-        num_ingots: i32,
+        num_ingots: impl TryInto<IngotCount, Error: Into<AlloyError>>,
     ) -> Result<Self, AlloyError> {
-        if num_ingots > unit_constants::MAX_POSSIBLE_INGOTS {
-            Err(TooManyIngots)
-        } else if num_ingots <= 0 {
-            Err(TooFewIngots)
-        } else {
-            match T::check_valid_percentages(&percentages) {
-                Ok(percentages) => {
-                    let (alloy_type, max_ingots) =
-                        Self::get_updated_values(&percentages, num_ingots)?;
-                    Ok(Self {
-                        alloy_type,
-                        percentages,
-                        num_ingots,
-                        max_ingots,
-                    })
-                }
-                Err(e) => Err(e),
+        let num_ingots = num_ingots.try_into().map_err(Into::into)?.get();
+        check_definition::<T>()?;
+        match validated_percentages::<T>(percentages.as_ref()) {
+            Ok(percentages) => {
+                let (alloy_type, max_ingots) =
+                    Self::get_updated_values(percentages.as_ref(), num_ingots)?;
+                Ok(Self {
+                    alloy_type,
+                    percentages_f64: widened::<T>(&percentages),
+                    percentages,
+                    num_ingots,
+                    max_ingots,
+                })
             }
+            Err(e) => Err(e),
         }
     }
 
-    /// Gets the number of nuggets of each constituent needed to create the current number of ingots with the current constituent ratios
+    /// Tries to create a new instance of an alloy from a map of metals to percentages. \
+    /// Missing or extra metals are rejected the same way as with [`AlloyData::try_new`].
+    /// ### Example
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let map = HashMap::from([(Metal::Tin, 0.08), (Metal::Copper, 0.92)]);
+    ///
+    /// let alloy = AlloyData::<TinBronze>::try_new_from_map(&map, 7).expect("should be valid");
+    ///
+    /// assert_eq!(&[Copper(128), Tin(12)], alloy.nuggets());
+    /// ```
+    pub fn try_new_from_map(
+        percentages: &std::collections::HashMap<Metal, f32>,
+        num_ingots: impl TryInto<IngotCount, Error: Into<AlloyError>>,
+    ) -> Result<Self, AlloyError> {
+        Self::try_new(Percentages::from(percentages), num_ingots)
+    }
+
+    /// Gets the number of nuggets of each constituent needed to create the current number of ingots with the current constituent ratios. \
+    /// The nuggets always add up to exactly 20 per ingot, and each constituent's share of them stays within its percentage range.
     /// ### Example
     /// ```rust
     /// use vs_alloy_calculator::prelude::*;
@@ -89,6 +412,20 @@ impl<T: AlloyType> AlloyData<T> {
         self.alloy_type.nuggets()
     }
 
+    /// Gets the alloy type which stores the number of nuggets of each constituent
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = AlloyData::<TinBronze>::default();
+    /// let alloy_type: &TinBronze = alloy.alloy_type();
+    ///
+    /// assert_eq!(&[Copper(18), Tin(2)], alloy_type.as_ref());
+    /// ```
+    pub fn alloy_type(&self) -> &T {
+        &self.alloy_type
+    }
+
     /// Gets the number of ingots that are able to be created with the current constituent amounts
     /// ### Example
     /// ```rust
@@ -103,6 +440,20 @@ impl<T: AlloyType> AlloyData<T> {
         self.num_ingots
     }
 
+    /// Gets the number of ingots as an [`IngotCount`], which is always within what a crucible can make
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::IngotCount;
+    ///
+    /// let alloy = AlloyData::<TinBronze>::default();
+    ///
+    /// assert_eq!(IngotCount::MIN, alloy.ingot_count());
+    /// ```
+    pub fn ingot_count(&self) -> IngotCount {
+        IngotCount(self.num_ingots)
+    }
+
     /// Gets the maximum number of ingots possible with the current constituent percentages
     /// ### Example
     /// ```rust
@@ -128,7 +479,49 @@ impl<T: AlloyType> AlloyData<T> {
     /// assert_eq!(&[Copper(0.92), Tin(0.08)], percentages);
     /// ```
     pub fn percentages(&self) -> &[BaseMetal<f32>] {
-        &self.percentages
+        self.percentages.as_ref()
+    }
+
+    /// Gets the percentages of the constituents as checked [`Percent`] values
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = AlloyData::<TinBronze>::default();
+    /// let tin = alloy.percentages_typed()[1];
+    ///
+    /// assert_eq!(8.0, tin.whole());
+    /// ```
+    pub fn percentages_typed(&self) -> Box<[BaseMetal<Percent>]> {
+        typed(self.percentages.as_ref())
+    }
+
+    /// Gets the percentage of the supplied metal, or `None` when it is not part of the alloy
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = AlloyData::<TinBronze>::default();
+    ///
+    /// assert_eq!(Some(0.08), alloy.percentage_of(Metal::Tin));
+    /// assert_eq!(None, alloy.percentage_of(Metal::Zinc));
+    /// ```
+    pub fn percentage_of(&self, metal: Metal) -> Option<f32> {
+        self.percentages().find_metal(metal).map(BaseMetal::value)
+    }
+
+    /// Gets the nuggets of the supplied metal, or `None` when it is not part of the alloy
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = AlloyData::<TinBronze>::default();
+    ///
+    /// assert_eq!(Some(18), alloy.nuggets_of(Metal::Copper));
+    /// assert_eq!(None, alloy.nuggets_of(Metal::Zinc));
+    /// ```
+    pub fn nuggets_of(&self, metal: Metal) -> Option<i32> {
+        self.nuggets().find_metal(metal).map(BaseMetal::value)
     }
 
     /// Gets the ranges of percentages of the constituents for the given alloy
@@ -145,6 +538,52 @@ impl<T: AlloyType> AlloyData<T> {
         T::percentage_ranges()
     }
 
+    /// Gets the allowed percentage range of the supplied metal or `None` if the alloy does not use it
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::ConstituentRange;
+    ///
+    /// assert_eq!(Some(ConstituentRange::new(0.08, 0.12)), AlloyData::<TinBronze>::range_of(Metal::Tin));
+    /// assert_eq!(None, AlloyData::<TinBronze>::range_of(Metal::Zinc));
+    /// ```
+    pub fn range_of(metal: Metal) -> Option<Range> {
+        T::range_of(metal)
+    }
+
+    /// Gets the metals of the constituents for the given alloy in canonical order
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(&[Metal::Copper, Metal::Zinc], AlloyData::<Brass>::constituent_metals());
+    /// ```
+    pub fn constituent_metals() -> &'static [Metal] {
+        T::constituent_metals()
+    }
+
+    /// Iterates over the metal, range, percentage, and nuggets of each constituent together in canonical order
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = AlloyData::<TinBronze>::default();
+    /// let tin = alloy.constituents().nth(1).expect("should have tin");
+    ///
+    /// assert_eq!(Metal::Tin, tin.metal);
+    /// assert_eq!(0.08, tin.range.min);
+    /// assert_eq!(0.08, tin.percent);
+    /// assert_eq!(2, tin.nuggets);
+    /// ```
+    pub fn constituents(&self) -> Constituents<'_> {
+        Constituents {
+            ranges: T::percentage_ranges(),
+            percentages: self.percentages.as_ref(),
+            nuggets: self.nuggets(),
+            index: 0,
+        }
+    }
+
     /// Tries to update the number of ingots for the alloy. In addition, updates other values if successful.
     /// ### Example
     /// ```rust
@@ -165,10 +604,12 @@ impl<T: AlloyType> AlloyData<T> {
     /// assert_eq!(5, alloy.num_ingots()); // Values were not updated
     /// assert_eq!(&[Copper(92), Tin(8)], alloy.nuggets());
     /// ```
-    pub fn set_num_ingots(&mut self, num_ingots: i32) -> Result<(), AlloyError> {
-        if num_ingots <= 0 {
-            Err(TooFewIngots)
-        } else if self.max_ingots < num_ingots {
+    pub fn set_num_ingots(
+        &mut self,
+        num_ingots: impl TryInto<IngotCount, Error: Into<AlloyError>>,
+    ) -> Result<(), AlloyError> {
+        let num_ingots = num_ingots.try_into().map_err(Into::into)?.get();
+        if self.max_ingots < num_ingots {
             Err(TooManyIngots)
         } else {
             self.update_values(None, Some(num_ingots))?;
@@ -176,7 +617,50 @@ impl<T: AlloyType> AlloyData<T> {
         }
     }
 
-    /// Tries to update the percentages for the alloy. In addition, updates other values if successful.
+    /// Raises the number of ingots by the supplied step, stopping at [`max_ingots`](Self::max_ingots) instead of failing. \
+    /// A negative step lowers it instead, stopping at one ingot. Returns the new number of ingots,
+    /// which is unchanged if the alloy cannot be made at the saturated count.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let mut alloy = AlloyData::<TinBronze>::default();
+    /// assert_eq!(20, alloy.max_ingots());
+    ///
+    /// assert_eq!(2, alloy.increment_ingots(1));
+    /// assert_eq!(20, alloy.increment_ingots(100));
+    /// assert_eq!(&[Copper(368), Tin(32)], alloy.nuggets());
+    /// ```
+    pub fn increment_ingots(&mut self, by: i32) -> i32 {
+        let num_ingots = self
+            .num_ingots
+            .saturating_add(by)
+            .min(self.max_ingots)
+            .max(IngotCount::MIN.get());
+        match self.update_values(None, Some(num_ingots)) {
+            Ok(()) => num_ingots,
+            Err(_) => self.num_ingots,
+        }
+    }
+
+    /// Lowers the number of ingots by the supplied step, stopping at one ingot instead of failing. \
+    /// The same as [`increment_ingots`](Self::increment_ingots) with the step negated.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let mut alloy = AlloyData::<TinBronze>::try_new([Copper(0.92), Tin(0.08)], 5).expect("should be valid");
+    ///
+    /// assert_eq!(4, alloy.decrement_ingots(1));
+    /// assert_eq!(1, alloy.decrement_ingots(100));
+    /// assert_eq!(&[Copper(18), Tin(2)], alloy.nuggets());
+    /// ```
+    pub fn decrement_ingots(&mut self, by: i32) -> i32 {
+        self.increment_ingots(by.saturating_neg())
+    }
+
+    /// Tries to update the percentages for the alloy. In addition, updates other values if successful. \
+    /// Like [`AlloyData::try_new`], the percentages are stored as given; see [`AlloyData::set_percentages_normalized`] to rescale them first.
     /// ### Example
     /// ```rust
     /// use vs_alloy_calculator::prelude::*;
@@ -202,7 +686,7 @@ impl<T: AlloyType> AlloyData<T> {
         &mut self,
         percentages: impl AsRef<[BaseMetal<f32>]>,
     ) -> Result<(), AlloyError> {
-        match T::check_valid_percentages(&percentages) {
+        match validated_percentages::<T>(percentages.as_ref()) {
             Ok(p) => {
                 self.update_values(Some(p), None)?;
                 Ok(())
@@ -212,7 +696,7 @@ impl<T: AlloyType> AlloyData<T> {
     }
 
     /// Checks whether the supplied percentages are valid for the given alloy \
-    /// Returns the validated percentages in a reordered boxed slice or an error if invalid
+    /// Returns the validated percentages reordered into the alloy's inline storage or an error if invalid
     /// ### Example
     /// ```rust
     /// use vs_alloy_calculator::prelude::*;
@@ -220,37 +704,44 @@ impl<T: AlloyType> AlloyData<T> {
     /// let percentages = [Tin(0.08), Copper(0.92)];
     /// assert!(
     ///     AlloyData::<TinBronze>::check_valid_percentages(percentages)
-    ///         .is_ok_and(|p| p == Box::from([Copper(0.92), Tin(0.08)]))
+    ///         .is_ok_and(|p| p == [Copper(0.92), Tin(0.08)])
     /// );
     /// ```
     pub fn check_valid_percentages(
         percentages: impl AsRef<[BaseMetal<f32>]>,
-    ) -> Result<Box<[BaseMetal<f32>]>, AlloyError> {
+    ) -> Result<T::Percentages<f32>, AlloyError> {
         T::check_valid_percentages(percentages)
     }
 
     /// Tries to update the alloy's values. Should pass in either percentage, num_ingots, or both but never neither.
     fn update_values(
         &mut self,
-        percentages: Option<Box<[BaseMetal<f32>]>>,
+        percentages: Option<T::Percentages<f32>>,
         num_ingots: Option<i32>,
     ) -> Result<(), AlloyError> {
         let (alloy_type, max_ingots) = match (percentages, num_ingots) {
             (Some(percentages), Some(num_ingots)) => {
-                let update = Self::get_updated_values(&percentages, num_ingots)?;
+                let update = Self::get_updated_values(percentages.as_ref(), num_ingots)?;
+                self.percentages_f64 = widened::<T>(&percentages);
                 self.percentages = percentages;
                 self.num_ingots = num_ingots;
                 update
             }
             (Some(percentages), None) => {
-                let update = Self::get_updated_values(&percentages, self.num_ingots)?;
+                let update = Self::get_updated_values(percentages.as_ref(), self.num_ingots)?;
+                self.percentages_f64 = widened::<T>(&percentages);
                 self.percentages = percentages;
                 update
             }
             (None, Some(num_ingots)) => {
-                let update = Self::get_updated_values(&self.percentages, num_ingots)?;
+                // Max ingots only depends on the percentages so the cached value is still correct
+                let alloy_type = Self::validated_nuggets(
+                    self.percentages.as_ref(),
+                    num_ingots,
+                    self.max_ingots,
+                )?;
                 self.num_ingots = num_ingots;
-                update
+                (alloy_type, self.max_ingots)
             }
             (None, None) => return Err(InvalidValues),
         };
@@ -264,120 +755,426 @@ impl<T: AlloyType> AlloyData<T> {
         percentages: &[BaseMetal<f32>],
         num_ingots: i32,
     ) -> Result<(T, i32), AlloyError> {
-        use AlloyError::*;
+        let max_ingots = Self::validated_max_ingots(percentages);
+        Ok((
+            Self::validated_nuggets(percentages, num_ingots, max_ingots)?,
+            max_ingots,
+        ))
+    }
+
+    /// Calculates the constituent amounts for the supplied parameters using an already known max number of ingots
+    fn validated_nuggets(
+        percentages: &[BaseMetal<f32>],
+        num_ingots: i32,
+        max_ingots: i32,
+    ) -> Result<T, AlloyError> {
+        Self::nuggets_traced(percentages, num_ingots, max_ingots, None, None)
+    }
+
+    /// Calculates the constituent amounts like [`validated_nuggets`](Self::validated_nuggets) along with any corrections made after rounding
+    fn nuggets_with_adjustments_for(
+        percentages: &[BaseMetal<f32>],
+        num_ingots: i32,
+        max_ingots: i32,
+    ) -> Result<(T, AdjustmentReport), AlloyError> {
+        let mut report = AdjustmentReport::default();
+        let alloy_type =
+            Self::nuggets_traced(percentages, num_ingots, max_ingots, None, Some(&mut report))?;
+        Ok((alloy_type, report))
+    }
+
+    /// Calculates the constituent amounts like [`validated_nuggets`](Self::validated_nuggets),
+    /// recording each step when a trace is supplied and each correction when a report is supplied
+    fn nuggets_traced(
+        percentages: &[BaseMetal<f32>],
+        num_ingots: i32,
+        max_ingots: i32,
+        mut trace: Option<&mut Vec<TraceStep>>,
+        mut report: Option<&mut AdjustmentReport>,
+    ) -> Result<T, AlloyError> {
         use unit_constants::*;
 
-        // Constituent Amounts
-        let needed_units_c = (num_ingots * INGOT_UNIT_AMOUNT) as f32;
-        let mut remaining_units_c = needed_units_c;
-        let mut constituent_amounts = Vec::new();
-        // Max Ingots
-        let needed_units_mi = (MAX_POSSIBLE_INGOTS * INGOT_UNIT_AMOUNT) as f32;
-        let mut remaining_units_mi = needed_units_mi;
+        if num_ingots > max_ingots {
+            return Err(TooManyIngots);
+        }
 
+        let needed_units = ingot_units(num_ingots);
+        if let Some(trace) = trace.as_mut() {
+            trace.push(TraceStep::UnitsNeeded {
+                num_ingots,
+                units: needed_units as f32,
+            });
+        }
+        // Units are kept scaled by the fixed point percentages so every share is exact
+        let nugget_divisor = nugget_units(1) * PERCENT_SCALE;
+        let as_units = |scaled: i64| (scaled as f64 / PERCENT_SCALE as f64) as f32;
+        let slots = |scaled: i64| {
+            let units = div_ceil(scaled, PERCENT_SCALE);
+            i32::try_from(Capacity::VANILLA.slots_for_units(units)).unwrap_or(i32::MAX)
+        };
+        let mut remaining_units = needed_units.saturating_mul(PERCENT_SCALE);
         let len = percentages.len();
-        let slots_used = percentages.iter().enumerate().fold(0.0, |acc, (i, p)| {
-            let p = **p;
-            // Constituent Amounts
+        let mut buffer = [0; MAX_CONSTITUENTS];
+        let constituent_amounts = buffer.get_mut(..len).ok_or(InvalidConstituentAmounts)?;
+        for (i, p) in percentages.iter().enumerate() {
             if i < len - 1 {
-                let units = needed_units_c * p;
-                remaining_units_c -= units;
-                constituent_amounts.push(units as i32 / NUGGET_UNIT_AMOUNT);
+                let units = needed_units.saturating_mul(to_fixed_point(**p));
+                remaining_units -= units;
+                let nuggets = i32::try_from(units / nugget_divisor).map_err(|_| TooManyIngots)?;
+                constituent_amounts[i] = nuggets;
+                if let Some(trace) = trace.as_mut() {
+                    trace.push(TraceStep::Share {
+                        metal: p.metal(),
+                        percentage: **p,
+                        units: as_units(units),
+                        nuggets,
+                        slots: slots(units),
+                    });
+                }
             } else {
-                constituent_amounts
-                    .push((remaining_units_c / NUGGET_UNIT_AMOUNT as f32).ceil() as i32);
+                let nuggets = i32::try_from(div_ceil(remaining_units, nugget_divisor))
+                    .map_err(|_| TooManyIngots)?;
+                constituent_amounts[i] = nuggets;
+                if let Some(trace) = trace.as_mut() {
+                    trace.push(TraceStep::Remainder {
+                        metal: p.metal(),
+                        units: as_units(remaining_units),
+                        nuggets,
+                        slots: slots(remaining_units),
+                    });
+                }
+            }
+        }
+
+        Self::check_constituent_amounts(constituent_amounts, num_ingots, |adjustment| {
+            if let Some(trace) = trace.as_mut() {
+                trace.push(TraceStep::Adjusted(adjustment));
+            }
+            if let Some(report) = report.as_mut() {
+                report.adjustments.push(adjustment);
             }
-            // Max Ingots
-            let units = needed_units_mi * p;
-            remaining_units_mi -= units;
-            acc + (units / MAX_UNITS_PER_SLOT as f32).ceil()
         });
 
-        // Recursively run calculate_max_ingots until max_ingots is calculated
-        let max_ingots = if remaining_units_mi != 0.0 || slots_used > 4.0 {
-            Self::calculate_max_ingots(percentages, MAX_POSSIBLE_INGOTS - 1)
-        } else {
-            MAX_POSSIBLE_INGOTS
-        };
+        T::try_from_amounts(constituent_amounts)
+    }
 
-        if num_ingots > max_ingots {
-            return Err(TooManyIngots);
-        }
+    /// Calculates the maximum number of ingots possible with already validated percentages. \
+    /// This is the expensive part of updating an alloy so it is only called when the percentages change.
+    fn validated_max_ingots(percentages: &[BaseMetal<f32>]) -> i32 {
+        #[cfg(test)]
+        max_ingots_counter::increment();
+        Self::calculate_max_ingots(percentages, Capacity::VANILLA)
+    }
 
-        Self::check_constituent_amounts(&mut constituent_amounts, num_ingots);
+    /// Calculates the nuggets for the alloy type's default percentages and number of ingots,
+    /// without counting towards the max ingots searches or allocating so it can run while creating the default alloy
+    pub(crate) fn definition_nuggets() -> Result<T, AlloyError> {
+        let percentages = validated_percentages::<T>(T::default_percentages())?;
+        let max_ingots = Self::calculate_max_ingots(percentages.as_ref(), Capacity::VANILLA);
+        Self::validated_nuggets(percentages.as_ref(), T::default_ingots(), max_ingots)
+    }
 
-        Ok((T::try_from_vec(constituent_amounts)?, max_ingots))
+    /// Calculates the maximum number of ingots possible with the supplied constituent percentages in the capacity,
+    /// starting at the most that could fit, capped at [`IngotCount::MAX`], and working downwards
+    fn calculate_max_ingots(percentages: &[BaseMetal<f32>], capacity: Capacity) -> i32 {
+        (1..=max_search_ingots(capacity))
+            .rev()
+            .find(|&num_ingots| Self::ingots_fit(percentages, num_ingots, capacity))
+            .unwrap_or(0)
     }
 
-    /// Calculates the maximum number of ingots possible with the supplied constituent percentages starting at high value and working downwards
-    fn calculate_max_ingots(percentages: &[BaseMetal<f32>], cur_ingot_num: i32) -> i32 {
-        let needed_units = cur_ingot_num as f32 * unit_constants::INGOT_UNIT_AMOUNT as f32;
-        let mut remaining_units = needed_units;
+    /// Checks whether the supplied number of ingots can be made with the constituent percentages without overfilling the capacity
+    fn ingots_fit(percentages: &[BaseMetal<f32>], num_ingots: i32, capacity: Capacity) -> bool {
+        let needed_units = unit_constants::ingot_units(num_ingots);
+        // Units are kept scaled by the fixed point percentages so every share is exact
+        let mut remaining_units = needed_units.saturating_mul(PERCENT_SCALE);
 
-        let slots_used = percentages.as_ref().iter().fold(0.0, |acc, p| {
-            let units = needed_units * **p;
+        let fits = capacity.holds_units(percentages.iter().map(|p| {
+            let units = needed_units.saturating_mul(to_fixed_point(**p));
             remaining_units -= units;
-            acc + (units / unit_constants::MAX_UNITS_PER_SLOT as f32).ceil()
-        });
+            div_ceil(units, PERCENT_SCALE)
+        }));
+
+        remaining_units.abs() <= MAX_REMAINING_UNITS_ERROR && fits
+    }
+
+    /// Calculates the nuggets needed for the supplied percentages and number of ingots without creating an alloy. \
+    /// Returns the same nuggets as [`nuggets`](Self::nuggets) on an alloy created with [`try_new`](Self::try_new), or the same error.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let nuggets = AlloyData::<TinBronze>::nuggets_for([Copper(0.92), Tin(0.08)], 7).expect("should be valid");
+    ///
+    /// assert_eq!(&[Copper(128), Tin(12)], nuggets.nuggets());
+    /// AlloyData::<TinBronze>::nuggets_for([Copper(0.92), Tin(0.08)], 21).expect_err("should be too many ingots");
+    /// ```
+    pub fn nuggets_for(
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+        num_ingots: impl TryInto<IngotCount, Error: Into<AlloyError>>,
+    ) -> Result<T, AlloyError> {
+        let num_ingots = num_ingots.try_into().map_err(Into::into)?.get();
+        let percentages = T::check_valid_percentages(percentages)?;
+        let (alloy_type, _) = Self::get_updated_values(percentages.as_ref(), num_ingots)?;
+        Ok(alloy_type)
+    }
+
+    /// Calculates the maximum number of ingots that can be made with the supplied percentages without creating an alloy. \
+    /// Returns the same value as [`max_ingots`](Self::max_ingots) on an alloy created with them, or an error if they are invalid.
+    /// Percentages that cannot make even one ingot return [`TooManyIngots`](AlloyError::TooManyIngots), the same as [`try_new`](Self::try_new).
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(Ok(20), AlloyData::<TinBronze>::max_ingots_for([Copper(0.92), Tin(0.08)]));
+    /// assert_eq!(Ok(21), AlloyData::<TinBronze>::max_ingots_for([Tin(0.12), Copper(0.88)]));
+    /// AlloyData::<TinBronze>::max_ingots_for([Copper(0.8), Tin(0.2)]).expect_err("should be invalid ranges");
+    /// ```
+    pub fn max_ingots_for(percentages: impl AsRef<[BaseMetal<f32>]>) -> Result<i32, AlloyError> {
+        match Self::validated_max_ingots(validated_percentages::<T>(percentages.as_ref())?.as_ref())
+        {
+            0 => Err(TooManyIngots),
+            max_ingots => Ok(max_ingots),
+        }
+    }
+
+    /// Calculates the maximum number of ingots that can be made with the supplied percentages in a crucible of the supplied capacity,
+    /// such as one enlarged or shrunk by a mod. Returns the same value as [`max_ingots_for`](Self::max_ingots_for) for [`Capacity::VANILLA`].
+    ///
+    /// The result is capped at [`IngotCount::MAX`], the most ingots any count can hold, so a larger capacity can only raise it up to that limit
+    /// and never searches past it.
+    /// Alloys are always created for the vanilla crucible, so counts above [`max_ingots_for`](Self::max_ingots_for) still return
+    /// [`TooManyIngots`](AlloyError::TooManyIngots) from [`try_new`](Self::try_new).
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::IngotCount;
+    /// use vs_alloy_calculator::crucible::Capacity;
+    ///
+    /// let small = Capacity { slots: 2, ..Capacity::VANILLA };
+    /// let large = Capacity { slots: 8, ..Capacity::VANILLA };
+    ///
+    /// assert_eq!(Ok(20), AlloyData::<TinBronze>::max_ingots_in([Copper(0.92), Tin(0.08)], Capacity::VANILLA));
+    /// assert_eq!(Ok(6), AlloyData::<TinBronze>::max_ingots_in([Copper(0.92), Tin(0.08)], small));
+    /// assert_eq!(Ok(IngotCount::MAX.get()), AlloyData::<TinBronze>::max_ingots_in([Copper(0.92), Tin(0.08)], large));
+    /// ```
+    pub fn max_ingots_in(
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+        capacity: Capacity,
+    ) -> Result<i32, AlloyError> {
+        Ok(Self::calculate_max_ingots(
+            validated_percentages::<T>(percentages.as_ref())?.as_ref(),
+            capacity,
+        ))
+    }
 
-        if remaining_units != 0.0 || slots_used > 4.0 {
-            Self::calculate_max_ingots(percentages, cur_ingot_num - 1)
+    /// Lists every number of ingots that can be made with the supplied percentages, checking each count on its own
+    /// rather than assuming everything up to the max is possible. \
+    /// Percentages that cannot make even one ingot return [`TooManyIngots`](AlloyError::TooManyIngots) instead of an empty list.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let counts = AlloyData::<TinBronze>::achievable_ingot_counts([Copper(0.92), Tin(0.08)]).expect("should be valid");
+    ///
+    /// assert_eq!((1..=20).collect::<Vec<_>>(), counts);
+    /// ```
+    pub fn achievable_ingot_counts(
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+    ) -> Result<Vec<i32>, AlloyError> {
+        let counts = Self::achievable_counts_for(T::check_valid_percentages(percentages)?.as_ref());
+        if counts.is_empty() {
+            Err(TooManyIngots)
         } else {
-            cur_ingot_num
+            Ok(counts)
         }
     }
 
-    fn check_constituent_amounts(amounts: &mut Vec<i32>, num_ingots: i32) {
+    /// Lists every number of ingots that can be made with already validated percentages
+    fn achievable_counts_for(percentages: &[BaseMetal<f32>]) -> Vec<i32> {
+        (1..=unit_constants::MAX_POSSIBLE_INGOTS)
+            .filter(|&num_ingots| Self::ingots_fit(percentages, num_ingots, Capacity::VANILLA))
+            .collect()
+    }
+
+    /// Percentages rounded to basis points, the canonical form used for equality, ordering, and hashing
+    fn basis_points(&self) -> impl Iterator<Item = i64> + '_ {
+        self.percentages
+            .as_ref()
+            .iter()
+            .map(|p| to_basis_points(**p))
+    }
+
+    /// Gets updated values using percentages supplied in basis points (hundredths of a percent) with exact integer math. \
+    /// Mirrors [`get_updated_values`](Self::get_updated_values) so both converge on the same nuggets.
+    fn get_updated_values_basis_points(
+        basis_points: &[i64],
+        num_ingots: i32,
+    ) -> Result<(T, i32), AlloyError> {
         use unit_constants::*;
 
-        let two_constituents = amounts.len() == 2;
-        let invalid_sum = amounts.iter().sum::<i32>() != NUM_NUGGETS_PER_INGOT * num_ingots;
+        let max_ingots = Self::calculate_max_ingots_basis_points(basis_points, Capacity::VANILLA);
+        if num_ingots > max_ingots {
+            return Err(TooManyIngots);
+        }
 
-        let (a, amounts) = amounts.split_at_mut(1);
-        let a = &mut a[0];
-        let (b, c) = amounts.split_at_mut(1);
-        let b = &mut b[0];
+        let needed_units = ingot_units(num_ingots);
+        let nugget_units = i64::from(NUGGET_UNIT_AMOUNT);
+        let (last, rest) = basis_points.split_last().ok_or(InvalidPercentages)?;
+        // The last constituent takes whatever is left over
+        let divisor = BASIS_POINTS * nugget_units;
+        let mut buffer = [0; MAX_CONSTITUENTS];
+        let constituent_amounts = buffer
+            .get_mut(..basis_points.len())
+            .ok_or(InvalidConstituentAmounts)?;
+        let nuggets = rest
+            .iter()
+            .map(|bp| needed_units * bp / divisor)
+            .chain([(needed_units * last + divisor - 1) / divisor]);
+        for (amount, nuggets) in constituent_amounts.iter_mut().zip(nuggets) {
+            *amount = i32::try_from(nuggets).map_err(|_| TooManyIngots)?;
+        }
 
-        let ranges = Self::percentage_ranges();
-        let max_b = (ranges[1].max * (NUM_NUGGETS_PER_INGOT * num_ingots) as f32).floor() as i32;
+        Self::check_constituent_amounts(constituent_amounts, num_ingots, |_| ());
 
-        if two_constituents {
-            if invalid_sum {
-                if *b < max_b {
-                    *b += 1;
-                } else {
-                    *a += 1;
-                }
-            } else if *b > max_b {
-                *b -= 1;
-                *a += 1;
+        Ok((T::try_from_amounts(constituent_amounts)?, max_ingots))
+    }
+
+    /// Calculates the maximum number of ingots that fit in the supplied capacity with the basis point percentages using exact integer math,
+    /// capped at [`IngotCount::MAX`]
+    pub(crate) fn calculate_max_ingots_basis_points(
+        basis_points: &[i64],
+        capacity: Capacity,
+    ) -> i32 {
+        (1..=max_search_ingots(capacity))
+            .rev()
+            .find(|&num_ingots| {
+                let needed_units = unit_constants::ingot_units(num_ingots);
+                capacity.holds_units(
+                    basis_points
+                        .iter()
+                        .map(|bp| (needed_units * bp + BASIS_POINTS - 1) / BASIS_POINTS),
+                )
+            })
+            .unwrap_or(0)
+    }
+
+    /// Moves nuggets between constituents when rounding leaves the wrong total or a constituent outside of its range,
+    /// so the nuggets always add up to [`ingot_nuggets`](unit_constants::ingot_nuggets) and each constituent stays within its range
+    /// whenever the batch allows it. Every nugget that was moved is passed to `record`.
+    fn check_constituent_amounts(
+        amounts: &mut [i32],
+        num_ingots: i32,
+        mut record: impl FnMut(Adjustment),
+    ) {
+        use AdjustmentReason::*;
+        use unit_constants::*;
+
+        let mut shift = |amounts: &mut [i32], index: usize, change: i32, reason| {
+            amounts[index] += change;
+            record(Adjustment {
+                metal: T::constituent_metals()[index],
+                change,
+                reason,
+            });
+        };
+
+        let total = ingot_nuggets(num_ingots);
+        // Whole nugget bounds of each constituent for this batch, using exact integer math
+        let mut bounds = [(0, i32::MAX); MAX_CONSTITUENTS];
+        for (bound, range) in bounds.iter_mut().zip(T::percentage_ranges()) {
+            let low = (to_basis_points(range.min) * total + BASIS_POINTS - 1) / BASIS_POINTS;
+            let high = to_basis_points(range.max) * total / BASIS_POINTS;
+            *bound = (
+                i32::try_from(low).unwrap_or(i32::MAX),
+                i32::try_from(high).unwrap_or(i32::MAX),
+            );
+        }
+        let has_room = |amounts: &[i32], i: usize| amounts[i] < bounds[i].1;
+        let has_spare = |amounts: &[i32], i: usize| amounts[i] > bounds[i].0;
+        // The latest other constituent that passes the check, if any
+        let latest =
+            |amounts: &[i32], skip: Option<usize>, check: &dyn Fn(&[i32], usize) -> bool| {
+                (0..amounts.len())
+                    .rev()
+                    .find(|&i| Some(i) != skip && check(amounts, i))
+            };
+        let sum = |amounts: &[i32]| amounts.iter().map(|&a| i64::from(a)).sum::<i64>();
+
+        // The latest constituent with room to spare takes a missing nugget, falling back to the first
+        while sum(amounts) < total {
+            let index = latest(amounts, None, &has_room).unwrap_or(0);
+            shift(amounts, index, 1, SumCorrection);
+        }
+        while sum(amounts) > total {
+            let index = latest(amounts, None, &has_spare).unwrap_or(0);
+            shift(amounts, index, -1, SumCorrection);
+        }
+
+        // Nuggets move one at a time from a constituent outside its range to the latest one that can take them
+        for i in (0..amounts.len()).rev() {
+            while amounts[i] > bounds[i].1 {
+                let Some(index) = latest(amounts, Some(i), &has_room) else {
+                    break;
+                };
+                shift(amounts, i, -1, RangeClamp);
+                shift(amounts, index, 1, RangeClamp);
             }
-        } else {
-            let c = &mut c[0];
-            let max_c =
-                (ranges[2].max * (NUM_NUGGETS_PER_INGOT * num_ingots) as f32).floor() as i32;
-
-            if invalid_sum {
-                if *c < max_c {
-                    *c += 1;
-                } else if *b < max_b {
-                    *b += 1;
-                } else {
-                    *a += 1;
-                }
-            } else if *c > max_c {
-                *c -= 1;
-                if *b + 1 > max_b {
-                    *a += 1;
-                } else {
-                    *b += 1;
-                }
+            while amounts[i] < bounds[i].0 {
+                let Some(index) = latest(amounts, Some(i), &has_spare) else {
+                    break;
+                };
+                shift(amounts, index, -1, RangeClamp);
+                shift(amounts, i, 1, RangeClamp);
             }
         }
     }
 }
 
 impl Alloy {
+    /// Tries to create a new instance of the alloy chosen at runtime, the same as [`Alloys::try_new`]. \
+    /// Fails with the same errors as [`AlloyData::try_new`] for the matching alloy type.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::AlloyError;
+    ///
+    /// let alloy = Alloy::try_new(Alloys::Electrum, [Gold(0.5), Silver(0.5)], 2).expect("should be valid");
+    /// assert_eq!(&[Gold(20), Silver(20)], alloy.nuggets());
+    ///
+    /// assert_eq!(Err(AlloyError::TooFewIngots), Alloy::try_new(Alloys::Electrum, [Gold(0.5), Silver(0.5)], 0));
+    /// ```
+    pub fn try_new(
+        kind: Alloys,
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+        num_ingots: impl TryInto<IngotCount, Error: Into<AlloyError>>,
+    ) -> Result<Alloy, AlloyError> {
+        kind.try_new(percentages, num_ingots)
+    }
+
+    /// Returns which kind of alloy this is
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = Alloys::Brass.get_default();
+    ///
+    /// assert!(Alloys::Brass == alloy.kind());
+    /// ```
+    pub fn kind(&self) -> Alloys {
+        match self {
+            Alloy::TinBronze(_) => Alloys::TinBronze,
+            Alloy::BismuthBronze(_) => Alloys::BismuthBronze,
+            Alloy::BlackBronze(_) => Alloys::BlackBronze,
+            Alloy::Brass(_) => Alloys::Brass,
+            Alloy::Molybdochalkos(_) => Alloys::Molybdochalkos,
+            Alloy::LeadSolder(_) => Alloys::LeadSolder,
+            Alloy::SilverSolder(_) => Alloys::SilverSolder,
+            Alloy::Electrum(_) => Alloys::Electrum,
+            Alloy::Cupronickel(_) => Alloys::Cupronickel,
+        }
+    }
+
     /// Returns the name of the alloy as a string
     /// ### Example
     /// ```rust
@@ -403,7 +1200,8 @@ impl Alloy {
         }
     }
 
-    /// Gets the number of nuggets of each constituent needed to create the current number of ingots with the current constituent ratios
+    /// Gets the number of nuggets of each constituent needed to create the current number of ingots with the current constituent ratios. \
+    /// The nuggets always add up to exactly 20 per ingot, and each constituent's share of them stays within its percentage range.
     /// ### Example
     /// ```rust
     /// use vs_alloy_calculator::prelude::*;
@@ -415,15 +1213,15 @@ impl Alloy {
     /// ```
     pub fn nuggets(&self) -> &[BaseMetal<i32>] {
         match self {
-            Alloy::TinBronze(alloy_data) => alloy_data.alloy_type.nuggets(),
-            Alloy::BismuthBronze(alloy_data) => alloy_data.alloy_type.nuggets(),
-            Alloy::BlackBronze(alloy_data) => alloy_data.alloy_type.nuggets(),
-            Alloy::Brass(alloy_data) => alloy_data.alloy_type.nuggets(),
-            Alloy::Molybdochalkos(alloy_data) => alloy_data.alloy_type.nuggets(),
-            Alloy::LeadSolder(alloy_data) => alloy_data.alloy_type.nuggets(),
-            Alloy::SilverSolder(alloy_data) => alloy_data.alloy_type.nuggets(),
-            Alloy::Electrum(alloy_data) => alloy_data.alloy_type.nuggets(),
-            Alloy::Cupronickel(alloy_data) => alloy_data.alloy_type.nuggets(),
+            Alloy::TinBronze(alloy_data) => alloy_data.nuggets(),
+            Alloy::BismuthBronze(alloy_data) => alloy_data.nuggets(),
+            Alloy::BlackBronze(alloy_data) => alloy_data.nuggets(),
+            Alloy::Brass(alloy_data) => alloy_data.nuggets(),
+            Alloy::Molybdochalkos(alloy_data) => alloy_data.nuggets(),
+            Alloy::LeadSolder(alloy_data) => alloy_data.nuggets(),
+            Alloy::SilverSolder(alloy_data) => alloy_data.nuggets(),
+            Alloy::Electrum(alloy_data) => alloy_data.nuggets(),
+            Alloy::Cupronickel(alloy_data) => alloy_data.nuggets(),
         }
     }
 
@@ -439,18 +1237,32 @@ impl Alloy {
     /// ```
     pub fn num_ingots(&self) -> i32 {
         match self {
-            Alloy::TinBronze(alloy_data) => alloy_data.num_ingots,
-            Alloy::BismuthBronze(alloy_data) => alloy_data.num_ingots,
-            Alloy::BlackBronze(alloy_data) => alloy_data.num_ingots,
-            Alloy::Brass(alloy_data) => alloy_data.num_ingots,
-            Alloy::Molybdochalkos(alloy_data) => alloy_data.num_ingots,
-            Alloy::LeadSolder(alloy_data) => alloy_data.num_ingots,
-            Alloy::SilverSolder(alloy_data) => alloy_data.num_ingots,
-            Alloy::Electrum(alloy_data) => alloy_data.num_ingots,
-            Alloy::Cupronickel(alloy_data) => alloy_data.num_ingots,
+            Alloy::TinBronze(alloy_data) => alloy_data.num_ingots(),
+            Alloy::BismuthBronze(alloy_data) => alloy_data.num_ingots(),
+            Alloy::BlackBronze(alloy_data) => alloy_data.num_ingots(),
+            Alloy::Brass(alloy_data) => alloy_data.num_ingots(),
+            Alloy::Molybdochalkos(alloy_data) => alloy_data.num_ingots(),
+            Alloy::LeadSolder(alloy_data) => alloy_data.num_ingots(),
+            Alloy::SilverSolder(alloy_data) => alloy_data.num_ingots(),
+            Alloy::Electrum(alloy_data) => alloy_data.num_ingots(),
+            Alloy::Cupronickel(alloy_data) => alloy_data.num_ingots(),
         }
     }
 
+    /// Gets the number of ingots as an [`IngotCount`], which is always within what a crucible can make
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::IngotCount;
+    ///
+    /// let alloy = Alloys::TinBronze.get_default();
+    ///
+    /// assert_eq!(IngotCount::MIN, alloy.ingot_count());
+    /// ```
+    pub fn ingot_count(&self) -> IngotCount {
+        IngotCount(self.num_ingots())
+    }
+
     /// Gets the maximum number of ingots possible with the current constituent percentages
     /// ### Example
     /// ```rust
@@ -463,15 +1275,48 @@ impl Alloy {
     /// ```
     pub fn max_ingots(&self) -> i32 {
         match self {
-            Alloy::TinBronze(alloy_data) => alloy_data.max_ingots,
-            Alloy::BismuthBronze(alloy_data) => alloy_data.max_ingots,
-            Alloy::BlackBronze(alloy_data) => alloy_data.max_ingots,
-            Alloy::Brass(alloy_data) => alloy_data.max_ingots,
-            Alloy::Molybdochalkos(alloy_data) => alloy_data.max_ingots,
-            Alloy::LeadSolder(alloy_data) => alloy_data.max_ingots,
-            Alloy::SilverSolder(alloy_data) => alloy_data.max_ingots,
-            Alloy::Electrum(alloy_data) => alloy_data.max_ingots,
-            Alloy::Cupronickel(alloy_data) => alloy_data.max_ingots,
+            Alloy::TinBronze(alloy_data) => alloy_data.max_ingots(),
+            Alloy::BismuthBronze(alloy_data) => alloy_data.max_ingots(),
+            Alloy::BlackBronze(alloy_data) => alloy_data.max_ingots(),
+            Alloy::Brass(alloy_data) => alloy_data.max_ingots(),
+            Alloy::Molybdochalkos(alloy_data) => alloy_data.max_ingots(),
+            Alloy::LeadSolder(alloy_data) => alloy_data.max_ingots(),
+            Alloy::SilverSolder(alloy_data) => alloy_data.max_ingots(),
+            Alloy::Electrum(alloy_data) => alloy_data.max_ingots(),
+            Alloy::Cupronickel(alloy_data) => alloy_data.max_ingots(),
+        }
+    }
+
+    /// Lists every number of ingots that can be made with the alloy's current percentages
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = Alloys::Brass.get_default();
+    ///
+    /// assert_eq!(alloy.max_ingots(), *alloy.achievable_ingot_counts().last().unwrap());
+    /// ```
+    pub fn achievable_ingot_counts(&self) -> Vec<i32> {
+        match self {
+            Alloy::TinBronze(a) => AlloyData::<TinBronze>::achievable_counts_for(a.percentages()),
+            Alloy::BismuthBronze(a) => {
+                AlloyData::<BismuthBronze>::achievable_counts_for(a.percentages())
+            }
+            Alloy::BlackBronze(a) => {
+                AlloyData::<BlackBronze>::achievable_counts_for(a.percentages())
+            }
+            Alloy::Brass(a) => AlloyData::<Brass>::achievable_counts_for(a.percentages()),
+            Alloy::Molybdochalkos(a) => {
+                AlloyData::<Molybdochalkos>::achievable_counts_for(a.percentages())
+            }
+            Alloy::LeadSolder(a) => AlloyData::<LeadSolder>::achievable_counts_for(a.percentages()),
+            Alloy::SilverSolder(a) => {
+                AlloyData::<SilverSolder>::achievable_counts_for(a.percentages())
+            }
+            Alloy::Electrum(a) => AlloyData::<Electrum>::achievable_counts_for(a.percentages()),
+            Alloy::Cupronickel(a) => {
+                AlloyData::<Cupronickel>::achievable_counts_for(a.percentages())
+            }
         }
     }
 
@@ -487,15 +1332,110 @@ impl Alloy {
     /// ```
     pub fn percentages(&self) -> &[BaseMetal<f32>] {
         match self {
-            Alloy::TinBronze(alloy_data) => &alloy_data.percentages,
-            Alloy::BismuthBronze(alloy_data) => &alloy_data.percentages,
-            Alloy::BlackBronze(alloy_data) => &alloy_data.percentages,
-            Alloy::Brass(alloy_data) => &alloy_data.percentages,
-            Alloy::Molybdochalkos(alloy_data) => &alloy_data.percentages,
-            Alloy::LeadSolder(alloy_data) => &alloy_data.percentages,
-            Alloy::SilverSolder(alloy_data) => &alloy_data.percentages,
-            Alloy::Electrum(alloy_data) => &alloy_data.percentages,
-            Alloy::Cupronickel(alloy_data) => &alloy_data.percentages,
+            Alloy::TinBronze(alloy_data) => alloy_data.percentages(),
+            Alloy::BismuthBronze(alloy_data) => alloy_data.percentages(),
+            Alloy::BlackBronze(alloy_data) => alloy_data.percentages(),
+            Alloy::Brass(alloy_data) => alloy_data.percentages(),
+            Alloy::Molybdochalkos(alloy_data) => alloy_data.percentages(),
+            Alloy::LeadSolder(alloy_data) => alloy_data.percentages(),
+            Alloy::SilverSolder(alloy_data) => alloy_data.percentages(),
+            Alloy::Electrum(alloy_data) => alloy_data.percentages(),
+            Alloy::Cupronickel(alloy_data) => alloy_data.percentages(),
+        }
+    }
+
+    /// Gets the percentages of the constituents as checked [`Percent`] values
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = Alloys::Brass.get_default();
+    /// let zinc = alloy.percentages_typed()[1];
+    ///
+    /// assert_eq!(Metal::Zinc, zinc.metal());
+    /// assert_eq!(alloy.percentages()[1].into_inner(), zinc.into_inner().fraction());
+    /// ```
+    pub fn percentages_typed(&self) -> Box<[BaseMetal<Percent>]> {
+        typed(self.percentages())
+    }
+
+    /// Gets the percentage of the supplied metal, or `None` when it is not part of the alloy
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = Alloys::TinBronze.get_default();
+    ///
+    /// assert_eq!(Some(0.08), alloy.percentage_of(Metal::Tin));
+    /// assert_eq!(None, alloy.percentage_of(Metal::Zinc));
+    /// ```
+    pub fn percentage_of(&self, metal: Metal) -> Option<f32> {
+        self.percentages().find_metal(metal).map(BaseMetal::value)
+    }
+
+    /// Gets the nuggets of the supplied metal, or `None` when it is not part of the alloy
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = Alloys::TinBronze.get_default();
+    ///
+    /// assert_eq!(Some(18), alloy.nuggets_of(Metal::Copper));
+    /// assert_eq!(None, alloy.nuggets_of(Metal::Zinc));
+    /// ```
+    pub fn nuggets_of(&self, metal: Metal) -> Option<i32> {
+        self.nuggets().find_metal(metal).map(BaseMetal::value)
+    }
+
+    /// Returns the allowed percentage range of the supplied metal or `None` if the alloy does not use it
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::ConstituentRange;
+    ///
+    /// let alloy = Alloys::Brass.get_default();
+    ///
+    /// assert_eq!(Some(ConstituentRange::new(0.30, 0.40)), alloy.range_of(Metal::Zinc));
+    /// assert_eq!(None, alloy.range_of(Metal::Tin));
+    /// ```
+    pub fn range_of(&self, metal: Metal) -> Option<Range> {
+        self.kind().range_of(metal)
+    }
+
+    /// Returns the metals of the constituents in canonical order
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = Alloys::Electrum.get_default();
+    ///
+    /// assert_eq!(&[Metal::Gold, Metal::Silver], alloy.constituent_metals());
+    /// ```
+    pub fn constituent_metals(&self) -> &'static [Metal] {
+        self.kind().constituent_metals()
+    }
+
+    /// Iterates over the metal, range, percentage, and nuggets of each constituent together in canonical order
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = Alloys::TinBronze.get_default();
+    /// let metals: Vec<Metal> = alloy.constituents().map(|c| c.metal).collect();
+    ///
+    /// assert_eq!(vec![Metal::Copper, Metal::Tin], metals);
+    /// ```
+    pub fn constituents(&self) -> Constituents<'_> {
+        match self {
+            Alloy::TinBronze(alloy_data) => alloy_data.constituents(),
+            Alloy::BismuthBronze(alloy_data) => alloy_data.constituents(),
+            Alloy::BlackBronze(alloy_data) => alloy_data.constituents(),
+            Alloy::Brass(alloy_data) => alloy_data.constituents(),
+            Alloy::Molybdochalkos(alloy_data) => alloy_data.constituents(),
+            Alloy::LeadSolder(alloy_data) => alloy_data.constituents(),
+            Alloy::SilverSolder(alloy_data) => alloy_data.constituents(),
+            Alloy::Electrum(alloy_data) => alloy_data.constituents(),
+            Alloy::Cupronickel(alloy_data) => alloy_data.constituents(),
         }
     }
 
@@ -519,7 +1459,11 @@ impl Alloy {
     /// assert_eq!(5, alloy.num_ingots()); // Values were not updated
     /// assert_eq!(&[Copper(92), Tin(8)], alloy.nuggets());
     /// ```
-    pub fn set_num_ingots(&mut self, num_ingots: i32) -> Result<(), AlloyError> {
+    pub fn set_num_ingots(
+        &mut self,
+        num_ingots: impl TryInto<IngotCount, Error: Into<AlloyError>>,
+    ) -> Result<(), AlloyError> {
+        let num_ingots: IngotCount = num_ingots.try_into().map_err(Into::into)?;
         match self {
             Alloy::TinBronze(alloy_data) => alloy_data.set_num_ingots(num_ingots),
             Alloy::BismuthBronze(alloy_data) => alloy_data.set_num_ingots(num_ingots),
@@ -533,6 +1477,81 @@ impl Alloy {
         }
     }
 
+    /// Raises the number of ingots by the supplied step, stopping at the max, the same as [`AlloyData::increment_ingots`]
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let mut alloy = Alloys::TinBronze.get_default();
+    /// assert_eq!(20, alloy.max_ingots());
+    ///
+    /// assert_eq!(2, alloy.increment_ingots(1));
+    /// assert_eq!(20, alloy.increment_ingots(100));
+    /// assert_eq!(20, alloy.increment_ingots(1)); // Already at the max
+    /// ```
+    pub fn increment_ingots(&mut self, by: i32) -> i32 {
+        match self {
+            Alloy::TinBronze(alloy_data) => alloy_data.increment_ingots(by),
+            Alloy::BismuthBronze(alloy_data) => alloy_data.increment_ingots(by),
+            Alloy::BlackBronze(alloy_data) => alloy_data.increment_ingots(by),
+            Alloy::Brass(alloy_data) => alloy_data.increment_ingots(by),
+            Alloy::Molybdochalkos(alloy_data) => alloy_data.increment_ingots(by),
+            Alloy::LeadSolder(alloy_data) => alloy_data.increment_ingots(by),
+            Alloy::SilverSolder(alloy_data) => alloy_data.increment_ingots(by),
+            Alloy::Electrum(alloy_data) => alloy_data.increment_ingots(by),
+            Alloy::Cupronickel(alloy_data) => alloy_data.increment_ingots(by),
+        }
+    }
+
+    /// Resets the alloy to the defaults for its type, the same as [`AlloyData::reset_to_default`]
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let mut alloy = Alloys::Brass.try_new([Copper(0.7), Zinc(0.3)], 4).expect("should be valid");
+    /// alloy.reset_to_default();
+    ///
+    /// assert_eq!(Alloys::Brass.get_default(), alloy);
+    /// ```
+    pub fn reset_to_default(&mut self) {
+        match self {
+            Alloy::TinBronze(alloy_data) => alloy_data.reset_to_default(),
+            Alloy::BismuthBronze(alloy_data) => alloy_data.reset_to_default(),
+            Alloy::BlackBronze(alloy_data) => alloy_data.reset_to_default(),
+            Alloy::Brass(alloy_data) => alloy_data.reset_to_default(),
+            Alloy::Molybdochalkos(alloy_data) => alloy_data.reset_to_default(),
+            Alloy::LeadSolder(alloy_data) => alloy_data.reset_to_default(),
+            Alloy::SilverSolder(alloy_data) => alloy_data.reset_to_default(),
+            Alloy::Electrum(alloy_data) => alloy_data.reset_to_default(),
+            Alloy::Cupronickel(alloy_data) => alloy_data.reset_to_default(),
+        }
+    }
+
+    /// Lowers the number of ingots by the supplied step, stopping at one ingot, the same as [`AlloyData::decrement_ingots`]
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let mut alloy = Alloys::TinBronze.try_new([Copper(0.92), Tin(0.08)], 5).expect("should be valid");
+    ///
+    /// assert_eq!(4, alloy.decrement_ingots(1));
+    /// assert_eq!(1, alloy.decrement_ingots(100));
+    /// assert_eq!(1, alloy.decrement_ingots(1)); // Already at one ingot
+    /// ```
+    pub fn decrement_ingots(&mut self, by: i32) -> i32 {
+        match self {
+            Alloy::TinBronze(alloy_data) => alloy_data.decrement_ingots(by),
+            Alloy::BismuthBronze(alloy_data) => alloy_data.decrement_ingots(by),
+            Alloy::BlackBronze(alloy_data) => alloy_data.decrement_ingots(by),
+            Alloy::Brass(alloy_data) => alloy_data.decrement_ingots(by),
+            Alloy::Molybdochalkos(alloy_data) => alloy_data.decrement_ingots(by),
+            Alloy::LeadSolder(alloy_data) => alloy_data.decrement_ingots(by),
+            Alloy::SilverSolder(alloy_data) => alloy_data.decrement_ingots(by),
+            Alloy::Electrum(alloy_data) => alloy_data.decrement_ingots(by),
+            Alloy::Cupronickel(alloy_data) => alloy_data.decrement_ingots(by),
+        }
+    }
+
     /// Tries to update the percentages for the alloy. In addition, updates other values if successful.
     /// ### Example
     /// ```rust
@@ -573,3 +1592,155 @@ impl Alloy {
         }
     }
 }
+
+impl TryFrom<(Alloys, &[BaseMetal<f32>], i32)> for Alloy {
+    type Error = AlloyError;
+
+    /// Creates the alloy at runtime like [`Alloys::try_new`]. \
+    /// The error shows which stage failed, [`TooManyIngots`] or [`TooFewIngots`] for the number of ingots
+    /// and [`InvalidPercentages`] or one of the constituent errors such as [`UnknownConstituent`] for the percentages.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let percentages = [Copper(0.7), Zinc(0.3)];
+    /// let alloy = Alloy::try_from((Alloys::Brass, &percentages[..], 5)).expect("should be valid");
+    ///
+    /// assert_eq!(&[Copper(70), Zinc(30)], alloy.nuggets());
+    /// ```
+    fn try_from(
+        (alloy, percentages, num_ingots): (Alloys, &[BaseMetal<f32>], i32),
+    ) -> Result<Self, Self::Error> {
+        alloy.try_new(percentages, num_ingots)
+    }
+}
+
+impl TryFrom<(Alloys, Vec<BaseMetal<f32>>, i32)> for Alloy {
+    type Error = AlloyError;
+
+    /// Creates the alloy at runtime from owned percentages like [`Alloys::try_new`]
+    fn try_from(
+        (alloy, percentages, num_ingots): (Alloys, Vec<BaseMetal<f32>>, i32),
+    ) -> Result<Self, Self::Error> {
+        alloy.try_new(percentages, num_ingots)
+    }
+}
+
+impl TryFrom<(Alloys, i32)> for Alloy {
+    type Error = AlloyError;
+
+    /// Creates the alloy at runtime using its default percentages
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = Alloy::try_from((Alloys::TinBronze, 5)).expect("should be valid");
+    ///
+    /// assert_eq!(&[Copper(92), Tin(8)], alloy.nuggets());
+    /// ```
+    fn try_from((alloy, num_ingots): (Alloys, i32)) -> Result<Self, Self::Error> {
+        alloy.try_new(alloy.default_percentages(), num_ingots)
+    }
+}
+
+/// Error from converting an [`Alloy`] into the [`AlloyData`] of a different alloy type. Gives back the alloy that was converted.
+/// ### Example
+/// ```rust
+/// use vs_alloy_calculator::prelude::*;
+///
+/// let alloy = Alloys::Brass.get_default();
+/// assert!(alloy.as_brass().is_some());
+/// assert!(alloy.as_tin_bronze().is_none());
+///
+/// let error = AlloyData::<TinBronze>::try_from(alloy).unwrap_err();
+/// assert_eq!(Alloys::Brass, error.actual);
+/// assert_eq!("expected Tin Bronze but found Brass", error.to_string());
+///
+/// let brass = AlloyData::<Brass>::try_from(error.alloy).expect("should be brass");
+/// assert_eq!(&[Copper(14), Zinc(6)], brass.nuggets());
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AlloyMismatch {
+    /// The alloy that was asked for
+    pub expected: Alloys,
+    /// The alloy that was actually held
+    pub actual: Alloys,
+    /// The alloy that failed to convert
+    pub alloy: Alloy,
+}
+
+impl std::fmt::Display for AlloyMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected {} but found {}", self.expected, self.actual)
+    }
+}
+
+impl std::error::Error for AlloyMismatch {}
+
+/// Implements the conversions between each alloy type's [`AlloyData`] and its [`Alloy`] variant, which share the type's name. \
+/// Only the built-in types have an [`Alloys`] variant, so alloys declared in other crates get none of these.
+macro_rules! impl_alloy_conversions {
+    ($($alloy_type:ident => $as_alloy:ident),* $(,)?) => {
+        $(
+            impl From<&AlloyData<$alloy_type>> for Alloys {
+                /// Names the kind of alloy
+                fn from(_: &AlloyData<$alloy_type>) -> Self {
+                    Alloys::$alloy_type
+                }
+            }
+
+            impl From<&$alloy_type> for Alloys {
+                /// Names the kind of alloy
+                fn from(_: &$alloy_type) -> Self {
+                    Alloys::$alloy_type
+                }
+            }
+
+            impl From<AlloyData<$alloy_type>> for Alloy {
+                /// Wraps the alloy in its matching variant
+                fn from(alloy_data: AlloyData<$alloy_type>) -> Self {
+                    Alloy::$alloy_type(alloy_data)
+                }
+            }
+
+            impl TryFrom<Alloy> for AlloyData<$alloy_type> {
+                type Error = AlloyMismatch;
+
+                /// Unwraps the alloy when it is the matching variant
+                fn try_from(alloy: Alloy) -> Result<Self, Self::Error> {
+                    match alloy {
+                        Alloy::$alloy_type(alloy_data) => Ok(alloy_data),
+                        alloy => Err(AlloyMismatch {
+                            expected: Alloys::$alloy_type,
+                            actual: alloy.kind(),
+                            alloy,
+                        }),
+                    }
+                }
+            }
+        )*
+
+        impl Alloy {
+            $(
+                #[doc = concat!("Borrows the alloy as `AlloyData<", stringify!($alloy_type), ">` when it is that variant")]
+                pub fn $as_alloy(&self) -> Option<&AlloyData<$alloy_type>> {
+                    match self {
+                        Alloy::$alloy_type(alloy_data) => Some(alloy_data),
+                        _ => None,
+                    }
+                }
+            )*
+        }
+    };
+}
+impl_alloy_conversions!(
+    TinBronze => as_tin_bronze,
+    BismuthBronze => as_bismuth_bronze,
+    BlackBronze => as_black_bronze,
+    Brass => as_brass,
+    Molybdochalkos => as_molybdochalkos,
+    LeadSolder => as_lead_solder,
+    SilverSolder => as_silver_solder,
+    Electrum => as_electrum,
+    Cupronickel => as_cupronickel,
+);
@@ -0,0 +1,394 @@
+//! # Creation Options
+//! Opt-in adjustments applied to the inputs before an alloy is validated and created.
+use super::*;
+
+/// Options for [`AlloyData::try_new_with_options`]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CreateOptions {
+    /// Proportionally rescale the percentages to total exactly 1.0 before validating them
+    pub normalize_to_one: bool,
+    /// Largest distance the percentages may total from 1.0 and still be normalized
+    pub max_correction: f32,
+}
+
+/// Options that rescale any total within the default correction to exactly 1.0
+const NORMALIZE: CreateOptions = CreateOptions {
+    normalize_to_one: true,
+    max_correction: 0.02,
+};
+
+impl Default for CreateOptions {
+    fn default() -> Self {
+        Self {
+            normalize_to_one: false,
+            max_correction: 0.02,
+        }
+    }
+}
+
+/// An alloy created with [`CreateOptions`] along with any adjustment that was made to the inputs
+#[derive(PartialEq, Debug)]
+pub struct Adjusted<A> {
+    /// The newly created alloy, whose percentages are the adjusted values
+    pub alloy: A,
+    /// Factor each percentage was multiplied by to total 1.0, which is 1.0 when no normalization was needed or requested
+    pub scale: f32,
+}
+
+impl<A> Adjusted<A> {
+    /// Wraps the adjusted alloy, keeping the scale
+    fn map_alloy<B>(self, wrap: impl FnOnce(A) -> B) -> Adjusted<B> {
+        Adjusted {
+            alloy: wrap(self.alloy),
+            scale: self.scale,
+        }
+    }
+}
+
+/// Outcome of [`AlloyData::set_percentages_adjusting_ingots`]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AdjustOutcome {
+    /// Number of ingots before the percentages were updated
+    pub previous_ingots: i32,
+    /// Number of ingots after the percentages were updated
+    pub num_ingots: i32,
+}
+
+impl AdjustOutcome {
+    /// Whether the number of ingots was lowered to fit the new maximum
+    pub fn clamped(&self) -> bool {
+        self.num_ingots != self.previous_ingots
+    }
+}
+
+/// Proportionally rescales the percentages so they total exactly 1.0, reordered into the alloy's inline storage \
+/// Percentages that total zero are returned unchanged, and the ranges are not checked
+/// ### Example
+/// ```rust
+/// use vs_alloy_calculator::prelude::*;
+/// use vs_alloy_calculator::normalize_sum;
+///
+/// let percentages = normalize_sum::<BismuthBronze>([Copper(0.6), Bismuth(0.19), Zinc(0.2)]).expect("should be valid metals");
+///
+/// assert_eq!(Metal::Bismuth, percentages[2].metal());
+/// assert!((percentages.iter().map(|p| **p).sum::<f32>() - 1.0).abs() < f32::EPSILON);
+/// ```
+pub fn normalize_sum<T: AlloyType>(
+    percentages: impl AsRef<[BaseMetal<f32>]>,
+) -> Result<T::Percentages<f32>, AlloyError> {
+    let mut normalized = T::percentages_from_fn(|_| 0.0);
+    reorder_constituents_into(
+        percentages.as_ref(),
+        T::METALS,
+        |_, _| true,
+        normalized.as_mut(),
+    )?;
+    let sum = normalized.as_ref().iter().map(|p| **p).sum::<f32>();
+    if sum != 0.0 {
+        for p in normalized.as_mut() {
+            *p = p.map(|v| v / sum);
+        }
+    }
+    Ok(normalized)
+}
+
+impl<T: AlloyType> AlloyData<T> {
+    /// Tries to create a new instance of an alloy like [`AlloyData::try_new`] after applying the supplied options to the inputs. \
+    /// With `normalize_to_one` set, percentages totalling within `max_correction` of 1.0 are rescaled before the ranges are checked.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::CreateOptions;
+    ///
+    /// let options = CreateOptions { normalize_to_one: true, ..Default::default() };
+    ///
+    /// let adjusted = AlloyData::<TinBronze>::try_new_with_options([Copper(0.92), Tin(0.09)], 2, options)
+    ///     .expect("should be valid");
+    ///
+    /// assert!(adjusted.scale < 1.0);
+    /// assert!((adjusted.alloy.percentages()[0].into_inner() - 0.92 / 1.01).abs() < 1e-6);
+    /// ```
+    pub fn try_new_with_options(
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+        num_ingots: impl TryInto<IngotCount, Error: Into<AlloyError>>,
+        options: CreateOptions,
+    ) -> Result<Adjusted<Self>, AlloyError> {
+        let num_ingots = num_ingots.try_into().map_err(Into::into)?;
+        let adjusted = apply_options::<T>(percentages.as_ref(), options)?;
+        Ok(Adjusted {
+            alloy: Self::try_new(adjusted.alloy, num_ingots)?,
+            scale: adjusted.scale,
+        })
+    }
+}
+
+impl<T: AlloyType> AlloyData<T> {
+    /// Tries to create a new instance of an alloy like [`AlloyData::try_new`] after rescaling the percentages to total exactly 1.0. \
+    /// Totals more than the default [`max_correction`](CreateOptions::max_correction) away from 1.0 are rejected,
+    /// and the rescaled percentages must still fall within the alloy's ranges.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = AlloyData::<TinBronze>::try_new_normalized([Copper(0.915), Tin(0.08)], 1).expect("should be valid");
+    /// let total: f32 = alloy.percentages().values().sum();
+    ///
+    /// assert!((total - 1.0).abs() < 1e-6);
+    /// ```
+    pub fn try_new_normalized(
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+        num_ingots: impl TryInto<IngotCount, Error: Into<AlloyError>>,
+    ) -> Result<Self, AlloyError> {
+        let adjusted = apply_options::<T>(percentages.as_ref(), NORMALIZE)?;
+        Self::try_new(adjusted.alloy, num_ingots)
+    }
+
+    /// Tries to update the percentages like [`AlloyData::set_percentages`] after rescaling them to total exactly 1.0,
+    /// the same as [`AlloyData::try_new_normalized`]. Nothing is updated on error.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let mut alloy = AlloyData::<Brass>::default();
+    /// alloy.set_percentages_normalized([Copper(0.65), Zinc(0.35)]).expect("should be valid");
+    ///
+    /// alloy.set_percentages_normalized([Copper(0.9), Zinc(0.3)]).expect_err("should be too far from 1.0");
+    /// assert_eq!(&[Copper(0.65), Zinc(0.35)], alloy.percentages()); // Values were not updated
+    /// ```
+    pub fn set_percentages_normalized(
+        &mut self,
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+    ) -> Result<(), AlloyError> {
+        let adjusted = apply_options::<T>(percentages.as_ref(), NORMALIZE)?;
+        self.set_percentages(adjusted.alloy)
+    }
+}
+
+impl Alloy {
+    /// Tries to update the percentages after rescaling them to total exactly 1.0, the same as [`AlloyData::set_percentages_normalized`]
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let mut alloy = Alloys::Brass.get_default();
+    /// alloy.set_percentages_normalized([Copper(0.65), Zinc(0.35)]).expect("should be valid");
+    ///
+    /// alloy.set_percentages_normalized([Copper(0.9), Zinc(0.3)]).expect_err("should be too far from 1.0");
+    /// assert_eq!(&[Copper(0.65), Zinc(0.35)], alloy.percentages()); // Values were not updated
+    /// ```
+    pub fn set_percentages_normalized(
+        &mut self,
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+    ) -> Result<(), AlloyError> {
+        match self {
+            Alloy::TinBronze(alloy_data) => alloy_data.set_percentages_normalized(percentages),
+            Alloy::BismuthBronze(alloy_data) => alloy_data.set_percentages_normalized(percentages),
+            Alloy::BlackBronze(alloy_data) => alloy_data.set_percentages_normalized(percentages),
+            Alloy::Brass(alloy_data) => alloy_data.set_percentages_normalized(percentages),
+            Alloy::Molybdochalkos(alloy_data) => alloy_data.set_percentages_normalized(percentages),
+            Alloy::LeadSolder(alloy_data) => alloy_data.set_percentages_normalized(percentages),
+            Alloy::SilverSolder(alloy_data) => alloy_data.set_percentages_normalized(percentages),
+            Alloy::Electrum(alloy_data) => alloy_data.set_percentages_normalized(percentages),
+            Alloy::Cupronickel(alloy_data) => alloy_data.set_percentages_normalized(percentages),
+        }
+    }
+}
+
+impl Alloys {
+    /// Tries to create a new instance of the alloy after rescaling the percentages to total exactly 1.0, the same as [`AlloyData::try_new_normalized`]
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = Alloys::TinBronze.try_new_normalized([Copper(0.915), Tin(0.08)], 1).expect("should be valid");
+    /// let total: f32 = alloy.percentages().iter().map(|p| p.into_inner()).sum();
+    ///
+    /// assert!((total - 1.0).abs() < 1e-6);
+    /// Alloys::TinBronze.try_new_normalized([Copper(0.8), Tin(0.1)], 1).expect_err("should be too far from 1.0");
+    /// ```
+    pub fn try_new_normalized(
+        &self,
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+        num_ingots: impl TryInto<IngotCount, Error: Into<AlloyError>>,
+    ) -> Result<Alloy, AlloyError> {
+        Ok(match self {
+            Alloys::TinBronze => {
+                Alloy::TinBronze(AlloyData::try_new_normalized(percentages, num_ingots)?)
+            }
+            Alloys::BismuthBronze => {
+                Alloy::BismuthBronze(AlloyData::try_new_normalized(percentages, num_ingots)?)
+            }
+            Alloys::BlackBronze => {
+                Alloy::BlackBronze(AlloyData::try_new_normalized(percentages, num_ingots)?)
+            }
+            Alloys::Brass => Alloy::Brass(AlloyData::try_new_normalized(percentages, num_ingots)?),
+            Alloys::Molybdochalkos => {
+                Alloy::Molybdochalkos(AlloyData::try_new_normalized(percentages, num_ingots)?)
+            }
+            Alloys::LeadSolder => {
+                Alloy::LeadSolder(AlloyData::try_new_normalized(percentages, num_ingots)?)
+            }
+            Alloys::SilverSolder => {
+                Alloy::SilverSolder(AlloyData::try_new_normalized(percentages, num_ingots)?)
+            }
+            Alloys::Electrum => {
+                Alloy::Electrum(AlloyData::try_new_normalized(percentages, num_ingots)?)
+            }
+            Alloys::Cupronickel => {
+                Alloy::Cupronickel(AlloyData::try_new_normalized(percentages, num_ingots)?)
+            }
+        })
+    }
+
+    /// Tries to create a new instance of an alloy like [`Alloys::try_new`] after applying the supplied options to the inputs
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::CreateOptions;
+    ///
+    /// let options = CreateOptions { normalize_to_one: true, ..Default::default() };
+    ///
+    /// let adjusted = Alloys::Brass.try_new_with_options([Copper(0.7), Zinc(0.31)], 2, options);
+    ///
+    /// assert!(adjusted.is_ok());
+    /// ```
+    pub fn try_new_with_options(
+        &self,
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+        num_ingots: impl TryInto<IngotCount, Error: Into<AlloyError>>,
+        options: CreateOptions,
+    ) -> Result<Adjusted<Alloy>, AlloyError> {
+        Ok(match self {
+            Alloys::TinBronze => AlloyData::try_new_with_options(percentages, num_ingots, options)?
+                .map_alloy(Alloy::TinBronze),
+            Alloys::BismuthBronze => {
+                AlloyData::try_new_with_options(percentages, num_ingots, options)?
+                    .map_alloy(Alloy::BismuthBronze)
+            }
+            Alloys::BlackBronze => {
+                AlloyData::try_new_with_options(percentages, num_ingots, options)?
+                    .map_alloy(Alloy::BlackBronze)
+            }
+            Alloys::Brass => AlloyData::try_new_with_options(percentages, num_ingots, options)?
+                .map_alloy(Alloy::Brass),
+            Alloys::Molybdochalkos => {
+                AlloyData::try_new_with_options(percentages, num_ingots, options)?
+                    .map_alloy(Alloy::Molybdochalkos)
+            }
+            Alloys::LeadSolder => {
+                AlloyData::try_new_with_options(percentages, num_ingots, options)?
+                    .map_alloy(Alloy::LeadSolder)
+            }
+            Alloys::SilverSolder => {
+                AlloyData::try_new_with_options(percentages, num_ingots, options)?
+                    .map_alloy(Alloy::SilverSolder)
+            }
+            Alloys::Electrum => AlloyData::try_new_with_options(percentages, num_ingots, options)?
+                .map_alloy(Alloy::Electrum),
+            Alloys::Cupronickel => {
+                AlloyData::try_new_with_options(percentages, num_ingots, options)?
+                    .map_alloy(Alloy::Cupronickel)
+            }
+        })
+    }
+}
+
+/// Applies the options to the percentages, returning the adjusted percentages in the alloy's inline storage in place of the alloy
+fn apply_options<T: AlloyType>(
+    percentages: &[BaseMetal<f32>],
+    options: CreateOptions,
+) -> Result<Adjusted<T::Percentages<f32>>, AlloyError> {
+    if !options.normalize_to_one {
+        return Ok(Adjusted {
+            alloy: validated_percentages::<T>(percentages)?,
+            scale: 1.0,
+        });
+    }
+    let sum = percentages.iter().map(|p| **p).sum::<f32>();
+    if sum <= 0.0 || (sum - 1.0).abs() > options.max_correction {
+        return Err(InvalidPercentages);
+    }
+    Ok(Adjusted {
+        alloy: normalize_sum::<T>(percentages)?,
+        scale: 1.0 / sum,
+    })
+}
+
+impl<T: AlloyType> AlloyData<T> {
+    /// Tries to update the percentages like [`AlloyData::set_percentages`], lowering the number of ingots to the new maximum
+    /// instead of failing with [`TooManyIngots`](AlloyError::TooManyIngots) when the current number no longer fits. \
+    /// Nothing is updated on error.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let mut alloy = AlloyData::<TinBronze>::try_new([Copper(0.88), Tin(0.12)], 21).expect("should be valid");
+    ///
+    /// let outcome = alloy.set_percentages_adjusting_ingots([Copper(0.92), Tin(0.08)]).expect("should be valid");
+    /// assert!(outcome.clamped());
+    /// assert_eq!(20, outcome.num_ingots);
+    /// assert_eq!(20, alloy.num_ingots());
+    /// ```
+    pub fn set_percentages_adjusting_ingots(
+        &mut self,
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+    ) -> Result<AdjustOutcome, AlloyError> {
+        let percentages = validated_percentages::<T>(percentages.as_ref())?;
+        let previous_ingots = self.num_ingots;
+        let num_ingots = previous_ingots.min(Self::validated_max_ingots(percentages.as_ref()));
+        self.update_values(Some(percentages), Some(num_ingots))?;
+        Ok(AdjustOutcome {
+            previous_ingots,
+            num_ingots,
+        })
+    }
+}
+
+impl Alloy {
+    /// Tries to update the percentages, lowering the number of ingots to the new maximum if needed, the same as [`AlloyData::set_percentages_adjusting_ingots`]
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::AdjustOutcome;
+    ///
+    /// let mut alloy = Alloys::TinBronze.try_new([Copper(0.88), Tin(0.12)], 21).expect("should be valid");
+    ///
+    /// let outcome = alloy.set_percentages_adjusting_ingots([Copper(0.92), Tin(0.08)]).expect("should be valid");
+    /// assert_eq!(AdjustOutcome { previous_ingots: 21, num_ingots: 20 }, outcome);
+    /// assert!(outcome.clamped());
+    ///
+    /// let outcome = alloy.set_percentages_adjusting_ingots([Copper(0.9), Tin(0.1)]).expect("should be valid");
+    /// assert_eq!(AdjustOutcome { previous_ingots: 20, num_ingots: 20 }, outcome);
+    /// assert!(!outcome.clamped());
+    /// ```
+    pub fn set_percentages_adjusting_ingots(
+        &mut self,
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+    ) -> Result<AdjustOutcome, AlloyError> {
+        match self {
+            Alloy::TinBronze(alloy_data) => {
+                alloy_data.set_percentages_adjusting_ingots(percentages)
+            }
+            Alloy::BismuthBronze(alloy_data) => {
+                alloy_data.set_percentages_adjusting_ingots(percentages)
+            }
+            Alloy::BlackBronze(alloy_data) => {
+                alloy_data.set_percentages_adjusting_ingots(percentages)
+            }
+            Alloy::Brass(alloy_data) => alloy_data.set_percentages_adjusting_ingots(percentages),
+            Alloy::Molybdochalkos(alloy_data) => {
+                alloy_data.set_percentages_adjusting_ingots(percentages)
+            }
+            Alloy::LeadSolder(alloy_data) => {
+                alloy_data.set_percentages_adjusting_ingots(percentages)
+            }
+            Alloy::SilverSolder(alloy_data) => {
+                alloy_data.set_percentages_adjusting_ingots(percentages)
+            }
+            Alloy::Electrum(alloy_data) => alloy_data.set_percentages_adjusting_ingots(percentages),
+            Alloy::Cupronickel(alloy_data) => {
+                alloy_data.set_percentages_adjusting_ingots(percentages)
+            }
+        }
+    }
+}
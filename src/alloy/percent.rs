@@ -0,0 +1,166 @@
+//! # Whole Percents
+//! Setting and reading percentages as whole integer percents, validated exactly against the alloy's ranges in basis points.
+use super::*;
+
+impl<T: AlloyType> AlloyData<T> {
+    /// Tries to create a new instance of an alloy from whole percentages such as `Copper(92.0)`, the way the game's tooltips show them. \
+    /// The values are divided by 100 and then validated the same as [`AlloyData::try_new`].
+    ///
+    /// Returns [`MixedPercentScales`](AlloyError::MixedPercentScales) when some values are fractions of one and others are whole percents.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::AlloyError;
+    ///
+    /// let alloy = AlloyData::<TinBronze>::try_new_whole_percent([Copper(92.0), Tin(8.0)], 7).expect("should be valid");
+    /// assert_eq!(&[Copper(0.92), Tin(0.08)], alloy.percentages());
+    ///
+    /// let mixed = AlloyData::<TinBronze>::try_new_whole_percent([Copper(0.92), Tin(8.0)], 7);
+    /// assert_eq!(Err(AlloyError::MixedPercentScales), mixed);
+    /// ```
+    pub fn try_new_whole_percent(
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+        num_ingots: impl TryInto<IngotCount, Error: Into<AlloyError>>,
+    ) -> Result<Self, AlloyError> {
+        Self::try_new(from_whole_percents(percentages.as_ref())?, num_ingots)
+    }
+
+    /// Gets the percentages of the constituents rounded to whole percents
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = AlloyData::<BismuthBronze>::default();
+    ///
+    /// assert_eq!(Box::from([Copper(60), Zinc(20), Bismuth(20)]), alloy.percents_u8());
+    /// ```
+    pub fn percents_u8(&self) -> Box<[BaseMetal<u8>]> {
+        self.percentages
+            .as_ref()
+            .iter()
+            .map(|p| p.update_inner_value(((to_basis_points(**p) + 50) / 100) as u8))
+            .collect()
+    }
+
+    /// Tries to update the percentages of the alloy from whole percents, which must total exactly 100 and fall within the alloy's ranges. \
+    /// In addition, updates other values if successful.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let mut alloy = AlloyData::<TinBronze>::default();
+    ///
+    /// alloy.set_percents(&[(Metal::Tin, 12), (Metal::Copper, 88)]).expect("should be valid");
+    /// assert_eq!(&[Copper(0.88), Tin(0.12)], alloy.percentages());
+    ///
+    /// alloy.set_percents(&[(Metal::Copper, 88), (Metal::Tin, 11)]).expect_err("should not total 100");
+    /// assert_eq!(&[Copper(0.88), Tin(0.12)], alloy.percentages()); // Values were not updated
+    /// ```
+    pub fn set_percents(&mut self, percents: &[(Metal, u8)]) -> Result<(), AlloyError> {
+        if percents.iter().map(|(_, p)| u32::from(*p)).sum::<u32>() != 100 {
+            return Err(InvalidPercentages);
+        }
+        let ranges = basis_point_ranges::<T>();
+        let mut reorder: Vec<Option<i64>> = vec![None; ranges.len()];
+        for (metal, percent) in percents {
+            let index = T::constituent_metals()
+                .iter()
+                .position(|m| m == metal)
+                .ok_or(UnknownConstituent(*metal))?;
+            let bp = i64::from(*percent) * 100;
+            if reorder[index].is_some() {
+                return Err(DuplicateConstituent(*metal));
+            } else if !(ranges[index].0..=ranges[index].1).contains(&bp) {
+                return Err(InvalidPercentages);
+            }
+            reorder[index] = Some(bp);
+        }
+        let values = reorder
+            .into_iter()
+            .zip(T::constituent_metals())
+            .map(|(bp, metal)| bp.ok_or(MissingConstituent(*metal)))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.update_values(Some(from_basis_points::<T>(&values)), None)
+    }
+
+    /// Tries to set a single constituent to a whole percent, rebalancing the others to keep the total at 100. \
+    /// The remaining difference is taken up by the other constituents in order, each staying within its range.
+    /// In addition, updates other values if successful.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let mut alloy = AlloyData::<BismuthBronze>::default();
+    /// assert_eq!(&[Copper(0.6), Zinc(0.2), Bismuth(0.2)], alloy.percentages());
+    ///
+    /// alloy.set_percent(Metal::Copper, 52).expect("should be valid");
+    /// assert_eq!(&[Copper(0.52), Zinc(0.28), Bismuth(0.2)], alloy.percentages());
+    /// ```
+    pub fn set_percent(&mut self, metal: Metal, percent: u8) -> Result<(), AlloyError> {
+        let index = T::constituent_metals()
+            .iter()
+            .position(|m| *m == metal)
+            .ok_or(UnknownConstituent(metal))?;
+        let ranges = basis_point_ranges::<T>();
+        let bp = i64::from(percent) * 100;
+        if !(ranges[index].0..=ranges[index].1).contains(&bp) {
+            return Err(InvalidPercentages);
+        }
+        let mut values: Vec<i64> = self.basis_points().collect();
+        values[index] = bp;
+        let fixed: Vec<bool> = (0..values.len()).map(|i| i == index).collect();
+        if distribute_difference(&mut values, &ranges, &fixed) != 0 {
+            return Err(InvalidPercentages);
+        }
+        self.update_values(Some(from_basis_points::<T>(&values)), None)
+    }
+}
+
+impl Alloys {
+    /// Tries to create a new instance of the alloy from whole percentages, the same as [`AlloyData::try_new_whole_percent`]
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = Alloys::BismuthBronze
+    ///     .try_new_whole_percent([Copper(60.0), Zinc(20.0), Bismuth(20.0)], 2)
+    ///     .expect("should be valid");
+    ///
+    /// assert_eq!(&[Copper(0.6), Zinc(0.2), Bismuth(0.2)], alloy.percentages());
+    /// ```
+    pub fn try_new_whole_percent(
+        &self,
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+        num_ingots: impl TryInto<IngotCount, Error: Into<AlloyError>>,
+    ) -> Result<Alloy, AlloyError> {
+        self.try_new(from_whole_percents(percentages.as_ref())?, num_ingots)
+    }
+}
+
+/// Converts whole percentages to fractions of one, rejecting inputs where some values are already fractions. \
+/// A value strictly between 0 and 1 next to one above 1 can only be a mix of the two scales, since no constituent's range allows either as a whole percent.
+fn from_whole_percents(percentages: &[BaseMetal<f32>]) -> Result<Vec<BaseMetal<f32>>, AlloyError> {
+    let fractional = percentages.iter().any(|p| **p > 0.0 && **p < 1.0);
+    let whole = percentages.iter().any(|p| **p > 1.0);
+    if fractional && whole {
+        return Err(MixedPercentScales);
+    }
+    Ok(percentages.iter().map(|p| p.map(|v| v / 100.0)).collect())
+}
+
+/// Gets the alloy's ranges in basis points
+pub(super) fn basis_point_ranges<T: AlloyType>() -> Vec<(i64, i64)> {
+    T::percentage_ranges()
+        .iter()
+        .map(|r| (to_basis_points(r.min), to_basis_points(r.max)))
+        .collect()
+}
+
+/// Converts validated basis points in canonical order back into float percentages
+pub(super) fn from_basis_points<T: AlloyType>(values: &[i64]) -> T::Percentages<f32> {
+    T::percentages_from_fn(|i| {
+        values
+            .get(i)
+            .map_or(0.0, |bp| *bp as f32 / BASIS_POINTS as f32)
+    })
+}
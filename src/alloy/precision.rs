@@ -0,0 +1,158 @@
+//! # f64 Percentages
+//! Full precision [`f64`] percentages alongside the [`f32`] ones, for callers that adjust ratios many times over.
+//! The f64 values are validated and stored as given; the nuggets are worked out from the same values narrowed to [`f32`].
+use super::*;
+
+type RangeF64 = ConstituentRange<f64>;
+
+/// Largest difference from 1.0 allowed in the total of f64 percentages, enough for the rounding in adding them up
+const TOTAL_TOLERANCE_F64: f64 = 4.0 * f64::EPSILON;
+
+/// How far past either end of a range an f64 percentage may be, enough for rounding left by many small adjustments
+const RANGE_TOLERANCE_F64: f64 = 64.0 * f64::EPSILON;
+
+impl<T: AlloyType> AlloyData<T> {
+    /// Tries to create a new instance of an alloy from f64 percentages, the same as [`AlloyData::try_new`] otherwise. \
+    /// The percentages are kept at full precision in [`percentages_f64`](Self::percentages_f64).
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let percentages = [Copper(0.6000000000001), Zinc(0.2499999999999), Bismuth(0.15)];
+    ///
+    /// let alloy = AlloyData::<BismuthBronze>::try_new_f64(percentages, 5).expect("should be valid");
+    ///
+    /// assert_eq!(&percentages, alloy.percentages_f64());
+    /// assert_eq!(&[Copper(0.6), Zinc(0.25), Bismuth(0.15)], alloy.percentages());
+    /// assert_eq!(&[Copper(60), Zinc(25), Bismuth(15)], alloy.nuggets());
+    /// ```
+    pub fn try_new_f64(
+        percentages: impl AsRef<[BaseMetal<f64>]>,
+        num_ingots: impl TryInto<IngotCount, Error: Into<AlloyError>>,
+    ) -> Result<Self, AlloyError> {
+        let num_ingots = num_ingots.try_into().map_err(Into::into)?.get();
+        check_definition::<T>()?;
+        let percentages_f64 = validated_f64_percentages::<T>(percentages.as_ref())?;
+        let percentages = narrowed::<T>(&percentages_f64);
+        let (alloy_type, max_ingots) = Self::get_updated_values(percentages.as_ref(), num_ingots)?;
+        Ok(Self {
+            alloy_type,
+            percentages,
+            percentages_f64,
+            num_ingots,
+            max_ingots,
+        })
+    }
+
+    /// Tries to update the percentages from f64 values, the same as [`AlloyData::set_percentages`] otherwise. \
+    /// Nothing is updated on error.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let mut alloy = AlloyData::<TinBronze>::default();
+    /// alloy.set_percentages_f64([Tin(0.1), Copper(0.9)]).expect("should be valid");
+    ///
+    /// assert_eq!(&[Copper(0.9), Tin(0.1)], alloy.percentages_f64());
+    /// assert_eq!(&[Copper(18), Tin(2)], alloy.nuggets());
+    ///
+    /// alloy.set_percentages_f64([Copper(0.9), Tin(0.12)]).expect_err("should not total 1.0");
+    /// assert_eq!(&[Copper(0.9), Tin(0.1)], alloy.percentages_f64());
+    /// ```
+    pub fn set_percentages_f64(
+        &mut self,
+        percentages: impl AsRef<[BaseMetal<f64>]>,
+    ) -> Result<(), AlloyError> {
+        let percentages_f64 = validated_f64_percentages::<T>(percentages.as_ref())?;
+        self.update_values(Some(narrowed::<T>(&percentages_f64)), None)?;
+        self.percentages_f64 = percentages_f64;
+        Ok(())
+    }
+
+    /// Gets the percentages at full precision. \
+    /// Percentages set through the f32 methods, such as [`AlloyData::set_percentages`], are widened from their f32 values
+    /// and rounded to 7 decimal places, about the precision of an f32, with the last constituent making the total 1.0.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = AlloyData::<TinBronze>::default();
+    ///
+    /// assert_eq!(&[Copper(0.92), Tin(0.08)], alloy.percentages_f64());
+    /// ```
+    pub fn percentages_f64(&self) -> &[BaseMetal<f64>] {
+        self.percentages_f64.as_ref()
+    }
+
+    /// Gets the ranges of percentages of the constituents for the given alloy as f64s
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::ConstituentRange;
+    ///
+    /// let ranges = AlloyData::<TinBronze>::f64_percentage_ranges();
+    ///
+    /// assert_eq!(Tin(ConstituentRange::new(0.08, 0.12)), ranges[1]);
+    /// ```
+    pub fn f64_percentage_ranges() -> Box<[BaseMetal<RangeF64>]> {
+        T::percentage_ranges()
+            .iter()
+            .map(|r| r.update_inner_value(range_f64(r)))
+            .collect()
+    }
+
+    /// Checks whether the supplied f64 percentages are valid for the given alloy \
+    /// Returns the validated percentages in a reordered boxed slice or an error if invalid
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let percentages = [Tin(0.08), Copper(0.92)];
+    /// assert!(
+    ///     AlloyData::<TinBronze>::check_valid_f64_percentages(percentages)
+    ///         .is_ok_and(|p| p == Box::from([Copper(0.92), Tin(0.08)]))
+    /// );
+    /// ```
+    pub fn check_valid_f64_percentages(
+        percentages: impl AsRef<[BaseMetal<f64>]>,
+    ) -> Result<Box<[BaseMetal<f64>]>, AlloyError> {
+        Ok(validated_f64_percentages::<T>(percentages.as_ref())?
+            .as_ref()
+            .into())
+    }
+}
+
+/// Converts a range to f64 through basis points, so `0.88` becomes the f64 nearest 0.88 rather than the widened f32
+fn range_f64(range: &Range) -> RangeF64 {
+    RangeF64::new(
+        to_basis_points(range.min) as f64 / BASIS_POINTS as f64,
+        to_basis_points(range.max) as f64 / BASIS_POINTS as f64,
+    )
+}
+
+/// Checks f64 percentages against the total and the ranges with slack for f64 rounding, returning them in canonical order
+fn validated_f64_percentages<T: AlloyType>(
+    percentages: &[BaseMetal<f64>],
+) -> Result<T::Percentages<f64>, AlloyError> {
+    let sum = percentages.iter().map(|p| **p).sum::<f64>();
+    if (sum - 1.0).abs().partial_cmp(&TOTAL_TOLERANCE_F64) != Some(std::cmp::Ordering::Less) {
+        return Err(InvalidPercentages);
+    }
+    let mut validated = T::percentages_from_fn(|_| 0.0);
+    reorder_constituents_into(
+        percentages,
+        T::constituent_metals(),
+        |i, p| {
+            let range = range_f64(&T::percentage_ranges()[i]);
+            range.min - RANGE_TOLERANCE_F64 <= **p && **p <= range.max + RANGE_TOLERANCE_F64
+        },
+        validated.as_mut(),
+    )?;
+    Ok(validated)
+}
+
+/// Narrows validated f64 percentages to the f32 values the nuggets are worked out from
+fn narrowed<T: AlloyType>(percentages: &T::Percentages<f64>) -> T::Percentages<f32> {
+    let percentages = percentages.as_ref();
+    T::percentages_from_fn(|i| percentages.get(i).map_or(0.0, |p| **p as f32))
+}
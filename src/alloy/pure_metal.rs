@@ -0,0 +1,180 @@
+//! # Pure Metals
+//! Recipes for smelting a single base metal into ingots without alloying it with anything.
+//! [`PureMetal`] mirrors the accessors of [`AlloyData`] so both can be driven by the same code.
+use super::*;
+
+/// The only valid percentage for a pure metal
+const FULL: Range = Range::new(1.0, 1.0);
+/// Trivial ranges indexed in the same order as [`Metal::ALL`]
+const RANGES: [BaseMetal<Range>; 8] = [
+    Nickel(FULL),
+    Copper(FULL),
+    Zinc(FULL),
+    Silver(FULL),
+    Tin(FULL),
+    Gold(FULL),
+    Lead(FULL),
+    Bismuth(FULL),
+];
+/// Trivial percentages indexed in the same order as [`Metal::ALL`]
+const PERCENTAGES: [BaseMetal<f32>; 8] = [
+    Nickel(1.0),
+    Copper(1.0),
+    Zinc(1.0),
+    Silver(1.0),
+    Tin(1.0),
+    Gold(1.0),
+    Lead(1.0),
+    Bismuth(1.0),
+];
+
+/// Struct for modeling smelting a single base metal into ingots
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct PureMetal {
+    nuggets: [BaseMetal<i32>; 1],
+    num_ingots: i32,
+}
+
+impl PureMetal {
+    /// Tries to create a new pure metal recipe for the supplied number of ingots
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let copper = PureMetal::new(Metal::Copper, 3).expect("should be valid");
+    ///
+    /// assert_eq!(&[Copper(60)], copper.nuggets());
+    /// ```
+    pub fn new(metal: Metal, num_ingots: i32) -> Result<Self, AlloyError> {
+        if num_ingots > Self::max_ingots_possible() {
+            Err(TooManyIngots)
+        } else if num_ingots <= 0 {
+            Err(TooFewIngots)
+        } else {
+            Ok(Self {
+                nuggets: [metal.with_value(num_ingots * unit_constants::NUM_NUGGETS_PER_INGOT)],
+                num_ingots,
+            })
+        }
+    }
+
+    /// Gets which metal is being smelted
+    pub fn metal(&self) -> Metal {
+        self.nuggets[0].metal()
+    }
+
+    /// Gets the name of the metal being smelted
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let gold = PureMetal::new(Metal::Gold, 1).expect("should be valid");
+    ///
+    /// assert_eq!("Gold", gold.name());
+    /// ```
+    pub fn name(&self) -> &'static str {
+        self.metal().name()
+    }
+
+    /// Gets the number of nuggets needed, which is always a single entry
+    pub fn nuggets(&self) -> &[BaseMetal<i32>] {
+        &self.nuggets
+    }
+
+    /// Gets the number of ingots that will be created
+    pub fn num_ingots(&self) -> i32 {
+        self.num_ingots
+    }
+
+    /// Gets the maximum number of ingots able to be created, which is limited only by the crucible
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let silver = PureMetal::new(Metal::Silver, 1).expect("should be valid");
+    ///
+    /// assert_eq!(25, silver.max_ingots());
+    /// ```
+    pub fn max_ingots(&self) -> i32 {
+        Self::max_ingots_possible()
+    }
+
+    /// Gets the percentages, which is always 100% of the one metal
+    pub fn percentages(&self) -> &'static [BaseMetal<f32>] {
+        let index = self.index();
+        &PERCENTAGES[index..=index]
+    }
+
+    /// Gets the range of percentages, which is always exactly 100% of the one metal
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::ConstituentRange;
+    ///
+    /// let tin = PureMetal::new(Metal::Tin, 1).expect("should be valid");
+    ///
+    /// assert_eq!(&[Tin(ConstituentRange::new(1.0, 1.0))], tin.percentage_ranges());
+    /// ```
+    pub fn percentage_ranges(&self) -> &'static [BaseMetal<Range>] {
+        let index = self.index();
+        &RANGES[index..=index]
+    }
+
+    /// Gets the allowed percentage range of the supplied metal or `None` if it is not the metal being smelted
+    pub fn range_of(&self, metal: Metal) -> Option<Range> {
+        (metal == self.metal()).then_some(FULL)
+    }
+
+    /// Gets the metals of the constituents, which is always only the one metal
+    pub fn constituent_metals(&self) -> &'static [Metal] {
+        let index = self.index();
+        &Metal::ALL[index..=index]
+    }
+
+    /// Iterates over the metal, range, percentage, and nuggets of the single constituent
+    pub fn constituents(&self) -> Constituents<'_> {
+        Constituents {
+            ranges: self.percentage_ranges(),
+            percentages: self.percentages(),
+            nuggets: &self.nuggets,
+            index: 0,
+        }
+    }
+
+    /// Tries to update the number of ingots. In addition, updates the nuggets if successful.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let mut lead = PureMetal::new(Metal::Lead, 1).expect("should be valid");
+    /// lead.set_num_ingots(25).expect("should be valid");
+    ///
+    /// assert_eq!(&[Lead(500)], lead.nuggets());
+    /// assert!(lead.set_num_ingots(26).is_err());
+    /// ```
+    pub fn set_num_ingots(&mut self, num_ingots: i32) -> Result<(), AlloyError> {
+        *self = Self::new(self.metal(), num_ingots)?;
+        Ok(())
+    }
+
+    /// Checks the percentages are 100% of the metal being smelted, within the same tolerance as an alloy's total
+    pub(super) fn check_percentages(
+        &self,
+        percentages: &[BaseMetal<f32>],
+    ) -> Result<(), AlloyError> {
+        match percentages {
+            [p] if p.metal() == self.metal() && (**p - 1.0).abs() < TOTAL_TOLERANCE => Ok(()),
+            _ => Err(InvalidPercentages),
+        }
+    }
+
+    /// Position of the metal in [`Metal::ALL`] and the trivial tables
+    fn index(&self) -> usize {
+        self.metal() as usize
+    }
+
+    /// Number of ingots that fill the crucible with a single metal
+    fn max_ingots_possible() -> i32 {
+        Capacity::VANILLA.max_possible_ingots()
+    }
+}
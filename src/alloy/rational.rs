@@ -0,0 +1,161 @@
+//! # Rational Percentages
+//! Exact ratio support for alloy percentages, enabled with the `rational` feature.
+//! Validation and unit math are done with [`Ratio`] so mixes such as 1/3 never pick up float error.
+use super::*;
+use num_rational::Ratio;
+use num_traits::CheckedAdd;
+
+type RationalRange = ConstituentRange<Ratio<i32>>;
+
+impl<T: AlloyType> AlloyData<T> {
+    /// Tries to create a new instance of an alloy from exact rational percentages. \
+    /// Validation against the alloy's ranges and all of the unit math are done exactly,
+    /// converging to the same nugget amounts as [`AlloyData::try_new`] whenever both can represent the input.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use num_rational::Ratio;
+    ///
+    /// let percentages = [Copper(Ratio::new(7, 12)), Zinc(Ratio::new(1, 4)), Bismuth(Ratio::new(1, 6))];
+    ///
+    /// let alloy = AlloyData::<BismuthBronze>::try_new_rational(percentages, 12).expect("should be valid");
+    ///
+    /// assert_eq!(&[Copper(140), Zinc(60), Bismuth(40)], alloy.nuggets());
+    /// ```
+    pub fn try_new_rational(
+        percentages: impl AsRef<[BaseMetal<Ratio<i32>>]>,
+        num_ingots: i32,
+    ) -> Result<Self, AlloyError> {
+        if num_ingots > unit_constants::MAX_POSSIBLE_INGOTS {
+            return Err(TooManyIngots);
+        } else if num_ingots <= 0 {
+            return Err(TooFewIngots);
+        }
+        check_definition::<T>()?;
+        let rationals = Self::check_valid_rational_percentages(percentages)?;
+
+        let max_ingots = Self::calculate_max_ingots_rational(&rationals);
+        if num_ingots > max_ingots {
+            return Err(TooManyIngots);
+        }
+
+        use unit_constants::*;
+        let needed_units = Ratio::from_integer(ingot_units(num_ingots));
+        let mut remaining_units = needed_units;
+        let mut constituent_amounts = Vec::new();
+        let (last, rest) = rationals.split_last().ok_or(InvalidPercentages)?;
+        for p in rest {
+            let units = needed_units * widen(**p);
+            remaining_units -= units;
+            constituent_amounts.push(units.to_integer() as i32 / NUGGET_UNIT_AMOUNT);
+        }
+        // The last constituent takes whatever is left over, exactly as the float path does
+        debug_assert_eq!(remaining_units, needed_units * widen(**last));
+        constituent_amounts.push(
+            (remaining_units / i64::from(NUGGET_UNIT_AMOUNT))
+                .ceil()
+                .to_integer() as i32,
+        );
+
+        Self::check_constituent_amounts(&mut constituent_amounts, num_ingots, |_| ());
+
+        Ok(Self {
+            alloy_type: T::try_from_amounts(&constituent_amounts)?,
+            percentages: T::percentages_from_fn(|i| {
+                rationals
+                    .get(i)
+                    .map_or(0.0, |p| *p.numer() as f32 / *p.denom() as f32)
+            }),
+            percentages_f64: T::percentages_from_fn(|i| {
+                rationals
+                    .get(i)
+                    .map_or(0.0, |p| f64::from(*p.numer()) / f64::from(*p.denom()))
+            }),
+            num_ingots,
+            max_ingots,
+        })
+    }
+
+    /// Gets the ranges of percentages of the constituents for the given alloy as exact ratios
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::ConstituentRange;
+    /// use num_rational::Ratio;
+    ///
+    /// let ranges = AlloyData::<TinBronze>::rational_percentage_ranges();
+    ///
+    /// assert_eq!(Copper(ConstituentRange::new(Ratio::new(22, 25), Ratio::new(23, 25))), ranges[0]);
+    /// ```
+    pub fn rational_percentage_ranges() -> Box<[BaseMetal<RationalRange>]> {
+        T::percentage_ranges()
+            .iter()
+            .map(|r| r.update_inner_value(RationalRange::new(to_ratio(r.min), to_ratio(r.max))))
+            .collect()
+    }
+
+    /// Checks whether the supplied rational percentages are valid for the given alloy \
+    /// Returns the validated percentages in a reordered boxed slice or an error if invalid,
+    /// including for a zero denominator built with [`Ratio::new_raw`]
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use num_rational::Ratio;
+    ///
+    /// let percentages = [Tin(Ratio::new(2, 25)), Copper(Ratio::new(23, 25))];
+    /// assert!(
+    ///     AlloyData::<TinBronze>::check_valid_rational_percentages(percentages)
+    ///         .is_ok_and(|p| p == Box::from([Copper(Ratio::new(23, 25)), Tin(Ratio::new(2, 25))]))
+    /// );
+    /// ```
+    pub fn check_valid_rational_percentages(
+        percentages: impl AsRef<[BaseMetal<Ratio<i32>>]>,
+    ) -> Result<Box<[BaseMetal<Ratio<i32>>]>, AlloyError> {
+        let percentages = percentages.as_ref();
+        if percentages.iter().any(|p| *p.denom() == 0) {
+            return Err(InvalidPercentages);
+        }
+        let sum = percentages
+            .iter()
+            .try_fold(Ratio::from_integer(0i64), |acc, p| {
+                acc.checked_add(&widen(**p))
+            })
+            .ok_or(InvalidPercentages)?;
+        if sum != Ratio::from_integer(1) {
+            return Err(InvalidPercentages);
+        }
+
+        let ranges = Self::rational_percentage_ranges();
+        // Compared widened so unreduced ratios such as `Ratio::new_raw(i32::MIN, -1)` cannot overflow
+        reorder_constituents(percentages, T::constituent_metals(), |i, p| {
+            widen(ranges[i].min) <= widen(**p) && widen(**p) <= widen(ranges[i].max)
+        })
+    }
+
+    /// Calculates the maximum number of ingots possible with the supplied rational percentages using exact unit math
+    fn calculate_max_ingots_rational(percentages: &[BaseMetal<Ratio<i32>>]) -> i32 {
+        use unit_constants::*;
+        (1..=MAX_POSSIBLE_INGOTS)
+            .rev()
+            .find(|&num_ingots| {
+                let needed_units = Ratio::from_integer(ingot_units(num_ingots));
+                Capacity::VANILLA.holds_units(
+                    percentages
+                        .iter()
+                        .map(|p| (needed_units * widen(**p)).ceil().to_integer()),
+                )
+            })
+            .unwrap_or(0)
+    }
+}
+
+/// Converts a whole-percent float range bound into an exact ratio
+fn to_ratio(value: f32) -> Ratio<i32> {
+    Ratio::new((value * 100.0).round() as i32, 100)
+}
+
+/// Widens a ratio so that intermediate unit math cannot overflow, reducing it in the process. \
+/// The denominator must not be zero, which [`AlloyData::check_valid_rational_percentages`] rules out.
+fn widen(value: Ratio<i32>) -> Ratio<i64> {
+    Ratio::new(i64::from(*value.numer()), i64::from(*value.denom()))
+}
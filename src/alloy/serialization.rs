@@ -0,0 +1,105 @@
+//! # Serialization
+//! Serde support, enabled with the `serde` feature.
+//! Deserializing an alloy always goes through [`AlloyData::try_new`] so untrusted input can never produce an invalid alloy.
+//! The nuggets and max ingots are derived from the percentages and number of ingots.
+//! They are written out for convenience and are optional when reading, but are rejected if present and they do not match.
+//! A [`PureMetal`] is written in the same shape, with its single constituent at 100%, and read back through [`PureMetal::new`].
+use super::*;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Shape an [`AlloyData`] is serialized as
+#[derive(Serialize)]
+struct AlloyDataRef<'a> {
+    percentages: &'a [BaseMetal<f32>],
+    num_ingots: i32,
+    nuggets: &'a [BaseMetal<i32>],
+    max_ingots: i32,
+}
+
+/// Unvalidated shape an [`AlloyData`] is deserialized from before being checked
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawAlloyData {
+    percentages: Vec<BaseMetal<f32>>,
+    num_ingots: i32,
+    #[serde(default)]
+    nuggets: Option<Vec<BaseMetal<i32>>>,
+    #[serde(default)]
+    max_ingots: Option<i32>,
+}
+
+impl<T: AlloyType> TryFrom<RawAlloyData> for AlloyData<T> {
+    type Error = AlloyError;
+
+    fn try_from(raw: RawAlloyData) -> Result<Self, Self::Error> {
+        let alloy = Self::try_new(raw.percentages, raw.num_ingots)?;
+        if raw.nuggets.is_some_and(|n| n != alloy.nuggets()) {
+            Err(InvalidConstituentAmounts)
+        } else if raw.max_ingots.is_some_and(|m| m != alloy.max_ingots()) {
+            Err(InvalidValues)
+        } else {
+            Ok(alloy)
+        }
+    }
+}
+
+impl<T: AlloyType> Serialize for AlloyData<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        AlloyDataRef {
+            percentages: self.percentages(),
+            num_ingots: self.num_ingots(),
+            nuggets: self.nuggets(),
+            max_ingots: self.max_ingots(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T: AlloyType> Deserialize<'de> for AlloyData<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        RawAlloyData::deserialize(deserializer)?
+            .try_into()
+            .map_err(D::Error::custom)
+    }
+}
+
+impl TryFrom<RawAlloyData> for PureMetal {
+    type Error = AlloyError;
+
+    fn try_from(raw: RawAlloyData) -> Result<Self, Self::Error> {
+        let metal = match raw.percentages.as_slice() {
+            [p] => p.metal(),
+            _ => return Err(InvalidPercentages),
+        };
+        let pure = Self::new(metal, raw.num_ingots)?;
+        pure.check_percentages(&raw.percentages)?;
+        if raw.nuggets.is_some_and(|n| n != pure.nuggets()) {
+            Err(InvalidConstituentAmounts)
+        } else if raw.max_ingots.is_some_and(|m| m != pure.max_ingots()) {
+            Err(InvalidValues)
+        } else {
+            Ok(pure)
+        }
+    }
+}
+
+impl Serialize for PureMetal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        AlloyDataRef {
+            percentages: self.percentages(),
+            num_ingots: self.num_ingots(),
+            nuggets: self.nuggets(),
+            max_ingots: self.max_ingots(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PureMetal {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        RawAlloyData::deserialize(deserializer)?
+            .try_into()
+            .map_err(D::Error::custom)
+    }
+}
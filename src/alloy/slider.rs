@@ -0,0 +1,175 @@
+//! # Slider Bindings
+//! View-model for drawing a percentage slider per constituent in a GUI.
+use super::*;
+
+/// Everything needed to draw the slider for one constituent of an alloy
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SliderBinding {
+    pub metal: Metal,
+    /// The constituent's full range
+    pub range: Range,
+    /// The part of the range the slider can actually be dragged to while the other constituents rebalance within their own ranges,
+    /// matching what [`AlloyData::set_percent`] and [`AlloyData::set_percentage_of`] accept
+    pub reachable: Range,
+    /// The smallest change the slider should make, one whole percent
+    pub step: f32,
+    /// The constituent's current percentage
+    pub value: f32,
+    /// Whether the slider can be dragged anywhere other than its current value
+    pub movable: bool,
+}
+
+/// Step used by every slider, matching the whole percents accepted by [`AlloyData::set_percent`]
+const STEP: f32 = 0.01;
+
+impl<T: AlloyType> AlloyData<T> {
+    /// Gets a slider binding for each constituent in canonical order
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = AlloyData::<TinBronze>::default();
+    /// let bindings = alloy.slider_bindings();
+    ///
+    /// assert_eq!(Metal::Tin, bindings[1].metal);
+    /// assert_eq!(0.08, bindings[1].value);
+    /// assert_eq!((0.08, 0.12), (bindings[1].reachable.min, bindings[1].reachable.max));
+    /// ```
+    pub fn slider_bindings(&self) -> Vec<SliderBinding> {
+        slider_bindings(self.percentages.as_ref(), T::percentage_ranges())
+    }
+}
+
+impl<T: AlloyType> AlloyData<T> {
+    /// Tries to set a single constituent's percentage, as when dragging its slider, rebalancing the others to keep the total at 100%. \
+    /// The remainder is shared among the other constituents in proportion to their current values, with any that would leave
+    /// their range held at its edge and the rest shared among the others. For two-metal alloys the other metal simply gets `1.0 - value`.
+    ///
+    /// Returns [`UnknownConstituent`](AlloyError::UnknownConstituent) if the metal is not part of the alloy,
+    /// or [`InvalidPercentages`](AlloyError::InvalidPercentages) if the value is outside the slider's reachable range. \
+    /// Nothing is updated on error.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let mut alloy = AlloyData::<BismuthBronze>::default();
+    /// assert_eq!(&[Copper(0.6), Zinc(0.2), Bismuth(0.2)], alloy.percentages());
+    ///
+    /// // Zinc and Bismuth would each get 25%, but Bismuth stops at 20%
+    /// alloy.set_percentage_of(Metal::Copper, 0.5).expect("should be valid");
+    /// assert_eq!(&[Copper(0.5), Zinc(0.3), Bismuth(0.2)], alloy.percentages());
+    ///
+    /// alloy.set_percentage_of(Metal::Copper, 0.45).expect_err("should be out of range");
+    /// assert_eq!(&[Copper(0.5), Zinc(0.3), Bismuth(0.2)], alloy.percentages()); // Values were not updated
+    /// ```
+    pub fn set_percentage_of(&mut self, metal: Metal, value: f32) -> Result<(), AlloyError> {
+        let index = T::constituent_metals()
+            .iter()
+            .position(|m| *m == metal)
+            .ok_or(UnknownConstituent(metal))?;
+        if !value.is_finite() {
+            return Err(InvalidPercentages);
+        }
+        let ranges = percent::basis_point_ranges::<T>();
+        let bp = to_basis_points(value);
+        if !(ranges[index].0..=ranges[index].1).contains(&bp) {
+            return Err(InvalidPercentages);
+        }
+        let mut values: Vec<i64> = self.basis_points().collect();
+        values[index] = bp;
+        let fixed: Vec<bool> = (0..values.len()).map(|i| i == index).collect();
+        let mut values = rebalance(&values, &ranges, &fixed);
+        if distribute_difference(&mut values, &ranges, &fixed) != 0 {
+            return Err(InvalidPercentages);
+        }
+        self.update_values(Some(percent::from_basis_points::<T>(&values)), None)
+    }
+}
+
+/// Keeps the fixed values and shares the rest of the whole among the others in proportion to their supplied values,
+/// holding any that would leave their range at its edge and sharing again among the rest. \
+/// Shares are rounded down, so the result may fall a little short of the whole.
+pub(super) fn rebalance(current: &[i64], ranges: &[(i64, i64)], fixed: &[bool]) -> Vec<i64> {
+    let mut values = current.to_vec();
+    let mut held = fixed.to_vec();
+    loop {
+        let free: Vec<usize> = (0..values.len()).filter(|&i| !held[i]).collect();
+        let remainder = BASIS_POINTS
+            - (0..values.len())
+                .filter(|&i| held[i])
+                .map(|i| values[i])
+                .sum::<i64>();
+        let weight: i64 = free.iter().map(|&i| current[i].max(0)).sum();
+        for &i in &free {
+            values[i] = if weight > 0 {
+                remainder * current[i].max(0) / weight
+            } else {
+                remainder / free.len() as i64
+            };
+        }
+        // Hold whichever side of the ranges is overshot, since holding both at once could overcorrect
+        let over: Vec<usize> = free
+            .iter()
+            .copied()
+            .filter(|&i| values[i] > ranges[i].1)
+            .collect();
+        let out_of_range: Vec<(usize, i64)> = if over.is_empty() {
+            free.iter()
+                .filter(|&&i| values[i] < ranges[i].0)
+                .map(|&i| (i, ranges[i].0))
+                .collect()
+        } else {
+            over.into_iter().map(|i| (i, ranges[i].1)).collect()
+        };
+        if out_of_range.is_empty() {
+            return values;
+        }
+        for (i, edge) in out_of_range {
+            values[i] = edge;
+            held[i] = true;
+        }
+    }
+}
+
+impl Alloy {
+    /// Gets a slider binding for each constituent in canonical order
+    pub fn slider_bindings(&self) -> Vec<SliderBinding> {
+        slider_bindings(self.percentages(), self.kind().percentage_ranges())
+    }
+}
+
+/// Works out the draggable interval of each constituent in basis points so the bounds are exact
+fn slider_bindings(
+    percentages: &[BaseMetal<f32>],
+    ranges: &[BaseMetal<Range>],
+) -> Vec<SliderBinding> {
+    let bounds: Vec<(i64, i64)> = ranges
+        .iter()
+        .map(|r| (to_basis_points(r.min), to_basis_points(r.max)))
+        .collect();
+    let (min_sum, max_sum) = bounds
+        .iter()
+        .fold((0, 0), |(lo, hi), (min, max)| (lo + min, hi + max));
+
+    percentages
+        .iter()
+        .zip(ranges)
+        .zip(&bounds)
+        .map(|((p, range), (min, max))| {
+            // The others can give up at most down to their minimums and take on at most up to their maximums
+            let low = (*min).max(BASIS_POINTS - (max_sum - max));
+            let high = (*max).min(BASIS_POINTS - (min_sum - min));
+            SliderBinding {
+                metal: p.metal(),
+                range: **range,
+                reachable: Range::new(
+                    low as f32 / BASIS_POINTS as f32,
+                    high.max(low) as f32 / BASIS_POINTS as f32,
+                ),
+                step: STEP,
+                value: **p,
+                movable: low < high,
+            }
+        })
+        .collect()
+}
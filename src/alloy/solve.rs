@@ -0,0 +1,211 @@
+//! # Solving
+//! Searching the nugget amounts that make valid alloys when part of the recipe is already decided.
+use super::*;
+
+/// The representable recipe closest to a requested mix at a fixed number of ingots
+#[derive(PartialEq, Debug)]
+pub struct AchievableMix<T: AlloyType> {
+    /// The recipe, whose percentages are the exact ratios of its nuggets
+    pub alloy: AlloyData<T>,
+    /// Sum of the absolute differences between the requested and exact percentages, as a fraction of 1
+    pub distance: f32,
+}
+
+impl<T: AlloyType> AlloyData<T> {
+    /// Finds every valid alloy where the supplied metal contributes exactly the supplied number of nuggets. \
+    /// Results are sorted by number of ingots and then by nugget amounts in canonical order.
+    /// The percentages of each result are the ratios of its nuggets, so creating an alloy with them gives back the same nuggets.
+    /// Returns [`NoSolution`](AlloyError::NoSolution) if there are none.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let solutions = AlloyData::<TinBronze>::solve_with_fixed(Metal::Tin, 2).expect("should be solvable");
+    ///
+    /// assert_eq!(1, solutions.len());
+    /// assert_eq!(&[Copper(18), Tin(2)], solutions[0].nuggets());
+    /// ```
+    pub fn solve_with_fixed(metal: Metal, nuggets: i32) -> Result<Vec<Self>, AlloyError> {
+        let index = T::constituent_metals()
+            .iter()
+            .position(|m| *m == metal)
+            .ok_or(UnknownConstituent(metal))?;
+        let solutions: Vec<Self> = (1..=unit_constants::MAX_POSSIBLE_INGOTS)
+            .flat_map(Self::valid_recipes)
+            .filter(|recipe| *recipe.nuggets()[index] == nuggets)
+            .collect();
+        if solutions.is_empty() {
+            Err(NoSolution)
+        } else {
+            Ok(solutions)
+        }
+    }
+
+    /// Finds the valid alloy where the supplied metal's nuggets are closest to the target, optionally at a pinned number of ingots. \
+    /// Ties go to the recipe using fewer of the metal so a stack is never overdrawn, then to fewer ingots,
+    /// and then to the smallest nugget amounts in canonical order.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = AlloyData::<Brass>::nearest_to_target(Metal::Zinc, 50, None).expect("should be solvable");
+    ///
+    /// assert_eq!(Zinc(50), alloy.nuggets()[1]);
+    /// ```
+    pub fn nearest_to_target(
+        metal: Metal,
+        target_nuggets: i32,
+        ingots: Option<i32>,
+    ) -> Result<Self, AlloyError> {
+        let index = T::constituent_metals()
+            .iter()
+            .position(|m| *m == metal)
+            .ok_or(UnknownConstituent(metal))?;
+        let ingots = match ingots {
+            Some(n) if n > unit_constants::MAX_POSSIBLE_INGOTS => return Err(TooManyIngots),
+            Some(n) if n <= 0 => return Err(TooFewIngots),
+            Some(n) => n..=n,
+            None => 1..=unit_constants::MAX_POSSIBLE_INGOTS,
+        };
+        let key = |recipe: &Self| {
+            let nuggets = *recipe.nuggets()[index];
+            (
+                nuggets.abs_diff(target_nuggets),
+                nuggets,
+                recipe.num_ingots,
+                recipe.nuggets().iter().map(|n| **n).collect::<Vec<_>>(),
+            )
+        };
+        ingots
+            .flat_map(Self::valid_recipes)
+            .min_by_key(key)
+            .ok_or(NoSolution)
+    }
+
+    /// Finds the nugget split at the supplied number of ingots whose exact percentages are closest to the requested ones,
+    /// measured as the sum of absolute differences. Ties go to the split with the most of the primary metal.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let mix = AlloyData::<TinBronze>::nearest_achievable([Copper(0.92), Tin(0.08)], 1).expect("should be valid");
+    ///
+    /// // At 1 ingot you'll actually get 90/10
+    /// assert_eq!(&[Copper(18), Tin(2)], mix.alloy.nuggets());
+    /// assert_eq!(&[Copper(0.9), Tin(0.1)], mix.alloy.percentages());
+    /// assert!((mix.distance - 0.04).abs() < 1e-6);
+    /// ```
+    pub fn nearest_achievable(
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+        num_ingots: i32,
+    ) -> Result<AchievableMix<T>, AlloyError> {
+        if num_ingots > unit_constants::MAX_POSSIBLE_INGOTS {
+            return Err(TooManyIngots);
+        } else if num_ingots <= 0 {
+            return Err(TooFewIngots);
+        }
+        let requested: Vec<i64> = T::check_valid_percentages(percentages)?
+            .as_ref()
+            .iter()
+            .map(|p| to_basis_points(**p))
+            .collect();
+        let total = unit_constants::ingot_nuggets(num_ingots);
+        // Every split has the same total, so scaling both sides by it keeps the comparison exact
+        let distance = |recipe: &Self| -> i64 {
+            requested
+                .iter()
+                .zip(recipe.nuggets())
+                .map(|(bp, n)| (bp * total - i64::from(**n) * BASIS_POINTS).abs())
+                .sum()
+        };
+        let alloy = Self::valid_recipes(num_ingots)
+            .into_iter()
+            .min_by_key(|recipe| (distance(recipe), -*recipe.nuggets()[0]))
+            .ok_or(NoSolution)?;
+        let off = distance(&alloy);
+        Ok(AchievableMix {
+            alloy,
+            distance: (off as f64 / (BASIS_POINTS * total) as f64) as f32,
+        })
+    }
+
+    /// Enumerates the valid alloys for every split of nuggets that fits in the vanilla crucible, the same as
+    /// [`valid_recipes_in`](Self::valid_recipes_in) with [`Capacity::VANILLA`]
+    pub(crate) fn valid_recipes(num_ingots: i32) -> Vec<Self> {
+        Self::valid_recipes_in(num_ingots, Capacity::VANILLA)
+    }
+
+    /// Enumerates every split of nuggets in canonical order that makes the supplied number of ingots,
+    /// keeps each constituent within its range, and fits in the capacity, as the alloys built from them with [`from_nuggets`](Self::from_nuggets). \
+    /// Splits that no percentages re-derive are left out.
+    pub(crate) fn valid_recipes_in(num_ingots: i32, capacity: Capacity) -> Vec<Self> {
+        use unit_constants::*;
+        let total = ingot_nuggets(num_ingots);
+        // Inclusive bounds on the nuggets of each constituent using exact integer math
+        let bounds: Vec<(i32, i32)> = T::percentage_ranges()
+            .iter()
+            .map(|r| {
+                let min = (to_basis_points(r.min) * total + BASIS_POINTS - 1) / BASIS_POINTS;
+                let max = to_basis_points(r.max) * total / BASIS_POINTS;
+                (min as i32, max as i32)
+            })
+            .collect();
+
+        let mut splits = vec![Vec::new()];
+        for (i, (min, max)) in bounds.iter().enumerate() {
+            let last = i == bounds.len() - 1;
+            splits = splits
+                .into_iter()
+                .flat_map(|split: Vec<i32>| {
+                    let used: i32 = split.iter().sum();
+                    let range = if last {
+                        let rest = total as i32 - used;
+                        rest..=rest
+                    } else {
+                        *min..=*max
+                    };
+                    range
+                        .filter(|n| min <= n && n <= max)
+                        .map(move |n| [split.as_slice(), &[n]].concat())
+                })
+                .collect();
+        }
+        splits
+            .into_iter()
+            .filter(|split| {
+                let nuggets = T::constituent_metals()
+                    .iter()
+                    .copied()
+                    .zip(split.iter().copied());
+                crucible::fits_in(capacity, &capacity.stacks(nuggets)).is_ok()
+            })
+            .filter_map(|split| Self::from_nuggets(&split, num_ingots))
+            .collect()
+    }
+
+    /// Builds the alloy that [`try_new`](Self::try_new) creates from the ratios of the nugget amounts in canonical order,
+    /// or returns `None` if those percentages do not give back exactly the same nuggets. \
+    /// Every ratio but the last is rounded up to the fixed point precision and the last takes the rest,
+    /// so a share that lands exactly on a nugget is not floored to the one below by float error.
+    pub(crate) fn from_nuggets(nuggets: &[i32], num_ingots: i32) -> Option<Self> {
+        let total = i64::from(nuggets.iter().sum::<i32>());
+        if total <= 0 {
+            return None;
+        }
+        let mut scaled = [0; MAX_CONSTITUENTS];
+        let (last, rest) = scaled.get_mut(..nuggets.len())?.split_last_mut()?;
+        for (share, n) in rest.iter_mut().zip(nuggets) {
+            *share = div_ceil(i64::from(*n) * PERCENT_SCALE, total);
+        }
+        *last = PERCENT_SCALE - rest.iter().sum::<i64>();
+        let percentages =
+            T::percentages_from_fn(|i| (scaled[i] as f64 / PERCENT_SCALE as f64) as f32);
+        Self::try_new(percentages, num_ingots).ok().filter(|alloy| {
+            alloy
+                .nuggets()
+                .iter()
+                .map(|n| **n)
+                .eq(nuggets.iter().copied())
+        })
+    }
+}
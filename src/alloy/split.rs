@@ -0,0 +1,115 @@
+//! # Splitting
+//! Dividing a recipe into several smaller crucible loads with the same percentages.
+use super::*;
+
+impl<T: AlloyType> AlloyData<T> {
+    /// Splits the alloy into the supplied number of parts with the same percentages whose ingots total the original. \
+    /// Ingots are spread as evenly as possible with the earlier parts taking one extra when they do not divide evenly.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = AlloyData::<Brass>::try_new([Copper(0.7), Zinc(0.3)], 21).expect("should be valid");
+    /// let parts = alloy.split(2).expect("should split");
+    ///
+    /// assert_eq!(vec![11, 10], parts.iter().map(|p| p.num_ingots()).collect::<Vec<_>>());
+    /// ```
+    pub fn split(&self, parts: usize) -> Result<Vec<Self>, AlloyError> {
+        let parts = i32::try_from(parts).map_err(|_| TooFewIngots)?;
+        if parts == 0 {
+            return Err(InvalidValues);
+        } else if parts > self.num_ingots {
+            return Err(TooFewIngots);
+        }
+        let (base, extra) = (self.num_ingots / parts, self.num_ingots % parts);
+        (0..parts)
+            .map(|i| Self::try_new(self.percentages(), base + i32::from(i < extra)))
+            .collect()
+    }
+
+    /// Gets how many more nuggets of each constituent the parts need in total than the unsplit alloy, which can differ due to rounding
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = AlloyData::<TinBronze>::try_new([Copper(0.92), Tin(0.08)], 10).expect("should be valid");
+    /// let parts = alloy.split(2).expect("should split");
+    ///
+    /// assert_eq!(vec![Copper(0), Tin(0)], alloy.split_overhead(&parts));
+    /// ```
+    pub fn split_overhead(&self, parts: &[Self]) -> Vec<BaseMetal<i32>> {
+        self.nuggets()
+            .iter()
+            .enumerate()
+            .map(|(i, n)| {
+                let split: i32 = parts.iter().map(|p| *p.nuggets()[i]).sum();
+                n.update_inner_value(split - **n)
+            })
+            .collect()
+    }
+}
+
+impl Alloy {
+    /// Splits the alloy into the supplied number of parts with the same percentages whose ingots total the original
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = Alloys::Electrum.try_new([Gold(0.5), Silver(0.5)], 9).expect("should be valid");
+    /// let parts = alloy.split(3).expect("should split");
+    ///
+    /// assert!(parts.iter().all(|p| p.num_ingots() == 3));
+    /// ```
+    pub fn split(&self, parts: usize) -> Result<Vec<Alloy>, AlloyError> {
+        fn wrap<T: AlloyType>(
+            parts: Result<Vec<AlloyData<T>>, AlloyError>,
+            variant: fn(AlloyData<T>) -> Alloy,
+        ) -> Result<Vec<Alloy>, AlloyError> {
+            Ok(parts?.into_iter().map(variant).collect())
+        }
+        match self {
+            Alloy::TinBronze(alloy_data) => wrap(alloy_data.split(parts), Alloy::TinBronze),
+            Alloy::BismuthBronze(alloy_data) => wrap(alloy_data.split(parts), Alloy::BismuthBronze),
+            Alloy::BlackBronze(alloy_data) => wrap(alloy_data.split(parts), Alloy::BlackBronze),
+            Alloy::Brass(alloy_data) => wrap(alloy_data.split(parts), Alloy::Brass),
+            Alloy::Molybdochalkos(alloy_data) => {
+                wrap(alloy_data.split(parts), Alloy::Molybdochalkos)
+            }
+            Alloy::LeadSolder(alloy_data) => wrap(alloy_data.split(parts), Alloy::LeadSolder),
+            Alloy::SilverSolder(alloy_data) => wrap(alloy_data.split(parts), Alloy::SilverSolder),
+            Alloy::Electrum(alloy_data) => wrap(alloy_data.split(parts), Alloy::Electrum),
+            Alloy::Cupronickel(alloy_data) => wrap(alloy_data.split(parts), Alloy::Cupronickel),
+        }
+    }
+
+    /// Gets how many more nuggets of each constituent the parts need in total than the unsplit alloy. \
+    /// Returns an [`AlloyMismatch`] holding the first part that is a different alloy.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = Alloys::TinBronze.try_new([Copper(0.92), Tin(0.08)], 19).expect("should be valid");
+    /// let parts = alloy.split(3).expect("should split");
+    ///
+    /// assert_eq!(Ok(vec![Copper(-1), Tin(1)]), alloy.split_overhead(&parts));
+    /// assert!(alloy.split_overhead(&[Alloys::Brass.get_default()]).is_err());
+    /// ```
+    pub fn split_overhead(&self, parts: &[Alloy]) -> Result<Vec<BaseMetal<i32>>, AlloyMismatch> {
+        if let Some(part) = parts.iter().find(|p| p.kind() != self.kind()) {
+            return Err(AlloyMismatch {
+                expected: self.kind(),
+                actual: part.kind(),
+                alloy: part.clone(),
+            });
+        }
+        Ok(self
+            .nuggets()
+            .iter()
+            .enumerate()
+            .map(|(i, n)| {
+                let split: i32 = parts.iter().map(|p| *p.nuggets()[i]).sum();
+                n.update_inner_value(split - **n)
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,193 @@
+//! # Calculation Traces
+//! Step by step record of how an alloy's nuggets were worked out, for showing the working behind a result.
+use super::*;
+use crate::format::FormatOptions;
+use std::fmt::{Display, Formatter};
+
+/// A single step of the nugget calculation
+#[derive(Clone, PartialEq, Debug)]
+#[non_exhaustive]
+pub enum TraceStep {
+    /// The percentages passed validation and were put in canonical order
+    Validated { percentages: Box<[BaseMetal<f32>]> },
+    /// The most ingots the percentages fit in the crucible and whether the requested number is within it
+    SlotFeasibility { num_ingots: i32, max_ingots: i32 },
+    /// Units of metal needed for the requested ingots
+    UnitsNeeded { num_ingots: i32, units: f32 },
+    /// A constituent's share of the units, rounded down to whole nuggets
+    Share {
+        metal: Metal,
+        percentage: f32,
+        units: f32,
+        nuggets: i32,
+        slots: i32,
+    },
+    /// The last constituent takes whatever units are left, rounded up to whole nuggets
+    Remainder {
+        metal: Metal,
+        units: f32,
+        nuggets: i32,
+        slots: i32,
+    },
+    /// A nugget moved between constituents after rounding
+    Adjusted(Adjustment),
+    /// The final nugget amounts
+    Nuggets { nuggets: Box<[BaseMetal<i32>]> },
+}
+
+/// Every step taken to work out an alloy's nuggets, along with the results
+#[derive(Clone, PartialEq, Debug)]
+pub struct CalculationTrace {
+    pub steps: Vec<TraceStep>,
+    pub nuggets: Box<[BaseMetal<i32>]>,
+    pub max_ingots: i32,
+}
+
+impl<T: AlloyType> AlloyData<T> {
+    /// Works out the nuggets for the supplied parameters exactly like [`AlloyData::try_new`], recording each step along the way
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let trace = AlloyData::<TinBronze>::explain([Copper(0.92), Tin(0.08)], 5).expect("should be valid");
+    ///
+    /// assert_eq!(&[Copper(92), Tin(8)], &trace.nuggets[..]);
+    /// assert!(trace.to_string().starts_with("1. Validated percentages: 92% Copper, 8% Tin"));
+    /// ```
+    pub fn explain(
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+        num_ingots: i32,
+    ) -> Result<CalculationTrace, AlloyError> {
+        if num_ingots > unit_constants::MAX_POSSIBLE_INGOTS {
+            return Err(TooManyIngots);
+        } else if num_ingots <= 0 {
+            return Err(TooFewIngots);
+        }
+        let percentages = T::check_valid_percentages(percentages)?;
+        let percentages = percentages.as_ref();
+        let max_ingots = Self::validated_max_ingots(percentages);
+        let mut steps = vec![
+            TraceStep::Validated {
+                percentages: percentages.into(),
+            },
+            TraceStep::SlotFeasibility {
+                num_ingots,
+                max_ingots,
+            },
+        ];
+
+        let alloy_type =
+            Self::nuggets_traced(percentages, num_ingots, max_ingots, Some(&mut steps), None)?;
+        let nuggets: Box<[BaseMetal<i32>]> = alloy_type.nuggets().into();
+        steps.push(TraceStep::Nuggets {
+            nuggets: nuggets.clone(),
+        });
+        Ok(CalculationTrace {
+            steps,
+            nuggets,
+            max_ingots,
+        })
+    }
+}
+
+impl Display for TraceStep {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let options = FormatOptions::default();
+        let units = |units: f32| options.decimal(f64::from(units));
+        let slots = |slots: i32| if slots == 1 { "slot" } else { "slots" };
+        match self {
+            TraceStep::Validated { percentages } => {
+                let percentages: Vec<String> = percentages
+                    .iter()
+                    .map(|p| format!("{} {}", options.percent(**p), p.name()))
+                    .collect();
+                write!(f, "Validated percentages: {}", percentages.join(", "))
+            }
+            TraceStep::SlotFeasibility {
+                num_ingots,
+                max_ingots,
+            } => write!(
+                f,
+                "At most {} fit in the crucible, so {} {}",
+                options.ingots(*max_ingots),
+                options.ingots(*num_ingots),
+                if num_ingots <= max_ingots {
+                    "fit"
+                } else {
+                    "do not fit"
+                }
+            ),
+            TraceStep::UnitsNeeded {
+                num_ingots,
+                units: n,
+            } => {
+                write!(
+                    f,
+                    "{} need {} units",
+                    options.ingots(*num_ingots),
+                    units(*n)
+                )
+            }
+            TraceStep::Share {
+                metal,
+                percentage,
+                units: n,
+                nuggets,
+                slots: s,
+            } => write!(
+                f,
+                "{}: {} of the units is {} units, rounded down to {nuggets} nuggets in {s} {}",
+                metal.name(),
+                options.percent(*percentage),
+                units(*n),
+                slots(*s)
+            ),
+            TraceStep::Remainder {
+                metal,
+                units: n,
+                nuggets,
+                slots: s,
+            } => write!(
+                f,
+                "{}: the remaining {} units, rounded up to {nuggets} nuggets in {s} {}",
+                metal.name(),
+                units(*n),
+                slots(*s)
+            ),
+            TraceStep::Adjusted(adjustment) => write!(
+                f,
+                "{} {} nugget {}",
+                adjustment.metal.name(),
+                if adjustment.change > 0 {
+                    "gains a"
+                } else {
+                    "loses a"
+                },
+                match adjustment.reason {
+                    AdjustmentReason::SumCorrection => "to make up the total",
+                    AdjustmentReason::RangeClamp => "to keep the last constituent within its range",
+                }
+            ),
+            TraceStep::Nuggets { nuggets } => {
+                let nuggets: Vec<String> = nuggets
+                    .iter()
+                    .map(|n| format!("{} {}", **n, n.name()))
+                    .collect();
+                write!(f, "Result: {} nuggets", nuggets.join(", "))
+            }
+        }
+    }
+}
+
+impl Display for CalculationTrace {
+    /// Writes the steps as a numbered list, one per line
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}. {step}", i + 1)?;
+        }
+        Ok(())
+    }
+}
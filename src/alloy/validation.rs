@@ -0,0 +1,115 @@
+//! # Validation Report
+//! Every problem with a set of percentages at once, rather than the first error found by [`AlloyData::check_valid_percentages`].
+use super::*;
+
+/// A single problem found with the supplied percentages
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ValidationIssue {
+    /// The metal's percentage is outside the range allowed for the alloy
+    OutOfRange {
+        metal: Metal,
+        value: f32,
+        range: Range,
+    },
+    /// The metal is not a constituent of the alloy
+    UnknownMetal { metal: Metal },
+    /// The metal was supplied more than once
+    DuplicateMetal { metal: Metal },
+    /// The constituent was not supplied
+    MissingMetal { metal: Metal },
+    /// The percentages do not total 1.0
+    BadTotal { sum: f32 },
+}
+
+/// Every problem found by [`AlloyData::validate_percentages`], in the order the percentages were supplied,
+/// followed by any missing constituents in canonical order and then the total
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Whether no problems were found, the same as [`AlloyData::check_valid_percentages`] succeeding
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl<T: AlloyType> AlloyData<T> {
+    /// Checks the supplied percentages like [`AlloyData::check_valid_percentages`] but gathers every problem instead of stopping at the first
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::{ConstituentRange, ValidationIssue};
+    ///
+    /// let report = AlloyData::<TinBronze>::validate_percentages([Copper(0.86), Zinc(0.14)]);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         ValidationIssue::OutOfRange { metal: Metal::Copper, value: 0.86, range: ConstituentRange::new(0.88, 0.92) },
+    ///         ValidationIssue::UnknownMetal { metal: Metal::Zinc },
+    ///         ValidationIssue::MissingMetal { metal: Metal::Tin },
+    ///     ],
+    ///     report.issues
+    /// );
+    /// ```
+    pub fn validate_percentages(percentages: impl AsRef<[BaseMetal<f32>]>) -> ValidationReport {
+        let percentages = percentages.as_ref();
+        let metals = T::constituent_metals();
+        let ranges = T::percentage_ranges();
+        let mut seen = vec![false; metals.len()];
+        let mut issues = Vec::new();
+        for p in percentages {
+            let metal = p.metal();
+            match metals.iter().position(|m| *m == metal) {
+                None => issues.push(ValidationIssue::UnknownMetal { metal }),
+                Some(i) if seen[i] => issues.push(ValidationIssue::DuplicateMetal { metal }),
+                Some(i) => {
+                    seen[i] = true;
+                    if !ranges[i].contains(p) {
+                        issues.push(ValidationIssue::OutOfRange {
+                            metal,
+                            value: **p,
+                            range: *ranges[i],
+                        });
+                    }
+                }
+            }
+        }
+        issues.extend(
+            metals
+                .iter()
+                .zip(&seen)
+                .filter(|(_, seen)| !**seen)
+                .map(|(metal, _)| ValidationIssue::MissingMetal { metal: *metal }),
+        );
+        if !T::check_percentages_total(percentages) {
+            issues.push(ValidationIssue::BadTotal {
+                sum: percentages.iter().map(|p| **p).sum(),
+            });
+        }
+        ValidationReport { issues }
+    }
+}
+
+impl Alloys {
+    /// Checks the supplied percentages for the alloy and gathers every problem, the same as [`AlloyData::validate_percentages`]
+    pub fn validate_percentages(
+        &self,
+        percentages: impl AsRef<[BaseMetal<f32>]>,
+    ) -> ValidationReport {
+        match self {
+            Alloys::TinBronze => AlloyData::<TinBronze>::validate_percentages(percentages),
+            Alloys::BismuthBronze => AlloyData::<BismuthBronze>::validate_percentages(percentages),
+            Alloys::BlackBronze => AlloyData::<BlackBronze>::validate_percentages(percentages),
+            Alloys::Brass => AlloyData::<Brass>::validate_percentages(percentages),
+            Alloys::Molybdochalkos => {
+                AlloyData::<Molybdochalkos>::validate_percentages(percentages)
+            }
+            Alloys::LeadSolder => AlloyData::<LeadSolder>::validate_percentages(percentages),
+            Alloys::SilverSolder => AlloyData::<SilverSolder>::validate_percentages(percentages),
+            Alloys::Electrum => AlloyData::<Electrum>::validate_percentages(percentages),
+            Alloys::Cupronickel => AlloyData::<Cupronickel>::validate_percentages(percentages),
+        }
+    }
+}
@@ -10,8 +10,10 @@ pub mod base_metal_names {
 }
 
 /// Enum for modeling different values of base metals used to create an [`Alloy`](crate::Alloy)
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
-pub enum BaseMetal<T: Copy> {
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum BaseMetal<T> {
     Nickel(T),
     Copper(T),
     Zinc(T),
@@ -23,7 +25,239 @@ pub enum BaseMetal<T: Copy> {
 }
 use BaseMetal::*;
 
-impl<T: Copy> BaseMetal<T> {
+/// Enum for identifying a base metal without any value attached, eg. for use as a map key
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Metal {
+    Nickel,
+    Copper,
+    Zinc,
+    Silver,
+    Tin,
+    Gold,
+    Lead,
+    Bismuth,
+}
+
+impl Metal {
+    /// All of the metals in the same order as the variants are declared
+    pub const ALL: [Metal; 8] = [
+        Metal::Nickel,
+        Metal::Copper,
+        Metal::Zinc,
+        Metal::Silver,
+        Metal::Tin,
+        Metal::Gold,
+        Metal::Lead,
+        Metal::Bismuth,
+    ];
+
+    /// Returns the name of the metal as a string
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!("Bismuth", Metal::Bismuth.name());
+    /// ```
+    pub fn name(&self) -> &'static str {
+        use base_metal_names::*;
+        match self {
+            Metal::Nickel => NICKEL,
+            Metal::Copper => COPPER,
+            Metal::Zinc => ZINC,
+            Metal::Silver => SILVER,
+            Metal::Tin => TIN,
+            Metal::Gold => GOLD,
+            Metal::Lead => LEAD,
+            Metal::Bismuth => BISMUTH,
+        }
+    }
+
+    /// Creates the base metal variant for this metal holding the supplied value
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(Zinc(0.3), Metal::Zinc.with_value(0.3));
+    /// ```
+    pub fn with_value<T>(self, value: T) -> BaseMetal<T> {
+        match self {
+            Metal::Nickel => Nickel(value),
+            Metal::Copper => Copper(value),
+            Metal::Zinc => Zinc(value),
+            Metal::Silver => Silver(value),
+            Metal::Tin => Tin(value),
+            Metal::Gold => Gold(value),
+            Metal::Lead => Lead(value),
+            Metal::Bismuth => Bismuth(value),
+        }
+    }
+}
+
+/// Running totals of nuggets of each metal
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub struct MetalTotals {
+    /// Indexed in the same order as [`Metal::ALL`]
+    nuggets: [i32; 8],
+}
+
+impl MetalTotals {
+    /// Creates totals with nothing counted yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the total nuggets of the supplied metal
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::MetalTotals;
+    ///
+    /// let totals = MetalTotals::from_iter([Copper(10), Tin(2), Copper(5)]);
+    ///
+    /// assert_eq!(15, totals.get(Metal::Copper));
+    /// assert_eq!(0, totals.get(Metal::Zinc));
+    /// ```
+    pub fn get(&self, metal: Metal) -> i32 {
+        self.nuggets[metal as usize]
+    }
+
+    /// Adds nuggets of a metal to the totals, saturating at the bounds of `i32`
+    pub fn add(&mut self, nuggets: BaseMetal<i32>) {
+        let total = &mut self.nuggets[nuggets.metal() as usize];
+        *total = total.saturating_add(*nuggets);
+    }
+
+    /// Sets the total nuggets of a metal
+    pub fn set(&mut self, nuggets: BaseMetal<i32>) {
+        self.nuggets[nuggets.metal() as usize] = *nuggets;
+    }
+
+    /// Iterates over the metals with a non-zero total in the same order as [`Metal::ALL`]
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::MetalTotals;
+    ///
+    /// let totals = MetalTotals::from_iter([Tin(2), Copper(10)]);
+    ///
+    /// assert_eq!(vec![Copper(10), Tin(2)], totals.iter().collect::<Vec<_>>());
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = BaseMetal<i32>> + '_ {
+        Metal::ALL
+            .into_iter()
+            .map(|metal| metal.with_value(self.get(metal)))
+            .filter(|n| **n != 0)
+    }
+}
+
+impl FromIterator<BaseMetal<i32>> for MetalTotals {
+    fn from_iter<I: IntoIterator<Item = BaseMetal<i32>>>(iter: I) -> Self {
+        let mut totals = Self::new();
+        for nuggets in iter {
+            totals.add(nuggets);
+        }
+        totals
+    }
+}
+
+/// Percentage input for creating an alloy, collected from loosely ordered sources such as maps or iterators of tuples
+///
+/// No validation happens here. The alloy reorders and validates the percentages when they are passed to [`AlloyData::try_new`](crate::AlloyData::try_new).
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct Percentages(Vec<BaseMetal<f32>>);
+
+impl Percentages {
+    /// Creates an empty percentage input
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl AsRef<[BaseMetal<f32>]> for Percentages {
+    fn as_ref(&self) -> &[BaseMetal<f32>] {
+        &self.0
+    }
+}
+
+impl FromIterator<(Metal, f32)> for Percentages {
+    /// Collects the percentages as given, so repeated metals are kept and later rejected by validation
+    fn from_iter<I: IntoIterator<Item = (Metal, f32)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(metal, percentage)| metal.with_value(percentage))
+                .collect(),
+        )
+    }
+}
+
+impl FromIterator<BaseMetal<f32>> for Percentages {
+    fn from_iter<I: IntoIterator<Item = BaseMetal<f32>>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl FromIterator<BaseMetal<crate::Percent>> for Percentages {
+    fn from_iter<I: IntoIterator<Item = BaseMetal<crate::Percent>>>(iter: I) -> Self {
+        Self(iter.into_iter().map(|p| p.map(f32::from)).collect())
+    }
+}
+
+impl<const N: usize> From<[BaseMetal<crate::Percent>; N]> for Percentages {
+    /// Collects already checked percentages so they can be passed anywhere percentages are accepted
+    fn from(percentages: [BaseMetal<crate::Percent>; N]) -> Self {
+        percentages.into_iter().collect()
+    }
+}
+
+impl From<&std::collections::HashMap<Metal, f32>> for Percentages {
+    /// Sorts the entries by metal so the result does not depend on the map's iteration order
+    /// ### Example
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::Percentages;
+    ///
+    /// let map = HashMap::from([(Metal::Tin, 0.1), (Metal::Copper, 0.9)]);
+    ///
+    /// assert_eq!([Copper(0.9), Tin(0.1)].as_slice(), Percentages::from(&map).as_ref());
+    /// ```
+    fn from(map: &std::collections::HashMap<Metal, f32>) -> Self {
+        let mut percentages: Self = map.iter().map(|(m, p)| (*m, *p)).collect();
+        percentages.0.sort_by_key(|p| p.metal());
+        percentages
+    }
+}
+
+impl<T> BaseMetal<T> {
+    /// Creates the base metal variant for the metal holding the supplied value, the same as [`Metal::with_value`]
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let tin = BaseMetal::from_metal(Metal::Tin, 12);
+    ///
+    /// assert_eq!(Tin(12), tin);
+    /// assert_eq!(Metal::Tin, tin.metal());
+    /// ```
+    pub fn from_metal(metal: Metal, value: T) -> Self {
+        metal.with_value(value)
+    }
+
+    /// Creates the base metal variant named by the string holding the supplied value. \
+    /// The name is matched like [`Metal`]'s [`FromStr`](std::str::FromStr) implementation, ignoring case.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(Ok(Copper(0.92)), BaseMetal::try_from_name("copper", 0.92));
+    /// assert!(BaseMetal::try_from_name("iron", 0.92).is_err());
+    /// ```
+    pub fn try_from_name(name: &str, value: T) -> Result<Self, crate::parse::ParseMetalError> {
+        Ok(name.parse::<Metal>()?.with_value(value))
+    }
+
     /// Returns the name of the base metal variant as a string
     /// ### Example
     /// ```rust
@@ -35,19 +269,44 @@ impl<T: Copy> BaseMetal<T> {
     /// assert_eq!("Copper", name);
     /// ```
     pub fn name(&self) -> &str {
-        use base_metal_names::*;
+        self.metal().name()
+    }
+
+    /// Returns which metal the base metal variant is without its value
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let copper = Copper(5);
+    ///
+    /// assert_eq!(Metal::Copper, copper.metal());
+    /// ```
+    pub fn metal(&self) -> Metal {
         match self {
-            Nickel(_) => NICKEL,
-            Copper(_) => COPPER,
-            Zinc(_) => ZINC,
-            Silver(_) => SILVER,
-            Tin(_) => TIN,
-            Gold(_) => GOLD,
-            Lead(_) => LEAD,
-            Bismuth(_) => BISMUTH,
+            Nickel(_) => Metal::Nickel,
+            Copper(_) => Metal::Copper,
+            Zinc(_) => Metal::Zinc,
+            Silver(_) => Metal::Silver,
+            Tin(_) => Metal::Tin,
+            Gold(_) => Metal::Gold,
+            Lead(_) => Metal::Lead,
+            Bismuth(_) => Metal::Bismuth,
         }
     }
 
+    /// Checks whether two base metals are the same metal, ignoring their values. \
+    /// Equality and hashing on [`BaseMetal`] include the value, so use [`metal`](BaseMetal::metal) as the key when a map should hold one entry per metal.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert!(Copper(5).same_metal(&Copper(0.5)));
+    /// assert!(!Copper(5).same_metal(&Tin(5)));
+    /// ```
+    pub fn same_metal<U>(&self, other: &BaseMetal<U>) -> bool {
+        self.metal() == other.metal()
+    }
+
     /// Updates the base metal with the new value in place
     /// ### Example
     /// ```rust
@@ -83,7 +342,7 @@ impl<T: Copy> BaseMetal<T> {
     /// let copper: BaseMetal<f64> = copper.update_inner_value(10.0);
     /// assert_eq!(10.0, *copper);
     /// ```
-    pub fn update_inner_value<U: Copy>(&self, value: U) -> BaseMetal<U> {
+    pub fn update_inner_value<U>(&self, value: U) -> BaseMetal<U> {
         match self {
             Nickel(_) => Nickel(value),
             Copper(_) => Copper(value),
@@ -97,7 +356,166 @@ impl<T: Copy> BaseMetal<T> {
     }
 }
 
-impl<T: Copy> std::ops::Deref for BaseMetal<T> {
+impl<T> BaseMetal<T> {
+    /// Creates a new instance of the same base metal by applying a function to its value, \
+    /// such as scaling nuggets to units with `nuggets.map(|n| n * NUGGET_UNIT_AMOUNT)`
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let label: BaseMetal<String> = Tin(12).map(|n| format!("{n} nuggets"));
+    ///
+    /// assert_eq!(Tin(String::from("12 nuggets")), label);
+    /// ```
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> BaseMetal<U> {
+        let metal = self.metal();
+        metal.with_value(f(self.into_inner()))
+    }
+
+    /// Consumes the base metal and returns its value
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(String::from("tin"), Tin(String::from("tin")).into_inner());
+    /// ```
+    pub fn into_inner(self) -> T {
+        match self {
+            Nickel(t) | Copper(t) | Zinc(t) | Silver(t) | Tin(t) | Gold(t) | Lead(t)
+            | Bismuth(t) => t,
+        }
+    }
+
+    /// Pairs the values of two base metals of the same metal, keeping the metal
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(Ok(Tin((12, 0.08))), Tin(12).zip(Tin(0.08)));
+    /// assert!(Tin(12).zip(Copper(0.92)).is_err());
+    /// ```
+    pub fn zip<U>(self, other: BaseMetal<U>) -> Result<BaseMetal<(T, U)>, MetalMismatch> {
+        self.try_combine(other, |a, b| (a, b))
+    }
+
+    /// Combines the values of two base metals of the same metal with a function, keeping the metal
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(Ok(Copper(15)), Copper(10).try_combine(Copper(5), |a, b| a + b));
+    ///
+    /// let error = Copper(10).try_combine(Zinc(5), |a, b| a + b).unwrap_err();
+    /// assert_eq!(Metal::Copper, error.expected);
+    /// assert_eq!(Metal::Zinc, error.actual);
+    /// ```
+    pub fn try_combine<U, V>(
+        self,
+        other: BaseMetal<U>,
+        f: impl FnOnce(T, U) -> V,
+    ) -> Result<BaseMetal<V>, MetalMismatch> {
+        let (expected, actual) = (self.metal(), other.metal());
+        if expected != actual {
+            return Err(MetalMismatch { expected, actual });
+        }
+        Ok(expected.with_value(f(self.into_inner(), other.into_inner())))
+    }
+
+    /// Gets a base metal of the same variant holding a reference to the value, so it can be mapped without moving the value
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let name = Copper(String::from("ore"));
+    /// let length = name.as_value_ref().map(|s| s.len());
+    ///
+    /// assert_eq!(Copper(3), length);
+    /// assert_eq!(Copper(String::from("ore")), name); // Still usable
+    /// ```
+    pub fn as_value_ref(&self) -> BaseMetal<&T> {
+        self.update_inner_value(&**self)
+    }
+}
+
+/// Error from combining two base metals that hold different metals
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MetalMismatch {
+    /// The metal of the base metal being combined into
+    pub expected: Metal,
+    /// The metal of the other base metal
+    pub actual: Metal,
+}
+
+impl std::fmt::Display for MetalMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "expected {} but found {}",
+            self.expected.name(),
+            self.actual.name()
+        )
+    }
+}
+
+impl std::error::Error for MetalMismatch {}
+
+impl<T: Copy> BaseMetal<T> {
+    /// Gets a copy of the value, which reads better than `**metal` in iterator chains
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = AlloyData::<TinBronze>::try_new([Copper(0.92), Tin(0.08)], 5).expect("should be valid");
+    ///
+    /// assert_eq!(100, alloy.nuggets().iter().map(BaseMetal::value).sum::<i32>());
+    /// ```
+    pub fn value(&self) -> T {
+        **self
+    }
+}
+
+/// Extension for querying a slice of base metals, such as [`AlloyData::nuggets`](crate::AlloyData::nuggets)
+pub trait BaseMetalSlice<T> {
+    /// Iterates over copies of the values in order, without their metals
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let percentages = [Copper(0.92), Tin(0.08)];
+    ///
+    /// assert_eq!(vec![0.92, 0.08], percentages.values().collect::<Vec<_>>());
+    /// ```
+    fn values(&self) -> impl Iterator<Item = T> + '_
+    where
+        T: Copy;
+
+    /// Finds the first entry for the metal, whatever its value
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = AlloyData::<TinBronze>::try_new([Copper(0.92), Tin(0.08)], 5).expect("should be valid");
+    ///
+    /// assert_eq!(Some(&Tin(8)), alloy.nuggets().find_metal(Metal::Tin));
+    /// assert_eq!(None, alloy.nuggets().find_metal(Metal::Zinc));
+    /// ```
+    fn find_metal(&self, metal: Metal) -> Option<&BaseMetal<T>>;
+}
+
+impl<T> BaseMetalSlice<T> for [BaseMetal<T>] {
+    fn values(&self) -> impl Iterator<Item = T> + '_
+    where
+        T: Copy,
+    {
+        self.iter().map(BaseMetal::value)
+    }
+
+    fn find_metal(&self, metal: Metal) -> Option<&BaseMetal<T>> {
+        self.iter().find(|m| m.metal() == metal)
+    }
+}
+
+impl<T> std::ops::Deref for BaseMetal<T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
@@ -113,3 +531,98 @@ impl<T: Copy> std::ops::Deref for BaseMetal<T> {
         }
     }
 }
+
+impl<T> std::ops::DerefMut for BaseMetal<T> {
+    /// Gets mutable access to the value while the metal stays the same
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let mut copper = Copper(18);
+    /// *copper += 5;
+    ///
+    /// assert_eq!(Copper(23), copper);
+    /// ```
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            Nickel(t) | Copper(t) | Zinc(t) | Silver(t) | Tin(t) | Gold(t) | Lead(t)
+            | Bismuth(t) => t,
+        }
+    }
+}
+
+impl<T: std::ops::Add<Output = T>> std::ops::Add for BaseMetal<T> {
+    type Output = Result<BaseMetal<T>, MetalMismatch>;
+
+    /// Adds the values of two base metals of the same metal, the same as [`BaseMetal::try_combine`] with `+`. \
+    /// The sum is a [`Result`] rather than a panic when the metals differ.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(Ok(Copper(110)), Copper(18) + Copper(92));
+    /// assert!((Copper(18) + Tin(2)).is_err());
+    /// ```
+    fn add(self, rhs: Self) -> Self::Output {
+        self.try_combine(rhs, T::add)
+    }
+}
+
+impl<T: std::ops::Sub<Output = T>> std::ops::Sub for BaseMetal<T> {
+    type Output = Result<BaseMetal<T>, MetalMismatch>;
+
+    /// Subtracts the value of another base metal of the same metal, the same as [`BaseMetal::try_combine`] with `-`
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(Ok(Tin(6)), Tin(8) - Tin(2));
+    /// assert!((Tin(8) - Zinc(2)).is_err());
+    /// ```
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.try_combine(rhs, T::sub)
+    }
+}
+
+impl<T: std::ops::Mul<Output = T>> std::ops::Mul<T> for BaseMetal<T> {
+    type Output = BaseMetal<T>;
+
+    /// Scales the value, such as the nuggets of a recipe for several batches
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(Copper(54), Copper(18) * 3);
+    /// ```
+    fn mul(self, rhs: T) -> Self::Output {
+        self.map(|value| value * rhs)
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for BaseMetal<T> {
+    /// Writes the name of the metal followed by its value, passing any width or precision on to the value
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!("Copper: 128", Copper(128).to_string());
+    /// assert_eq!("Tin: 0.08", format!("{:.2}", Tin(0.08)));
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.name())?;
+        (**self).fmt(f)
+    }
+}
+
+impl BaseMetal<f32> {
+    /// Gets the same base metal with its value written as a whole percentage with the default [`FormatOptions`](crate::format::FormatOptions)
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!("Copper: 92%", Copper(0.92).as_percent().to_string());
+    /// ```
+    pub fn as_percent(&self) -> BaseMetal<String> {
+        self.update_inner_value(crate::format::FormatOptions::default().percent(**self))
+    }
+}
@@ -0,0 +1,24 @@
+//! Prints the verification cases as a checklist to work through at an in-game crucible, or as CSV with `--csv`.
+use vs_alloy_calculator::verification::{self, CSV_HEADER};
+
+fn main() {
+    let cases = verification::cases();
+    if std::env::args().any(|arg| arg == "--csv") {
+        println!("{CSV_HEADER}");
+        for case in &cases {
+            println!("{}", case.to_csv_row());
+        }
+    } else {
+        let mut alloy = None;
+        for case in &cases {
+            if alloy != Some(case.alloy) {
+                if alloy.is_some() {
+                    println!();
+                }
+                println!("## {}", case.alloy.name());
+                alloy = Some(case.alloy);
+            }
+            println!("{case}");
+        }
+    }
+}
@@ -0,0 +1,178 @@
+//! # Crucible
+//! Checks for whether planned item stacks will physically fit in a crucible.
+//! The slot and stack limits live on [`Capacity`], which the max ingot searches, planner, and shopping list all go through,
+//! and the splits the planner and solvers consider are laid out in stacks and checked with [`fits_in`].
+use crate::Metal;
+use crate::unit_constants::*;
+
+/// The space available for smelting, which mods may enlarge well past the vanilla crucible
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Capacity {
+    /// Number of slots
+    pub slots: i32,
+    /// Most units of metal a single slot holds
+    pub max_units_per_slot: i32,
+}
+
+impl Capacity {
+    /// The vanilla crucible with 4 slots that each hold a stack of 128 nuggets
+    pub const VANILLA: Self = Self {
+        slots: CRUCIBLE_SLOTS,
+        max_units_per_slot: MAX_UNITS_PER_SLOT,
+    };
+
+    /// The most ingots that could fit if every slot were filled, saturating at `i32::MAX`
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::crucible::Capacity;
+    ///
+    /// assert_eq!(25, Capacity::VANILLA.max_possible_ingots());
+    /// assert_eq!(51, Capacity { slots: 8, ..Capacity::VANILLA }.max_possible_ingots());
+    /// ```
+    pub fn max_possible_ingots(&self) -> i32 {
+        let units = i64::from(self.slots) * i64::from(self.max_units_per_slot);
+        i32::try_from(units / i64::from(INGOT_UNIT_AMOUNT)).unwrap_or(i32::MAX)
+    }
+
+    /// The most nuggets a single slot holds
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::crucible::Capacity;
+    ///
+    /// assert_eq!(128, Capacity::VANILLA.max_stack_size());
+    /// ```
+    pub fn max_stack_size(&self) -> i32 {
+        self.max_units_per_slot / NUGGET_UNIT_AMOUNT
+    }
+
+    /// Number of slots needed to hold the units of a single metal, rounded up to whole slots
+    pub(crate) fn slots_for_units(&self, units: i64) -> i64 {
+        if units <= 0 {
+            0
+        } else if self.max_units_per_slot <= 0 {
+            i64::MAX
+        } else {
+            (units - 1) / i64::from(self.max_units_per_slot) + 1
+        }
+    }
+
+    /// Lays out the nuggets of each metal in full stacks followed by the rest, leaving out metals with no nuggets
+    pub(crate) fn stacks(
+        &self,
+        nuggets: impl IntoIterator<Item = (Metal, i32)>,
+    ) -> Vec<(Metal, i32)> {
+        let stack = self.max_stack_size();
+        let mut stacks = Vec::new();
+        for (metal, nuggets) in nuggets {
+            let (full, rest) = nuggets
+                .checked_div(stack)
+                .zip(nuggets.checked_rem(stack))
+                .unwrap_or((0, nuggets));
+            stacks.extend(std::iter::repeat_n((metal, stack), full.max(0) as usize));
+            if rest > 0 {
+                stacks.push((metal, rest));
+            }
+        }
+        stacks
+    }
+
+    /// Checks whether the units of each metal, every metal in its own slots, fit in the slots available
+    pub(crate) fn holds_units(&self, units: impl IntoIterator<Item = i64>) -> bool {
+        units
+            .into_iter()
+            .map(|units| self.slots_for_units(units))
+            .fold(0, i64::saturating_add)
+            <= i64::from(self.slots)
+    }
+}
+
+/// Error describing which crucible constraint a set of stacks breaks and by how much
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum CapacityError {
+    /// More stacks than the crucible has slots
+    TooManyStacks { stacks: i32, excess: i32 },
+    /// A stack holds more nuggets than fit in a single slot
+    StackTooLarge {
+        index: usize,
+        metal: Metal,
+        nuggets: i32,
+        excess: i32,
+    },
+    /// A stack holds no nuggets at all
+    EmptyStack { index: usize, metal: Metal },
+}
+
+impl CapacityError {
+    /// Returns a stable machine readable code for the error
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::crucible::CapacityError;
+    ///
+    /// assert_eq!("too_many_stacks", CapacityError::TooManyStacks { stacks: 5, excess: 1 }.code());
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            CapacityError::TooManyStacks { .. } => "too_many_stacks",
+            CapacityError::StackTooLarge { .. } => "stack_too_large",
+            CapacityError::EmptyStack { .. } => "empty_stack",
+        }
+    }
+}
+
+/// Checks that each stack of nuggets fits in a single slot and that there are no more stacks than slots in the vanilla crucible, \
+/// the same as [`fits_in`] with [`Capacity::VANILLA`]
+/// ### Example
+/// ```rust
+/// use vs_alloy_calculator::crucible::{self, CapacityError};
+/// use vs_alloy_calculator::Metal;
+///
+/// assert_eq!(Ok(()), crucible::fits(&[(Metal::Copper, 128), (Metal::Tin, 12)]));
+/// assert_eq!(
+///     Err(CapacityError::StackTooLarge { index: 0, metal: Metal::Copper, nuggets: 130, excess: 2 }),
+///     crucible::fits(&[(Metal::Copper, 130)])
+/// );
+/// ```
+pub fn fits(stacks: &[(Metal, i32)]) -> Result<(), CapacityError> {
+    fits_in(Capacity::VANILLA, stacks)
+}
+
+/// Checks that each stack of nuggets fits in a single slot and that there are no more stacks than slots in the supplied capacity
+/// ### Example
+/// ```rust
+/// use vs_alloy_calculator::crucible::{self, Capacity, CapacityError};
+/// use vs_alloy_calculator::Metal;
+///
+/// let large = Capacity { slots: 6, ..Capacity::VANILLA };
+/// let stacks = [(Metal::Copper, 128); 5];
+///
+/// assert_eq!(Ok(()), crucible::fits_in(large, &stacks));
+/// assert_eq!(
+///     Err(CapacityError::TooManyStacks { stacks: 5, excess: 1 }),
+///     crucible::fits_in(Capacity::VANILLA, &stacks)
+/// );
+/// ```
+pub fn fits_in(capacity: Capacity, stacks: &[(Metal, i32)]) -> Result<(), CapacityError> {
+    for (index, (metal, nuggets)) in stacks.iter().copied().enumerate() {
+        if nuggets <= 0 {
+            return Err(CapacityError::EmptyStack { index, metal });
+        }
+        if capacity.slots_for_units(nugget_units(nuggets)) > 1 {
+            return Err(CapacityError::StackTooLarge {
+                index,
+                metal,
+                nuggets,
+                excess: nuggets.saturating_sub(capacity.max_stack_size()),
+            });
+        }
+    }
+    let stacks = i32::try_from(stacks.len()).unwrap_or(i32::MAX);
+    if stacks > capacity.slots {
+        Err(CapacityError::TooManyStacks {
+            stacks,
+            excess: stacks.saturating_sub(capacity.slots),
+        })
+    } else {
+        Ok(())
+    }
+}
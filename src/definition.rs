@@ -0,0 +1,169 @@
+//! # Alloy Definitions
+//! Checks that an alloy's ranges and default percentages are consistent with each other before the alloy is used.
+use crate::{AlloyData, AlloyType, BaseMetal, ConstituentRange, Metal};
+
+/// Reason an alloy definition is not self-consistent. \
+/// Totals are compared by their bits so the error is [`Eq`] and can be held by an [`AlloyError`](crate::AlloyError).
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum AlloyDefinitionError {
+    /// The definition has no constituents
+    NoConstituents,
+    /// A metal appears in the ranges more than once
+    DuplicateMetal(Metal),
+    /// A range's minimum is above its maximum or outside of 0.0 to 1.0
+    InvalidRange(Metal),
+    /// The ranges cannot total 1.0, either because the minimums are too high or the maximums too low
+    UnreachableTotal { min_sum: f32, max_sum: f32 },
+    /// The ranges can only total 1.0 using fractional percents
+    NoWholePercentMix,
+    /// The defaults do not list exactly the metals of the ranges in the same order
+    DefaultMetalsMismatch,
+    /// A default percentage is outside of its range
+    DefaultOutOfRange(Metal),
+    /// The default percentages do not total 1.0
+    DefaultTotal(f32),
+    /// The alloy type's default nuggets are not the nuggets calculated for its default percentages and number of ingots
+    DefaultNuggetsMismatch,
+}
+
+impl PartialEq for AlloyDefinitionError {
+    fn eq(&self, other: &Self) -> bool {
+        use AlloyDefinitionError::*;
+        match (self, other) {
+            (DuplicateMetal(a), DuplicateMetal(b))
+            | (InvalidRange(a), InvalidRange(b))
+            | (DefaultOutOfRange(a), DefaultOutOfRange(b)) => a == b,
+            (
+                UnreachableTotal { min_sum, max_sum },
+                UnreachableTotal {
+                    min_sum: other_min,
+                    max_sum: other_max,
+                },
+            ) => {
+                min_sum.to_bits() == other_min.to_bits() && max_sum.to_bits() == other_max.to_bits()
+            }
+            (DefaultTotal(a), DefaultTotal(b)) => a.to_bits() == b.to_bits(),
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+impl Eq for AlloyDefinitionError {}
+
+impl AlloyDefinitionError {
+    /// Returns a stable machine readable code for the error
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::definition::AlloyDefinitionError;
+    ///
+    /// assert_eq!("no_whole_percent_mix", AlloyDefinitionError::NoWholePercentMix.code());
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NoConstituents => "no_constituents",
+            Self::DuplicateMetal(_) => "duplicate_metal",
+            Self::InvalidRange(_) => "invalid_range",
+            Self::UnreachableTotal { .. } => "unreachable_total",
+            Self::NoWholePercentMix => "no_whole_percent_mix",
+            Self::DefaultMetalsMismatch => "default_metals_mismatch",
+            Self::DefaultOutOfRange(_) => "default_out_of_range",
+            Self::DefaultTotal(_) => "default_total",
+            Self::DefaultNuggetsMismatch => "default_nuggets_mismatch",
+        }
+    }
+}
+
+/// Checks that an alloy definition is self-consistent. \
+/// The metals must be distinct, the ranges must allow a whole-percent mix totalling exactly 100%,
+/// and the defaults must be a valid mix of the same metals in the same order.
+/// ### Example
+/// ```rust
+/// use vs_alloy_calculator::prelude::*;
+/// use vs_alloy_calculator::ConstituentRange;
+/// use vs_alloy_calculator::definition::{validate_definition, AlloyDefinitionError};
+///
+/// assert!(validate_definition(TinBronze::percentage_ranges(), TinBronze::default_percentages()).is_ok());
+///
+/// let ranges = [Copper(ConstituentRange::new(0.6, 0.7)), Tin(ConstituentRange::new(0.5, 0.6))];
+/// assert_eq!(
+///     Err(AlloyDefinitionError::UnreachableTotal { min_sum: 1.1, max_sum: 1.3 }),
+///     validate_definition(&ranges, &[Copper(0.6), Tin(0.4)])
+/// );
+/// ```
+pub fn validate_definition(
+    ranges: &[BaseMetal<ConstituentRange<f32>>],
+    defaults: &[BaseMetal<f32>],
+) -> Result<(), AlloyDefinitionError> {
+    use AlloyDefinitionError::*;
+
+    if ranges.is_empty() {
+        return Err(NoConstituents);
+    }
+    for (i, r) in ranges.iter().enumerate() {
+        if ranges[..i].iter().any(|other| other.metal() == r.metal()) {
+            return Err(DuplicateMetal(r.metal()));
+        } else if !(0.0 <= r.min && r.min <= r.max && r.max <= 1.0) {
+            return Err(InvalidRange(r.metal()));
+        }
+    }
+
+    // Whole percents are compared exactly, with float noise in the bounds rounded away first
+    let whole = |value: f32| (f64::from(value) * 100.0 * 1e4).round() / 1e4;
+    let min_sum: f32 = ranges.iter().map(|r| r.min).sum();
+    let max_sum: f32 = ranges.iter().map(|r| r.max).sum();
+    let min_whole: f64 = ranges.iter().map(|r| whole(r.min).ceil()).sum();
+    let max_whole: f64 = ranges.iter().map(|r| whole(r.max).floor()).sum();
+    if ranges.iter().map(|r| whole(r.min)).sum::<f64>() > 100.0
+        || ranges.iter().map(|r| whole(r.max)).sum::<f64>() < 100.0
+    {
+        return Err(UnreachableTotal {
+            min_sum: round_hundredths(min_sum),
+            max_sum: round_hundredths(max_sum),
+        });
+    } else if min_whole > 100.0 || max_whole < 100.0 {
+        return Err(NoWholePercentMix);
+    }
+
+    if defaults.len() != ranges.len()
+        || defaults
+            .iter()
+            .zip(ranges)
+            .any(|(d, r)| d.metal() != r.metal())
+    {
+        return Err(DefaultMetalsMismatch);
+    }
+    if let Some(d) = defaults.iter().zip(ranges).find(|(d, r)| !r.contains(d)) {
+        return Err(DefaultOutOfRange(d.0.metal()));
+    }
+    let default_sum: f32 = defaults.iter().map(|d| **d).sum();
+    if (default_sum - 1.0).abs() >= crate::alloy::TOTAL_TOLERANCE {
+        return Err(DefaultTotal(default_sum));
+    }
+    Ok(())
+}
+
+/// Checks an alloy type's definition like [`validate_definition`],
+/// and that its [`Default`] nuggets are the ones calculated for its default percentages and number of ingots. \
+/// Creating an [`AlloyData`] only checks the ranges and defaults, and [`Default`] checks nothing,
+/// so an alloy declared in another crate with [`define_alloy!`](crate::define_alloy) or by hand should be checked with this in its tests.
+/// ### Example
+/// ```rust
+/// use vs_alloy_calculator::prelude::*;
+/// use vs_alloy_calculator::definition::validate_alloy_type;
+///
+/// assert!(validate_alloy_type::<BlackBronze>().is_ok());
+/// ```
+pub fn validate_alloy_type<T: AlloyType + Default>() -> Result<(), AlloyDefinitionError> {
+    validate_definition(T::percentage_ranges(), T::default_percentages())?;
+    match AlloyData::<T>::definition_nuggets() {
+        Ok(nuggets) if nuggets.nuggets() == T::default().nuggets() => Ok(()),
+        _ => Err(AlloyDefinitionError::DefaultNuggetsMismatch),
+    }
+}
+
+/// Rounds a sum of ranges to hundredths so reported totals do not carry float noise
+fn round_hundredths(value: f32) -> f32 {
+    (value * 100.0).round() / 100.0
+}
@@ -0,0 +1,283 @@
+//! # Formatting
+//! Options for how numbers are written by the text renderers, so output can be configured once rather than post-processed.
+use crate::{Alloy, AlloyData, AlloyType, BaseMetal, PureMetal};
+use std::fmt::{Display, Formatter};
+
+/// How percentages are written
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PercentStyle {
+    /// Out of 100, eg. `92%`
+    Whole,
+    /// As a fraction of 1, eg. `0.92`
+    Fraction,
+}
+
+/// Options for how the renderers write numbers
+///
+/// The defaults match the output of the plain [`Display`] implementations.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FormatOptions {
+    /// Maximum number of decimal places, with trailing zeros trimmed
+    pub decimal_places: usize,
+    /// Whether percentages are written out of 100 or as a fraction of 1
+    pub percent_style: PercentStyle,
+    /// Separator between groups of thousands, or none
+    pub thousands_separator: Option<char>,
+    /// Separator between the whole and fractional parts of a number
+    pub decimal_separator: char,
+    /// Whether to write units after values, eg. `%` after whole percentages and `ingots` after ingot counts
+    pub unit_suffixes: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            decimal_places: 2,
+            percent_style: PercentStyle::Whole,
+            thousands_separator: None,
+            decimal_separator: '.',
+            unit_suffixes: true,
+        }
+    }
+}
+
+impl FormatOptions {
+    /// Writes a whole number
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::format::FormatOptions;
+    ///
+    /// let options = FormatOptions { thousands_separator: Some('.'), ..Default::default() };
+    ///
+    /// assert_eq!("3.200", options.integer(3200));
+    /// assert_eq!("-1.000.000", options.integer(-1_000_000));
+    /// ```
+    pub fn integer(&self, value: i64) -> String {
+        let digits = value.unsigned_abs().to_string();
+        let mut grouped = String::new();
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                grouped.extend(self.thousands_separator);
+            }
+            grouped.push(c);
+        }
+        if value < 0 {
+            grouped.insert(0, '-');
+        }
+        grouped
+    }
+
+    /// Writes a number rounded to at most the configured decimal places
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::format::FormatOptions;
+    ///
+    /// let options = FormatOptions { decimal_separator: ',', ..Default::default() };
+    ///
+    /// assert_eq!("91,5", options.decimal(91.5));
+    /// assert_eq!("12", options.decimal(12.0));
+    /// ```
+    pub fn decimal(&self, value: f64) -> String {
+        let rounded = format!("{:.*}", self.decimal_places, value.abs());
+        let (whole, fraction) = rounded.split_once('.').unwrap_or((&rounded, ""));
+        let fraction = fraction.trim_end_matches('0');
+        let whole = whole.parse::<i64>().unwrap_or(0);
+        let negative = value < 0.0 && (whole != 0 || !fraction.is_empty());
+        let mut written = String::new();
+        if negative {
+            written.push('-');
+        }
+        written.push_str(&self.integer(whole));
+        if !fraction.is_empty() {
+            written.push(self.decimal_separator);
+            written.push_str(fraction);
+        }
+        written
+    }
+
+    /// Writes a percentage given as a fraction of 1 in the configured style
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::format::{FormatOptions, PercentStyle};
+    ///
+    /// assert_eq!("92%", FormatOptions::default().percent(0.92));
+    ///
+    /// let options = FormatOptions { percent_style: PercentStyle::Fraction, ..Default::default() };
+    /// assert_eq!("0.92", options.percent(0.92));
+    /// ```
+    pub fn percent(&self, value: f32) -> String {
+        // Round through basis points first so float noise such as 0.92 * 100 = 92.00001 never shows
+        let basis_points = (f64::from(value) * 10_000.0).round();
+        match self.percent_style {
+            PercentStyle::Whole if self.unit_suffixes => {
+                format!("{}%", self.decimal(basis_points / 100.0))
+            }
+            PercentStyle::Whole => self.decimal(basis_points / 100.0),
+            PercentStyle::Fraction => self.decimal(basis_points / 10_000.0),
+        }
+    }
+
+    /// Writes a number of ingots, followed by the unit when suffixes are on
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::format::FormatOptions;
+    ///
+    /// assert_eq!("7 ingots", FormatOptions::default().ingots(7));
+    /// ```
+    pub fn ingots(&self, num_ingots: i32) -> String {
+        let count = self.integer(i64::from(num_ingots));
+        if self.unit_suffixes {
+            format!("{count} ingots")
+        } else {
+            count
+        }
+    }
+}
+
+impl Alloy {
+    /// Writes a one line summary of the alloy with the default [`FormatOptions`]
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = Alloys::TinBronze.try_new([Copper(0.92), Tin(0.08)], 7).expect("should be valid");
+    ///
+    /// assert_eq!("Tin Bronze × 7 ingots: Copper 92% (128), Tin 8% (12)", alloy.summary());
+    /// ```
+    pub fn summary(&self) -> String {
+        self.summary_with(&FormatOptions::default())
+    }
+
+    /// Writes a one line summary of the alloy with the supplied [`FormatOptions`]
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::format::{FormatOptions, PercentStyle};
+    ///
+    /// let alloy = Alloys::TinBronze.try_new([Copper(0.92), Tin(0.08)], 7).expect("should be valid");
+    /// let options = FormatOptions { percent_style: PercentStyle::Fraction, ..Default::default() };
+    ///
+    /// assert_eq!("Tin Bronze × 7 ingots: Copper 0.92 (128), Tin 0.08 (12)", alloy.summary_with(&options));
+    /// ```
+    pub fn summary_with(&self, options: &FormatOptions) -> String {
+        let constituents: Vec<String> = self
+            .percentages()
+            .iter()
+            .zip(self.nuggets())
+            .map(|(p, n)| {
+                format!(
+                    "{} {} ({})",
+                    p.name(),
+                    options.percent(**p),
+                    options.integer(i64::from(**n))
+                )
+            })
+            .collect();
+        format!(
+            "{} × {}: {}",
+            self.kind().name(),
+            options.ingots(self.num_ingots()),
+            constituents.join(", ")
+        )
+    }
+}
+
+/// Writes a recipe for the [`Display`] implementations of [`Alloy`], [`AlloyData`], and [`PureMetal`]
+fn write_recipe(
+    f: &mut Formatter<'_>,
+    name: &str,
+    percentages: &[BaseMetal<f32>],
+    nuggets: &[BaseMetal<i32>],
+    num_ingots: i32,
+    max_ingots: i32,
+) -> std::fmt::Result {
+    let options = FormatOptions::default();
+    let mut constituents = percentages.iter().zip(nuggets).map(|(p, n)| {
+        format!(
+            "{} {} = {} nuggets",
+            p.name(),
+            options.percent(**p),
+            options.integer(i64::from(**n))
+        )
+    });
+    write!(
+        f,
+        "{name} × {} (max {})",
+        options.ingots(num_ingots),
+        options.ingots(max_ingots)
+    )?;
+    if f.alternate() {
+        constituents.try_for_each(|c| write!(f, "\n- {c}"))
+    } else {
+        write!(f, ": {}", constituents.collect::<Vec<_>>().join(", "))
+    }
+}
+
+impl<T: AlloyType> Display for AlloyData<T> {
+    /// Writes the recipe on one line as the name, ingots, max ingots, and each constituent's percentage and nuggets. \
+    /// The alternate format `{:#}` puts each constituent on its own line instead.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let alloy = AlloyData::<TinBronze>::try_new([Copper(0.92), Tin(0.08)], 7).expect("should be valid");
+    ///
+    /// assert_eq!(
+    ///     "Tin Bronze × 7 ingots (max 20 ingots): Copper 92% = 128 nuggets, Tin 8% = 12 nuggets",
+    ///     alloy.to_string()
+    /// );
+    /// assert_eq!(
+    ///     "Tin Bronze × 7 ingots (max 20 ingots)\n- Copper 92% = 128 nuggets\n- Tin 8% = 12 nuggets",
+    ///     format!("{alloy:#}")
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_recipe(
+            f,
+            T::name(),
+            self.percentages(),
+            self.nuggets(),
+            self.num_ingots(),
+            self.max_ingots(),
+        )
+    }
+}
+
+impl Display for Alloy {
+    /// Writes the recipe in the same format as [`AlloyData`], with `{:#}` putting each constituent on its own line
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_recipe(
+            f,
+            self.name(),
+            self.percentages(),
+            self.nuggets(),
+            self.num_ingots(),
+            self.max_ingots(),
+        )
+    }
+}
+
+impl Display for PureMetal {
+    /// Writes the recipe in the same format as [`AlloyData`], with the metal as the name and its single constituent at 100%
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let copper = PureMetal::new(Metal::Copper, 3).expect("should be valid");
+    ///
+    /// assert_eq!(
+    ///     "Copper × 3 ingots (max 25 ingots): Copper 100% = 60 nuggets",
+    ///     copper.to_string()
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_recipe(
+            f,
+            self.name(),
+            self.percentages(),
+            self.nuggets(),
+            self.num_ingots(),
+            self.max_ingots(),
+        )
+    }
+}
@@ -0,0 +1,122 @@
+//! # Game Codes
+//! Looking up alloys and metals from the lowercase codes the game and its mods use in item identifiers, eg. `ingot-bismuthbronze`.
+use crate::prelude::*;
+
+/// Item code prefixes that come before an alloy or metal code
+const ITEM_PREFIXES: &[&str] = &["ingot-", "metalbit-", "metalplate-", "metalsheet-"];
+
+/// Item code prefixes that come before a metal's ore mineral, which may be followed by the rock type
+const ORE_PREFIXES: &[&str] = &[
+    "nugget-",
+    "ore-poor-",
+    "ore-medium-",
+    "ore-rich-",
+    "ore-bountiful-",
+];
+
+/// Ore minerals that smelt into each metal
+const MINERALS: &[(&str, Metal)] = &[
+    ("pentlandite", Metal::Nickel),
+    ("nativecopper", Metal::Copper),
+    ("malachite", Metal::Copper),
+    ("sphalerite", Metal::Zinc),
+    ("nativesilver", Metal::Silver),
+    ("cassiterite", Metal::Tin),
+    ("nativegold", Metal::Gold),
+    ("galena", Metal::Lead),
+    ("bismuthinite", Metal::Bismuth),
+];
+
+impl Alloys {
+    /// Gets the code the game uses for the alloy in item identifiers
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!("bismuthbronze", Alloys::BismuthBronze.game_code());
+    /// ```
+    pub fn game_code(&self) -> &'static str {
+        match self {
+            Alloys::TinBronze => "tinbronze",
+            Alloys::BismuthBronze => "bismuthbronze",
+            Alloys::BlackBronze => "blackbronze",
+            Alloys::Brass => "brass",
+            Alloys::Molybdochalkos => "molybdochalkos",
+            Alloys::LeadSolder => "leadsolder",
+            Alloys::SilverSolder => "silversolder",
+            Alloys::Electrum => "electrum",
+            Alloys::Cupronickel => "cupronickel",
+        }
+    }
+
+    /// Finds the alloy from either its bare game code or a full item code such as `ingot-bismuthbronze` or `game:metalbit-blackbronze`
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(Some(Alloys::BlackBronze), Alloys::from_game_code("metalbit-blackbronze"));
+    /// assert_eq!(Some(Alloys::Brass), Alloys::from_game_code("brass"));
+    /// assert_eq!(None, Alloys::from_game_code("ingot-steel"));
+    /// ```
+    pub fn from_game_code(code: &str) -> Option<Alloys> {
+        let code = strip_domain(code);
+        let code = strip_any(code, ITEM_PREFIXES).unwrap_or(code);
+        Alloys::ALL.into_iter().find(|a| a.game_code() == code)
+    }
+}
+
+impl Metal {
+    /// Gets the code the game uses for the metal in item identifiers
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!("copper", Metal::Copper.game_code());
+    /// ```
+    pub fn game_code(&self) -> &'static str {
+        match self {
+            Metal::Nickel => "nickel",
+            Metal::Copper => "copper",
+            Metal::Zinc => "zinc",
+            Metal::Silver => "silver",
+            Metal::Tin => "tin",
+            Metal::Gold => "gold",
+            Metal::Lead => "lead",
+            Metal::Bismuth => "bismuth",
+        }
+    }
+
+    /// Finds the metal from either its bare game code or a full item code. \
+    /// Ingots and other metal items use the metal's code, eg. `ingot-tin`, while nuggets and ores use the mineral, eg. `nugget-cassiterite` or `ore-poor-galena-granite`.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(Some(Metal::Tin), Metal::from_game_code("nugget-cassiterite"));
+    /// assert_eq!(Some(Metal::Lead), Metal::from_game_code("ore-rich-galena-andesite"));
+    /// assert_eq!(Some(Metal::Zinc), Metal::from_game_code("ingot-zinc"));
+    /// assert_eq!(None, Metal::from_game_code("nugget-olivine"));
+    /// ```
+    pub fn from_game_code(code: &str) -> Option<Metal> {
+        let code = strip_domain(code);
+        if let Some(ore) = strip_any(code, ORE_PREFIXES) {
+            let mineral = ore.split('-').next()?;
+            return MINERALS
+                .iter()
+                .find(|(name, _)| *name == mineral)
+                .map(|(_, metal)| *metal);
+        }
+        let code = strip_any(code, ITEM_PREFIXES).unwrap_or(code);
+        Metal::ALL.into_iter().find(|m| m.game_code() == code)
+    }
+}
+
+/// Removes the `game:` domain that fully qualified asset locations start with
+fn strip_domain(code: &str) -> &str {
+    code.strip_prefix("game:").unwrap_or(code)
+}
+
+/// Removes the first of the prefixes that the code starts with
+fn strip_any<'a>(code: &'a str, prefixes: &[&str]) -> Option<&'a str> {
+    prefixes.iter().find_map(|p| code.strip_prefix(p))
+}
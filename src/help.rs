@@ -0,0 +1,252 @@
+//! # Help Text
+//! Longer, user facing explanations of an [`AlloyError`] with suggested fixes, independent of any UI toolkit.
+use crate::format::FormatOptions;
+use crate::prelude::*;
+use crate::{AlloyError, AlloyError::*, ConstituentRange};
+
+/// The input that caused an error, used to tailor its help text
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ErrorContext<'a> {
+    /// The alloy the input was for
+    pub alloy: Alloys,
+    /// The percentages that were supplied
+    pub percentages: &'a [BaseMetal<f32>],
+    /// The number of ingots that was supplied
+    pub num_ingots: i32,
+}
+
+impl<'a> ErrorContext<'a> {
+    pub fn new(alloy: Alloys, percentages: &'a [BaseMetal<f32>], num_ingots: i32) -> Self {
+        Self {
+            alloy,
+            percentages,
+            num_ingots,
+        }
+    }
+}
+
+/// Structured help for an error with a short headline, a longer detail, and up to two suggested fixes
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct HelpText {
+    pub headline: &'static str,
+    pub detail: String,
+    pub suggestions: Vec<String>,
+}
+
+/// Most suggestions a [`HelpText`] will offer
+const MAX_SUGGESTIONS: usize = 2;
+
+impl AlloyError {
+    /// Explains the error for the input that caused it, suggesting fixes where possible
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::help::ErrorContext;
+    ///
+    /// let percentages = [Copper(0.86), Tin(0.14)];
+    /// let error = Alloys::TinBronze.try_new(percentages, 2).expect_err("tin should be too high");
+    ///
+    /// let help = error.help(&ErrorContext::new(Alloys::TinBronze, &percentages, 2));
+    ///
+    /// assert_eq!("A percentage is out of range", help.headline);
+    /// assert_eq!("reduce Tin to at most 12%", help.suggestions[1]);
+    /// ```
+    pub fn help(&self, context: &ErrorContext) -> HelpText {
+        let format = FormatOptions::default();
+        let name = context.alloy.name();
+        match self {
+            InvalidPercentages => {
+                let sum: f32 = context.percentages.iter().map(|p| **p).sum();
+                if (sum - 1.0).abs() >= crate::alloy::TOTAL_TOLERANCE {
+                    sum_help(context, sum, &format)
+                } else {
+                    range_help(context, &format)
+                }
+            }
+            InvalidBaseMetals => metals_help(context, "The metals do not match the alloy", None),
+            UnknownConstituent(metal) => metals_help(
+                context,
+                "A metal is not part of the alloy",
+                Some(format!("remove {}", metal.name())),
+            ),
+            DuplicateConstituent(metal) => metals_help(
+                context,
+                "A metal is supplied more than once",
+                Some(format!("combine the {} entries into one", metal.name())),
+            ),
+            MissingConstituent(metal) => {
+                let first = context.alloy.range_of(*metal).map(|range| {
+                    format!(
+                        "add {} between {} and {}",
+                        metal.name(),
+                        format.percent(range.min),
+                        format.percent(range.max)
+                    )
+                });
+                metals_help(context, "A metal is missing", first)
+            }
+            WrongConstituentCount { .. } => metals_help(context, "Too many metals", None),
+            TooManyIngots => {
+                let max = context
+                    .alloy
+                    .try_new(context.percentages, 1)
+                    .map(|a| a.max_ingots())
+                    .unwrap_or(crate::unit_constants::MAX_POSSIBLE_INGOTS);
+                HelpText {
+                    headline: "Too many ingots for one crucible",
+                    detail: format!(
+                        "{} of {name} do not fit in the crucible with this mix",
+                        format.ingots(context.num_ingots)
+                    ),
+                    suggestions: vec![format!(
+                        "try {} — the maximum for this mix",
+                        format.ingots(max)
+                    )],
+                }
+            }
+            TooFewIngots => HelpText {
+                headline: "Too few ingots",
+                detail: format!("{} is not a positive number of ingots", context.num_ingots),
+                suggestions: vec![String::from("make at least 1 ingot")],
+            },
+            InvalidConstituentAmounts => HelpText {
+                headline: "The nugget amounts do not make a valid alloy",
+                detail: format!("The nuggets do not combine into whole ingots of {name}"),
+                suggestions: Vec::new(),
+            },
+            InvalidValues => HelpText {
+                headline: "The values do not agree with each other",
+                detail: format!("The supplied values for {name} are inconsistent"),
+                suggestions: Vec::new(),
+            },
+            NoSolution => HelpText {
+                headline: "No valid alloy fits these constraints",
+                detail: format!("No mix of {name} satisfies every constraint that was given"),
+                suggestions: Vec::new(),
+            },
+            MixedPercentScales => HelpText {
+                headline: "The percentages use two different scales",
+                detail: String::from(
+                    "Some percentages are fractions of one like 0.92 and others are whole percents like 8",
+                ),
+                suggestions: vec![String::from(
+                    "write every percentage the same way, such as 92 and 8 or 0.92 and 0.08",
+                )],
+            },
+            InvalidDefinition(error) => HelpText {
+                headline: "The alloy is not defined correctly",
+                detail: format!(
+                    "The ranges and defaults declared for the alloy are inconsistent ({})",
+                    error.code()
+                ),
+                suggestions: Vec::new(),
+            },
+        }
+    }
+}
+
+/// Help for percentages that do not total 100%, suggesting which constituents could take up the difference
+fn sum_help(context: &ErrorContext, sum: f32, format: &FormatOptions) -> HelpText {
+    let difference = 1.0 - sum;
+    let suggestions = context
+        .percentages
+        .iter()
+        .rev()
+        .filter_map(|p| {
+            let range = context.alloy.range_of(p.metal())?;
+            let target = **p + difference;
+            range.contains(&target).then(|| {
+                let verb = if difference > 0.0 { "raise" } else { "reduce" };
+                format!("{verb} {} to {}", p.name(), format.percent(target))
+            })
+        })
+        .take(MAX_SUGGESTIONS)
+        .collect();
+    HelpText {
+        headline: "The percentages do not add up to 100%",
+        detail: format!("They add up to {}", format.percent(sum)),
+        suggestions,
+    }
+}
+
+/// Help for metals that do not match the alloy, leading with the supplied suggestion when there is one
+fn metals_help(context: &ErrorContext, headline: &'static str, first: Option<String>) -> HelpText {
+    let metals: Vec<&str> = context
+        .alloy
+        .constituent_metals()
+        .iter()
+        .map(|m| m.name())
+        .collect();
+    let switches = Alloys::infer(context.percentages).into_iter().map(|a| {
+        format!(
+            "switch to {}, which these percentages are valid for",
+            a.name()
+        )
+    });
+    let removals = context
+        .percentages
+        .iter()
+        .filter(|p| context.alloy.range_of(p.metal()).is_none())
+        .map(|p| format!("remove {}", p.name()));
+    let mut suggestions: Vec<String> = Vec::new();
+    for suggestion in first.into_iter().chain(switches).chain(removals) {
+        if suggestions.len() < MAX_SUGGESTIONS && !suggestions.contains(&suggestion) {
+            suggestions.push(suggestion);
+        }
+    }
+    HelpText {
+        headline,
+        detail: format!(
+            "{} is made from exactly one each of {}",
+            context.alloy.name(),
+            metals.join(", ")
+        ),
+        suggestions,
+    }
+}
+
+/// Help for percentages that total 100% but fall outside of the alloy's ranges
+fn range_help(context: &ErrorContext, format: &FormatOptions) -> HelpText {
+    let out_of_range: Vec<(BaseMetal<f32>, ConstituentRange<f32>)> = context
+        .percentages
+        .iter()
+        .filter_map(|p| {
+            let range = context.alloy.range_of(p.metal())?;
+            (!range.contains(p)).then_some((*p, range))
+        })
+        .collect();
+    let detail = match out_of_range.first() {
+        Some((p, range)) => format!(
+            "{} is {}, but {} needs between {} and {}",
+            p.name(),
+            format.percent(**p),
+            context.alloy.name(),
+            format.percent(range.min),
+            format.percent(range.max)
+        ),
+        None => format!("The percentages are not valid for {}", context.alloy.name()),
+    };
+    HelpText {
+        headline: "A percentage is out of range",
+        detail,
+        suggestions: out_of_range
+            .iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(p, range)| {
+                if **p > range.max {
+                    format!(
+                        "reduce {} to at most {}",
+                        p.name(),
+                        format.percent(range.max)
+                    )
+                } else {
+                    format!(
+                        "raise {} to at least {}",
+                        p.name(),
+                        format.percent(range.min)
+                    )
+                }
+            })
+            .collect(),
+    }
+}
@@ -0,0 +1,73 @@
+//! # Ingot Counts
+//! A number of ingots checked once, up front, to be something a crucible could make.
+use crate::AlloyError::{self, *};
+use crate::unit_constants::MAX_POSSIBLE_INGOTS;
+
+/// A number of ingots from 1 up to [`MAX_POSSIBLE_INGOTS`](crate::unit_constants::MAX_POSSIBLE_INGOTS)
+///
+/// Anything that takes an ingot count also takes a plain `i32`, which is checked the same way as [`IngotCount::new`].
+/// ### Example
+/// ```rust
+/// use vs_alloy_calculator::prelude::*;
+/// use vs_alloy_calculator::IngotCount;
+///
+/// let num_ingots = IngotCount::new(7).expect("should be valid");
+/// let alloy = AlloyData::<TinBronze>::try_new([Copper(0.92), Tin(0.08)], num_ingots).expect("should be valid");
+///
+/// assert_eq!(num_ingots, alloy.ingot_count());
+/// assert_eq!(7, alloy.ingot_count().get());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct IngotCount(pub(crate) i32);
+
+impl IngotCount {
+    /// The fewest ingots that can be made
+    pub const MIN: Self = Self(1);
+    /// The most ingots that fit in a crucible
+    pub const MAX: Self = Self(MAX_POSSIBLE_INGOTS);
+
+    /// Tries to create an ingot count, returning [`TooFewIngots`](AlloyError::TooFewIngots) below one
+    /// and [`TooManyIngots`](AlloyError::TooManyIngots) above what fits in a crucible
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::{AlloyError, IngotCount};
+    ///
+    /// assert_eq!(Err(AlloyError::TooFewIngots), IngotCount::new(0));
+    /// assert_eq!(Ok(IngotCount::MAX), IngotCount::new(25));
+    /// assert_eq!(Err(AlloyError::TooManyIngots), IngotCount::new(26));
+    /// ```
+    pub fn new(num_ingots: i32) -> Result<Self, AlloyError> {
+        if num_ingots <= 0 {
+            Err(TooFewIngots)
+        } else if num_ingots > MAX_POSSIBLE_INGOTS {
+            Err(TooManyIngots)
+        } else {
+            Ok(Self(num_ingots))
+        }
+    }
+
+    /// Gets the number of ingots as a plain `i32`
+    pub fn get(self) -> i32 {
+        self.0
+    }
+}
+
+impl TryFrom<i32> for IngotCount {
+    type Error = AlloyError;
+
+    fn try_from(num_ingots: i32) -> Result<Self, Self::Error> {
+        Self::new(num_ingots)
+    }
+}
+
+impl From<IngotCount> for i32 {
+    fn from(num_ingots: IngotCount) -> Self {
+        num_ingots.get()
+    }
+}
+
+impl std::fmt::Display for IngotCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
@@ -0,0 +1,214 @@
+//! # Inventory
+//! Tracking how many nuggets of each metal are on hand and what a recipe is still missing.
+use crate::unit_constants::{INGOT_UNIT_AMOUNT, NUGGET_UNIT_AMOUNT};
+use crate::{Alloy, AlloyData, AlloyType, BaseMetal, Metal, MetalTotals, parse};
+
+/// Number of nuggets of each metal on hand
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub struct Inventory {
+    nuggets: MetalTotals,
+}
+
+impl Inventory {
+    /// Creates an empty inventory
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the number of nuggets on hand of the supplied metal
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::inventory::Inventory;
+    ///
+    /// let inventory = Inventory::from_iter([Copper(10), Tin(2), Copper(5)]);
+    ///
+    /// assert_eq!(15, inventory.nuggets(Metal::Copper));
+    /// assert_eq!(0, inventory.nuggets(Metal::Zinc));
+    /// ```
+    pub fn nuggets(&self, metal: Metal) -> i32 {
+        self.nuggets.get(metal)
+    }
+
+    /// Adds nuggets of a metal to the inventory
+    pub fn add(&mut self, nuggets: BaseMetal<i32>) {
+        self.nuggets.add(nuggets);
+    }
+
+    /// Sets the number of nuggets on hand of a metal
+    pub fn set(&mut self, nuggets: BaseMetal<i32>) {
+        self.nuggets.set(nuggets);
+    }
+
+    /// Calculates how many nuggets of each metal are missing to cover the supplied amounts
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::inventory::Inventory;
+    ///
+    /// let inventory = Inventory::from_iter([Copper(20), Tin(2)]);
+    /// let shortfall = inventory.shortfall(&[Copper(36), Tin(4)]);
+    ///
+    /// assert_eq!(&[Copper(16), Tin(2)], &*shortfall.missing);
+    /// assert!(!shortfall.is_satisfiable);
+    /// ```
+    pub fn shortfall(&self, needed: &[BaseMetal<i32>]) -> Shortfall {
+        let missing: Box<[BaseMetal<i32>]> = needed
+            .iter()
+            .map(|n| n.update_inner_value(n.saturating_sub(self.nuggets(n.metal())).max(0)))
+            .collect();
+        Shortfall {
+            is_satisfiable: missing.iter().all(|m| **m == 0),
+            missing,
+        }
+    }
+}
+
+/// Error from parsing tooltip text that had lines it could not read. Holds the inventory from every line that could be read.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TooltipParseError {
+    /// Nuggets from the lines that were recognized
+    pub inventory: Inventory,
+    /// Lines that were not recognized, numbered from 1, along with their text
+    pub unrecognized: Vec<(usize, String)>,
+}
+
+impl std::fmt::Display for TooltipParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self
+            .unrecognized
+            .iter()
+            .map(|(line, _)| line.to_string())
+            .collect();
+        write!(f, "unrecognized tooltip lines: {}", lines.join(", "))
+    }
+}
+
+impl std::error::Error for TooltipParseError {}
+
+impl Inventory {
+    /// Reads stacks of nuggets and ingots from text copied out of the game, one stack per line,
+    /// such as `Copper nugget x128`, `Nugget (Tin) 37`, or `3 × Ingot (Copper)`. \
+    /// Each line needs a metal, either by name or by its ore mineral, an item form, and a count, in any order and ignoring case.
+    /// Ingots are counted as the nuggets they are worth, repeated metals are added together, and blank lines are skipped.
+    ///
+    /// Lines that cannot be read do not stop the rest from being parsed.
+    /// They are returned with their line numbers in a [`TooltipParseError`] alongside the inventory from every other line.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::inventory::Inventory;
+    ///
+    /// let inventory = Inventory::parse_tooltip_text("Copper nugget x128\nNugget (Tin) 37").expect("should parse");
+    /// assert_eq!(128, inventory.nuggets(Metal::Copper));
+    /// assert_eq!(37, inventory.nuggets(Metal::Tin));
+    ///
+    /// let error = Inventory::parse_tooltip_text("Copper ingot x2\nFlint x4").unwrap_err();
+    /// assert_eq!(40, error.inventory.nuggets(Metal::Copper));
+    /// assert_eq!(vec![(2, String::from("Flint x4"))], error.unrecognized);
+    /// ```
+    pub fn parse_tooltip_text(text: &str) -> Result<Inventory, TooltipParseError> {
+        let mut inventory = Inventory::new();
+        let mut unrecognized = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match tooltip_stack(line) {
+                Some(nuggets) => inventory.add(nuggets),
+                None => unrecognized.push((index + 1, line.trim().to_string())),
+            }
+        }
+        if unrecognized.is_empty() {
+            Ok(inventory)
+        } else {
+            Err(TooltipParseError {
+                inventory,
+                unrecognized,
+            })
+        }
+    }
+}
+
+/// Reads a single stack from a tooltip line as the nuggets it is worth
+fn tooltip_stack(line: &str) -> Option<BaseMetal<i32>> {
+    let line = line.to_lowercase().replace(['×', '(', ')', ',', ':'], " ");
+    let (mut metal, mut nuggets_per_item, mut count) = (None, None, None);
+    for word in line.split_whitespace().filter(|w| *w != "x") {
+        let number = word
+            .strip_prefix('x')
+            .or_else(|| word.strip_suffix('x'))
+            .unwrap_or(word);
+        let seen = if let Ok(n) = number.parse::<i32>() {
+            count.replace(n).is_some()
+        } else if let Some(n) = item_nuggets(word) {
+            nuggets_per_item.replace(n).is_some()
+        } else if let Some(m) = tooltip_metal(word) {
+            metal.replace(m).is_some()
+        } else {
+            return None;
+        };
+        if seen {
+            return None;
+        }
+    }
+    let count = count.filter(|c| *c > 0)?;
+    Some(metal?.with_value(count.checked_mul(nuggets_per_item?)?))
+}
+
+/// Gets how many nuggets an item form is worth
+fn item_nuggets(word: &str) -> Option<i32> {
+    match word.trim_end_matches('s') {
+        "nugget" => Some(1),
+        "ingot" => Some(INGOT_UNIT_AMOUNT / NUGGET_UNIT_AMOUNT),
+        _ => None,
+    }
+}
+
+/// Finds a metal from its name or the mineral its nuggets are named after
+fn tooltip_metal(word: &str) -> Option<Metal> {
+    parse::metal(word).or_else(|| Metal::from_game_code(&format!("nugget-{word}")))
+}
+
+impl FromIterator<BaseMetal<i32>> for Inventory {
+    fn from_iter<I: IntoIterator<Item = BaseMetal<i32>>>(iter: I) -> Self {
+        let mut inventory = Self::new();
+        for nuggets in iter {
+            inventory.add(nuggets);
+        }
+        inventory
+    }
+}
+
+/// Report of how many nuggets a recipe still needs beyond what is in an [`Inventory`]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Shortfall {
+    /// Missing nuggets of each constituent in canonical order, zero for constituents that are covered
+    pub missing: Box<[BaseMetal<i32>]>,
+    /// Whether the inventory covers every constituent
+    pub is_satisfiable: bool,
+}
+
+impl<T: AlloyType> AlloyData<T> {
+    /// Calculates how many nuggets of each constituent are missing from the inventory to make the alloy
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::inventory::Inventory;
+    ///
+    /// let alloy = AlloyData::<TinBronze>::try_new([Copper(0.90), Tin(0.10)], 5).expect("should be valid");
+    /// let shortfall = alloy.shortfall(&Inventory::from_iter([Copper(100)]));
+    ///
+    /// assert_eq!(&[Copper(0), Tin(10)], &*shortfall.missing);
+    /// ```
+    pub fn shortfall(&self, inventory: &Inventory) -> Shortfall {
+        inventory.shortfall(self.nuggets())
+    }
+}
+
+impl Alloy {
+    /// Calculates how many nuggets of each constituent are missing from the inventory to make the alloy
+    pub fn shortfall(&self, inventory: &Inventory) -> Shortfall {
+        inventory.shortfall(self.nuggets())
+    }
+}
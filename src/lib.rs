@@ -28,32 +28,96 @@
 //!     alloy.nuggets(),
 //! );
 //! ```
+//!
+//! ## Panics
+//! No public function panics on any input. Out of range numbers, `NaN`, wrong-length or repeated constituents,
+//! and ingot counts anywhere in `i32` are all reported as an [`AlloyError`] (or the module's own error type) instead,
+//! and running totals saturate rather than overflow. This holds for both debug and release builds.
+//!
+//! ## Custom Alloys
+//! The `unstable-custom-alloys` feature makes `alloy_types::AlloyTypeDefinition` public so other crates can declare their own alloys
+//! with [`define_alloy!`] and use them with [`AlloyData`] like the built-in ones. They are not part of [`Alloy`] or [`Alloys`](alloy_types::Alloys) and have no conversion to either.
+//! Every constructor checks the definition with [`definition::validate_definition`] and returns [`AlloyError::InvalidDefinition`] if it is inconsistent.
+//! Alloys declared with [`define_alloy!`] only run the check the first time they are used and reuse the result after that.
+//! [`Default`] cannot fail, so it does not check the definition.
 
 // Modules
 mod alloy;
 mod base_metal;
+pub mod crucible;
+pub mod definition;
+pub mod format;
+mod game_code;
+pub mod help;
+mod ingot_count;
+pub mod inventory;
+pub mod parse;
+mod percent_value;
+pub mod planner;
+pub mod ranking;
+pub mod shopping;
+pub mod summaries;
 mod tests;
+pub mod verification;
 // Re-exports
+pub use alloy::AchievableMix;
+pub use alloy::AdjustOutcome;
+pub use alloy::Adjusted;
+pub use alloy::Adjustment;
+pub use alloy::AdjustmentReason;
+pub use alloy::AdjustmentReport;
 pub use alloy::Alloy;
+pub use alloy::AlloyBuilder;
 pub use alloy::AlloyData;
+pub use alloy::AlloyDoc;
+pub use alloy::AlloyMismatch;
+pub use alloy::AnyAlloy;
+pub use alloy::CalculationTrace;
+pub use alloy::ClampReport;
+pub use alloy::ClampedValue;
+pub use alloy::ConstituentView;
+pub use alloy::Constituents;
+pub use alloy::ContentsReport;
+pub use alloy::Conversion;
+pub use alloy::CreateOptions;
+pub use alloy::IngotEfficiency;
+pub use alloy::PureMetal;
+pub use alloy::SliderBinding;
+pub use alloy::Suggestion;
+pub use alloy::TraceStep;
+pub use alloy::ValidationIssue;
+pub use alloy::ValidationReport;
 pub use alloy::alloy_names;
 pub use alloy::alloy_types;
 pub use alloy::alloy_types::AlloyType;
+pub use alloy::normalize_sum;
 pub use base_metal::BaseMetal;
+pub use base_metal::BaseMetalSlice;
+pub use base_metal::Metal;
+pub use base_metal::MetalMismatch;
+pub use base_metal::MetalTotals;
+pub use base_metal::Percentages;
 pub use base_metal::base_metal_names;
+pub use ingot_count::IngotCount;
+pub use percent_value::Percent;
+pub use percent_value::PercentError;
 pub mod prelude {
     pub use crate::alloy::Alloy;
     pub use crate::alloy::AlloyData;
+    pub use crate::alloy::PureMetal;
     pub use crate::alloy::alloy_names;
     // Gives AlloyType and all of the types of alloys as standalone types and as an enum
     pub use crate::alloy::alloy_types::*;
     pub use crate::base_metal::BaseMetal;
     pub use crate::base_metal::BaseMetal::*;
+    pub use crate::base_metal::BaseMetalSlice;
+    pub use crate::base_metal::Metal;
     pub use crate::base_metal::base_metal_names;
 }
 // Imports
 use AlloyError::*;
 use BaseMetal::*;
+use definition::AlloyDefinitionError;
 
 pub mod unit_constants {
     pub const NUGGET_UNIT_AMOUNT: i32 = 5;
@@ -64,31 +128,301 @@ pub mod unit_constants {
     pub const MAX_POSSIBLE_INGOTS: i32 =
         MAX_STACK_SIZE * NUGGET_UNIT_AMOUNT * CRUCIBLE_SLOTS / INGOT_UNIT_AMOUNT; // 25
     pub const MAX_UNITS_PER_SLOT: i32 = MAX_STACK_SIZE * NUGGET_UNIT_AMOUNT; // 640
+
+    /// Units in the supplied number of ingots, widened so that large counts cannot overflow
+    pub(crate) const fn ingot_units(num_ingots: i32) -> i64 {
+        num_ingots as i64 * INGOT_UNIT_AMOUNT as i64
+    }
+
+    /// Units in the supplied number of nuggets, widened so that large counts cannot overflow
+    pub(crate) const fn nugget_units(nuggets: i32) -> i64 {
+        nuggets as i64 * NUGGET_UNIT_AMOUNT as i64
+    }
+
+    /// Nuggets in the supplied number of ingots, widened so that large counts cannot overflow
+    pub(crate) const fn ingot_nuggets(num_ingots: i32) -> i64 {
+        num_ingots as i64 * NUM_NUGGETS_PER_INGOT as i64
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum AlloyError {
     InvalidPercentages,
     InvalidConstituentAmounts,
+    /// A metal name could not be recognized
     InvalidBaseMetals,
+    /// The metal is not a constituent of the alloy
+    UnknownConstituent(Metal),
+    /// The metal was supplied more than once
+    DuplicateConstituent(Metal),
+    /// The alloy's constituent was not supplied
+    MissingConstituent(Metal),
+    /// More metals were supplied than the alloy has constituents
+    WrongConstituentCount {
+        expected: usize,
+        got: usize,
+    },
     InvalidValues,
     TooManyIngots,
     TooFewIngots,
+    NoSolution,
+    MixedPercentScales,
+    /// The alloy type's own ranges and defaults are inconsistent, which can only happen for an alloy declared in another crate
+    InvalidDefinition(AlloyDefinitionError),
+}
+
+impl AlloyError {
+    /// Returns a stable machine readable code for the error
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::AlloyError;
+    ///
+    /// assert_eq!("too_many_ingots", AlloyError::TooManyIngots.code());
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            InvalidPercentages => "invalid_percentages",
+            InvalidConstituentAmounts => "invalid_constituent_amounts",
+            InvalidBaseMetals => "invalid_base_metals",
+            UnknownConstituent(_) => "unknown_constituent",
+            DuplicateConstituent(_) => "duplicate_constituent",
+            MissingConstituent(_) => "missing_constituent",
+            WrongConstituentCount { .. } => "wrong_constituent_count",
+            InvalidValues => "invalid_values",
+            TooManyIngots => "too_many_ingots",
+            TooFewIngots => "too_few_ingots",
+            NoSolution => "no_solution",
+            MixedPercentScales => "mixed_percent_scales",
+            InvalidDefinition(_) => "invalid_definition",
+        }
+    }
+}
+
+impl std::fmt::Display for AlloyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidPercentages => {
+                f.write_str("percentages do not total 1.0 or are outside of the alloy's ranges")
+            }
+            InvalidConstituentAmounts => {
+                f.write_str("nugget amounts do not match the alloy's constituents")
+            }
+            InvalidBaseMetals => f.write_str("a metal name was not recognized"),
+            UnknownConstituent(metal) => write!(f, "{} is not part of the alloy", metal.name()),
+            DuplicateConstituent(metal) => {
+                write!(f, "{} is supplied more than once", metal.name())
+            }
+            MissingConstituent(metal) => write!(f, "{} is missing from the alloy", metal.name()),
+            WrongConstituentCount { expected, got } => {
+                write!(f, "expected at most {expected} metals but found {got}")
+            }
+            InvalidValues => f.write_str("values are inconsistent with each other"),
+            TooManyIngots => f.write_str("more ingots than the crucible can make"),
+            TooFewIngots => f.write_str("fewer than one ingot"),
+            NoSolution => f.write_str("no valid alloy satisfies the constraints"),
+            MixedPercentScales => {
+                f.write_str("some percentages are fractions of one and others are whole percents")
+            }
+            InvalidDefinition(error) => {
+                write!(f, "the alloy is not a valid definition ({})", error.code())
+            }
+        }
+    }
+}
+
+impl std::error::Error for AlloyError {}
+
+impl From<std::convert::Infallible> for AlloyError {
+    /// Lets conversions that cannot fail, such as an [`IngotCount`] into itself, be used where an [`AlloyError`] is expected
+    fn from(never: std::convert::Infallible) -> Self {
+        match never {}
+    }
 }
 
 /// Struct for modeling valid percentage ranges for constituents of an [`Alloy`]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct ConstituentRange<T: Copy + PartialOrd> {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConstituentRange<T> {
     pub min: T,
     pub max: T,
 }
-impl<T: Copy + PartialOrd> ConstituentRange<T> {
+impl<T> ConstituentRange<T> {
     pub const fn new(min: T, max: T) -> Self {
         Self { min, max }
     }
-
+}
+impl<T: PartialOrd> ConstituentRange<T> {
     pub fn contains(&self, item: &T) -> bool {
         self.min <= *item && *item <= self.max
     }
+
+    /// Restricts the value to be within the range. \
+    /// A value that cannot be compared with the range, such as NaN, is restricted to the minimum.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::ConstituentRange;
+    ///
+    /// let range = ConstituentRange::new(0.08, 0.12);
+    ///
+    /// assert_eq!(0.12, range.clamp(0.14));
+    /// assert_eq!(0.08, range.clamp(0.02));
+    /// assert_eq!(0.10, range.clamp(0.10));
+    /// assert_eq!(0.08, range.clamp(f64::NAN));
+    /// ```
+    pub fn clamp(self, value: T) -> T {
+        if value.partial_cmp(&self.min).is_none() || value < self.min {
+            self.min
+        } else if value > self.max {
+            self.max
+        } else {
+            value
+        }
+    }
+}
+
+macro_rules! impl_float_range {
+    ($($float:ty),*) => {$(
+        impl ConstituentRange<$float> {
+            /// Returns the distance between the minimum and maximum of the range
+            pub fn span(&self) -> $float {
+                self.max - self.min
+            }
+
+            /// Returns the value halfway between the minimum and maximum of the range
+            pub fn midpoint(&self) -> $float {
+                self.min + self.span() / 2.0
+            }
+
+            /// Maps `t` from `0.0..=1.0` onto the range, where `0.0` is the minimum and `1.0` is the maximum. \
+            /// `t` is clamped into `0.0..=1.0` first so the result is always within the range.
+            /// ### Example
+            /// ```rust
+            /// use vs_alloy_calculator::ConstituentRange;
+            ///
+            /// let range = ConstituentRange::new(0.50_f32, 0.70);
+            ///
+            /// assert_eq!(range.min, range.lerp(0.0));
+            /// assert_eq!(range.max, range.lerp(1.0));
+            /// assert_eq!(range.midpoint(), range.lerp(0.5));
+            /// ```
+            pub fn lerp(&self, t: f32) -> $float {
+                self.min + self.span() * t.clamp(0.0, 1.0) as $float
+            }
+
+            /// Steps through the range from the minimum to the maximum, `step` apart, such as every whole percent for a slider. \
+            /// The maximum is always the last value, even when the step does not divide the span evenly,
+            /// and a range whose minimum equals its maximum gives that single value.
+            /// Gives nothing when the step is not a positive finite number or the minimum is above the maximum.
+            /// ### Example
+            /// ```rust
+            /// use vs_alloy_calculator::ConstituentRange;
+            ///
+            /// let steps: Vec<f32> = ConstituentRange::new(0.50_f32, 0.70).iter_steps(0.03).collect();
+            ///
+            /// assert_eq!(8, steps.len());
+            /// assert_eq!((0.50, 0.70), (steps[0], steps[7]));
+            /// assert!((steps[6] - 0.68).abs() < 1e-6);
+            /// ```
+            pub fn iter_steps(&self, step: $float) -> impl Iterator<Item = $float> + use<> {
+                let Self { min, max } = *self;
+                let valid = step.is_finite() && step > 0.0 && min <= max;
+                // A little slack so a step that divides the span evenly lands on the maximum despite rounding
+                let slack = step * 1e-3;
+                let count = if valid {
+                    ((max - min + slack) / step).floor() as usize
+                } else {
+                    0
+                };
+                let last = min + step * count as $float;
+                let short_of_max = (valid && max - last > slack).then_some(last);
+                (0..count)
+                    .map(move |i| min + step * i as $float)
+                    .chain(short_of_max)
+                    .chain(valid.then_some(max))
+            }
+        }
+    )*};
+}
+impl_float_range!(f32, f64);
+
+macro_rules! impl_integer_range {
+    ($($integer:ty),*) => {$(
+        impl ConstituentRange<$integer> {
+            /// Returns the distance between the minimum and maximum of the range,
+            /// saturating at the bounds of the type instead of overflowing
+            pub fn span(&self) -> $integer {
+                Self::saturate(self.wide_span())
+            }
+
+            /// Distance between the minimum and maximum in `i128`, which holds it for every implemented type without overflowing
+            fn wide_span(&self) -> i128 {
+                i128::from(self.max) - i128::from(self.min)
+            }
+
+            /// Converts a value calculated in `i128` back to the type of the range, saturating at its bounds
+            fn saturate(value: i128) -> $integer {
+                <$integer>::try_from(value).unwrap_or(if value < 0 {
+                    <$integer>::MIN
+                } else {
+                    <$integer>::MAX
+                })
+            }
+
+            /// Returns the value halfway between the minimum and maximum of the range. \
+            /// When the span is odd the midpoint rounds down towards the minimum.
+            /// ### Example
+            /// ```rust
+            /// use vs_alloy_calculator::ConstituentRange;
+            ///
+            /// assert_eq!(10, ConstituentRange::new(8_i32, 12).midpoint());
+            /// assert_eq!(55, ConstituentRange::new(50_i32, 61).midpoint());
+            /// ```
+            pub fn midpoint(&self) -> $integer {
+                Self::saturate(i128::from(self.min) + (self.wide_span() >> 1))
+            }
+
+            /// Maps `t` from `0.0..=1.0` onto the range, where `0.0` is the minimum and `1.0` is the maximum. \
+            /// `t` is clamped into `0.0..=1.0` first and fractional results round down towards the minimum,
+            /// so `lerp(0.5)` always equals [`midpoint`](Self::midpoint).
+            /// ### Example
+            /// ```rust
+            /// use vs_alloy_calculator::ConstituentRange;
+            ///
+            /// let range = ConstituentRange::new(50_i32, 61);
+            ///
+            /// assert_eq!(50, range.lerp(0.0));
+            /// assert_eq!(61, range.lerp(1.0));
+            /// assert_eq!(range.midpoint(), range.lerp(0.5));
+            /// ```
+            pub fn lerp(&self, t: f32) -> $integer {
+                let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+                // t is exactly mantissa / 2^shift, so the offset is calculated without any float rounding
+                let (mantissa, shift) = f32_as_ratio(t);
+                let wide_span = self.wide_span();
+                let offset = (wide_span * mantissa)
+                    .checked_shr(shift)
+                    .unwrap_or(0)
+                    .clamp(wide_span.min(0), wide_span.max(0));
+                Self::saturate(i128::from(self.min) + offset)
+            }
+        }
+    )*};
+}
+impl_integer_range!(i32, i64, u8, u16, u32);
+
+/// Splits a finite, non-negative `f32` into an integer mantissa and the power of two it is divided by,
+/// so `value == mantissa / 2^shift` exactly
+fn f32_as_ratio(value: f32) -> (i128, u32) {
+    let bits = value.to_bits();
+    let exponent = (bits >> 23) & 0xff;
+    let fraction = i128::from(bits & 0x7f_ffff);
+    if exponent == 0 {
+        (fraction, 149)
+    } else {
+        (fraction | 0x80_0000, 150 - exponent)
+    }
 }
+
 type Range = ConstituentRange<f32>;
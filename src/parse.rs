@@ -0,0 +1,221 @@
+//! # Parsing
+//! Hardened entry points for turning untrusted text into alloys, metals, and percentages.
+//!
+//! Every function here is safe to call with any `&str`: none of them panic, and none of them allocate more than a small fixed amount.
+//! Inputs longer than [`MAX_INPUT_LEN`] bytes are rejected before any work is done.
+use crate::prelude::*;
+use crate::{AlloyError, Percentages};
+
+/// The longest input in bytes that any parser here will look at
+pub const MAX_INPUT_LEN: usize = 256;
+
+/// Parses an alloy from its display name, ignoring ASCII case, or from a game code such as `ingot-tinbronze`
+/// ### Example
+/// ```rust
+/// use vs_alloy_calculator::prelude::*;
+/// use vs_alloy_calculator::parse;
+///
+/// assert_eq!(Some(Alloys::TinBronze), parse::alloy("tin bronze"));
+/// assert_eq!(Some(Alloys::Brass), parse::alloy("game:ingot-brass"));
+/// assert_eq!(None, parse::alloy("steel"));
+/// ```
+pub fn alloy(input: &str) -> Option<Alloys> {
+    let input = bounded(input)?;
+    Alloys::ALL
+        .into_iter()
+        .find(|a| a.name().eq_ignore_ascii_case(input))
+        .or_else(|| Alloys::from_game_code(input))
+}
+
+/// Parses a metal from its display name, ignoring ASCII case, or from a game code such as `nugget-cassiterite`
+/// ### Example
+/// ```rust
+/// use vs_alloy_calculator::prelude::*;
+/// use vs_alloy_calculator::parse;
+///
+/// assert_eq!(Some(Metal::Copper), parse::metal("COPPER"));
+/// assert_eq!(Some(Metal::Tin), parse::metal("nugget-cassiterite"));
+/// assert_eq!(None, parse::metal("iron"));
+/// ```
+pub fn metal(input: &str) -> Option<Metal> {
+    let input = bounded(input)?;
+    Metal::ALL
+        .into_iter()
+        .find(|m| m.name().eq_ignore_ascii_case(input))
+        .or_else(|| Metal::from_game_code(input))
+}
+
+/// Parses a single percentage, either as a whole percent with a `%` suffix or as a fraction of one. \
+/// The result is always a finite fraction between 0 and 1.
+/// ### Example
+/// ```rust
+/// use vs_alloy_calculator::parse;
+///
+/// assert_eq!(Some(0.925), parse::percent("92.5%"));
+/// assert_eq!(Some(0.08), parse::percent("0.08"));
+/// assert_eq!(None, parse::percent("150%"));
+/// ```
+pub fn percent(input: &str) -> Option<f32> {
+    let input = bounded(input)?;
+    let value = match input.strip_suffix('%') {
+        Some(whole) => whole.trim_end().parse::<f32>().ok()? / 100.0,
+        None => input.parse::<f32>().ok()?,
+    };
+    (value.is_finite() && (0.0..=1.0).contains(&value)).then_some(value)
+}
+
+/// Parses a list of metals and their percentages, such as `copper 92%, tin 8%` or `Copper=0.92; Tin=0.08`. \
+/// Entries are separated by `,` or `;`, and each metal is separated from its percentage by whitespace, `:`, or `=`.
+///
+/// Only the text is checked here, so the result still needs to be validated by creating an alloy from it.
+/// Returns [`InvalidBaseMetals`](AlloyError::InvalidBaseMetals) for a metal name that is not recognized,
+/// [`DuplicateConstituent`](AlloyError::DuplicateConstituent) for a repeated metal,
+/// [`WrongConstituentCount`](AlloyError::WrongConstituentCount) for more entries than there are metals,
+/// and [`InvalidPercentages`](AlloyError::InvalidPercentages) for anything that is not a percentage.
+/// ### Example
+/// ```rust
+/// use vs_alloy_calculator::prelude::*;
+/// use vs_alloy_calculator::parse;
+///
+/// let percentages = parse::percentages("copper 92%, tin 8%").expect("should parse");
+/// let alloy = AlloyData::<TinBronze>::try_new(percentages, 5).expect("should be valid");
+///
+/// assert_eq!(&[Copper(92), Tin(8)], alloy.nuggets());
+/// ```
+pub fn percentages(input: &str) -> Result<Percentages, AlloyError> {
+    let input = bounded(input).ok_or(AlloyError::InvalidPercentages)?;
+    let entries = || input.split([',', ';']).filter(|e| !e.trim().is_empty());
+    let count = entries().count();
+    if count > Metal::ALL.len() {
+        return Err(AlloyError::WrongConstituentCount {
+            expected: Metal::ALL.len(),
+            got: count,
+        });
+    }
+
+    let mut seen = [false; Metal::ALL.len()];
+    entries()
+        .map(|entry| {
+            let (name, value) = entry
+                .trim()
+                .rsplit_once(is_separator)
+                .ok_or(AlloyError::InvalidPercentages)?;
+            let metal =
+                metal(name.trim_end_matches(is_separator)).ok_or(AlloyError::InvalidBaseMetals)?;
+            if std::mem::replace(&mut seen[metal as usize], true) {
+                return Err(AlloyError::DuplicateConstituent(metal));
+            }
+            let value = percent(value).ok_or(AlloyError::InvalidPercentages)?;
+            Ok(metal.with_value(value))
+        })
+        .collect()
+}
+
+/// Error from parsing an alloy name that does not match any alloy
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseAlloyError {
+    /// The text that failed to parse, cut short after [`MAX_INPUT_LEN`] characters
+    pub input: String,
+}
+
+impl std::fmt::Display for ParseAlloyError {
+    /// Writes the input along with every valid alloy name
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = Alloys::ALL.iter().map(Alloys::name).collect();
+        write!(
+            f,
+            "unknown alloy `{}`, expected one of: {}",
+            self.input,
+            names.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for ParseAlloyError {}
+
+impl std::str::FromStr for Alloys {
+    type Err = ParseAlloyError;
+
+    /// Parses an alloy from its display name, ignoring case, spaces, hyphens, and underscores
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(Ok(Alloys::TinBronze), "tin bronze".parse());
+    /// assert_eq!(Ok(Alloys::TinBronze), "TinBronze".parse());
+    /// assert_eq!(Ok(Alloys::SilverSolder), "silver-solder".parse());
+    /// assert!("tin bronz".parse::<Alloys>().unwrap_err().to_string().contains("Tin Bronze"));
+    /// ```
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let wanted = bounded(input).map(squash);
+        Alloys::ALL
+            .into_iter()
+            .find(|a| Some(squash(a.name())) == wanted)
+            .ok_or_else(|| ParseAlloyError {
+                input: input.chars().take(MAX_INPUT_LEN).collect(),
+            })
+    }
+}
+
+/// Error from parsing a metal name that does not match any metal
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseMetalError {
+    /// The text that failed to parse, cut short after [`MAX_INPUT_LEN`] characters
+    pub input: String,
+}
+
+impl std::fmt::Display for ParseMetalError {
+    /// Writes the input along with every valid metal name
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = Metal::ALL.iter().map(Metal::name).collect();
+        write!(
+            f,
+            "unknown metal `{}`, expected one of: {}",
+            self.input,
+            names.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for ParseMetalError {}
+
+impl std::str::FromStr for Metal {
+    type Err = ParseMetalError;
+
+    /// Parses a metal from its name, ignoring case and surrounding whitespace
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// assert_eq!(Ok(Metal::Copper), "copper".parse());
+    /// assert_eq!(Ok(Metal::Bismuth), " BISMUTH ".parse());
+    /// assert!("iron".parse::<Metal>().unwrap_err().to_string().contains("Nickel, Copper"));
+    /// ```
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let wanted = bounded(input);
+        Metal::ALL
+            .into_iter()
+            .find(|m| wanted.is_some_and(|w| m.name().eq_ignore_ascii_case(w)))
+            .ok_or_else(|| ParseMetalError {
+                input: input.chars().take(MAX_INPUT_LEN).collect(),
+            })
+    }
+}
+
+/// Lowercases a name and drops the characters that commonly separate its words
+fn squash(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '_'))
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Whether the character separates a metal from its percentage
+fn is_separator(c: char) -> bool {
+    c == ':' || c == '=' || c.is_whitespace()
+}
+
+/// Trims the input and rejects it when it is too long to be worth parsing
+fn bounded(input: &str) -> Option<&str> {
+    (input.len() <= MAX_INPUT_LEN).then(|| input.trim())
+}
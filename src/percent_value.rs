@@ -0,0 +1,161 @@
+//! # Percent Values
+//! A percentage checked to be a usable fraction before it ever reaches an alloy.
+use crate::AlloyError;
+
+/// A percentage stored as a finite fraction from 0 to 1, so `0.92` rather than `92.0`
+///
+/// Constituent ranges are still checked when an alloy is created, so a `Percent` only rules out values that could never be valid.
+/// ### Example
+/// ```rust
+/// use vs_alloy_calculator::prelude::*;
+/// use vs_alloy_calculator::{Percent, Percentages};
+///
+/// let copper = Percent::from_whole(92.0).expect("should be valid");
+/// let tin = Percent::from_fraction(0.08).expect("should be valid");
+/// let percentages = Percentages::from([Copper(copper), Tin(tin)]);
+///
+/// let alloy = AlloyData::<TinBronze>::try_new(percentages, 1).expect("should be valid");
+/// assert_eq!(&[Copper(copper), Tin(tin)], &alloy.percentages_typed()[..]);
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, PartialOrd, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "f32", into = "f32")
+)]
+pub struct Percent(f32);
+
+/// Why a value could not be made into a [`Percent`]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PercentError {
+    /// The value was `NaN` or infinite
+    NotFinite,
+    /// The value was below 0% or above 100%
+    OutOfBounds,
+}
+
+impl Percent {
+    /// Tries to create a percent from a fraction of one, such as `0.92`
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::{Percent, PercentError};
+    ///
+    /// assert_eq!(0.92, Percent::from_fraction(0.92).expect("should be valid").fraction());
+    /// assert_eq!(Err(PercentError::OutOfBounds), Percent::from_fraction(92.0));
+    /// assert_eq!(Err(PercentError::NotFinite), Percent::from_fraction(f32::NAN));
+    /// ```
+    pub fn from_fraction(fraction: f32) -> Result<Self, PercentError> {
+        if !fraction.is_finite() {
+            Err(PercentError::NotFinite)
+        } else if !(0.0..=1.0).contains(&fraction) {
+            Err(PercentError::OutOfBounds)
+        } else {
+            Ok(Self(fraction))
+        }
+    }
+
+    /// Tries to create a percent from a whole percentage, such as `92.0`
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::{Percent, PercentError};
+    ///
+    /// assert_eq!(0.92, Percent::from_whole(92.0).expect("should be valid").fraction());
+    /// assert_eq!(Err(PercentError::OutOfBounds), Percent::from_whole(-8.0));
+    /// ```
+    pub fn from_whole(whole: f32) -> Result<Self, PercentError> {
+        if !whole.is_finite() {
+            return Err(PercentError::NotFinite);
+        }
+        Self::from_fraction(whole / 100.0)
+    }
+
+    /// Gets the percent as a fraction of one
+    pub fn fraction(self) -> f32 {
+        self.0
+    }
+
+    /// Gets the percent as a whole percentage
+    pub fn whole(self) -> f32 {
+        self.0 * 100.0
+    }
+
+    /// Wraps a fraction that is already known to be valid, such as one taken from a validated alloy
+    pub(crate) fn from_validated(fraction: f32) -> Self {
+        Self(fraction)
+    }
+}
+
+impl TryFrom<f32> for Percent {
+    type Error = PercentError;
+
+    /// Creates a percent from a fraction of one, the same as [`Percent::from_fraction`]
+    fn try_from(fraction: f32) -> Result<Self, Self::Error> {
+        Self::from_fraction(fraction)
+    }
+}
+
+impl From<Percent> for f32 {
+    fn from(percent: Percent) -> Self {
+        percent.fraction()
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl TryFrom<rust_decimal::Decimal> for Percent {
+    type Error = PercentError;
+
+    /// Creates a percent from a decimal fraction of one, rounded to the nearest f32
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::{Percent, PercentError};
+    /// use rust_decimal::Decimal;
+    ///
+    /// assert_eq!(Percent::from_fraction(0.92), Percent::try_from(Decimal::new(92, 2)));
+    /// assert_eq!(Err(PercentError::OutOfBounds), Percent::try_from(Decimal::new(101, 2)));
+    /// ```
+    fn try_from(fraction: rust_decimal::Decimal) -> Result<Self, Self::Error> {
+        if !(rust_decimal::Decimal::ZERO..=rust_decimal::Decimal::ONE).contains(&fraction) {
+            return Err(PercentError::OutOfBounds);
+        }
+        f32::try_from(fraction)
+            .map_err(|_| PercentError::OutOfBounds)
+            .and_then(Self::from_fraction)
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl From<Percent> for rust_decimal::Decimal {
+    /// Converts the percent to the shortest decimal fraction of one that reads back as the same f32,
+    /// so `0.15` becomes exactly `0.15` rather than picking up float noise
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::Percent;
+    /// use rust_decimal::Decimal;
+    ///
+    /// let percent = Percent::from_whole(92.0).expect("should be valid");
+    ///
+    /// assert_eq!(Decimal::new(92, 2), Decimal::from(percent));
+    /// ```
+    fn from(percent: Percent) -> Self {
+        // A percent is always finite and between 0 and 1, so its shortest form always parses
+        percent.fraction().to_string().parse().unwrap_or_default()
+    }
+}
+
+impl std::fmt::Display for PercentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PercentError::NotFinite => "percentage is not a finite number",
+            PercentError::OutOfBounds => "percentage is not between 0% and 100%",
+        })
+    }
+}
+
+impl std::error::Error for PercentError {}
+
+impl From<PercentError> for AlloyError {
+    /// Any value that cannot be a percent is an [`InvalidPercentages`](AlloyError::InvalidPercentages) error
+    fn from(_: PercentError) -> Self {
+        AlloyError::InvalidPercentages
+    }
+}
@@ -0,0 +1,163 @@
+//! # Planner
+//! Planning sets of crucible batches around the nuggets that are on hand.
+use crate::alloy::alloy_types::*;
+use crate::crucible::Capacity;
+use crate::{Alloy, AlloyData, IngotCount, Metal};
+use std::collections::BTreeMap;
+
+/// The most nuggets a plan can be made for, which bounds the memory used by the search
+pub const MAX_PLAN_NUGGETS: i32 = 100_000;
+
+/// Error for when a plan cannot be made
+#[derive(PartialEq, Debug)]
+#[non_exhaustive]
+pub enum PlanError {
+    /// The alloy does not use the metal
+    NotAConstituent,
+    /// More nuggets were supplied than [`MAX_PLAN_NUGGETS`]
+    TooManyNuggets,
+    /// The nuggets cannot be used up exactly. Holds the closest total below the count that can be, and the batches that use it
+    Inexact { closest: i32, batches: Vec<Alloy> },
+}
+
+/// Finds the fewest valid batches of the alloy that together use exactly the supplied nuggets of the metal in the vanilla crucible, \
+/// the same as [`consume_exact_in`] with [`Capacity::VANILLA`]
+/// ### Example
+/// ```rust
+/// use vs_alloy_calculator::prelude::*;
+/// use vs_alloy_calculator::planner::{self, PlanError};
+///
+/// let batches = planner::consume_exact(Alloys::TinBronze, Metal::Tin, 37).expect("should be exact");
+/// assert_eq!(1, batches.len());
+/// assert_eq!(Tin(37), batches[0].nuggets()[1]);
+///
+/// let error = planner::consume_exact(Alloys::TinBronze, Metal::Tin, 3).unwrap_err();
+/// assert!(matches!(error, PlanError::Inexact { closest: 2, .. }));
+/// ```
+pub fn consume_exact(alloy: Alloys, metal: Metal, nuggets: i32) -> Result<Vec<Alloy>, PlanError> {
+    consume_exact_in(alloy, metal, nuggets, Capacity::VANILLA)
+}
+
+/// Finds the fewest valid batches of the alloy that together use exactly the supplied nuggets of the metal,
+/// where every batch's stacks fit in the supplied capacity. \
+/// Every batch is still one [`Alloys::try_new`] can create from its percentages, so a capacity larger than the vanilla crucible
+/// only allows the layouts it can hold and never more ingots than the vanilla crucible.
+/// ### Example
+/// ```rust
+/// use vs_alloy_calculator::prelude::*;
+/// use vs_alloy_calculator::crucible::Capacity;
+/// use vs_alloy_calculator::planner;
+///
+/// let small = Capacity { slots: 2, ..Capacity::VANILLA };
+///
+/// let batches = planner::consume_exact_in(Alloys::TinBronze, Metal::Tin, 50, small).expect("should be exact");
+/// assert!(batches.len() > 1);
+/// ```
+pub fn consume_exact_in(
+    alloy: Alloys,
+    metal: Metal,
+    nuggets: i32,
+    capacity: Capacity,
+) -> Result<Vec<Alloy>, PlanError> {
+    if nuggets > MAX_PLAN_NUGGETS {
+        return Err(PlanError::TooManyNuggets);
+    }
+    let (closest, batches) = match alloy {
+        Alloys::TinBronze => wrap(
+            plan::<TinBronze>(metal, nuggets, capacity),
+            Alloy::TinBronze,
+        ),
+        Alloys::BismuthBronze => wrap(
+            plan::<BismuthBronze>(metal, nuggets, capacity),
+            Alloy::BismuthBronze,
+        ),
+        Alloys::BlackBronze => wrap(
+            plan::<BlackBronze>(metal, nuggets, capacity),
+            Alloy::BlackBronze,
+        ),
+        Alloys::Brass => wrap(plan::<Brass>(metal, nuggets, capacity), Alloy::Brass),
+        Alloys::Molybdochalkos => wrap(
+            plan::<Molybdochalkos>(metal, nuggets, capacity),
+            Alloy::Molybdochalkos,
+        ),
+        Alloys::LeadSolder => wrap(
+            plan::<LeadSolder>(metal, nuggets, capacity),
+            Alloy::LeadSolder,
+        ),
+        Alloys::SilverSolder => wrap(
+            plan::<SilverSolder>(metal, nuggets, capacity),
+            Alloy::SilverSolder,
+        ),
+        Alloys::Electrum => wrap(plan::<Electrum>(metal, nuggets, capacity), Alloy::Electrum),
+        Alloys::Cupronickel => wrap(
+            plan::<Cupronickel>(metal, nuggets, capacity),
+            Alloy::Cupronickel,
+        ),
+    }
+    .ok_or(PlanError::NotAConstituent)?;
+    if closest == nuggets {
+        Ok(batches)
+    } else {
+        Err(PlanError::Inexact { closest, batches })
+    }
+}
+
+/// Wraps the batches of a plan into the unified alloy enum
+fn wrap<T: AlloyType>(
+    plan: Option<(i32, Vec<AlloyData<T>>)>,
+    variant: fn(AlloyData<T>) -> Alloy,
+) -> Option<(i32, Vec<Alloy>)> {
+    plan.map(|(total, batches)| (total, batches.into_iter().map(variant).collect()))
+}
+
+/// Finds the fewest batches that fit in the capacity using the largest total of the metal that does not exceed the nuggets. \
+/// Returns the total used with its batches, or `None` if the metal is not a constituent.
+fn plan<T: AlloyType + Clone>(
+    metal: Metal,
+    nuggets: i32,
+    capacity: Capacity,
+) -> Option<(i32, Vec<AlloyData<T>>)> {
+    let index = T::constituent_metals().iter().position(|m| *m == metal)?;
+
+    // Cheapest single batch for each amount of the metal, preferring fewer ingots
+    let mut batches: BTreeMap<i32, AlloyData<T>> = BTreeMap::new();
+    let max_ingots = capacity.max_possible_ingots().min(IngotCount::MAX.get());
+    for recipe in (1..=max_ingots)
+        .flat_map(|num_ingots| AlloyData::<T>::valid_recipes_in(num_ingots, capacity))
+    {
+        let amount = *recipe.nuggets()[index];
+        match batches.get(&amount) {
+            Some(best)
+                if (best.num_ingots(), best.nuggets())
+                    <= (recipe.num_ingots(), recipe.nuggets()) => {}
+            _ => {
+                batches.insert(amount, recipe);
+            }
+        }
+    }
+
+    // Fewest batches summing to each total, storing the last batch amount used to reach it
+    let target = nuggets.max(0) as usize;
+    let mut fewest: Vec<Option<(usize, i32)>> = vec![None; target + 1];
+    fewest[0] = Some((0, 0));
+    for total in 1..=target {
+        fewest[total] = batches
+            .keys()
+            .filter(|amount| **amount > 0 && **amount as usize <= total)
+            .filter_map(|amount| {
+                let (count, _) = fewest[total - *amount as usize]?;
+                Some((count + 1, *amount))
+            })
+            .min();
+    }
+
+    let reached = (0..=target).rev().find(|t| fewest[*t].is_some())?;
+    let mut plan = Vec::new();
+    let mut total = reached;
+    while total > 0 {
+        let (_, amount) = fewest[total]?;
+        plan.push(batches.get(&amount)?.clone());
+        total -= amount as usize;
+    }
+    Some((reached as i32, plan))
+}
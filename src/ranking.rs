@@ -0,0 +1,93 @@
+//! # Ranking
+//! Comparing the alloys against each other for how well they use a metal.
+use crate::alloy::alloy_types::*;
+use crate::unit_constants::MAX_POSSIBLE_INGOTS;
+use crate::{Alloy, AlloyData, Metal};
+
+/// An alloy's recipe using the least of a metal, ranked against the other alloys
+#[derive(PartialEq, Debug)]
+pub struct MetalEfficiency {
+    pub alloy: Alloys,
+    /// The valid recipe that uses the least of the metal
+    pub recipe: Alloy,
+    /// Ingots made per nugget of the metal used
+    pub ingots_per_nugget: f32,
+    /// Whether the recipe reaches the requested number of ingots, otherwise it is at the most the alloy can make
+    pub reached: bool,
+}
+
+/// Ranks each alloy containing the metal by the most ingots made per nugget of the metal at the supplied number of ingots. \
+/// Ties keep the order of [`Alloys::ALL`].
+/// ### Example
+/// ```rust
+/// use vs_alloy_calculator::prelude::*;
+/// use vs_alloy_calculator::ranking;
+///
+/// let ranking = ranking::by_metal_efficiency(Metal::Copper, 10);
+///
+/// assert_eq!(Alloys::Molybdochalkos, ranking[0].alloy);
+/// assert_eq!(Alloys::TinBronze, ranking[ranking.len() - 1].alloy);
+/// ```
+pub fn by_metal_efficiency(metal: Metal, ingots: i32) -> Vec<MetalEfficiency> {
+    let mut ranking: Vec<MetalEfficiency> = Alloys::ALL
+        .into_iter()
+        .filter_map(|alloy| {
+            let (recipe, nuggets, reached) = match alloy {
+                Alloys::TinBronze => wrap(least::<TinBronze>(metal, ingots), Alloy::TinBronze),
+                Alloys::BismuthBronze => {
+                    wrap(least::<BismuthBronze>(metal, ingots), Alloy::BismuthBronze)
+                }
+                Alloys::BlackBronze => {
+                    wrap(least::<BlackBronze>(metal, ingots), Alloy::BlackBronze)
+                }
+                Alloys::Brass => wrap(least::<Brass>(metal, ingots), Alloy::Brass),
+                Alloys::Molybdochalkos => wrap(
+                    least::<Molybdochalkos>(metal, ingots),
+                    Alloy::Molybdochalkos,
+                ),
+                Alloys::LeadSolder => wrap(least::<LeadSolder>(metal, ingots), Alloy::LeadSolder),
+                Alloys::SilverSolder => {
+                    wrap(least::<SilverSolder>(metal, ingots), Alloy::SilverSolder)
+                }
+                Alloys::Electrum => wrap(least::<Electrum>(metal, ingots), Alloy::Electrum),
+                Alloys::Cupronickel => {
+                    wrap(least::<Cupronickel>(metal, ingots), Alloy::Cupronickel)
+                }
+            }?;
+            Some(MetalEfficiency {
+                alloy,
+                ingots_per_nugget: recipe.num_ingots() as f32 / nuggets as f32,
+                recipe,
+                reached,
+            })
+        })
+        .collect();
+    ranking.sort_by(|a, b| b.ingots_per_nugget.total_cmp(&a.ingots_per_nugget));
+    ranking
+}
+
+/// Wraps a recipe into the unified alloy enum
+fn wrap<T: AlloyType>(
+    least: Option<(AlloyData<T>, i32, bool)>,
+    variant: fn(AlloyData<T>) -> Alloy,
+) -> Option<(Alloy, i32, bool)> {
+    least.map(|(recipe, nuggets, reached)| (variant(recipe), nuggets, reached))
+}
+
+/// Finds the valid recipe using the least of the metal at the number of ingots, falling back to the most ingots the alloy can make. \
+/// Returns the recipe, the nuggets of the metal it uses, and whether it reached the number of ingots.
+fn least<T: AlloyType>(metal: Metal, ingots: i32) -> Option<(AlloyData<T>, i32, bool)> {
+    let index = T::constituent_metals().iter().position(|m| *m == metal)?;
+    let (recipes, reached) = (1..=ingots.clamp(1, MAX_POSSIBLE_INGOTS))
+        .rev()
+        .map(AlloyData::<T>::valid_recipes)
+        .enumerate()
+        .find(|(_, recipes)| !recipes.is_empty())
+        .map(|(i, recipes)| (recipes, i == 0 && ingots <= MAX_POSSIBLE_INGOTS))?;
+    let recipe = recipes.into_iter().min_by_key(|recipe| {
+        let nuggets: Vec<i32> = recipe.nuggets().iter().map(|n| **n).collect();
+        (nuggets[index], nuggets)
+    })?;
+    let nuggets = *recipe.nuggets()[index];
+    Some((recipe, nuggets, reached))
+}
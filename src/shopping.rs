@@ -0,0 +1,145 @@
+//! # Shopping
+//! Combining the nuggets needed by several recipes into one list to gather.
+use crate::alloy::alloy_types::Alloys;
+use crate::crucible::Capacity;
+use crate::format::FormatOptions;
+use crate::{Alloy, BaseMetal, MetalTotals};
+use std::fmt;
+
+/// Nuggets needed by a single recipe on a [`ShoppingList`]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RecipeNeeds {
+    pub alloy: Alloys,
+    pub num_ingots: i32,
+    pub nuggets: Box<[BaseMetal<i32>]>,
+}
+
+/// Combined nuggets needed across several recipes, keeping which recipe needs what
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ShoppingList {
+    pub totals: MetalTotals,
+    pub recipes: Vec<RecipeNeeds>,
+    /// The crucible whose stack size the totals are split into
+    pub capacity: Capacity,
+}
+
+impl ShoppingList {
+    /// Splits the total nuggets of each metal into full stacks of the list's capacity and the remaining nuggets
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::shopping;
+    ///
+    /// let recipes = [Alloys::TinBronze.try_new([Copper(0.90), Tin(0.10)], 10).expect("should be valid")];
+    /// let list = shopping::aggregate(&recipes);
+    ///
+    /// assert_eq!(vec![Copper((1, 52)), Tin((0, 20))], list.stacks());
+    /// ```
+    pub fn stacks(&self) -> Vec<BaseMetal<(i32, i32)>> {
+        self.totals
+            .iter()
+            .map(|n| {
+                let stack = self.capacity.max_stack_size();
+                let split = n.checked_div(stack).zip(n.checked_rem(stack));
+                n.update_inner_value(split.unwrap_or((0, *n)))
+            })
+            .collect()
+    }
+}
+
+/// Sums the nuggets of every recipe per metal, splitting them into the stacks of the vanilla crucible, \
+/// the same as [`aggregate_in`] with [`Capacity::VANILLA`]
+/// ### Example
+/// ```rust
+/// use vs_alloy_calculator::prelude::*;
+/// use vs_alloy_calculator::shopping;
+///
+/// let recipes = [Alloys::TinBronze.get_default(), Alloys::Brass.get_default()];
+/// let list = shopping::aggregate(&recipes);
+///
+/// assert_eq!(32, list.totals.get(Metal::Copper));
+/// assert_eq!(2, list.recipes.len());
+/// ```
+pub fn aggregate(recipes: &[Alloy]) -> ShoppingList {
+    aggregate_in(recipes, Capacity::VANILLA)
+}
+
+/// Sums the nuggets of every recipe per metal, splitting them into the stacks of the supplied capacity
+/// ### Example
+/// ```rust
+/// use vs_alloy_calculator::prelude::*;
+/// use vs_alloy_calculator::crucible::Capacity;
+/// use vs_alloy_calculator::shopping;
+///
+/// let large_stacks = Capacity { max_units_per_slot: 1000, ..Capacity::VANILLA };
+/// let recipes = [Alloys::TinBronze.try_new([Copper(0.90), Tin(0.10)], 10).expect("should be valid")];
+/// let list = shopping::aggregate_in(&recipes, large_stacks);
+///
+/// assert_eq!(vec![Copper((0, 180)), Tin((0, 20))], list.stacks());
+/// ```
+pub fn aggregate_in(recipes: &[Alloy], capacity: Capacity) -> ShoppingList {
+    ShoppingList {
+        totals: recipes.iter().flat_map(|r| r.nuggets()).copied().collect(),
+        recipes: recipes
+            .iter()
+            .map(|r| RecipeNeeds {
+                alloy: r.kind(),
+                num_ingots: r.num_ingots(),
+                nuggets: r.nuggets().into(),
+            })
+            .collect(),
+        capacity,
+    }
+}
+
+impl ShoppingList {
+    /// Renders the list as Markdown with a table of totals followed by each recipe's needs, using the supplied [`FormatOptions`]
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    /// use vs_alloy_calculator::format::FormatOptions;
+    /// use vs_alloy_calculator::shopping;
+    ///
+    /// let list = shopping::aggregate(&[Alloys::TinBronze.get_default()]);
+    ///
+    /// assert_eq!(list.to_string(), list.to_markdown_with(&FormatOptions::default()));
+    /// ```
+    pub fn to_markdown_with(&self, options: &FormatOptions) -> String {
+        let mut markdown = String::new();
+        markdown.push_str("| Metal | Nuggets | Stacks |\n");
+        markdown.push_str("| --- | --- | --- |\n");
+        for (total, stacks) in self.totals.iter().zip(self.stacks()) {
+            let (full, rest) = *stacks;
+            markdown.push_str(&format!(
+                "| {} | {} | {} × {} + {} |\n",
+                total.name(),
+                options.integer(i64::from(*total)),
+                options.integer(i64::from(full)),
+                options.integer(i64::from(self.capacity.max_stack_size())),
+                options.integer(i64::from(rest)),
+            ));
+        }
+        for recipe in &self.recipes {
+            markdown.push_str(&format!(
+                "\n### {} × {}\n",
+                recipe.alloy.name(),
+                options.ingots(recipe.num_ingots)
+            ));
+            for n in &recipe.nuggets {
+                markdown.push_str(&format!(
+                    "- {}: {}\n",
+                    n.name(),
+                    options.integer(i64::from(**n))
+                ));
+            }
+        }
+        markdown
+    }
+}
+
+/// Renders the list as Markdown with a table of totals followed by each recipe's needs
+impl fmt::Display for ShoppingList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_markdown_with(&FormatOptions::default()))
+    }
+}
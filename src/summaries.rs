@@ -0,0 +1,74 @@
+//! # Summaries
+//! Everything about each alloy in one place for building selection menus.
+use crate::alloy::alloy_types::count_whole_percent_mixes;
+use crate::prelude::*;
+use crate::{AlloyDoc, ConstituentRange};
+
+/// A description of an alloy for selection menus, assembled from the alloy's own accessors
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AlloySummary {
+    pub alloy: Alloys,
+    pub name: &'static str,
+    /// Code the game uses for the alloy in item identifiers
+    pub game_code: &'static str,
+    /// Valid range of each constituent in canonical order
+    pub ranges: &'static [BaseMetal<ConstituentRange<f32>>],
+    /// Percentages the alloy defaults to in canonical order
+    pub default_percentages: &'static [BaseMetal<f32>],
+    /// Nuggets needed for the default percentages and ingots
+    pub default_nuggets: Box<[BaseMetal<i32>]>,
+    pub default_ingots: i32,
+    /// Most ingots that fit in the crucible at the default percentages
+    pub max_ingots: i32,
+    /// Number of valid mixes using whole percents
+    pub num_whole_percent_mixes: usize,
+    pub doc: &'static AlloyDoc,
+}
+
+impl Alloys {
+    /// Gets a summary of everything about the alloy for selection menus. \
+    /// The game does not give alloys a tier, color, or melting point that this library knows of, so those are not included.
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::prelude::*;
+    ///
+    /// let summary = Alloys::TinBronze.summary();
+    ///
+    /// assert_eq!("Tin Bronze", summary.name);
+    /// assert_eq!("tinbronze", summary.game_code);
+    /// assert_eq!(&[Copper(18), Tin(2)], &*summary.default_nuggets);
+    /// assert_eq!(20, summary.max_ingots);
+    /// assert_eq!(5, summary.num_whole_percent_mixes);
+    /// ```
+    pub fn summary(&self) -> AlloySummary {
+        let default = self.get_default();
+        AlloySummary {
+            alloy: *self,
+            name: self.name(),
+            game_code: self.game_code(),
+            ranges: self.percentage_ranges(),
+            default_percentages: self.default_percentages(),
+            default_nuggets: default.nuggets().into(),
+            default_ingots: default.num_ingots(),
+            max_ingots: default.max_ingots(),
+            num_whole_percent_mixes: count_whole_percent_mixes(self.percentage_ranges()),
+            doc: self.doc(),
+        }
+    }
+}
+
+/// Gets the summary of every alloy in the same order as [`Alloys::ALL`]
+/// ### Example
+/// ```rust
+/// use vs_alloy_calculator::prelude::*;
+/// use vs_alloy_calculator::summaries;
+///
+/// let menu = summaries::all();
+///
+/// assert_eq!(Alloys::ALL.len(), menu.len());
+/// assert_eq!(Alloys::Cupronickel, menu[8].alloy);
+/// ```
+pub fn all() -> Vec<AlloySummary> {
+    Alloys::ALL.iter().map(Alloys::summary).collect()
+}
@@ -1,3 +1,7 @@
+#[cfg(test)]
+#[macro_use]
+mod common;
+
 #[cfg(test)]
 mod tin_bronze_tests {
     use crate::AlloyError::*;
@@ -63,7 +67,7 @@ mod tin_bronze_tests {
     fn test_invalid_base_metals() {
         assert!(
             AlloyData::<TinBronze>::try_new([Lead(0.92), Copper(0.08)], 1)
-                .is_err_and(|e| e == InvalidBaseMetals)
+                .is_err_and(|e| e == UnknownConstituent(Metal::Lead))
         )
     }
 
@@ -184,3 +188,7171 @@ mod bismuth_bronze_tests {
         assert_eq!(Bismuth(4), nuggets[2]);
     }
 }
+
+#[cfg(all(test, feature = "rational"))]
+mod rational_tests {
+    use crate::AlloyError::*;
+    use crate::prelude::*;
+    use num_rational::Ratio;
+
+    fn to_f32(percentages: &[BaseMetal<Ratio<i32>>]) -> Vec<BaseMetal<f32>> {
+        percentages
+            .iter()
+            .map(|p| p.update_inner_value(*p.numer() as f32 / *p.denom() as f32))
+            .collect()
+    }
+
+    #[test]
+    fn test_thirds_style_ratios() {
+        let percentages = [
+            Copper(Ratio::new(7, 12)),
+            Zinc(Ratio::new(1, 4)),
+            Bismuth(Ratio::new(1, 6)),
+        ];
+        let alloy = AlloyData::<BismuthBronze>::try_new_rational(percentages, 1).unwrap();
+        assert_eq!(&[Copper(11), Zinc(5), Bismuth(4)], alloy.nuggets());
+        assert_eq!(21, alloy.max_ingots());
+
+        let alloy = AlloyData::<BismuthBronze>::try_new_rational(percentages, 12).unwrap();
+        assert_eq!(&[Copper(140), Zinc(60), Bismuth(40)], alloy.nuggets());
+    }
+
+    #[test]
+    fn test_any_order_base_metals() {
+        let alloy1 = AlloyData::<TinBronze>::try_new_rational(
+            [Copper(Ratio::new(9, 10)), Tin(Ratio::new(1, 10))],
+            3,
+        )
+        .unwrap();
+        let alloy2 = AlloyData::<TinBronze>::try_new_rational(
+            [Tin(Ratio::new(1, 10)), Copper(Ratio::new(9, 10))],
+            3,
+        )
+        .unwrap();
+        assert_eq!(alloy1, alloy2);
+    }
+
+    #[test]
+    fn test_exact_range_boundaries() {
+        // 23/25 is exactly the maximum copper for Tin Bronze
+        assert!(
+            AlloyData::<TinBronze>::try_new_rational(
+                [Copper(Ratio::new(23, 25)), Tin(Ratio::new(2, 25))],
+                1
+            )
+            .is_ok()
+        );
+        // One part in a thousand over the maximum is rejected
+        assert!(
+            AlloyData::<TinBronze>::try_new_rational(
+                [Copper(Ratio::new(921, 1000)), Tin(Ratio::new(79, 1000))],
+                1
+            )
+            .is_err_and(|e| e == InvalidPercentages)
+        );
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(
+            AlloyData::<TinBronze>::try_new_rational(
+                [Copper(Ratio::new(9, 10)), Tin(Ratio::new(1, 9))],
+                1
+            )
+            .is_err_and(|e| e == InvalidPercentages)
+        );
+        assert!(
+            AlloyData::<TinBronze>::try_new_rational(
+                [Lead(Ratio::new(9, 10)), Tin(Ratio::new(1, 10))],
+                1
+            )
+            .is_err_and(|e| e == UnknownConstituent(Metal::Lead))
+        );
+        assert!(
+            AlloyData::<BismuthBronze>::try_new_rational(
+                [Copper(Ratio::new(7, 10)), Zinc(Ratio::new(3, 10))],
+                1
+            )
+            .is_err_and(|e| e == MissingConstituent(Metal::Bismuth))
+        );
+        assert!(
+            AlloyData::<TinBronze>::try_new_rational(
+                [Copper(Ratio::new(9, 10)), Tin(Ratio::new(1, 10))],
+                0
+            )
+            .is_err_and(|e| e == TooFewIngots)
+        );
+        assert!(
+            AlloyData::<TinBronze>::try_new_rational(
+                [Copper(Ratio::new(23, 25)), Tin(Ratio::new(2, 25))],
+                21
+            )
+            .is_err_and(|e| e == TooManyIngots)
+        );
+    }
+
+    #[test]
+    fn test_cross_check_with_float_path() {
+        let mixes: [&[BaseMetal<Ratio<i32>>]; 4] = [
+            &[Copper(Ratio::new(23, 25)), Tin(Ratio::new(2, 25))],
+            &[Copper(Ratio::new(9, 10)), Tin(Ratio::new(1, 10))],
+            &[Copper(Ratio::new(22, 25)), Tin(Ratio::new(3, 25))],
+            &[Copper(Ratio::new(89, 100)), Tin(Ratio::new(11, 100))],
+        ];
+        for mix in mixes {
+            let float = to_f32(mix);
+            let max = AlloyData::<TinBronze>::try_new(&float, 1)
+                .unwrap()
+                .max_ingots();
+            for num_ingots in 1..=max {
+                let exact = AlloyData::<TinBronze>::try_new_rational(mix, num_ingots).unwrap();
+                let float = AlloyData::<TinBronze>::try_new(&float, num_ingots).unwrap();
+                assert_eq!(float.nuggets(), exact.nuggets(), "{mix:?} at {num_ingots}");
+                assert_eq!(float.max_ingots(), exact.max_ingots(), "{mix:?}");
+            }
+        }
+
+        let mix = [
+            Copper(Ratio::new(3, 5)),
+            Zinc(Ratio::new(1, 5)),
+            Bismuth(Ratio::new(1, 5)),
+        ];
+        let float = to_f32(&mix);
+        for num_ingots in 1..=21 {
+            let exact = AlloyData::<BismuthBronze>::try_new_rational(mix, num_ingots).unwrap();
+            let float = AlloyData::<BismuthBronze>::try_new(&float, num_ingots).unwrap();
+            assert_eq!(float.nuggets(), exact.nuggets(), "at {num_ingots}");
+            assert_eq!(float.max_ingots(), exact.max_ingots());
+        }
+    }
+}
+
+#[cfg(all(test, feature = "decimal"))]
+mod decimal_tests {
+    use crate::AlloyError::*;
+    use crate::prelude::*;
+    use crate::{Percent, PercentError};
+    use rust_decimal::Decimal;
+
+    fn dec(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_percent_conversions() {
+        for whole in 0..=100 {
+            let decimal = Decimal::new(whole, 2);
+            let percent = Percent::try_from(decimal).unwrap();
+            assert!((percent.whole() - whole as f32).abs() < 1e-4);
+            assert_eq!(decimal, Decimal::from(percent));
+        }
+        assert_eq!(
+            dec("0.0849999"),
+            Decimal::from(Percent::from_fraction(0.0849999).unwrap())
+        );
+        assert_eq!(Ok(0.0), Percent::try_from(dec("-0.0")).map(f32::from));
+        for bad in ["-0.0001", "1.0001", "100"] {
+            assert_eq!(Err(PercentError::OutOfBounds), Percent::try_from(dec(bad)));
+        }
+
+        // Typed percentages taken from an alloy build the same alloy through the decimal path
+        let alloy = AlloyData::<TinBronze>::try_new([Copper(0.915), Tin(0.085)], 2).unwrap();
+        let decimals: Vec<BaseMetal<Decimal>> = alloy
+            .percentages_typed()
+            .iter()
+            .map(|p| p.map(Decimal::from))
+            .collect();
+        assert_eq!(
+            Ok(alloy),
+            AlloyData::<TinBronze>::try_new_decimal(decimals, 2)
+        );
+    }
+
+    #[test]
+    fn test_boundary_classification() {
+        // Not representable in whole basis points, so always rejected by the decimal path
+        assert!(
+            AlloyData::<TinBronze>::try_new_decimal(
+                [Copper(dec("0.9150001")), Tin(dec("0.0849999"))],
+                1
+            )
+            .is_err_and(|e| e == InvalidPercentages)
+        );
+        assert!(
+            AlloyData::<TinBronze>::try_new_decimal([Copper(dec("0.915")), Tin(dec("0.085"))], 1)
+                .is_ok()
+        );
+        // The f32 path cannot tell these apart and accepts both with the same nuggets
+        let float_1 = AlloyData::<TinBronze>::try_new([Copper(0.9150001), Tin(0.0849999)], 10);
+        let float_2 = AlloyData::<TinBronze>::try_new([Copper(0.915), Tin(0.085)], 10);
+        assert_eq!(float_1.unwrap().nuggets(), float_2.unwrap().nuggets());
+    }
+
+    #[test]
+    fn test_range_minimum_boundary() {
+        // 0.079999999 rounds to the same f32 as 0.08, so the f32 path accepts it
+        let (copper, tin): (f32, f32) = (
+            "0.920000001".parse().unwrap(),
+            "0.079999999".parse().unwrap(),
+        );
+        assert_eq!(0.08, tin);
+        assert!(AlloyData::<TinBronze>::try_new([Copper(copper), Tin(tin)], 1).is_ok());
+        // The decimal path sees that it is below the minimum
+        assert!(
+            AlloyData::<TinBronze>::try_new_decimal(
+                [Copper(dec("0.920000001")), Tin(dec("0.079999999"))],
+                1
+            )
+            .is_err_and(|e| e == InvalidPercentages)
+        );
+        assert!(
+            AlloyData::<TinBronze>::try_new_decimal([Copper(dec("0.92")), Tin(dec("0.08"))], 1)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(
+            AlloyData::<TinBronze>::try_new_decimal([Copper(dec("0.92")), Tin(dec("0.09"))], 1)
+                .is_err_and(|e| e == InvalidPercentages)
+        );
+        assert!(
+            AlloyData::<TinBronze>::try_new_decimal([Lead(dec("0.92")), Tin(dec("0.08"))], 1)
+                .is_err_and(|e| e == UnknownConstituent(Metal::Lead))
+        );
+        assert!(
+            AlloyData::<TinBronze>::try_new_decimal([Copper(dec("0.92")), Tin(dec("0.08"))], 26)
+                .is_err_and(|e| e == TooManyIngots)
+        );
+        assert!(
+            AlloyData::<TinBronze>::try_new_decimal([Copper(dec("0.92")), Tin(dec("0.08"))], 0)
+                .is_err_and(|e| e == TooFewIngots)
+        );
+    }
+
+    #[test]
+    fn test_cross_check_with_float_path() {
+        let mixes: [&[BaseMetal<&str>]; 4] = [
+            &[Copper("0.92"), Tin("0.08")],
+            &[Copper("0.89"), Tin("0.11")],
+            &[Copper("0.60"), Zinc("0.20"), Bismuth("0.20")],
+            &[Copper("0.52"), Zinc("0.28"), Bismuth("0.20")],
+        ];
+        for mix in mixes {
+            let decimals: Vec<_> = mix.iter().map(|p| p.update_inner_value(dec(p))).collect();
+            let floats: Vec<_> = mix
+                .iter()
+                .map(|p| p.update_inner_value(p.parse::<f32>().unwrap()))
+                .collect();
+            for num_ingots in 1..=25 {
+                let (exact, float) = if mix.len() == 2 {
+                    (
+                        AlloyData::<TinBronze>::try_new_decimal(&decimals, num_ingots)
+                            .map(|a| (a.nuggets().to_vec(), a.max_ingots())),
+                        AlloyData::<TinBronze>::try_new(&floats, num_ingots)
+                            .map(|a| (a.nuggets().to_vec(), a.max_ingots())),
+                    )
+                } else {
+                    (
+                        AlloyData::<BismuthBronze>::try_new_decimal(&decimals, num_ingots)
+                            .map(|a| (a.nuggets().to_vec(), a.max_ingots())),
+                        AlloyData::<BismuthBronze>::try_new(&floats, num_ingots)
+                            .map(|a| (a.nuggets().to_vec(), a.max_ingots())),
+                    )
+                };
+                assert_eq!(float, exact, "{mix:?} at {num_ingots}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_float_truncation_matches_decimal() {
+        // 15 ingots of 59% copper is exactly 885 units (177 nuggets); f32 lands just under, so this only holds
+        // because shares are rounded to fixed point before being truncated
+        let exact = AlloyData::<BismuthBronze>::try_new_decimal(
+            [Copper(dec("0.59")), Zinc(dec("0.22")), Bismuth(dec("0.19"))],
+            15,
+        )
+        .unwrap();
+        let float =
+            AlloyData::<BismuthBronze>::try_new([Copper(0.59), Zinc(0.22), Bismuth(0.19)], 15)
+                .unwrap();
+        assert_eq!(&[Copper(177), Zinc(66), Bismuth(57)], exact.nuggets());
+        assert_eq!(exact.nuggets(), float.nuggets());
+    }
+
+    #[cfg(feature = "rational")]
+    #[test]
+    fn test_cross_check_with_rational_path() {
+        use num_rational::Ratio;
+        for (copper, tin) in [(92, 8), (91, 9), (90, 10), (89, 11), (88, 12)] {
+            let decimals = [Copper(Decimal::new(copper, 2)), Tin(Decimal::new(tin, 2))];
+            let rationals = [
+                Copper(Ratio::new(copper as i32, 100)),
+                Tin(Ratio::new(tin as i32, 100)),
+            ];
+            for num_ingots in 1..=25 {
+                assert_eq!(
+                    AlloyData::<TinBronze>::try_new_rational(rationals, num_ingots),
+                    AlloyData::<TinBronze>::try_new_decimal(decimals, num_ingots),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod constituent_range_tests {
+    use crate::ConstituentRange;
+
+    #[test]
+    fn test_clamp() {
+        let range = ConstituentRange::new(0.50, 0.70);
+        assert_eq!(0.50, range.clamp(0.10));
+        assert_eq!(0.70, range.clamp(0.90));
+        assert_eq!(0.60, range.clamp(0.60));
+
+        let range = ConstituentRange::new(8_i32, 12);
+        assert_eq!(8, range.clamp(-3));
+        assert_eq!(12, range.clamp(100));
+        assert_eq!(9, range.clamp(9));
+    }
+
+    #[test]
+    fn test_span_and_midpoint() {
+        let range = ConstituentRange::new(0.25_f64, 0.75);
+        assert_eq!(0.5, range.span());
+        assert_eq!(0.5, range.midpoint());
+
+        let range = ConstituentRange::new(50_i32, 70);
+        assert_eq!(20, range.span());
+        assert_eq!(60, range.midpoint());
+        // Odd spans round down towards the minimum
+        let range = ConstituentRange::new(45_u8, 55);
+        assert_eq!(50, range.midpoint());
+        let range = ConstituentRange::new(45_u8, 56);
+        assert_eq!(50, range.midpoint());
+    }
+
+    #[test]
+    fn test_lerp() {
+        let range = ConstituentRange::new(0.08_f32, 0.12);
+        assert_eq!(range.min, range.lerp(0.0));
+        assert_eq!(range.max, range.lerp(1.0));
+        assert_eq!(range.midpoint(), range.lerp(0.5));
+        // Out of bounds t is clamped
+        assert_eq!(range.min, range.lerp(-1.0));
+        assert_eq!(range.max, range.lerp(2.0));
+
+        let range = ConstituentRange::new(880_u16, 920);
+        assert_eq!(880, range.lerp(0.0));
+        assert_eq!(890, range.lerp(0.25));
+        assert_eq!(920, range.lerp(1.0));
+        for span in 0..50 {
+            let range = ConstituentRange::new(10_i64, 10 + span);
+            assert_eq!(range.midpoint(), range.lerp(0.5));
+        }
+        assert_eq!(880, ConstituentRange::new(880_u16, 920).lerp(f32::NAN));
+    }
+
+    #[test]
+    fn test_wide_ranges() {
+        let range = ConstituentRange::new(-2_i32, i32::MAX);
+        assert_eq!(i32::MAX, range.span());
+        assert_eq!((i32::MAX - 2) / 2, range.midpoint());
+        assert_eq!(-2, range.lerp(0.0));
+        assert_eq!(i32::MAX, range.lerp(1.0));
+        assert_eq!(range.midpoint(), range.lerp(0.5));
+
+        let range = ConstituentRange::new(i64::MIN, i64::MAX);
+        assert_eq!(i64::MAX, range.span());
+        assert_eq!(-1, range.midpoint());
+        assert_eq!(i64::MIN, range.lerp(0.0));
+        assert_eq!(i64::MAX, range.lerp(1.0));
+        assert_eq!(range.midpoint(), range.lerp(0.5));
+        assert_eq!(i64::MIN / 2 - 1, range.lerp(0.25));
+
+        let range = ConstituentRange::new(0_u32, u32::MAX);
+        assert_eq!(u32::MAX / 2, range.midpoint());
+        assert_eq!(u32::MAX, range.lerp(1.0));
+        assert_eq!(range.midpoint(), range.lerp(0.5));
+    }
+
+    #[test]
+    fn test_degenerate_ranges() {
+        let range = ConstituentRange::new(0.40_f32, 0.40);
+        assert_eq!(0.0, range.span());
+        assert_eq!(0.40, range.midpoint());
+        assert_eq!(0.40, range.lerp(0.3));
+        assert_eq!(0.40, range.clamp(0.9));
+
+        let range = ConstituentRange::new(7_u32, 7);
+        assert_eq!(0, range.span());
+        assert_eq!(7, range.midpoint());
+        assert_eq!(7, range.lerp(1.0));
+        assert_eq!(7, range.clamp(0));
+    }
+}
+
+#[cfg(test)]
+mod constituents_tests {
+    use crate::prelude::*;
+
+    fn assert_aligned(alloy: &Alloy, ranges: &[BaseMetal<crate::ConstituentRange<f32>>]) {
+        let views: Vec<_> = alloy.constituents().collect();
+        assert_eq!(ranges.len(), views.len());
+        assert_eq!(ranges.len(), alloy.constituents().len());
+        for (i, view) in views.iter().enumerate() {
+            assert_eq!(ranges[i].metal(), view.metal);
+            assert_eq!(*ranges[i], view.range);
+            assert_eq!(alloy.percentages()[i].metal(), view.metal);
+            assert_eq!(*alloy.percentages()[i], view.percent);
+            assert_eq!(alloy.nuggets()[i].metal(), view.metal);
+            assert_eq!(*alloy.nuggets()[i], view.nuggets);
+        }
+    }
+
+    #[test]
+    fn test_defaults_aligned() {
+        for kind in Alloys::ALL {
+            assert_aligned(&kind.get_default(), kind.percentage_ranges());
+        }
+    }
+
+    #[test]
+    fn test_aligned_after_shuffled_set_percentages() {
+        for kind in Alloys::ALL {
+            let mut alloy = kind.get_default();
+            let expected = alloy.percentages().to_vec();
+            let shuffled: Vec<_> = expected.iter().rev().copied().collect();
+            alloy.set_percentages(&shuffled).unwrap();
+            alloy.set_num_ingots(alloy.max_ingots()).unwrap();
+            assert_eq!(expected, alloy.percentages());
+            assert_aligned(&alloy, kind.percentage_ranges());
+        }
+    }
+
+    #[test]
+    fn test_aligned_after_reordered_input() {
+        let mut alloy = Alloys::BismuthBronze.get_default();
+        alloy
+            .set_percentages([Bismuth(0.15), Zinc(0.25), Copper(0.60)])
+            .unwrap();
+        let views: Vec<_> = alloy.constituents().collect();
+        assert_eq!(Metal::Copper, views[0].metal);
+        assert_eq!(0.60, views[0].percent);
+        assert_eq!(Metal::Zinc, views[1].metal);
+        assert_eq!(0.25, views[1].percent);
+        assert_eq!(Metal::Bismuth, views[2].metal);
+        assert_eq!(0.15, views[2].percent);
+        assert_eq!(20, views.iter().map(|v| v.nuggets).sum::<i32>());
+    }
+}
+
+#[cfg(test)]
+mod accessor_tests {
+    use crate::AlloyError;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_alloys_all_kind_round_trip() {
+        for kind in Alloys::ALL {
+            let alloy = kind.get_default();
+            assert!(kind == alloy.kind());
+            assert_eq!(kind.name(), alloy.name());
+        }
+    }
+
+    #[test]
+    fn test_metal_all_names() {
+        for metal in Metal::ALL {
+            let base_metal = [
+                Nickel(()),
+                Copper(()),
+                Zinc(()),
+                Silver(()),
+                Tin(()),
+                Gold(()),
+                Lead(()),
+                Bismuth(()),
+            ]
+            .into_iter()
+            .find(|b| b.metal() == metal)
+            .unwrap();
+            assert_eq!(base_metal.name(), metal.name());
+        }
+        assert_eq!(8, Metal::ALL.len());
+    }
+
+    #[test]
+    fn test_error_codes_unique() {
+        let errors = [
+            AlloyError::InvalidPercentages,
+            AlloyError::InvalidConstituentAmounts,
+            AlloyError::InvalidBaseMetals,
+            AlloyError::UnknownConstituent(Metal::Copper),
+            AlloyError::DuplicateConstituent(Metal::Copper),
+            AlloyError::MissingConstituent(Metal::Copper),
+            AlloyError::WrongConstituentCount {
+                expected: 2,
+                got: 3,
+            },
+            AlloyError::InvalidValues,
+            AlloyError::TooManyIngots,
+            AlloyError::TooFewIngots,
+            AlloyError::NoSolution,
+            AlloyError::MixedPercentScales,
+            AlloyError::InvalidDefinition(crate::definition::AlloyDefinitionError::NoConstituents),
+        ];
+        let mut codes: Vec<_> = errors.iter().map(AlloyError::code).collect();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(errors.len(), codes.len());
+    }
+}
+
+#[cfg(test)]
+mod generic_access_tests {
+    use crate::prelude::*;
+
+    fn total<T: AlloyType>(alloy: &AlloyData<T>) -> i32 {
+        alloy.alloy_type().as_ref().iter().map(|n| **n).sum()
+    }
+
+    fn check<T: AlloyType>(alloy: AlloyData<T>) {
+        assert_eq!(alloy.nuggets(), alloy.alloy_type().as_ref());
+        assert_eq!(20 * alloy.num_ingots(), total(&alloy));
+    }
+
+    #[test]
+    fn test_generic_total_for_all_alloys() {
+        check(AlloyData::<TinBronze>::default());
+        check(AlloyData::<BismuthBronze>::default());
+        check(AlloyData::<BlackBronze>::default());
+        check(AlloyData::<Brass>::default());
+        check(AlloyData::<Molybdochalkos>::default());
+        check(AlloyData::<LeadSolder>::default());
+        check(AlloyData::<SilverSolder>::default());
+        check(AlloyData::<Electrum>::default());
+        check(AlloyData::<Cupronickel>::default());
+
+        let alloy =
+            AlloyData::<BismuthBronze>::try_new([Copper(0.59), Zinc(0.22), Bismuth(0.19)], 9)
+                .unwrap();
+        assert_eq!(180, total(&alloy));
+    }
+
+    #[test]
+    fn test_alloy_enum_delegates_to_alloy_data() {
+        let data = AlloyData::<Brass>::try_new([Copper(0.65), Zinc(0.35)], 12).unwrap();
+        let mut alloy = Alloys::Brass.get_default();
+        alloy.set_percentages([Copper(0.65), Zinc(0.35)]).unwrap();
+        alloy.set_num_ingots(12).unwrap();
+        assert_eq!(data.nuggets(), alloy.nuggets());
+        assert_eq!(data.percentages(), alloy.percentages());
+        assert_eq!(data.num_ingots(), alloy.num_ingots());
+        assert_eq!(data.max_ingots(), alloy.max_ingots());
+    }
+}
+
+#[cfg(test)]
+mod range_lookup_tests {
+    use crate::AlloyError::*;
+    use crate::prelude::*;
+
+    /// Counts of (valid, invalid percentages, invalid base metals) across a sweep of inputs
+    fn validation_fingerprint<T: AlloyType>() -> (usize, usize, usize, i64) {
+        let ranges = T::percentage_ranges();
+        let foreign = Metal::ALL
+            .into_iter()
+            .find(|m| ranges.iter().all(|r| r.metal() != *m))
+            .unwrap();
+        let to_base_metal = |metal: Metal, value: f32| {
+            [
+                Nickel(value),
+                Copper(value),
+                Zinc(value),
+                Silver(value),
+                Tin(value),
+                Gold(value),
+                Lead(value),
+                Bismuth(value),
+            ]
+            .into_iter()
+            .find(|b| b.metal() == metal)
+            .unwrap()
+        };
+        let metals: Vec<Metal> = ranges.iter().map(|r| r.metal()).collect();
+        let mut inputs: Vec<Vec<BaseMetal<f32>>> = Vec::new();
+        let steps: Vec<f32> = (0..=200).map(|i| i as f32 * 0.005).collect();
+        let mut push_all = |values: Vec<f32>| {
+            let forward: Vec<_> = metals
+                .iter()
+                .zip(&values)
+                .map(|(m, v)| to_base_metal(*m, *v))
+                .collect();
+            let mut reversed = forward.clone();
+            reversed.reverse();
+            let mut duplicate = forward.clone();
+            duplicate[1] = to_base_metal(metals[0], values[1]);
+            let mut unknown = forward.clone();
+            unknown[1] = to_base_metal(foreign, values[1]);
+            inputs.extend([forward, reversed, duplicate, unknown]);
+        };
+        for a in &steps {
+            if metals.len() == 2 {
+                push_all(vec![*a, 1.0 - a]);
+            } else {
+                for b in &steps {
+                    push_all(vec![*a, *b, 1.0 - a - b]);
+                }
+            }
+        }
+
+        let (mut valid, mut percentages, mut base_metals, mut checksum) = (0, 0, 0, 0);
+        for input in inputs {
+            match T::check_valid_percentages(&input) {
+                Ok(p) => {
+                    valid += 1;
+                    for (i, p) in p.as_ref().iter().enumerate() {
+                        assert_eq!(metals[i], p.metal());
+                        checksum += (**p * 1000.0).round() as i64 * (i as i64 + 1);
+                    }
+                }
+                Err(InvalidPercentages) => percentages += 1,
+                Err(
+                    UnknownConstituent(_)
+                    | DuplicateConstituent(_)
+                    | MissingConstituent(_)
+                    | WrongConstituentCount { .. },
+                ) => base_metals += 1,
+                Err(e) => panic!("unexpected error {e:?}"),
+            }
+        }
+        (valid, percentages, base_metals, checksum)
+    }
+
+    #[test]
+    fn test_validation_outcomes_unchanged() {
+        let fingerprints = [
+            validation_fingerprint::<TinBronze>(),
+            validation_fingerprint::<BismuthBronze>(),
+            validation_fingerprint::<BlackBronze>(),
+            validation_fingerprint::<Brass>(),
+            validation_fingerprint::<Molybdochalkos>(),
+            validation_fingerprint::<LeadSolder>(),
+            validation_fingerprint::<SilverSolder>(),
+            validation_fingerprint::<Electrum>(),
+            validation_fingerprint::<Cupronickel>(),
+        ];
+        // Recorded before check_base_metal switched from positional to metal-keyed lookup
+        let expected = [
+            (16, 770, 18, 17560),
+            (798, 144324, 16482, 1236900),
+            (556, 147782, 13266, 754330),
+            (40, 724, 40, 53900),
+            (16, 770, 18, 17560),
+            (42, 720, 42, 63000),
+            (42, 720, 42, 60900),
+            (80, 644, 80, 119800),
+            (40, 722, 42, 51900),
+        ];
+        assert_eq!(expected, fingerprints);
+    }
+
+    #[test]
+    fn test_range_of_present_and_absent() {
+        for alloy in Alloys::ALL {
+            let ranges = alloy.percentage_ranges();
+            let default = alloy.get_default();
+            for metal in Metal::ALL {
+                let expected = ranges.iter().find(|r| r.metal() == metal).map(|r| **r);
+                assert_eq!(
+                    expected,
+                    alloy.range_of(metal),
+                    "{} {metal:?}",
+                    alloy.name()
+                );
+                assert_eq!(
+                    expected,
+                    default.range_of(metal),
+                    "{} {metal:?}",
+                    alloy.name()
+                );
+            }
+            assert_eq!(
+                ranges.len(),
+                Metal::ALL
+                    .into_iter()
+                    .filter(|m| alloy.range_of(*m).is_some())
+                    .count()
+            );
+        }
+    }
+
+    #[test]
+    fn test_range_of_generic() {
+        assert_eq!(
+            Some(crate::ConstituentRange::new(0.10, 0.20)),
+            AlloyData::<BismuthBronze>::range_of(Metal::Bismuth)
+        );
+        assert_eq!(None, AlloyData::<BismuthBronze>::range_of(Metal::Tin));
+        assert_eq!(
+            Some(crate::ConstituentRange::new(0.25, 0.35)),
+            Cupronickel::range_of(Metal::Nickel)
+        );
+        assert_eq!(None, Electrum::range_of(Metal::Copper));
+    }
+}
+
+#[cfg(test)]
+mod constituent_metals_tests {
+    use crate::alloy::alloy_types::AlloyType;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_constituent_metals() {
+        use Metal as M;
+        let expected: [&[Metal]; 9] = [
+            &[M::Copper, M::Tin],
+            &[M::Copper, M::Zinc, M::Bismuth],
+            &[M::Copper, M::Gold, M::Silver],
+            &[M::Copper, M::Zinc],
+            &[M::Lead, M::Copper],
+            &[M::Tin, M::Lead],
+            &[M::Tin, M::Silver],
+            &[M::Gold, M::Silver],
+            &[M::Copper, M::Nickel],
+        ];
+        for (alloy, metals) in Alloys::ALL.into_iter().zip(expected) {
+            assert_eq!(metals, alloy.constituent_metals(), "{}", alloy.name());
+            assert_eq!(metals, alloy.get_default().constituent_metals());
+        }
+        assert_eq!(expected[0], TinBronze::constituent_metals());
+        assert_eq!(expected[8], AlloyData::<Cupronickel>::constituent_metals());
+    }
+
+    #[test]
+    fn test_constituent_metals_match_ranges() {
+        for alloy in Alloys::ALL {
+            let from_ranges: Vec<Metal> = alloy
+                .percentage_ranges()
+                .iter()
+                .map(|r| r.metal())
+                .collect();
+            assert_eq!(from_ranges, alloy.constituent_metals(), "{}", alloy.name());
+            let default = alloy.get_default();
+            let from_nuggets: Vec<Metal> = default.nuggets().iter().map(|n| n.metal()).collect();
+            let from_percentages: Vec<Metal> =
+                default.percentages().iter().map(|p| p.metal()).collect();
+            assert_eq!(from_ranges, from_nuggets);
+            assert_eq!(from_ranges, from_percentages);
+        }
+    }
+
+    fn check_count<T: AlloyType>(kind: Alloys) {
+        assert_eq!(
+            T::percentage_ranges().len(),
+            T::constituent_count(),
+            "{kind}"
+        );
+        assert_eq!(T::constituent_metals().len(), T::constituent_count());
+        assert_eq!(T::N_CONSTITUENTS, T::constituent_count());
+        assert_eq!(kind.num_constituents(), T::constituent_count());
+        assert_eq!(kind.constituent_metals(), T::constituent_metals());
+    }
+
+    #[test]
+    fn test_constituent_count_matches_ranges() {
+        for_each_alloy!(check_count(Alloys));
+    }
+}
+
+#[cfg(test)]
+mod shape_tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_num_constituents() {
+        let expected = [2, 3, 3, 2, 2, 2, 2, 2, 2];
+        for (alloy, n) in Alloys::ALL.into_iter().zip(expected) {
+            assert_eq!(n, alloy.num_constituents(), "{}", alloy.name());
+            assert_eq!(alloy.percentage_ranges().len(), alloy.num_constituents());
+            assert_eq!(alloy.constituent_metals().len(), alloy.num_constituents());
+            assert_eq!(n, alloy.get_default().nuggets().len());
+            assert_eq!(n == 2, alloy.is_binary());
+            assert_eq!(n == 3, alloy.is_ternary());
+        }
+    }
+
+    #[test]
+    fn test_n_constituents_const() {
+        assert_eq!(
+            TinBronze::N_CONSTITUENTS,
+            Alloys::TinBronze.num_constituents()
+        );
+        assert_eq!(
+            BismuthBronze::N_CONSTITUENTS,
+            Alloys::BismuthBronze.num_constituents()
+        );
+        assert_eq!(
+            BlackBronze::N_CONSTITUENTS,
+            Alloys::BlackBronze.num_constituents()
+        );
+        assert_eq!(Brass::N_CONSTITUENTS, Alloys::Brass.num_constituents());
+        assert_eq!(
+            Molybdochalkos::N_CONSTITUENTS,
+            Alloys::Molybdochalkos.num_constituents()
+        );
+        assert_eq!(
+            LeadSolder::N_CONSTITUENTS,
+            Alloys::LeadSolder.num_constituents()
+        );
+        assert_eq!(
+            SilverSolder::N_CONSTITUENTS,
+            Alloys::SilverSolder.num_constituents()
+        );
+        assert_eq!(
+            Electrum::N_CONSTITUENTS,
+            Alloys::Electrum.num_constituents()
+        );
+        assert_eq!(
+            Cupronickel::N_CONSTITUENTS,
+            Alloys::Cupronickel.num_constituents()
+        );
+    }
+}
+
+#[cfg(test)]
+mod canonical_eq_tests {
+    use crate::prelude::*;
+    use std::collections::HashMap;
+    use std::hash::{BuildHasher, RandomState};
+
+    fn parse(s: &str) -> f32 {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_equal_after_rounding() {
+        let noisy = parse("0.9000001");
+        assert_ne!(0.92, noisy);
+        let a = AlloyData::<TinBronze>::try_new([Copper(0.90), Tin(0.10)], 5).unwrap();
+        let b = AlloyData::<TinBronze>::try_new([Copper(noisy), Tin(1.0 - noisy)], 5).unwrap();
+        assert_ne!(a.percentages(), b.percentages());
+        assert_eq!(a, b);
+        assert_eq!(std::cmp::Ordering::Equal, a.cmp(&b));
+        let hasher = RandomState::new();
+        assert_eq!(hasher.hash_one(&a), hasher.hash_one(&b));
+
+        let a = Alloys::TinBronze
+            .try_new([Copper(0.90), Tin(0.10)], 5)
+            .unwrap();
+        let b = Alloys::TinBronze
+            .try_new([Tin(1.0 - noisy), Copper(noisy)], 5)
+            .unwrap();
+        assert_eq!(a, b);
+        assert_eq!(hasher.hash_one(&a), hasher.hash_one(&b));
+    }
+
+    #[test]
+    fn test_different_values_not_equal() {
+        let a = AlloyData::<Brass>::try_new([Copper(0.7), Zinc(0.3)], 5).unwrap();
+        let b = AlloyData::<Brass>::try_new([Copper(0.7), Zinc(0.3)], 6).unwrap();
+        let c = AlloyData::<Brass>::try_new([Copper(0.69), Zinc(0.31)], 5).unwrap();
+        assert_ne!(a, b);
+        assert_ne!(a, c);
+        assert!(a < b);
+        assert!(c < a);
+        assert_ne!(
+            Alloys::Brass.get_default(),
+            Alloys::Cupronickel.get_default()
+        );
+    }
+
+    #[test]
+    fn test_hashmap_round_trip() {
+        let mut recipes = HashMap::new();
+        for num_ingots in 1..=5 {
+            let alloy =
+                AlloyData::<Electrum>::try_new([Gold(0.5), Silver(0.5)], num_ingots).unwrap();
+            let nuggets = alloy.nuggets().to_vec();
+            recipes.insert(alloy, nuggets);
+        }
+        assert_eq!(5, recipes.len());
+        let noisy = parse("0.5000001");
+        let key = AlloyData::<Electrum>::try_new([Gold(noisy), Silver(1.0 - noisy)], 3).unwrap();
+        assert_eq!(Some(&vec![Gold(30), Silver(30)]), recipes.get(&key));
+
+        let mut alloys = HashMap::new();
+        for alloy in Alloys::ALL {
+            alloys.insert(alloy.get_default(), alloy.name());
+        }
+        for alloy in Alloys::ALL {
+            assert_eq!(Some(&alloy.name()), alloys.get(&alloy.get_default()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod pure_metal_tests {
+    use crate::AlloyError::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_nuggets_for_every_metal() {
+        for metal in Metal::ALL {
+            for num_ingots in 1..=25 {
+                let pure = PureMetal::new(metal, num_ingots).unwrap();
+                assert_eq!(&[metal.with_value(20 * num_ingots)], pure.nuggets());
+                assert_eq!(num_ingots, pure.num_ingots());
+                assert_eq!(metal, pure.metal());
+                assert_eq!(metal.name(), pure.name());
+            }
+        }
+    }
+
+    #[test]
+    fn test_max_ingots() {
+        let pure = PureMetal::new(Metal::Copper, 1).unwrap();
+        assert_eq!(25, pure.max_ingots());
+        assert!(PureMetal::new(Metal::Copper, 25).is_ok());
+        assert_eq!(Err(TooManyIngots), PureMetal::new(Metal::Copper, 26));
+        assert_eq!(Err(TooFewIngots), PureMetal::new(Metal::Copper, 0));
+
+        let mut pure = PureMetal::new(Metal::Gold, 4).unwrap();
+        assert_eq!(Err(TooManyIngots), pure.set_num_ingots(26));
+        assert_eq!(&[Gold(80)], pure.nuggets());
+        pure.set_num_ingots(10).unwrap();
+        assert_eq!(&[Gold(200)], pure.nuggets());
+    }
+
+    #[test]
+    fn test_trivial_ranges() {
+        for metal in Metal::ALL {
+            let pure = PureMetal::new(metal, 2).unwrap();
+            let full = crate::ConstituentRange::new(1.0, 1.0);
+            assert_eq!(&[metal.with_value(full)], pure.percentage_ranges());
+            assert_eq!(&[metal.with_value(1.0)], pure.percentages());
+            assert_eq!(&[metal], pure.constituent_metals());
+            for other in Metal::ALL {
+                let expected = (other == metal).then_some(full);
+                assert_eq!(expected, pure.range_of(other));
+            }
+            let views: Vec<_> = pure.constituents().collect();
+            assert_eq!(1, views.len());
+            assert_eq!(metal, views[0].metal);
+            assert_eq!(1.0, views[0].percent);
+            assert_eq!(40, views[0].nuggets);
+        }
+    }
+
+    #[test]
+    fn test_any_alloy() {
+        let mut boxed: Box<dyn crate::AnyAlloy> =
+            Box::new(PureMetal::new(Metal::Silver, 2).unwrap());
+        assert_eq!("Silver", boxed.name());
+        assert_eq!(&[Silver(1.0)], boxed.percentages());
+        assert_eq!(25, boxed.max_ingots());
+        boxed.set_num_ingots(5).unwrap();
+        assert_eq!(&[Silver(100)], boxed.nuggets());
+        assert_eq!(Err(TooManyIngots), boxed.set_num_ingots(26));
+        assert_eq!(5, boxed.num_ingots());
+
+        boxed.set_percentages(&[Silver(1.0)]).unwrap();
+        assert_eq!(
+            Err(InvalidPercentages),
+            boxed.set_percentages(&[Silver(0.9)])
+        );
+        assert_eq!(Err(InvalidPercentages), boxed.set_percentages(&[Gold(1.0)]));
+        assert_eq!(
+            Err(InvalidPercentages),
+            boxed.set_percentages(&[Silver(0.5), Gold(0.5)])
+        );
+        assert_eq!(&[Silver(100)], boxed.nuggets());
+    }
+
+    #[test]
+    fn test_display() {
+        let pure = PureMetal::new(Metal::Bismuth, 25).unwrap();
+        assert_eq!(
+            "Bismuth × 25 ingots (max 25 ingots): Bismuth 100% = 500 nuggets",
+            pure.to_string()
+        );
+        assert_eq!(
+            "Bismuth × 25 ingots (max 25 ingots)\n- Bismuth 100% = 500 nuggets",
+            format!("{pure:#}")
+        );
+        for metal in Metal::ALL {
+            let pure = PureMetal::new(metal, 1).unwrap();
+            assert!(pure.to_string().starts_with(metal.name()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod default_values_tests {
+    use crate::prelude::*;
+
+    fn check<T: AlloyType + Default>(alloy: Alloys) {
+        let data = AlloyData::<T>::default();
+        assert_eq!(
+            data.percentages(),
+            alloy.default_percentages(),
+            "{}",
+            alloy.name()
+        );
+        assert_eq!(
+            data.num_ingots(),
+            alloy.default_ingots(),
+            "{}",
+            alloy.name()
+        );
+        assert_eq!(T::default_percentages(), alloy.default_percentages());
+        assert_eq!(T::default_ingots(), alloy.default_ingots());
+        assert_eq!(data.percentages(), alloy.get_default().percentages());
+        assert!(T::check_valid_percentages(alloy.default_percentages()).is_ok());
+        let built = AlloyData::<T>::try_new(alloy.default_percentages(), alloy.default_ingots());
+        assert!(built.is_ok_and(|built| built == data));
+    }
+
+    #[test]
+    fn test_default_percentages_match_default_impls() {
+        for_each_alloy!(check(Alloys));
+    }
+}
+
+#[cfg(test)]
+mod convert_tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_keeps_shared_metal_in_range() {
+        let alloy = Alloys::BlackBronze
+            .try_new([Copper(0.70), Gold(0.16), Silver(0.14)], 8)
+            .unwrap();
+        let conversion = alloy.convert_to(Alloys::Brass);
+        assert_eq!(&[Copper(0.70), Zinc(0.30)], conversion.alloy.percentages());
+        assert_eq!(8, conversion.alloy.num_ingots());
+        assert_eq!(None, conversion.clamped_from);
+    }
+
+    #[test]
+    fn test_tin_bronze_to_brass() {
+        // Tin Bronze copper (88-92%) never falls in Brass' range (60-70%) so Brass' defaults are used
+        let alloy = Alloys::TinBronze
+            .try_new([Copper(0.90), Tin(0.10)], 12)
+            .unwrap();
+        let conversion = alloy.convert_to(Alloys::Brass);
+        assert_eq!(
+            Alloys::Brass.default_percentages(),
+            conversion.alloy.percentages()
+        );
+        assert_eq!(12, conversion.alloy.num_ingots());
+        assert_eq!(None, conversion.clamped_from);
+
+        let alloy = Alloys::LeadSolder
+            .try_new([Tin(0.55), Lead(0.45)], 3)
+            .unwrap();
+        let conversion = alloy.convert_to(Alloys::SilverSolder);
+        assert_eq!(&[Tin(0.55), Silver(0.45)], conversion.alloy.percentages());
+    }
+
+    #[test]
+    fn test_clamps_ingots() {
+        let alloy = Alloys::TinBronze
+            .try_new([Copper(0.92), Tin(0.08)], 20)
+            .unwrap();
+        let conversion = alloy.convert_to(Alloys::BlackBronze);
+        assert_eq!(Alloys::BlackBronze, conversion.alloy.kind());
+        assert_eq!(conversion.alloy.max_ingots(), conversion.alloy.num_ingots());
+        assert!(conversion.alloy.num_ingots() < 20);
+        assert_eq!(Some(20), conversion.clamped_from);
+    }
+
+    #[test]
+    fn test_drops_shared_metals_that_cannot_balance() {
+        // Both copper and zinc are shared, but 65% + 20% leaves nothing valid for Brass' zinc
+        let alloy = Alloys::BismuthBronze
+            .try_new([Copper(0.65), Zinc(0.20), Bismuth(0.15)], 2)
+            .unwrap();
+        let conversion = alloy.convert_to(Alloys::Brass);
+        assert_eq!(&[Copper(0.65), Zinc(0.35)], conversion.alloy.percentages());
+    }
+
+    #[test]
+    fn test_every_pair_is_valid() {
+        for from in Alloys::ALL {
+            for to in Alloys::ALL {
+                let alloy = from.get_default();
+                let conversion = alloy.convert_to(to);
+                assert_eq!(to, conversion.alloy.kind());
+                assert!(to.try_new(conversion.alloy.percentages(), 1).is_ok());
+            }
+        }
+        let alloy = Alloys::Cupronickel
+            .try_new([Copper(0.70), Nickel(0.30)], 6)
+            .unwrap();
+        assert_eq!(alloy, alloy.convert_to(Alloys::Cupronickel).alloy);
+    }
+}
+
+#[cfg(test)]
+mod contents_tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_exactly_valid_pile() {
+        let alloy = AlloyData::<BismuthBronze>::try_new([Copper(0.6), Zinc(0.2), Bismuth(0.2)], 13)
+            .unwrap();
+        let report = AlloyData::<BismuthBronze>::classify_contents(alloy.nuggets());
+        assert!(report.in_range);
+        assert_eq!(13, report.ingots);
+        assert_eq!(0, report.leftover_units);
+        assert_eq!(&[Copper(0.6), Zinc(0.2), Bismuth(0.2)], &*report.ratio);
+
+        // Boundaries are inclusive
+        let report = Alloys::TinBronze.classify_contents(&[Tin(12), Copper(88)]);
+        assert!(report.in_range);
+        assert_eq!(5, report.ingots);
+        assert_eq!(&[Copper(0.88), Tin(0.12)], &*report.ratio);
+    }
+
+    #[test]
+    fn test_pile_outside_range() {
+        // 87% copper is 1% below Tin Bronze's minimum
+        let report = Alloys::TinBronze.classify_contents(&[Copper(87), Tin(13)]);
+        assert!(!report.in_range);
+        assert_eq!(0, report.ingots);
+        assert_eq!(500, report.leftover_units);
+
+        let report = Alloys::TinBronze.classify_contents(&[Copper(90), Tin(9), Zinc(1)]);
+        assert!(!report.in_range);
+        assert_eq!(3, report.ratio.len());
+        assert_eq!(Metal::Zinc, report.ratio[2].metal());
+
+        let report = Alloys::BismuthBronze.classify_contents(&[Copper(14), Zinc(6)]);
+        assert!(!report.in_range);
+        assert_eq!(Bismuth(0.0), report.ratio[2]);
+
+        assert!(!Alloys::Brass.classify_contents(&[]).in_range);
+    }
+
+    #[test]
+    fn test_matching_contents() {
+        for alloy in Alloys::ALL {
+            // 100 nuggets split by whole percents is exactly 5 ingots of the default mix
+            let pile: Vec<_> = alloy
+                .default_percentages()
+                .iter()
+                .map(|p| p.update_inner_value((**p * 100.0).round() as i32))
+                .collect();
+            let matches = Alloys::matching_contents(&pile);
+            // No two alloys share the same set of metals, so a pile can only ever match one
+            assert_eq!(1, matches.len(), "{}", alloy.name());
+            assert_eq!(alloy, matches[0].0);
+            assert_eq!(5, matches[0].1.ingots);
+        }
+        assert!(Alloys::matching_contents(&[Copper(50), Tin(50)]).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod crucible_tests {
+    use crate::crucible::{self, Capacity, CapacityError};
+    use crate::prelude::*;
+
+    #[test]
+    fn test_four_full_stacks_fit() {
+        let stacks = [
+            (Metal::Copper, 128),
+            (Metal::Copper, 128),
+            (Metal::Tin, 128),
+            (Metal::Tin, 128),
+        ];
+        assert_eq!(Ok(()), crucible::fits(&stacks));
+        assert_eq!(Ok(()), crucible::fits(&[]));
+    }
+
+    #[test]
+    fn test_fifth_stack() {
+        let stacks = [
+            (Metal::Copper, 128),
+            (Metal::Copper, 128),
+            (Metal::Tin, 128),
+            (Metal::Tin, 128),
+            (Metal::Zinc, 1),
+        ];
+        assert_eq!(
+            Err(CapacityError::TooManyStacks {
+                stacks: 5,
+                excess: 1
+            }),
+            crucible::fits(&stacks)
+        );
+    }
+
+    #[test]
+    fn test_oversized_and_empty_stacks() {
+        assert_eq!(
+            Err(CapacityError::StackTooLarge {
+                index: 1,
+                metal: Metal::Gold,
+                nuggets: 129,
+                excess: 1
+            }),
+            crucible::fits(&[(Metal::Silver, 20), (Metal::Gold, 129)])
+        );
+        assert_eq!(
+            Err(CapacityError::EmptyStack {
+                index: 0,
+                metal: Metal::Lead
+            }),
+            crucible::fits(&[(Metal::Lead, 0)])
+        );
+    }
+
+    #[test]
+    fn test_fits_in_capacity() {
+        let modded = Capacity {
+            slots: 2,
+            max_units_per_slot: 1000,
+        };
+        assert_eq!(200, modded.max_stack_size());
+        assert_eq!(
+            Ok(()),
+            crucible::fits_in(modded, &[(Metal::Copper, 200), (Metal::Tin, 200)])
+        );
+        assert_eq!(
+            Err(CapacityError::StackTooLarge {
+                index: 0,
+                metal: Metal::Copper,
+                nuggets: 201,
+                excess: 1
+            }),
+            crucible::fits_in(modded, &[(Metal::Copper, 201)])
+        );
+        assert_eq!(
+            Err(CapacityError::TooManyStacks {
+                stacks: 3,
+                excess: 1
+            }),
+            crucible::fits_in(modded, &[(Metal::Copper, 1); 3])
+        );
+        let empty = Capacity {
+            slots: 0,
+            max_units_per_slot: 0,
+        };
+        assert_eq!(Ok(()), crucible::fits_in(empty, &[]));
+        assert!(crucible::fits_in(empty, &[(Metal::Copper, 1)]).is_err());
+    }
+
+    #[test]
+    fn test_fits_agrees_with_max_ingots() {
+        for kind in Alloys::ALL {
+            let mut alloy = kind.get_default();
+            alloy.set_num_ingots(alloy.max_ingots()).unwrap();
+            let stacks: Vec<_> = alloy
+                .nuggets()
+                .iter()
+                .flat_map(|n| {
+                    let (full, rest) = (**n / 128, **n % 128);
+                    std::iter::repeat_n((n.metal(), 128), full as usize)
+                        .chain((rest > 0).then_some((n.metal(), rest)))
+                })
+                .collect();
+            assert_eq!(Ok(()), crucible::fits(&stacks), "{kind}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod inventory_tests {
+    use crate::inventory::Inventory;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_fully_covered() {
+        let alloy = AlloyData::<BismuthBronze>::default();
+        let inventory = Inventory::from_iter([Copper(64), Zinc(4), Bismuth(10), Gold(3)]);
+        let shortfall = alloy.shortfall(&inventory);
+        assert!(shortfall.is_satisfiable);
+        assert_eq!(&[Copper(0), Zinc(0), Bismuth(0)], &*shortfall.missing);
+    }
+
+    #[test]
+    fn test_partially_covered() {
+        let alloy = Alloys::TinBronze
+            .try_new([Copper(0.90), Tin(0.10)], 10)
+            .unwrap();
+        assert_eq!(&[Copper(180), Tin(20)], alloy.nuggets());
+        let mut inventory = Inventory::new();
+        inventory.add(Copper(170));
+        inventory.add(Copper(8));
+        inventory.set(Tin(6));
+        let shortfall = alloy.shortfall(&inventory);
+        assert!(!shortfall.is_satisfiable);
+        assert_eq!(&[Copper(2), Tin(14)], &*shortfall.missing);
+    }
+
+    #[test]
+    fn test_missing_metal_entirely() {
+        let alloy = AlloyData::<Electrum>::default();
+        let shortfall = alloy.shortfall(&Inventory::from_iter([Gold(100)]));
+        assert!(!shortfall.is_satisfiable);
+        assert_eq!(&[Gold(0), Silver(12)], &*shortfall.missing);
+
+        let shortfall = alloy.shortfall(&Inventory::new());
+        assert_eq!(alloy.nuggets(), &*shortfall.missing);
+    }
+}
+
+#[cfg(test)]
+mod accuracy_tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_one_ingot_diverges() {
+        let alloy = AlloyData::<TinBronze>::try_new([Copper(0.92), Tin(0.08)], 1).unwrap();
+        assert_eq!(vec![Copper(0.90), Tin(0.10)], alloy.actual_percentages());
+        let accuracy = alloy.accuracy();
+        assert_eq!(Metal::Copper, accuracy[0].0);
+        assert!((accuracy[0].1 + 0.02).abs() < 1e-6);
+        assert!((accuracy[1].1 - 0.02).abs() < 1e-6);
+        assert!(!alloy.is_faithful(0.01));
+
+        let alloy = Alloys::Electrum
+            .try_new([Gold(0.47), Silver(0.53)], 1)
+            .unwrap();
+        assert!(!alloy.is_faithful(0.01));
+        let alloy = Alloys::Cupronickel
+            .try_new([Copper(0.73), Nickel(0.27)], 1)
+            .unwrap();
+        assert!(!alloy.is_faithful(0.01));
+    }
+
+    #[test]
+    fn test_max_ingots_near_zero_divergence() {
+        let mixes: [(Alloys, &[BaseMetal<f32>]); 4] = [
+            (Alloys::Cupronickel, &[Copper(0.73), Nickel(0.27)]),
+            (Alloys::SilverSolder, &[Tin(0.55), Silver(0.45)]),
+            (Alloys::Electrum, &[Gold(0.45), Silver(0.55)]),
+            (Alloys::TinBronze, &[Copper(0.92), Tin(0.08)]),
+        ];
+        for (alloy, mix) in mixes {
+            let max_ingots = alloy.try_new(mix, 1).unwrap().max_ingots();
+            let alloy = alloy.try_new(mix, max_ingots).unwrap();
+            assert!(max_ingots >= 20);
+            assert!(
+                alloy.is_faithful(0.005),
+                "{} {:?}",
+                alloy.name(),
+                alloy.accuracy()
+            );
+        }
+    }
+
+    #[test]
+    fn test_actual_percentages_sum_to_one() {
+        for alloy in Alloys::ALL {
+            let alloy = alloy.get_default();
+            let sum: f32 = alloy.actual_percentages().iter().map(|p| **p).sum();
+            assert!((sum - 1.0).abs() < 1e-6);
+            assert_eq!(alloy.constituent_metals().len(), alloy.accuracy().len());
+        }
+    }
+}
+
+#[cfg(test)]
+mod max_ingots_cache_tests {
+    use crate::alloy::max_ingots_counter;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_set_num_ingots_reuses_cache() {
+        let mut alloy =
+            AlloyData::<BismuthBronze>::try_new([Copper(0.6), Zinc(0.25), Bismuth(0.15)], 1)
+                .unwrap();
+        let max_ingots = alloy.max_ingots();
+        let before = max_ingots_counter::get();
+        for _ in 0..3 {
+            for num_ingots in (1..=max_ingots).chain([max_ingots + 1, 0]) {
+                let result = alloy.set_num_ingots(num_ingots);
+                let fresh = AlloyData::<BismuthBronze>::try_new(alloy.percentages(), num_ingots);
+                match fresh {
+                    Ok(fresh) => {
+                        assert_eq!(Ok(()), result);
+                        assert_eq!(fresh.nuggets(), alloy.nuggets());
+                        assert_eq!(fresh.max_ingots(), alloy.max_ingots());
+                    }
+                    Err(e) => assert_eq!(Err(e), result),
+                }
+            }
+        }
+        // Only the fresh constructions above searched for the max
+        assert_eq!(
+            3 * (max_ingots as usize + 1),
+            max_ingots_counter::get() - before
+        );
+    }
+
+    #[test]
+    fn test_set_percentages_refreshes_cache() {
+        let mut alloy = Alloys::TinBronze.get_default();
+        assert_eq!(20, alloy.max_ingots());
+        let before = max_ingots_counter::get();
+        alloy.set_percentages([Copper(0.88), Tin(0.12)]).unwrap();
+        assert_eq!(1, max_ingots_counter::get() - before);
+        assert_eq!(21, alloy.max_ingots());
+        alloy.set_num_ingots(21).unwrap();
+        alloy.set_num_ingots(4).unwrap();
+        assert_eq!(1, max_ingots_counter::get() - before);
+        let fresh = AlloyData::<TinBronze>::try_new([Copper(0.88), Tin(0.12)], 4).unwrap();
+        assert_eq!(fresh.nuggets(), alloy.nuggets());
+    }
+}
+
+#[cfg(test)]
+mod solve_tests {
+    use crate::AlloyError::*;
+    use crate::prelude::*;
+    use crate::{planner, ranking};
+
+    fn assert_valid<T: AlloyType>(alloy: &AlloyData<T>) {
+        let report = AlloyData::<T>::classify_contents(alloy.nuggets());
+        assert!(report.in_range, "{:?}", alloy.nuggets());
+        assert_eq!(alloy.num_ingots(), report.ingots);
+        assert_eq!(0, report.leftover_units);
+        assert!(alloy.num_ingots() <= alloy.max_ingots());
+        assert!(T::check_valid_percentages(alloy.percentages()).is_ok());
+    }
+
+    /// Creating the alloy again from its own percentages and number of ingots must give back the same nuggets
+    fn assert_rederives(alloy: &Alloy) {
+        let created = alloy
+            .kind()
+            .try_new(alloy.percentages(), alloy.num_ingots())
+            .unwrap();
+        assert_eq!(alloy.nuggets(), created.nuggets(), "{alloy:?}");
+        assert_eq!(alloy.max_ingots(), created.max_ingots(), "{alloy:?}");
+        let mut updated = alloy.clone();
+        updated.set_num_ingots(alloy.num_ingots()).unwrap();
+        assert_eq!(alloy.nuggets(), updated.nuggets(), "{alloy:?}");
+    }
+
+    fn check_rederives<T: AlloyType + Default>()
+    where
+        Alloy: From<AlloyData<T>>,
+    {
+        for metal in T::constituent_metals() {
+            for nuggets in [1, 12, 64] {
+                for alloy in AlloyData::<T>::solve_with_fixed(*metal, nuggets).unwrap_or_default() {
+                    assert_rederives(&alloy.into());
+                }
+                for ingots in [None, Some(7)] {
+                    if let Ok(alloy) = AlloyData::<T>::nearest_to_target(*metal, nuggets, ingots) {
+                        assert_rederives(&alloy.into());
+                    }
+                }
+            }
+        }
+        let kind = Alloy::from(AlloyData::<T>::default()).kind();
+        for num_ingots in 1..=25 {
+            for percentages in kind.boundary_mixes() {
+                if let Ok(mix) = AlloyData::<T>::nearest_achievable(&percentages, num_ingots) {
+                    assert_rederives(&mix.alloy.into());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_results_rederive() {
+        let alloy = AlloyData::<TinBronze>::nearest_to_target(Metal::Tin, 12, Some(7)).unwrap();
+        assert_eq!(&[Copper(128), Tin(12)], alloy.nuggets());
+        let mut locked = alloy.clone();
+        locked.set_num_ingots(7).unwrap();
+        assert_eq!(Tin(12), locked.nuggets()[1]);
+        for_each_alloy!(check_rederives);
+        for kind in Alloys::iter() {
+            for metal in kind.constituent_metals() {
+                if let Ok(batches) = planner::consume_exact(kind, *metal, 75) {
+                    batches.iter().for_each(assert_rederives);
+                }
+            }
+        }
+        for metal in Metal::ALL {
+            for entry in ranking::by_metal_efficiency(metal, 10) {
+                assert_rederives(&entry.recipe);
+            }
+        }
+    }
+
+    #[test]
+    fn test_two_tin_forces_one_ingot() {
+        let solutions = AlloyData::<TinBronze>::solve_with_fixed(Metal::Tin, 2).unwrap();
+        assert_eq!(1, solutions.len());
+        assert_eq!(1, solutions[0].num_ingots());
+        assert_eq!(&[Copper(0.90), Tin(0.10)], solutions[0].percentages());
+        assert_valid(&solutions[0]);
+    }
+
+    #[test]
+    fn test_sixty_four_tin() {
+        // 64 tin at 8-12% needs at least 27 ingots, which can never fit in the crucible
+        assert_eq!(
+            Err(NoSolution),
+            AlloyData::<TinBronze>::solve_with_fixed(Metal::Tin, 64)
+        );
+
+        let solutions = AlloyData::<LeadSolder>::solve_with_fixed(Metal::Tin, 64).unwrap();
+        assert_eq!(
+            vec![6, 7],
+            solutions.iter().map(|s| s.num_ingots()).collect::<Vec<_>>()
+        );
+        for solution in &solutions {
+            assert_eq!(Tin(64), solution.nuggets()[0]);
+            assert_valid(solution);
+        }
+
+        let solutions = AlloyData::<BismuthBronze>::solve_with_fixed(Metal::Zinc, 64).unwrap();
+        assert!(solutions.len() > 1);
+        for solution in &solutions {
+            assert_eq!(Zinc(64), solution.nuggets()[1]);
+            assert_valid(solution);
+        }
+    }
+
+    #[test]
+    fn test_solve_errors() {
+        assert_eq!(
+            Err(UnknownConstituent(Metal::Tin)),
+            AlloyData::<Brass>::solve_with_fixed(Metal::Tin, 10)
+        );
+        assert_eq!(
+            Err(NoSolution),
+            AlloyData::<Brass>::solve_with_fixed(Metal::Zinc, 0)
+        );
+    }
+}
+
+#[cfg(test)]
+mod nearest_target_tests {
+    use crate::AlloyError::*;
+    use crate::prelude::*;
+
+    fn stacks(nuggets: &[i32]) -> i32 {
+        nuggets.iter().map(|n| (n + 127) / 128).sum()
+    }
+
+    /// Closest achievable zinc count for brass, worked out directly from its 30-40% zinc range
+    fn brass_min_distance(target: i32, ingots: Option<i32>) -> i32 {
+        let counts = ingots.map_or(1..=25, |n| n..=n);
+        counts
+            .flat_map(|n| (6 * n..=8 * n).map(move |zinc| (zinc, 20 * n - zinc)))
+            .filter(|(zinc, copper)| stacks(&[*zinc, *copper]) <= 4)
+            .map(|(zinc, _)| (zinc - target).abs())
+            .min()
+            .unwrap()
+    }
+
+    /// Closest achievable bismuth count for bismuth bronze, worked out directly from its ranges
+    /// Whether creating the alloy from the nugget ratios, rounded up to 7 decimal places with the last taking the rest,
+    /// gives back the same nuggets. Splits that fill whole stacks with a ratio that does not end within 7 decimals,
+    /// such as 256 of 480, are counted as an extra stack when the max ingots are worked out from the ratio.
+    fn rederives<T: AlloyType>(split: &[i32], num_ingots: i32) -> bool {
+        let total = f64::from(split.iter().sum::<i32>());
+        let mut scaled: Vec<f64> = split
+            .iter()
+            .map(|n| (f64::from(*n) * 1e7 / total).ceil())
+            .collect();
+        let rest: f64 = scaled[..split.len() - 1].iter().sum();
+        scaled[split.len() - 1] = 1e7 - rest;
+        let percentages: Vec<BaseMetal<f32>> = T::constituent_metals()
+            .iter()
+            .zip(&scaled)
+            .map(|(m, s)| m.with_value((s / 1e7) as f32))
+            .collect();
+        AlloyData::<T>::try_new(&percentages, num_ingots)
+            .is_ok_and(|a| a.nuggets().iter().map(|n| **n).eq(split.iter().copied()))
+    }
+
+    fn bismuth_bronze_min_distance(target: i32) -> i32 {
+        let mut best = i32::MAX;
+        for n in 1..=25 {
+            // Totals are multiples of 20 so every bound divides exactly
+            let total: i32 = 20 * n;
+            for zinc in total * 2 / 10..=total * 3 / 10 {
+                for bismuth in total / 10..=total * 2 / 10 {
+                    let copper = total - zinc - bismuth;
+                    if copper * 10 >= total * 5
+                        && copper * 10 <= total * 7
+                        && stacks(&[copper, zinc, bismuth]) <= 4
+                        && rederives::<BismuthBronze>(&[copper, zinc, bismuth], n)
+                    {
+                        best = best.min((bismuth - target).abs());
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn test_brass_targets() {
+        for target in [1, 7, 50, 51, 99, 127, 170, 250] {
+            let alloy = AlloyData::<Brass>::nearest_to_target(Metal::Zinc, target, None).unwrap();
+            let zinc = *alloy.nuggets()[1];
+            assert_eq!(
+                brass_min_distance(target, None),
+                (zinc - target).abs(),
+                "{target}"
+            );
+            assert!(AlloyData::<Brass>::classify_contents(alloy.nuggets()).in_range);
+        }
+        for (target, ingots) in [(50, 5), (3, 2), (200, 20)] {
+            let alloy =
+                AlloyData::<Brass>::nearest_to_target(Metal::Zinc, target, Some(ingots)).unwrap();
+            let zinc = *alloy.nuggets()[1];
+            assert_eq!(ingots, alloy.num_ingots());
+            assert_eq!(
+                brass_min_distance(target, Some(ingots)),
+                (zinc - target).abs()
+            );
+        }
+    }
+
+    #[test]
+    fn test_bismuth_bronze_targets() {
+        for target in [1, 3, 37, 64, 101] {
+            let alloy = AlloyData::<BismuthBronze>::nearest_to_target(Metal::Bismuth, target, None)
+                .unwrap();
+            let bismuth = *alloy.nuggets()[2];
+            assert_eq!(
+                bismuth_bronze_min_distance(target),
+                (bismuth - target).abs()
+            );
+            assert!(AlloyData::<BismuthBronze>::classify_contents(alloy.nuggets()).in_range);
+        }
+    }
+
+    #[test]
+    fn test_tie_breaking() {
+        // 6 and 8 zinc are both one away from 7 but only fewer of the metal wins the tie
+        let alloy = AlloyData::<Brass>::nearest_to_target(Metal::Zinc, 7, Some(1)).unwrap();
+        assert_eq!(&[Copper(13), Zinc(7)], alloy.nuggets());
+        let alloy = AlloyData::<Brass>::nearest_to_target(Metal::Zinc, 9, Some(1)).unwrap();
+        assert_eq!(&[Copper(12), Zinc(8)], alloy.nuggets());
+        let alloy = AlloyData::<Brass>::nearest_to_target(Metal::Zinc, 12, None).unwrap();
+        assert_eq!(2, alloy.num_ingots());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Err(UnknownConstituent(Metal::Gold)),
+            AlloyData::<Brass>::nearest_to_target(Metal::Gold, 5, None)
+        );
+        assert_eq!(
+            Err(TooManyIngots),
+            AlloyData::<Brass>::nearest_to_target(Metal::Zinc, 5, Some(26))
+        );
+        assert_eq!(
+            Err(TooFewIngots),
+            AlloyData::<Brass>::nearest_to_target(Metal::Zinc, 5, Some(0))
+        );
+    }
+}
+
+#[cfg(test)]
+mod planner_tests {
+    use crate::crucible::{self, Capacity};
+    use crate::planner::{self, PlanError};
+    use crate::prelude::*;
+
+    fn tin_used(batches: &[Alloy]) -> i32 {
+        batches
+            .iter()
+            .map(|b| {
+                let report = b.kind().classify_contents(b.nuggets());
+                assert!(report.in_range);
+                assert_eq!(0, report.leftover_units);
+                b.nuggets()
+                    .iter()
+                    .filter(|n| n.metal() == Metal::Tin)
+                    .map(|n| **n)
+                    .sum::<i32>()
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_exactly_solvable() {
+        let batches = planner::consume_exact(Alloys::TinBronze, Metal::Tin, 37).unwrap();
+        assert_eq!(1, batches.len());
+        assert_eq!(37, tin_used(&batches));
+        assert!(
+            planner::consume_exact(Alloys::TinBronze, Metal::Tin, 0)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_impossible_count() {
+        // The smallest Tin Bronze batches use 2 and 4 tin, so 3 tin is only ever 2 used with 1 left over
+        match planner::consume_exact(Alloys::TinBronze, Metal::Tin, 3) {
+            Err(PlanError::Inexact { closest, batches }) => {
+                assert_eq!(2, closest);
+                assert_eq!(1, batches.len());
+                assert_eq!(2, tin_used(&batches));
+            }
+            other => panic!("expected an inexact plan, got {other:?}"),
+        }
+        assert_eq!(
+            Err(PlanError::NotAConstituent),
+            planner::consume_exact(Alloys::Brass, Metal::Tin, 10)
+        );
+    }
+
+    #[test]
+    fn test_needs_two_batches() {
+        // A single Tin Bronze batch can use at most 50 tin before the copper needs a fifth slot
+        let batches = planner::consume_exact(Alloys::TinBronze, Metal::Tin, 50).unwrap();
+        assert_eq!(1, batches.len());
+        let batches = planner::consume_exact(Alloys::TinBronze, Metal::Tin, 51).unwrap();
+        assert_eq!(2, batches.len());
+        assert_eq!(51, tin_used(&batches));
+        let batches = planner::consume_exact(Alloys::LeadSolder, Metal::Tin, 300).unwrap();
+        assert_eq!(2, batches.len());
+        assert_eq!(300, tin_used(&batches));
+    }
+
+    #[test]
+    fn test_capacity() {
+        assert_eq!(
+            planner::consume_exact(Alloys::TinBronze, Metal::Tin, 50),
+            planner::consume_exact_in(Alloys::TinBronze, Metal::Tin, 50, Capacity::VANILLA)
+        );
+        // Two slots only hold a single stack of copper, so the tin is spread over more batches
+        let small = Capacity {
+            slots: 2,
+            ..Capacity::VANILLA
+        };
+        let batches = planner::consume_exact_in(Alloys::TinBronze, Metal::Tin, 50, small).unwrap();
+        assert!(batches.len() > 1);
+        assert_eq!(50, tin_used(&batches));
+        for batch in &batches {
+            let stacks = small.stacks(batch.nuggets().iter().map(|n| (n.metal(), **n)));
+            assert_eq!(Ok(()), crucible::fits_in(small, &stacks));
+        }
+        let empty = Capacity {
+            slots: 0,
+            max_units_per_slot: 0,
+        };
+        assert!(matches!(
+            planner::consume_exact_in(Alloys::TinBronze, Metal::Tin, 2, empty),
+            Err(PlanError::Inexact { closest: 0, .. })
+        ));
+    }
+}
+
+#[cfg(test)]
+mod efficiency_tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_tin_bronze_table() {
+        let table = AlloyData::<TinBronze>::efficiency_table([Copper(0.92), Tin(0.08)]).unwrap();
+        let leftover: Vec<f32> = table.iter().map(|e| e.leftover_units).collect();
+        let expected = [4.0, 8.0, 2.0, 6.0, 0.0].repeat(4);
+        assert_eq!(expected, leftover);
+        for (i, e) in table.iter().enumerate() {
+            assert_eq!(i as i32 + 1, e.num_ingots);
+            assert_eq!(20 * e.num_ingots, e.total_nuggets);
+            assert_eq!(20.0, e.nuggets_per_ingot);
+        }
+        assert_eq!(
+            vec![5, 10, 15, 20],
+            AlloyData::<TinBronze>::most_efficient_counts([Tin(0.08), Copper(0.92)]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_other_mixes() {
+        // 50/50 splits evenly into 20 nuggets at every count
+        assert_eq!(
+            (1..=25).collect::<Vec<_>>(),
+            AlloyData::<SilverSolder>::most_efficient_counts([Tin(0.5), Silver(0.5)]).unwrap()
+        );
+        assert!(AlloyData::<Brass>::efficiency_table([Copper(0.5), Zinc(0.5)]).is_err());
+    }
+}
+
+#[cfg(test)]
+mod ranking_tests {
+    use crate::prelude::*;
+    use crate::ranking;
+
+    #[test]
+    fn test_copper_at_ten_ingots() {
+        // 200 nuggets at each alloy's minimum copper percentage
+        let expected = [
+            (Alloys::Molybdochalkos, 16),
+            (Alloys::BismuthBronze, 100),
+            (Alloys::Brass, 120),
+            (Alloys::Cupronickel, 130),
+            (Alloys::BlackBronze, 136),
+            (Alloys::TinBronze, 176),
+        ];
+        let ranking = ranking::by_metal_efficiency(Metal::Copper, 10);
+        assert_eq!(expected.len(), ranking.len());
+        for (entry, (alloy, copper)) in ranking.iter().zip(expected) {
+            assert_eq!(alloy, entry.alloy);
+            assert_eq!(alloy, entry.recipe.kind());
+            assert!(entry.reached);
+            assert_eq!(10, entry.recipe.num_ingots());
+            assert_eq!(10.0 / copper as f32, entry.ingots_per_nugget);
+            let used: i32 = entry
+                .recipe
+                .nuggets()
+                .iter()
+                .filter(|n| n.metal() == Metal::Copper)
+                .map(|n| **n)
+                .sum();
+            assert_eq!(copper, used);
+        }
+    }
+
+    #[test]
+    fn test_unreachable_count_is_flagged() {
+        // Tin Bronze can use at most 21 ingots before its copper needs a fifth slot
+        let ranking = ranking::by_metal_efficiency(Metal::Tin, 25);
+        let tin_bronze = ranking
+            .iter()
+            .find(|e| e.alloy == Alloys::TinBronze)
+            .unwrap();
+        assert!(!tin_bronze.reached);
+        assert!(tin_bronze.recipe.num_ingots() < 25);
+        assert!(ranking.iter().any(|e| e.reached));
+        assert!(
+            ranking::by_metal_efficiency(Metal::Copper, 30)
+                .iter()
+                .all(|e| !e.reached)
+        );
+    }
+}
+
+#[cfg(test)]
+mod shopping_tests {
+    use crate::crucible::Capacity;
+    use crate::prelude::*;
+    use crate::shopping;
+
+    #[test]
+    fn test_aggregate_shared_copper() {
+        let recipes = [
+            Alloys::TinBronze
+                .try_new([Copper(0.90), Tin(0.10)], 10)
+                .unwrap(),
+            Alloys::Brass
+                .try_new([Copper(0.65), Zinc(0.35)], 4)
+                .unwrap(),
+            Alloys::BlackBronze
+                .try_new([Copper(0.80), Gold(0.10), Silver(0.10)], 5)
+                .unwrap(),
+        ];
+        let list = shopping::aggregate(&recipes);
+        let copper: i32 = recipes
+            .iter()
+            .flat_map(|r| r.nuggets())
+            .filter(|n| n.metal() == Metal::Copper)
+            .map(|n| **n)
+            .sum();
+        assert_eq!(180 + 52 + 80, copper);
+        assert_eq!(copper, list.totals.get(Metal::Copper));
+        assert_eq!(20, list.totals.get(Metal::Tin));
+        assert_eq!(28, list.totals.get(Metal::Zinc));
+        assert_eq!(10, list.totals.get(Metal::Gold));
+        assert_eq!(10, list.totals.get(Metal::Silver));
+
+        assert_eq!(3, list.recipes.len());
+        assert_eq!(Alloys::Brass, list.recipes[1].alloy);
+        assert_eq!(4, list.recipes[1].num_ingots);
+        assert_eq!(recipes[1].nuggets(), &*list.recipes[1].nuggets);
+        assert_eq!(Copper((2, 56)), list.stacks()[0]);
+    }
+
+    #[test]
+    fn test_markdown() {
+        let recipes = [Alloys::TinBronze.get_default(), Alloys::Brass.get_default()];
+        let expected = "\
+| Metal | Nuggets | Stacks |
+| --- | --- | --- |
+| Copper | 32 | 0 × 128 + 32 |
+| Zinc | 6 | 0 × 128 + 6 |
+| Tin | 2 | 0 × 128 + 2 |
+
+### Tin Bronze × 1 ingots
+- Copper: 18
+- Tin: 2
+
+### Brass × 1 ingots
+- Copper: 14
+- Zinc: 6
+";
+        assert_eq!(expected, shopping::aggregate(&recipes).to_string());
+        assert!(shopping::aggregate(&[]).totals.iter().next().is_none());
+    }
+
+    #[test]
+    fn test_capacity() {
+        let recipes = [Alloys::TinBronze
+            .try_new([Copper(0.90), Tin(0.10)], 10)
+            .unwrap()];
+        let small_stacks = Capacity {
+            max_units_per_slot: 250,
+            ..Capacity::VANILLA
+        };
+        let list = shopping::aggregate_in(&recipes, small_stacks);
+        assert_eq!(vec![Copper((3, 30)), Tin((0, 20))], list.stacks());
+        assert!(list.to_string().contains("| Copper | 180 | 3 × 50 + 30 |"));
+        assert_eq!(shopping::aggregate(&recipes).totals, list.totals);
+        // A capacity too small for a single nugget leaves everything loose instead of dividing by zero
+        let empty = Capacity {
+            slots: 0,
+            max_units_per_slot: 0,
+        };
+        let list = shopping::aggregate_in(&recipes, empty);
+        assert_eq!(vec![Copper((0, 180)), Tin((0, 20))], list.stacks());
+    }
+}
+
+#[cfg(test)]
+mod split_tests {
+    use crate::AlloyError::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_even_split() {
+        let alloy = AlloyData::<TinBronze>::try_new([Copper(0.9), Tin(0.1)], 20).unwrap();
+        let parts = alloy.split(4).unwrap();
+        assert_eq!(4, parts.len());
+        for part in &parts {
+            assert_eq!(5, part.num_ingots());
+            assert_eq!(alloy.percentages(), part.percentages());
+            assert_eq!(&[Copper(90), Tin(10)], part.nuggets());
+        }
+        assert_eq!(vec![Copper(0), Tin(0)], alloy.split_overhead(&parts));
+        assert_eq!(alloy, alloy.split(1).unwrap().remove(0));
+    }
+
+    #[test]
+    fn test_uneven_split() {
+        let alloy = Alloys::TinBronze
+            .try_new([Copper(0.92), Tin(0.08)], 19)
+            .unwrap();
+        let parts = alloy.split(3).unwrap();
+        let counts: Vec<i32> = parts.iter().map(|p| p.num_ingots()).collect();
+        assert_eq!(vec![7, 6, 6], counts);
+        assert_eq!(19, counts.iter().sum::<i32>());
+        // Rounding each part separately uses more tin than rounding the whole batch
+        let overhead = alloy.split_overhead(&parts).unwrap();
+        assert_eq!(0, overhead.iter().map(|n| **n).sum::<i32>());
+        assert_eq!(Tin(1), overhead[1]);
+        assert_eq!(Copper(-1), overhead[0]);
+    }
+
+    #[test]
+    fn test_split_errors() {
+        let alloy = AlloyData::<Brass>::try_new([Copper(0.7), Zinc(0.3)], 3).unwrap();
+        assert_eq!(Err(TooFewIngots), alloy.split(4));
+        assert_eq!(Err(InvalidValues), alloy.split(0));
+        assert!(Alloys::Brass.get_default().split(2).is_err());
+    }
+
+    #[test]
+    fn test_overhead_of_other_alloys() {
+        let alloy = Alloys::BismuthBronze.get_default();
+        let tin_bronze = Alloys::TinBronze.get_default();
+        let error = alloy
+            .split_overhead(&[alloy.clone(), tin_bronze.clone()])
+            .unwrap_err();
+        assert_eq!(Alloys::BismuthBronze, error.expected);
+        assert_eq!(Alloys::TinBronze, error.actual);
+        assert_eq!(tin_bronze, error.alloy);
+        assert!(tin_bronze.split_overhead(&[alloy]).is_err());
+    }
+}
+
+#[cfg(test)]
+mod percentages_input_tests {
+    use crate::AlloyError::*;
+    use crate::Percentages;
+    use crate::prelude::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_from_map() {
+        let map = HashMap::from([
+            (Metal::Bismuth, 0.15),
+            (Metal::Copper, 0.6),
+            (Metal::Zinc, 0.25),
+        ]);
+        let expected =
+            AlloyData::<BismuthBronze>::try_new([Copper(0.6), Zinc(0.25), Bismuth(0.15)], 5)
+                .unwrap();
+        assert_eq!(
+            expected,
+            AlloyData::<BismuthBronze>::try_new_from_map(&map, 5).unwrap()
+        );
+        assert_eq!(
+            expected,
+            AlloyData::<BismuthBronze>::try_new(Percentages::from(&map), 5).unwrap()
+        );
+        let count = crate::IngotCount::new(5).unwrap();
+        assert_eq!(
+            expected,
+            AlloyData::<BismuthBronze>::try_new_from_map(&map, count).unwrap()
+        );
+        assert_eq!(
+            Err(TooManyIngots),
+            AlloyData::<BismuthBronze>::try_new_from_map(&map, 26)
+        );
+    }
+
+    #[test]
+    fn test_from_iterator() {
+        let percentages: Percentages = [
+            (Metal::Zinc, 0.25),
+            (Metal::Bismuth, 0.15),
+            (Metal::Copper, 0.6),
+        ]
+        .into_iter()
+        .collect();
+        let alloy = AlloyData::<BismuthBronze>::try_new(&percentages, 5).unwrap();
+        assert_eq!(
+            &[Copper(0.6), Zinc(0.25), Bismuth(0.15)],
+            alloy.percentages()
+        );
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        // A missing metal is rejected with the same error as the slice input
+        let map = HashMap::from([(Metal::Copper, 0.7), (Metal::Zinc, 0.3)]);
+        assert_eq!(
+            Err(MissingConstituent(Metal::Bismuth)),
+            AlloyData::<BismuthBronze>::try_new_from_map(&map, 5)
+        );
+        assert_eq!(
+            AlloyData::<BismuthBronze>::try_new([Copper(0.7), Zinc(0.3)], 5),
+            AlloyData::<BismuthBronze>::try_new_from_map(&map, 5)
+        );
+        let repeated: Percentages = [
+            (Metal::Copper, 0.5),
+            (Metal::Copper, 0.25),
+            (Metal::Zinc, 0.25),
+        ]
+        .into_iter()
+        .collect();
+        assert!(AlloyData::<BismuthBronze>::try_new(repeated, 5).is_err());
+    }
+}
+
+#[cfg(test)]
+mod non_copy_base_metal_tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_string_labels() {
+        let alloy = AlloyData::<TinBronze>::try_new([Copper(0.92), Tin(0.08)], 7).unwrap();
+        let labels: Vec<BaseMetal<String>> = alloy
+            .nuggets()
+            .iter()
+            .map(|n| n.map(|count| format!("{count}x {}", n.name())))
+            .collect();
+        assert_eq!(
+            vec![
+                Copper(String::from("128x Copper")),
+                Tin(String::from("12x Tin"))
+            ],
+            labels
+        );
+        assert_eq!("Copper", labels[0].name());
+        assert_eq!("128x Copper", labels[0].as_str());
+        assert_eq!(
+            Copper(&String::from("128x Copper")),
+            labels[0].as_value_ref()
+        );
+    }
+
+    #[test]
+    fn test_owned_values() {
+        let mut breakdown = Zinc(vec![3, 3, 2]);
+        breakdown.update(vec![4, 4]);
+        assert_eq!(Metal::Zinc, breakdown.metal());
+        assert_eq!(Zinc(8), breakdown.clone().map(|v| v.iter().sum::<i32>()));
+        assert_eq!(vec![4, 4], breakdown.into_inner());
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use crate::AlloyError::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_round_trip() {
+        let alloy =
+            AlloyData::<BismuthBronze>::try_new([Copper(0.6), Zinc(0.25), Bismuth(0.15)], 4)
+                .unwrap();
+        let json = serde_json::to_string(&alloy).unwrap();
+        let back: AlloyData<BismuthBronze> = serde_json::from_str(&json).unwrap();
+        assert_eq!(alloy, back);
+        assert_eq!(alloy.nuggets(), back.nuggets());
+        assert_eq!(alloy.max_ingots(), back.max_ingots());
+
+        let alloy = Alloys::Electrum.get_default();
+        let json = serde_json::to_string(&alloy).unwrap();
+        assert_eq!(alloy, serde_json::from_str::<Alloy>(&json).unwrap());
+    }
+
+    #[test]
+    fn test_pure_metal_round_trip() {
+        for metal in Metal::ALL {
+            let pure = PureMetal::new(metal, 3).unwrap();
+            let json = serde_json::to_string(&pure).unwrap();
+            assert_eq!(pure, serde_json::from_str::<PureMetal>(&json).unwrap());
+        }
+
+        let json = serde_json::to_string(&PureMetal::new(Metal::Gold, 2).unwrap()).unwrap();
+        assert_eq!(
+            r#"{"percentages":[{"Gold":1.0}],"num_ingots":2,"nuggets":[{"Gold":40}],"max_ingots":25}"#,
+            json
+        );
+        let json = r#"{"percentages":[{"Gold":1.0}],"num_ingots":2}"#;
+        assert_eq!(
+            PureMetal::new(Metal::Gold, 2).unwrap(),
+            serde_json::from_str(json).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_pure_metal_rejects_invalid_state() {
+        for json in [
+            r#"{"percentages":[{"Gold":0.5}],"num_ingots":2}"#,
+            r#"{"percentages":[{"Gold":0.5},{"Silver":0.5}],"num_ingots":2}"#,
+            r#"{"percentages":[],"num_ingots":2}"#,
+            r#"{"percentages":[{"Gold":1.0}],"num_ingots":26}"#,
+            r#"{"percentages":[{"Gold":1.0}],"num_ingots":2,"nuggets":[{"Gold":41}]}"#,
+            r#"{"percentages":[{"Gold":1.0}],"num_ingots":2,"max_ingots":30}"#,
+        ] {
+            assert!(serde_json::from_str::<PureMetal>(json).is_err(), "{json}");
+        }
+    }
+
+    #[test]
+    fn test_derived_fields_optional() {
+        let json = r#"{"percentages":[{"Copper":0.9},{"Tin":0.1}],"num_ingots":2}"#;
+        let alloy: AlloyData<TinBronze> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            AlloyData::<TinBronze>::try_new([Copper(0.9), Tin(0.1)], 2).unwrap(),
+            alloy
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_state() {
+        // Out of range percentages
+        let json = r#"{"percentages":[{"Copper":0.5},{"Tin":0.5}],"num_ingots":2}"#;
+        assert!(serde_json::from_str::<AlloyData<TinBronze>>(json).is_err());
+        // Wrong metals for the alloy
+        let json = r#"{"percentages":[{"Copper":0.9},{"Zinc":0.1}],"num_ingots":2}"#;
+        assert!(serde_json::from_str::<AlloyData<TinBronze>>(json).is_err());
+        // Too many ingots
+        let json = r#"{"percentages":[{"Copper":0.9},{"Tin":0.1}],"num_ingots":1000}"#;
+        assert!(serde_json::from_str::<AlloyData<TinBronze>>(json).is_err());
+        // Inflated max ingots
+        let json =
+            r#"{"percentages":[{"Copper":0.9},{"Tin":0.1}],"num_ingots":2,"max_ingots":999}"#;
+        assert!(serde_json::from_str::<AlloyData<TinBronze>>(json).is_err());
+        // Nuggets that do not match the percentages
+        let json = r#"{"percentages":[{"Copper":0.9},{"Tin":0.1}],"num_ingots":2,"nuggets":[{"Copper":1},{"Tin":1}]}"#;
+        assert!(serde_json::from_str::<AlloyData<TinBronze>>(json).is_err());
+        // Unknown fields
+        let json = r#"{"percentages":[{"Copper":0.9},{"Tin":0.1}],"num_ingots":2,"bonus":1}"#;
+        assert!(serde_json::from_str::<AlloyData<TinBronze>>(json).is_err());
+        // Invalid alloy inside the unified enum
+        let json = r#"{"Brass":{"percentages":[{"Copper":0.1},{"Zinc":0.9}],"num_ingots":1}}"#;
+        assert!(serde_json::from_str::<Alloy>(json).is_err());
+    }
+
+    #[test]
+    fn test_error_message() {
+        let json = r#"{"percentages":[{"Copper":0.5},{"Tin":0.5}],"num_ingots":2}"#;
+        let err = serde_json::from_str::<AlloyData<TinBronze>>(json).unwrap_err();
+        assert!(err.to_string().contains(&InvalidPercentages.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod infer_tests {
+    use crate::AlloyError::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_infer_single_alloy() {
+        assert_eq!(
+            vec![Alloys::TinBronze],
+            Alloys::infer(&[Tin(0.08), Copper(0.92)])
+        );
+        assert_eq!(
+            vec![Alloys::Electrum],
+            Alloys::infer(&[Gold(0.5), Silver(0.5)])
+        );
+    }
+
+    #[test]
+    fn test_infer_every_default() {
+        // The vanilla alloys all have distinct sets of metals so each default mix is only valid for its own alloy
+        for alloy in Alloys::ALL {
+            assert_eq!(vec![alloy], Alloys::infer(alloy.default_percentages()));
+        }
+    }
+
+    #[test]
+    fn test_infer_none() {
+        assert!(Alloys::infer(&[Copper(0.5), Tin(0.5)]).is_empty());
+        assert!(Alloys::infer(&[Copper(0.5), Lead(0.3), Tin(0.2)]).is_empty());
+        assert!(Alloys::infer(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_suggestion() {
+        let mut alloy = Alloys::Brass.get_default();
+        let before = alloy.percentages().to_vec();
+        let suggestion = alloy
+            .set_percentages_or_suggest([Copper(0.92), Tin(0.08)])
+            .unwrap_err();
+        assert_eq!(InvalidPercentages, suggestion.error);
+        assert_eq!(vec![Alloys::TinBronze], suggestion.valid_for);
+        assert_eq!(before, alloy.percentages());
+
+        // Out of range for the alloy itself does not suggest it
+        let suggestion = alloy
+            .set_percentages_or_suggest([Copper(0.9), Zinc(0.1)])
+            .unwrap_err();
+        assert!(suggestion.valid_for.is_empty());
+        assert_eq!(InvalidPercentages.to_string(), suggestion.to_string());
+
+        assert_eq!(
+            Ok(()),
+            alloy.set_percentages_or_suggest([Copper(0.65), Zinc(0.35)])
+        );
+    }
+}
+
+#[cfg(test)]
+mod normalize_tests {
+    use crate::AlloyError::*;
+    use crate::prelude::*;
+    use crate::{CreateOptions, normalize_sum};
+
+    const NORMALIZE: CreateOptions = CreateOptions {
+        normalize_to_one: true,
+        max_correction: 0.02,
+    };
+
+    fn total(percentages: &[BaseMetal<f32>]) -> f32 {
+        percentages.iter().map(|p| **p).sum()
+    }
+
+    #[test]
+    fn test_normalize_sum() {
+        let normalized =
+            normalize_sum::<BismuthBronze>([Copper(0.6), Bismuth(0.19), Zinc(0.2)]).unwrap();
+        assert!((total(&normalized) - 1.0).abs() < 1e-6);
+        assert_eq!(Metal::Bismuth, normalized[2].metal());
+        assert!(*normalized[0] > 0.6);
+        assert_eq!(
+            Ok([Copper(0.0), Tin(0.0)]),
+            normalize_sum::<TinBronze>([Tin(0.0), Copper(0.0)])
+        );
+        assert_eq!(
+            Err(crate::AlloyError::MissingConstituent(Metal::Tin)),
+            normalize_sum::<TinBronze>([Copper(1.0)])
+        );
+    }
+
+    #[test]
+    fn test_sum_under_one() {
+        let adjusted = AlloyData::<BismuthBronze>::try_new_with_options(
+            [Copper(0.6), Zinc(0.2), Bismuth(0.19)],
+            4,
+            NORMALIZE,
+        )
+        .unwrap();
+        assert!((adjusted.scale - 1.0 / 0.99).abs() < 1e-6);
+        assert!((total(adjusted.alloy.percentages()) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_sum_over_one() {
+        let adjusted = Alloys::TinBronze
+            .try_new_with_options([Copper(0.92), Tin(0.09)], 2, NORMALIZE)
+            .unwrap();
+        assert!((adjusted.scale - 1.0 / 1.01).abs() < 1e-6);
+        assert!((total(adjusted.alloy.percentages()) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_correction_too_large() {
+        assert_eq!(
+            Err(InvalidPercentages),
+            AlloyData::<TinBronze>::try_new_with_options([Copper(1.0), Tin(0.1)], 2, NORMALIZE)
+        );
+        let loose = CreateOptions {
+            max_correction: 0.15,
+            ..NORMALIZE
+        };
+        assert!(
+            AlloyData::<TinBronze>::try_new_with_options([Copper(1.0), Tin(0.1)], 2, loose).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_ranges_checked_after_normalizing() {
+        // Tin is in range before normalizing but is pushed below 8% by it
+        assert_eq!(
+            Err(InvalidPercentages),
+            AlloyData::<TinBronze>::try_new_with_options([Copper(0.93), Tin(0.08)], 2, NORMALIZE)
+        );
+    }
+
+    #[test]
+    fn test_float_noise_keeps_max_ingots() {
+        // Splitting the units between these percentages leaves a tiny float remainder that used to lower the max
+        let alloy = AlloyData::<TinBronze>::try_new([Copper(0.9109), Tin(0.0891)], 2).unwrap();
+        assert_eq!(21, alloy.max_ingots());
+        let normalized = normalize_sum::<TinBronze>([Copper(0.92), Tin(0.09)]).unwrap();
+        assert_eq!(
+            21,
+            AlloyData::<TinBronze>::try_new(normalized, 2)
+                .unwrap()
+                .max_ingots()
+        );
+    }
+
+    #[test]
+    fn test_off_by_default() {
+        let adjusted = AlloyData::<Brass>::try_new_with_options(
+            [Copper(0.7), Zinc(0.3)],
+            2,
+            CreateOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(1.0, adjusted.scale);
+        assert_eq!(&[Copper(0.7), Zinc(0.3)], adjusted.alloy.percentages());
+        assert!(
+            AlloyData::<Brass>::try_new_with_options(
+                [Copper(0.7), Zinc(0.32)],
+                2,
+                CreateOptions::default()
+            )
+            .is_err()
+        );
+    }
+}
+
+#[cfg(test)]
+mod whole_percent_tests {
+    use crate::AlloyError::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_set_percents() {
+        let mut alloy =
+            AlloyData::<BismuthBronze>::try_new([Copper(0.6), Zinc(0.25), Bismuth(0.15)], 4)
+                .unwrap();
+        alloy
+            .set_percents(&[(Metal::Bismuth, 20), (Metal::Copper, 50), (Metal::Zinc, 30)])
+            .unwrap();
+        assert_eq!(&[Copper(0.5), Zinc(0.3), Bismuth(0.2)], alloy.percentages());
+        assert_eq!(
+            AlloyData::<BismuthBronze>::try_new([Copper(0.5), Zinc(0.3), Bismuth(0.2)], 4).unwrap(),
+            alloy
+        );
+        assert_eq!(
+            Box::from([Copper(50), Zinc(30), Bismuth(20)]),
+            alloy.percents_u8()
+        );
+    }
+
+    #[test]
+    fn test_set_percents_errors() {
+        let mut alloy = AlloyData::<BismuthBronze>::default();
+        let before = alloy.percentages().to_vec();
+        // Out of range
+        assert_eq!(
+            Err(InvalidPercentages),
+            alloy.set_percents(&[(Metal::Copper, 75), (Metal::Zinc, 20), (Metal::Bismuth, 5)])
+        );
+        // Does not total 100
+        assert_eq!(
+            Err(InvalidPercentages),
+            alloy.set_percents(&[(Metal::Copper, 60), (Metal::Zinc, 25), (Metal::Bismuth, 14)])
+        );
+        assert_eq!(
+            Err(InvalidPercentages),
+            alloy.set_percents(&[(Metal::Copper, 255), (Metal::Zinc, 255)])
+        );
+        // Wrong or repeated metals
+        assert_eq!(
+            Err(UnknownConstituent(Metal::Tin)),
+            alloy.set_percents(&[(Metal::Copper, 60), (Metal::Tin, 25), (Metal::Bismuth, 15)])
+        );
+        assert_eq!(
+            Err(DuplicateConstituent(Metal::Copper)),
+            alloy.set_percents(&[
+                (Metal::Copper, 60),
+                (Metal::Copper, 25),
+                (Metal::Bismuth, 15)
+            ])
+        );
+        assert_eq!(
+            Err(MissingConstituent(Metal::Bismuth)),
+            alloy.set_percents(&[(Metal::Copper, 70), (Metal::Zinc, 30)])
+        );
+        assert_eq!(before, alloy.percentages());
+    }
+
+    #[test]
+    fn test_set_percent_rebalances() {
+        let mut alloy = AlloyData::<TinBronze>::default();
+        alloy.set_percent(Metal::Tin, 11).unwrap();
+        assert_eq!(Box::from([Copper(89), Tin(11)]), alloy.percents_u8());
+
+        let mut alloy = AlloyData::<BismuthBronze>::default();
+        alloy.set_percent(Metal::Copper, 70).unwrap();
+        assert_eq!(
+            Box::from([Copper(70), Zinc(20), Bismuth(10)]),
+            alloy.percents_u8()
+        );
+        alloy.set_percent(Metal::Bismuth, 20).unwrap();
+        assert_eq!(
+            Box::from([Copper(60), Zinc(20), Bismuth(20)]),
+            alloy.percents_u8()
+        );
+    }
+
+    #[test]
+    fn test_set_percent_errors() {
+        let mut alloy = AlloyData::<Brass>::default();
+        let before = alloy.percentages().to_vec();
+        assert_eq!(Err(InvalidPercentages), alloy.set_percent(Metal::Zinc, 41));
+        assert_eq!(
+            Err(UnknownConstituent(Metal::Tin)),
+            alloy.set_percent(Metal::Tin, 10)
+        );
+        assert_eq!(before, alloy.percentages());
+    }
+
+    #[test]
+    fn test_set_percent_always_totals_100() {
+        // Every in-range whole percent of every vanilla constituent leaves room for the others to make up 100
+        fn check<T: AlloyType>() {
+            let ranges = AlloyData::<T>::percentage_ranges();
+            for (range, metal) in ranges.iter().zip(T::constituent_metals()) {
+                let (min, max) = (
+                    (range.min * 100.0).round() as u8,
+                    (range.max * 100.0).round() as u8,
+                );
+                for percent in min..=max {
+                    let mut alloy = AlloyData::<T>::try_new(T::default_percentages(), 1).unwrap();
+                    alloy.set_percent(*metal, percent).unwrap();
+                    let percents = alloy.percents_u8();
+                    assert_eq!(100, percents.iter().map(|p| u32::from(**p)).sum::<u32>());
+                    assert!(
+                        percents
+                            .iter()
+                            .any(|p| p.metal() == *metal && **p == percent)
+                    );
+                }
+            }
+        }
+        for_each_alloy!(check);
+    }
+}
+
+#[cfg(test)]
+mod game_code_tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_alloy_codes() {
+        for alloy in Alloys::ALL {
+            let code = alloy.game_code();
+            assert_eq!(Some(alloy), Alloys::from_game_code(code));
+            for prefix in ["ingot-", "metalbit-", "metalplate-", "game:ingot-"] {
+                assert_eq!(
+                    Some(alloy),
+                    Alloys::from_game_code(&format!("{prefix}{code}"))
+                );
+            }
+            assert_eq!(None, Alloys::from_game_code(&format!("nugget-{code}")));
+            assert_eq!(None, Alloys::from_game_code(&format!("{code}-ingot")));
+            assert_eq!(None, Alloys::from_game_code(&code.to_uppercase()));
+        }
+        assert_eq!(None, Alloys::from_game_code("ingot-steel"));
+        assert_eq!(None, Alloys::from_game_code("Bismuth Bronze"));
+        assert_eq!(None, Alloys::from_game_code(""));
+    }
+
+    #[test]
+    fn test_metal_codes() {
+        for metal in Metal::ALL {
+            let code = metal.game_code();
+            assert_eq!(Some(metal), Metal::from_game_code(code));
+            for prefix in ["ingot-", "metalbit-", "game:metalplate-"] {
+                assert_eq!(
+                    Some(metal),
+                    Metal::from_game_code(&format!("{prefix}{code}"))
+                );
+            }
+            assert_eq!(None, Metal::from_game_code(&format!("ingot-{code}x")));
+        }
+        assert_eq!(None, Metal::from_game_code("ingot-iron"));
+        assert_eq!(None, Metal::from_game_code("ingot-tinbronze"));
+    }
+
+    #[test]
+    fn test_ore_codes() {
+        let cases = [
+            ("nugget-pentlandite", Metal::Nickel),
+            ("nugget-nativecopper", Metal::Copper),
+            ("ore-poor-malachite-granite", Metal::Copper),
+            ("ore-medium-sphalerite-basalt", Metal::Zinc),
+            ("nugget-nativesilver", Metal::Silver),
+            ("ore-bountiful-cassiterite-chalk", Metal::Tin),
+            ("game:nugget-nativegold", Metal::Gold),
+            ("ore-rich-galena-andesite", Metal::Lead),
+            ("nugget-bismuthinite", Metal::Bismuth),
+        ];
+        for (code, metal) in cases {
+            assert_eq!(Some(metal), Metal::from_game_code(code), "{code}");
+        }
+        // Ores use mineral names rather than the metal's code
+        assert_eq!(None, Metal::from_game_code("nugget-copper"));
+        assert_eq!(None, Metal::from_game_code("ore-poor-magnetite-granite"));
+        assert_eq!(None, Metal::from_game_code("ore-huge-galena-granite"));
+    }
+}
+
+#[cfg(test)]
+mod format_tests {
+    use crate::format::{FormatOptions, PercentStyle};
+    use crate::prelude::*;
+    use crate::shopping;
+
+    fn big_list() -> shopping::ShoppingList {
+        let recipe = || {
+            Alloys::BismuthBronze
+                .try_new([Copper(0.6), Zinc(0.25), Bismuth(0.15)], 20)
+                .unwrap()
+        };
+        shopping::aggregate(&[recipe(), recipe(), recipe()])
+    }
+
+    #[test]
+    fn test_default_matches_display() {
+        let list = big_list();
+        assert_eq!(
+            list.to_string(),
+            list.to_markdown_with(&FormatOptions::default())
+        );
+    }
+
+    #[test]
+    fn test_german_style() {
+        let options = FormatOptions {
+            thousands_separator: Some('.'),
+            decimal_separator: ',',
+            ..Default::default()
+        };
+        let alloy = Alloys::TinBronze
+            .try_new([Copper(0.915), Tin(0.085)], 2)
+            .unwrap();
+        assert_eq!(
+            "Tin Bronze × 2 ingots: Copper 91,5% (36), Tin 8,5% (4)",
+            alloy.summary_with(&options)
+        );
+        let expected = "\
+| Metal | Nuggets | Stacks |
+| --- | --- | --- |
+| Copper | 720 | 5 × 128 + 80 |
+| Zinc | 300 | 2 × 128 + 44 |
+| Bismuth | 180 | 1 × 128 + 52 |
+
+### Bismuth Bronze × 20 ingots
+- Copper: 240
+- Zinc: 100
+- Bismuth: 60
+
+### Bismuth Bronze × 20 ingots
+- Copper: 240
+- Zinc: 100
+- Bismuth: 60
+
+### Bismuth Bronze × 20 ingots
+- Copper: 240
+- Zinc: 100
+- Bismuth: 60
+";
+        assert_eq!(expected, big_list().to_markdown_with(&options));
+    }
+
+    #[test]
+    fn test_fraction_without_suffixes() {
+        let options = FormatOptions {
+            percent_style: PercentStyle::Fraction,
+            decimal_places: 3,
+            unit_suffixes: false,
+            ..Default::default()
+        };
+        let alloy = Alloys::BismuthBronze
+            .try_new([Copper(0.6), Zinc(0.25), Bismuth(0.15)], 20)
+            .unwrap();
+        assert_eq!(
+            "Bismuth Bronze × 20: Copper 0.6 (240), Zinc 0.25 (100), Bismuth 0.15 (60)",
+            alloy.summary_with(&options)
+        );
+        let list = shopping::aggregate(&[alloy]);
+        assert!(
+            list.to_markdown_with(&options)
+                .contains("### Bismuth Bronze × 20\n")
+        );
+    }
+
+    #[test]
+    fn test_thousands_and_rounding() {
+        let options = FormatOptions {
+            thousands_separator: Some(','),
+            decimal_places: 0,
+            ..Default::default()
+        };
+        assert_eq!("1,234,567", options.integer(1_234_567));
+        assert_eq!("999", options.integer(999));
+        assert_eq!("-12,000", options.integer(-12_000));
+        assert_eq!("92%", options.percent(0.915));
+        assert_eq!("1,000", options.decimal(999.6));
+        assert_eq!("0", options.decimal(-0.0001));
+        let alloy = Alloys::Brass
+            .try_new([Copper(0.65), Zinc(0.35)], 10)
+            .unwrap();
+        assert_eq!(
+            "Brass × 10 ingots: Copper 65% (130), Zinc 35% (70)",
+            alloy.summary_with(&options)
+        );
+    }
+}
+
+#[cfg(test)]
+mod help_tests {
+    use crate::AlloyError::*;
+    use crate::help::{ErrorContext, HelpText};
+    use crate::prelude::*;
+
+    fn help_for(alloy: Alloys, percentages: &[BaseMetal<f32>], num_ingots: i32) -> HelpText {
+        let error = alloy.try_new(percentages, num_ingots).unwrap_err();
+        error.help(&ErrorContext::new(alloy, percentages, num_ingots))
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let help = help_for(Alloys::TinBronze, &[Copper(0.86), Tin(0.14)], 2);
+        assert_eq!(
+            HelpText {
+                headline: "A percentage is out of range",
+                detail: String::from("Copper is 86%, but Tin Bronze needs between 88% and 92%"),
+                suggestions: vec![
+                    String::from("raise Copper to at least 88%"),
+                    String::from("reduce Tin to at most 12%"),
+                ],
+            },
+            help
+        );
+    }
+
+    #[test]
+    fn test_sum_mismatch() {
+        let help = help_for(
+            Alloys::BismuthBronze,
+            &[Copper(0.6), Zinc(0.2), Bismuth(0.15)],
+            2,
+        );
+        assert_eq!(
+            HelpText {
+                headline: "The percentages do not add up to 100%",
+                detail: String::from("They add up to 95%"),
+                suggestions: vec![
+                    String::from("raise Bismuth to 20%"),
+                    String::from("raise Zinc to 25%"),
+                ],
+            },
+            help
+        );
+    }
+
+    #[test]
+    fn test_near_miss_sum() {
+        // Rejected by try_new, so the help has to explain the total rather than the ranges
+        let help = help_for(Alloys::TinBronze, &[Copper(0.915), Tin(0.08)], 2);
+        assert_eq!("The percentages do not add up to 100%", help.headline);
+        assert_eq!("They add up to 99.5%", help.detail);
+        assert_eq!(
+            vec![
+                String::from("raise Tin to 8.5%"),
+                String::from("raise Copper to 92%"),
+            ],
+            help.suggestions
+        );
+    }
+
+    #[test]
+    fn test_too_many_ingots() {
+        let help = help_for(Alloys::TinBronze, &[Copper(0.88), Tin(0.12)], 24);
+        assert_eq!(
+            HelpText {
+                headline: "Too many ingots for one crucible",
+                detail: String::from(
+                    "24 ingots of Tin Bronze do not fit in the crucible with this mix"
+                ),
+                suggestions: vec![String::from("try 21 ingots — the maximum for this mix")],
+            },
+            help
+        );
+    }
+
+    #[test]
+    fn test_wrong_metals() {
+        let help = help_for(Alloys::Brass, &[Copper(0.92), Tin(0.08)], 2);
+        assert_eq!("A percentage is out of range", help.headline);
+        let percentages = [Copper(0.7), Tin(0.3)];
+        let help = InvalidBaseMetals.help(&ErrorContext::new(Alloys::Brass, &percentages, 2));
+        assert_eq!(
+            "Brass is made from exactly one each of Copper, Zinc",
+            help.detail
+        );
+        assert_eq!(vec![String::from("remove Tin")], help.suggestions);
+        let percentages = [Copper(0.92), Tin(0.08)];
+        let help = InvalidBaseMetals.help(&ErrorContext::new(Alloys::Brass, &percentages, 2));
+        assert_eq!(
+            "switch to Tin Bronze, which these percentages are valid for",
+            help.suggestions[0]
+        );
+        assert!(help.suggestions.len() <= 2);
+    }
+}
+
+#[cfg(test)]
+mod docs_tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_every_alloy_documented() {
+        for alloy in Alloys::ALL {
+            let doc = alloy.doc();
+            assert!(!doc.description.trim().is_empty(), "{alloy:?}");
+            assert!(!doc.acquisition.trim().is_empty(), "{alloy:?}");
+            assert!(!doc.uses.trim().is_empty(), "{alloy:?}");
+            assert_eq!(doc.description, alloy.description());
+            assert_eq!(doc, alloy.get_default().doc());
+        }
+    }
+
+    #[test]
+    fn test_descriptions_are_distinct() {
+        let mut descriptions: Vec<&str> = Alloys::ALL.iter().map(|a| a.description()).collect();
+        descriptions.sort();
+        descriptions.dedup();
+        assert_eq!(Alloys::ALL.len(), descriptions.len());
+    }
+}
+
+#[cfg(test)]
+mod definition_tests {
+    use crate::ConstituentRange;
+    use crate::definition::{AlloyDefinitionError::*, validate_alloy_type, validate_definition};
+    use crate::prelude::*;
+
+    fn range(min: f32, max: f32) -> ConstituentRange<f32> {
+        ConstituentRange::new(min, max)
+    }
+
+    #[test]
+    fn test_builtin_definitions() {
+        fn check<T: AlloyType + Default>() {
+            assert_eq!(
+                Ok(()),
+                validate_definition(T::percentage_ranges(), T::default_percentages())
+            );
+            assert_eq!(Ok(()), validate_alloy_type::<T>());
+        }
+        for_each_alloy!(check);
+        for alloy in Alloys::ALL {
+            assert_eq!(
+                Ok(()),
+                validate_definition(alloy.percentage_ranges(), alloy.default_percentages())
+            );
+        }
+    }
+
+    #[test]
+    fn test_mins_above_one() {
+        let ranges = [
+            Copper(range(0.5, 0.7)),
+            Zinc(range(0.3, 0.4)),
+            Bismuth(range(0.25, 0.3)),
+        ];
+        assert_eq!(
+            Err(UnreachableTotal {
+                min_sum: 1.05,
+                max_sum: 1.4
+            }),
+            validate_definition(&ranges, &[Copper(0.5), Zinc(0.3), Bismuth(0.2)])
+        );
+        let ranges = [Copper(range(0.1, 0.2)), Tin(range(0.1, 0.2))];
+        assert!(matches!(
+            validate_definition(&ranges, &[Copper(0.2), Tin(0.2)]),
+            Err(UnreachableTotal { .. })
+        ));
+    }
+
+    #[test]
+    fn test_duplicate_metals() {
+        let ranges = [
+            Copper(range(0.5, 0.7)),
+            Tin(range(0.1, 0.3)),
+            Copper(range(0.2, 0.4)),
+        ];
+        assert_eq!(
+            Err(DuplicateMetal(Metal::Copper)),
+            validate_definition(&ranges, &[Copper(0.6), Tin(0.2), Copper(0.2)])
+        );
+    }
+
+    #[test]
+    fn test_only_fractional_mix() {
+        let ranges = [Copper(range(0.605, 0.608)), Tin(range(0.392, 0.395))];
+        assert_eq!(
+            Err(NoWholePercentMix),
+            validate_definition(&ranges, &[Copper(0.606), Tin(0.394)])
+        );
+    }
+
+    #[test]
+    fn test_bad_ranges_and_defaults() {
+        assert_eq!(Err(NoConstituents), validate_definition(&[], &[]));
+        assert_eq!(
+            Err(InvalidRange(Metal::Tin)),
+            validate_definition(
+                &[Copper(range(0.8, 0.9)), Tin(range(0.2, 0.1))],
+                &[Copper(0.85), Tin(0.15)]
+            )
+        );
+        let ranges = TinBronze::percentage_ranges();
+        assert_eq!(
+            Err(DefaultMetalsMismatch),
+            validate_definition(ranges, &[Tin(0.1), Copper(0.9)])
+        );
+        assert_eq!(
+            Err(DefaultMetalsMismatch),
+            validate_definition(ranges, &[Copper(1.0)])
+        );
+        assert_eq!(
+            Err(DefaultOutOfRange(Metal::Copper)),
+            validate_definition(ranges, &[Copper(0.87), Tin(0.13)])
+        );
+        assert!(matches!(
+            validate_definition(ranges, &[Copper(0.9), Tin(0.08)]),
+            Err(DefaultTotal(_))
+        ));
+    }
+}
+
+#[cfg(test)]
+mod achievable_counts_tests {
+    use crate::prelude::*;
+
+    /// Every mix of the alloy's ranges in half percent steps that totals exactly 100%
+    fn half_percent_mixes<T: AlloyType>() -> Vec<Vec<BaseMetal<f32>>> {
+        let ranges = AlloyData::<T>::percentage_ranges();
+        let steps: Vec<(i32, i32)> = ranges
+            .iter()
+            .map(|r| {
+                (
+                    (r.min * 200.0).round() as i32,
+                    (r.max * 200.0).round() as i32,
+                )
+            })
+            .collect();
+        let mut current = vec![0; ranges.len()];
+        fn fill(
+            index: usize,
+            steps: &[(i32, i32)],
+            current: &mut Vec<i32>,
+            out: &mut Vec<Vec<i32>>,
+        ) {
+            if index == steps.len() - 1 {
+                let last = 200 - current[..index].iter().sum::<i32>();
+                if (steps[index].0..=steps[index].1).contains(&last) {
+                    current[index] = last;
+                    out.push(current.clone());
+                }
+                return;
+            }
+            for value in steps[index].0..=steps[index].1 {
+                current[index] = value;
+                fill(index + 1, steps, current, out);
+            }
+        }
+        let mut raw = Vec::new();
+        fill(0, &steps, &mut current, &mut raw);
+        raw.into_iter()
+            .map(|values| {
+                ranges
+                    .iter()
+                    .zip(values)
+                    .map(|(r, v)| r.update_inner_value(v as f32 / 200.0))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn check<T: AlloyType>() {
+        let mixes = half_percent_mixes::<T>();
+        assert!(!mixes.is_empty());
+        for mix in mixes {
+            let alloy = AlloyData::<T>::try_new(&mix, 1).unwrap();
+            let counts = AlloyData::<T>::achievable_ingot_counts(&mix).unwrap();
+            assert_eq!(
+                (1..=alloy.max_ingots()).collect::<Vec<_>>(),
+                counts,
+                "{mix:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_counts_are_contiguous() {
+        for_each_alloy!(check);
+    }
+
+    #[test]
+    fn test_alloy_counts() {
+        for alloy in Alloys::ALL {
+            let alloy = alloy.get_default();
+            assert_eq!(
+                (1..=alloy.max_ingots()).collect::<Vec<_>>(),
+                alloy.achievable_ingot_counts()
+            );
+        }
+        assert!(AlloyData::<Brass>::achievable_ingot_counts([Copper(0.9), Zinc(0.1)]).is_err());
+    }
+}
+
+#[cfg(test)]
+mod min_exact_tests {
+    use crate::prelude::*;
+
+    /// Smallest count whose rounded nuggets follow the percentages exactly, found by building every count
+    fn brute_force<T: AlloyType>(percentages: &[BaseMetal<f32>]) -> Option<i32> {
+        let max = AlloyData::<T>::try_new(percentages, 1)
+            .unwrap()
+            .max_ingots();
+        (1..=max).find(|&n| {
+            let alloy = AlloyData::<T>::try_new(percentages, n).unwrap();
+            alloy
+                .percentages()
+                .iter()
+                .zip(alloy.nuggets())
+                .all(|(p, nuggets)| (**p * (n * 20) as f32 - **nuggets as f32).abs() < 1e-3)
+        })
+    }
+
+    #[test]
+    fn test_tin_bronze() {
+        assert_eq!(
+            Ok(Some(5)),
+            AlloyData::<TinBronze>::min_ingots_for_exact([Copper(0.92), Tin(0.08)])
+        );
+        let mix = [Copper(0.89), Tin(0.11)];
+        let exact = AlloyData::<TinBronze>::min_ingots_for_exact(mix).unwrap();
+        assert_eq!(brute_force::<TinBronze>(&mix), exact);
+        assert_eq!(Some(5), exact);
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        for copper in 88..=92 {
+            let mix = [
+                Copper(copper as f32 / 100.0),
+                Tin((100 - copper) as f32 / 100.0),
+            ];
+            assert_eq!(
+                Ok(brute_force::<TinBronze>(&mix)),
+                AlloyData::<TinBronze>::min_ingots_for_exact(mix)
+            );
+        }
+        for (copper, zinc) in [(60, 20), (55, 25), (57, 28), (61, 23)] {
+            let mix = [
+                Copper(copper as f32 / 100.0),
+                Zinc(zinc as f32 / 100.0),
+                Bismuth((100 - copper - zinc) as f32 / 100.0),
+            ];
+            assert_eq!(
+                Ok(brute_force::<BismuthBronze>(&mix)),
+                AlloyData::<BismuthBronze>::min_ingots_for_exact(mix),
+                "{mix:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_no_exact_count() {
+        // A hundredth of a percent only becomes a whole nugget at 100 ingots, well past what fits in the crucible
+        assert_eq!(
+            Ok(None),
+            AlloyData::<Electrum>::min_ingots_for_exact([Gold(0.5001), Silver(0.4999)])
+        );
+        assert!(AlloyData::<Electrum>::min_ingots_for_exact([Gold(0.9), Silver(0.1)]).is_err());
+    }
+}
+
+#[cfg(test)]
+mod nearest_achievable_tests {
+    use crate::AlloyError::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_tin_bronze_batch_sizes() {
+        let request = [Copper(0.92), Tin(0.08)];
+        let one = AlloyData::<TinBronze>::nearest_achievable(request, 1).unwrap();
+        assert_eq!(&[Copper(18), Tin(2)], one.alloy.nuggets());
+        assert!((one.distance - 0.04).abs() < 1e-6);
+
+        let three = AlloyData::<TinBronze>::nearest_achievable(request, 3).unwrap();
+        assert_eq!(&[Copper(55), Tin(5)], three.alloy.nuggets());
+        assert_eq!(3, three.alloy.num_ingots());
+        assert!((three.distance - 2.0 / 300.0).abs() < 1e-6);
+
+        let five = AlloyData::<TinBronze>::nearest_achievable(request, 5).unwrap();
+        assert_eq!(&[Copper(92), Tin(8)], five.alloy.nuggets());
+        assert_eq!(0.0, five.distance);
+    }
+
+    #[test]
+    fn test_three_metals() {
+        let mix = AlloyData::<BismuthBronze>::nearest_achievable(
+            [Copper(0.57), Zinc(0.28), Bismuth(0.15)],
+            1,
+        )
+        .unwrap();
+        assert_eq!(&[Copper(11), Zinc(6), Bismuth(3)], mix.alloy.nuggets());
+        assert_eq!(
+            &[Copper(0.55), Zinc(0.3), Bismuth(0.15)],
+            mix.alloy.percentages()
+        );
+        assert!((mix.distance - 0.04).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_ties_favor_primary_metal() {
+        // 180.5 copper nuggets are requested so 180 and 181 are equally close
+        let mix =
+            AlloyData::<TinBronze>::nearest_achievable([Copper(0.9025), Tin(0.0975)], 10).unwrap();
+        assert_eq!(&[Copper(181), Tin(19)], mix.alloy.nuggets());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Err(InvalidPercentages),
+            AlloyData::<TinBronze>::nearest_achievable([Copper(0.5), Tin(0.5)], 1)
+        );
+        assert_eq!(
+            Err(TooFewIngots),
+            AlloyData::<TinBronze>::nearest_achievable([Copper(0.9), Tin(0.1)], 0)
+        );
+        assert_eq!(
+            Err(TooManyIngots),
+            AlloyData::<TinBronze>::nearest_achievable([Copper(0.9), Tin(0.1)], 26)
+        );
+    }
+}
+
+#[cfg(test)]
+mod adjustment_tests {
+    use crate::prelude::*;
+    use crate::{Adjustment, AdjustmentReason::*};
+
+    #[test]
+    fn test_60_21_19_rounds_without_adjustments() {
+        let (alloy, report) = AlloyData::<BismuthBronze>::try_new_reporting(
+            [Copper(0.6), Zinc(0.21), Bismuth(0.19)],
+            1,
+        )
+        .unwrap();
+        assert_eq!(&[Copper(12), Zinc(4), Bismuth(4)], alloy.nuggets());
+        assert!(report.is_empty());
+    }
+
+    #[test]
+    fn test_sum_correction_reported() {
+        let (alloy, report) = AlloyData::<BismuthBronze>::try_new_reporting(
+            [Copper(0.51), Zinc(0.29), Bismuth(0.20)],
+            1,
+        )
+        .unwrap();
+        assert_eq!(&[Copper(10), Zinc(6), Bismuth(4)], alloy.nuggets());
+        assert_eq!(
+            vec![Adjustment {
+                metal: Metal::Zinc,
+                change: 1,
+                reason: SumCorrection
+            }],
+            report.adjustments
+        );
+    }
+
+    #[test]
+    fn test_range_clamp_reported() {
+        let (alloy, report) =
+            AlloyData::<TinBronze>::try_new_reporting([Copper(0.88), Tin(0.12)], 1).unwrap();
+        assert_eq!(&[Copper(18), Tin(2)], alloy.nuggets());
+        assert_eq!(
+            vec![
+                Adjustment {
+                    metal: Metal::Tin,
+                    change: -1,
+                    reason: RangeClamp
+                },
+                Adjustment {
+                    metal: Metal::Copper,
+                    change: 1,
+                    reason: RangeClamp
+                },
+            ],
+            report.adjustments
+        );
+    }
+
+    #[test]
+    fn test_adjustments_match_reporting_constructor() {
+        let (alloy, report) = AlloyData::<BismuthBronze>::try_new_reporting(
+            [Copper(0.52), Zinc(0.28), Bismuth(0.20)],
+            3,
+        )
+        .unwrap();
+        assert_eq!(report, alloy.adjustments());
+    }
+
+    #[test]
+    fn test_reporting_keeps_errors() {
+        assert_eq!(
+            Err(crate::AlloyError::InvalidPercentages),
+            AlloyData::<TinBronze>::try_new_reporting([Copper(0.5), Tin(0.5)], 1)
+        );
+    }
+}
+
+#[cfg(test)]
+mod parse_tests {
+    use crate::AlloyError::*;
+    use crate::parse;
+    use crate::prelude::*;
+
+    /// Fragments that hostile or sloppy input is built from, including multibyte characters next to separators
+    const FRAGMENTS: &[&str] = &[
+        "copper",
+        "Tin",
+        "BISMUTH",
+        "zinc",
+        "game:",
+        "ingot-",
+        "nugget-",
+        "ore-poor-",
+        "galena",
+        "-",
+        ":",
+        "=",
+        ",",
+        ";",
+        " ",
+        "\t",
+        "%",
+        ".",
+        "0",
+        "9",
+        "92",
+        "1e9",
+        "-1",
+        "NaN",
+        "inf",
+        "é",
+        "ｃ",
+        "💥",
+        "\u{0}",
+        "\u{200b}",
+    ];
+
+    /// Small xorshift generator so the random cases are the same on every run
+    struct Cases(u64);
+
+    impl Cases {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn input(&mut self) -> String {
+            let len = self.next() % 24;
+            (0..len)
+                .map(|_| FRAGMENTS[(self.next() % FRAGMENTS.len() as u64) as usize])
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_random_inputs_never_panic() {
+        let mut cases = Cases(0x5eed_a110);
+        for _ in 0..5_000 {
+            let input = cases.input();
+            if let Some(alloy) = parse::alloy(&input) {
+                assert_eq!(Some(alloy), parse::alloy(alloy.game_code()), "{input:?}");
+            }
+            if let Some(metal) = parse::metal(&input) {
+                assert_eq!(Some(metal), parse::metal(metal.game_code()), "{input:?}");
+            }
+            if let Some(value) = parse::percent(&input) {
+                assert!(
+                    value.is_finite() && (0.0..=1.0).contains(&value),
+                    "{input:?}"
+                );
+            }
+            if let Ok(percentages) = parse::percentages(&input) {
+                assert!(percentages.as_ref().len() <= Metal::ALL.len(), "{input:?}");
+                for alloy in Alloys::ALL {
+                    let _ = alloy.try_new(percentages.as_ref(), 1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_slice_of_multibyte_input_never_panics() {
+        let input = "cöpper ９２%, tïn 8%; 💥=0.5";
+        for start in 0..=input.len() {
+            for end in start..=input.len() {
+                if let Some(slice) = input.get(start..end) {
+                    let _ = parse::alloy(slice);
+                    let _ = parse::metal(slice);
+                    let _ = parse::percent(slice);
+                    let _ = parse::percentages(slice);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_oversized_input_rejected() {
+        let input = format!("copper 92%,{}tin 8%", " ".repeat(parse::MAX_INPUT_LEN));
+        assert_eq!(Err(InvalidPercentages), parse::percentages(&input));
+        assert_eq!(None, parse::metal(&"copper".repeat(100)));
+    }
+
+    #[test]
+    fn test_too_many_entries_rejected() {
+        assert_eq!(
+            Err(WrongConstituentCount {
+                expected: 8,
+                got: 9
+            }),
+            parse::percentages("a 1,b 1,c 1,d 1,e 1,f 1,g 1,h 1,i 1")
+        );
+    }
+
+    #[test]
+    fn test_names_and_codes_round_trip() {
+        for alloy in Alloys::ALL {
+            assert_eq!(Some(alloy), parse::alloy(alloy.name()));
+            assert_eq!(Some(alloy), parse::alloy(&alloy.name().to_uppercase()));
+            assert_eq!(Some(alloy), parse::alloy(alloy.game_code()));
+        }
+        for metal in Metal::ALL {
+            assert_eq!(Some(metal), parse::metal(metal.name()));
+            assert_eq!(Some(metal), parse::metal(metal.game_code()));
+        }
+    }
+
+    #[test]
+    fn test_whole_percents_round_trip() {
+        for whole in 0..=100 {
+            assert_eq!(
+                Some(whole as f32 / 100.0),
+                parse::percent(&format!("{whole}%"))
+            );
+        }
+    }
+
+    #[test]
+    fn test_percent_rejects_non_fractions() {
+        for input in [
+            "", "%", "NaN", "inf", "-0.1", "1.5", "101%", "1e39%", "９２%",
+        ] {
+            assert_eq!(None, parse::percent(input), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_percentages_formats() {
+        let expected = [Copper(0.6), Zinc(0.2), Bismuth(0.2)];
+        for input in [
+            "copper 60%, zinc 20%, bismuth 20%",
+            "Copper=0.6; Zinc=0.2; Bismuth=0.2",
+            "copper: 60%, zinc: 20%, bismuth: 20%,",
+            "game:ingot-copper=0.6;game:ingot-zinc=0.2;game:ingot-bismuth=0.2",
+        ] {
+            assert_eq!(
+                Ok(&expected[..]),
+                parse::percentages(input).as_ref().map(|p| p.as_ref()),
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_percentages_errors() {
+        assert_eq!(
+            Err(DuplicateConstituent(Metal::Tin)),
+            parse::percentages("tin 8%, tin 92%")
+        );
+        assert_eq!(Err(InvalidBaseMetals), parse::percentages("iron 100%"));
+        assert_eq!(Err(InvalidPercentages), parse::percentages("copper"));
+        assert_eq!(Err(InvalidPercentages), parse::percentages("copper lots"));
+    }
+}
+
+#[cfg(test)]
+mod wide_units_tests {
+    use crate::IngotCount;
+    use crate::crucible::{self, Capacity, CapacityError};
+    use crate::prelude::*;
+
+    /// A modded smelter with 100 slots that each hold stacks of 10,000 nuggets
+    const INDUSTRIAL: Capacity = Capacity {
+        slots: 100,
+        max_units_per_slot: 10_000 * crate::unit_constants::NUGGET_UNIT_AMOUNT,
+    };
+
+    #[test]
+    fn test_industrial_capacity_max_ingots() {
+        assert_eq!(50_000, INDUSTRIAL.max_possible_ingots());
+        // The search starts at the most ingots a count can hold rather than everything the capacity could fit
+        for bp in [[9200, 800], [9150, 850]] {
+            assert_eq!(
+                IngotCount::MAX.get(),
+                AlloyData::<TinBronze>::calculate_max_ingots_basis_points(&bp, INDUSTRIAL)
+            );
+        }
+    }
+
+    #[test]
+    fn test_industrial_capacity_public_max_ingots() {
+        // Capped at the most ingots an alloy can be created with
+        for percentages in [[Copper(0.92), Tin(0.08)], [Copper(0.915), Tin(0.085)]] {
+            let max_ingots =
+                AlloyData::<TinBronze>::max_ingots_in(percentages, INDUSTRIAL).unwrap();
+            assert_eq!(IngotCount::MAX.get(), max_ingots);
+        }
+    }
+
+    #[test]
+    fn test_vanilla_capacity_matches_alloys() {
+        for (bp, percentages) in [
+            ([9200, 800], [Copper(0.92), Tin(0.08)]),
+            ([9150, 850], [Copper(0.915), Tin(0.085)]),
+            ([8800, 1200], [Copper(0.88), Tin(0.12)]),
+        ] {
+            let alloy = AlloyData::<TinBronze>::try_new(percentages, 1).unwrap();
+            assert_eq!(
+                alloy.max_ingots(),
+                AlloyData::<TinBronze>::calculate_max_ingots_basis_points(&bp, Capacity::VANILLA)
+            );
+        }
+    }
+
+    #[test]
+    fn test_max_possible_ingots_saturates() {
+        let huge = Capacity {
+            slots: i32::MAX,
+            max_units_per_slot: i32::MAX,
+        };
+        assert_eq!(i32::MAX, huge.max_possible_ingots());
+    }
+
+    #[test]
+    fn test_huge_stack_does_not_overflow() {
+        assert_eq!(
+            Err(CapacityError::StackTooLarge {
+                index: 0,
+                metal: Metal::Copper,
+                nuggets: i32::MAX,
+                excess: i32::MAX - 128,
+            }),
+            crucible::fits(&[(Metal::Copper, i32::MAX)])
+        );
+    }
+}
+
+#[cfg(test)]
+mod tuple_conversion_tests {
+    use crate::AlloyError::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_every_alloy_from_tuple() {
+        for kind in Alloys::ALL {
+            let percentages = kind.default_percentages();
+            let borrowed = Alloy::try_from((kind, percentages, 3)).unwrap();
+            let owned = Alloy::try_from((kind, percentages.to_vec(), 3)).unwrap();
+            let defaults = Alloy::try_from((kind, 3)).unwrap();
+            assert_eq!(kind, borrowed.kind());
+            assert_eq!(borrowed, owned);
+            assert_eq!(borrowed, defaults);
+            assert_eq!(3, defaults.num_ingots());
+        }
+    }
+
+    #[test]
+    fn test_tuple_bad_percentages() {
+        let percentages = [Copper(0.5), Zinc(0.5)];
+        assert_eq!(
+            Err(InvalidPercentages),
+            Alloy::try_from((Alloys::Brass, &percentages[..], 5))
+        );
+        assert_eq!(
+            Err(UnknownConstituent(Metal::Lead)),
+            Alloy::try_from((Alloys::Brass, vec![Copper(0.7), Lead(0.3)], 5))
+        );
+    }
+
+    #[test]
+    fn test_tuple_bad_ingot_count() {
+        assert_eq!(Err(TooFewIngots), Alloy::try_from((Alloys::Brass, 0)));
+        assert_eq!(Err(TooManyIngots), Alloy::try_from((Alloys::Brass, 26)));
+        assert_eq!(
+            Err(TooManyIngots),
+            Alloy::try_from((
+                Alloys::BlackBronze,
+                Alloys::BlackBronze.default_percentages(),
+                16
+            ))
+        );
+    }
+}
+
+#[cfg(test)]
+mod slider_binding_tests {
+    use crate::prelude::*;
+
+    fn whole(value: f32) -> u8 {
+        (value * 100.0).round() as u8
+    }
+
+    #[test]
+    fn test_bismuth_bronze_copper_at_max() {
+        let alloy =
+            AlloyData::<BismuthBronze>::try_new([Copper(0.7), Zinc(0.2), Bismuth(0.1)], 1).unwrap();
+        let bindings = alloy.slider_bindings();
+        let metals: Vec<Metal> = bindings.iter().map(|b| b.metal).collect();
+        assert_eq!(vec![Metal::Copper, Metal::Zinc, Metal::Bismuth], metals);
+        // Copper gives way when zinc or bismuth are raised, so every slider keeps its full range
+        for binding in &bindings {
+            assert_eq!(binding.range, binding.reachable);
+            assert!(binding.movable);
+            assert_eq!(0.01, binding.step);
+        }
+        assert_eq!(0.7, bindings[0].value);
+    }
+
+    fn check_reachable_bounds<T: AlloyType + Default>() {
+        let alloy = AlloyData::<T>::default();
+        for binding in alloy.slider_bindings() {
+            let (low, high) = (whole(binding.reachable.min), whole(binding.reachable.max));
+            for (percent, valid) in [
+                (low, true),
+                (high, true),
+                (low - 1, false),
+                (high + 1, false),
+            ] {
+                let mut copy = AlloyData::<T>::default();
+                let result = copy.set_percent(binding.metal, percent);
+                assert_eq!(valid, result.is_ok(), "{binding:?} {percent}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_reachable_bounds_match_set_percent() {
+        for_each_alloy!(check_reachable_bounds);
+    }
+
+    #[test]
+    fn test_two_metal_intervals_mirror() {
+        for kind in Alloys::ALL {
+            let alloy = kind.get_default();
+            if let [first, second] = alloy.slider_bindings()[..] {
+                assert_eq!(
+                    100,
+                    whole(first.reachable.min) + whole(second.reachable.max)
+                );
+                assert_eq!(
+                    100,
+                    whole(first.reachable.max) + whole(second.reachable.min)
+                );
+                assert_eq!(100, whole(first.value) + whole(second.value));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod verification_tests {
+    use crate::prelude::*;
+    use crate::verification::{self, CSV_HEADER, CaseKind};
+
+    #[test]
+    fn test_every_corner_covered_exactly_once() {
+        let cases = verification::cases();
+        for alloy in Alloys::ALL {
+            let corners: Vec<_> = cases
+                .iter()
+                .filter(|c| c.alloy == alloy && c.kind == CaseKind::Corner)
+                .map(|c| c.percentages.clone())
+                .collect();
+            let expected = alloy.boundary_mixes();
+            assert!(!expected.is_empty(), "{alloy:?}");
+            assert_eq!(expected, corners, "{alloy:?}");
+        }
+    }
+
+    #[test]
+    fn test_one_default_and_max_batch_per_alloy() {
+        let cases = verification::cases();
+        for alloy in Alloys::ALL {
+            let of_kind = |kind| {
+                cases
+                    .iter()
+                    .filter(|c| c.alloy == alloy && c.kind == kind)
+                    .collect::<Vec<_>>()
+            };
+            let (defaults, max_batches) = (of_kind(CaseKind::Default), of_kind(CaseKind::MaxBatch));
+            assert_eq!(1, defaults.len(), "{alloy:?}");
+            assert_eq!(1, max_batches.len(), "{alloy:?}");
+            assert_eq!(1, defaults[0].num_ingots);
+            assert_eq!(max_batches[0].max_ingots, max_batches[0].num_ingots);
+            assert_eq!(alloy.default_percentages(), &defaults[0].percentages[..]);
+        }
+    }
+
+    #[test]
+    fn test_expected_results_match_library() {
+        for case in verification::cases() {
+            let alloy = case
+                .alloy
+                .try_new(&case.percentages, case.num_ingots)
+                .unwrap();
+            assert_eq!(alloy.nuggets(), &case.nuggets[..]);
+            assert_eq!(alloy.max_ingots(), case.max_ingots);
+        }
+    }
+
+    #[test]
+    fn test_csv_rows_match_header() {
+        let columns = CSV_HEADER.split(',').count();
+        for case in verification::cases() {
+            assert_eq!(columns, case.to_csv_row().split(',').count());
+        }
+    }
+
+    #[test]
+    fn test_checklist_line() {
+        let case = verification::cases()
+            .into_iter()
+            .find(|c| c.alloy == Alloys::Brass && c.kind == CaseKind::MaxBatch)
+            .unwrap();
+        assert_eq!(
+            "[ ] Brass (max batch): 70% Copper, 30% Zinc for 21 ingots -> 294 Copper, 126 Zinc nuggets, max 21 ingots",
+            case.to_string()
+        );
+    }
+}
+
+#[cfg(test)]
+mod calculation_trace_tests {
+    use crate::AlloyError::*;
+    use crate::TraceStep;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_59_22_19_shows_ceil_on_last_constituent() {
+        let trace =
+            AlloyData::<BismuthBronze>::explain([Copper(0.59), Zinc(0.22), Bismuth(0.19)], 1)
+                .unwrap();
+        assert!(trace.steps.iter().any(|s| matches!(
+            s,
+            TraceStep::Remainder { metal: Metal::Bismuth, units, nuggets: 4, slots: 1 }
+                if (units - 19.0).abs() < 0.001
+        )));
+        assert_eq!(&[Copper(11), Zinc(5), Bismuth(4)], &trace.nuggets[..]);
+        assert_eq!(
+            "1. Validated percentages: 59% Copper, 22% Zinc, 19% Bismuth\n\
+             2. At most 21 ingots fit in the crucible, so 1 ingots fit\n\
+             3. 1 ingots need 100 units\n\
+             4. Copper: 59% of the units is 59 units, rounded down to 11 nuggets in 1 slot\n\
+             5. Zinc: 22% of the units is 22 units, rounded down to 4 nuggets in 1 slot\n\
+             6. Bismuth: the remaining 19 units, rounded up to 4 nuggets in 1 slot\n\
+             7. Zinc gains a nugget to make up the total\n\
+             8. Result: 11 Copper, 5 Zinc, 4 Bismuth nuggets",
+            trace.to_string()
+        );
+    }
+
+    #[test]
+    fn test_range_clamp_steps() {
+        let trace = AlloyData::<TinBronze>::explain([Copper(0.88), Tin(0.12)], 3).unwrap();
+        let adjusted = trace
+            .steps
+            .iter()
+            .filter(|s| matches!(s, TraceStep::Adjusted(_)))
+            .count();
+        assert_eq!(2, adjusted);
+    }
+
+    fn check_matches_untraced<T: AlloyType>(percentages: &[BaseMetal<f32>]) {
+        for num_ingots in 0..=26 {
+            let traced = AlloyData::<T>::explain(percentages, num_ingots);
+            let untraced = AlloyData::<T>::try_new(percentages, num_ingots);
+            match (traced, untraced) {
+                (Ok(trace), Ok(alloy)) => {
+                    assert_eq!(alloy.nuggets(), &trace.nuggets[..]);
+                    assert_eq!(alloy.max_ingots(), trace.max_ingots);
+                    assert_eq!(
+                        Some(&TraceStep::Nuggets {
+                            nuggets: trace.nuggets.clone()
+                        }),
+                        trace.steps.last()
+                    );
+                }
+                (traced, untraced) => {
+                    assert_eq!(untraced.err(), traced.err(), "{percentages:?} {num_ingots}")
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_traced_matches_untraced() {
+        for copper in 88..=92 {
+            let tin = 100 - copper;
+            check_matches_untraced::<TinBronze>(&[
+                Copper(copper as f32 / 100.0),
+                Tin(tin as f32 / 100.0),
+            ]);
+        }
+        for copper in 50..=70 {
+            for zinc in 20..=30 {
+                let bismuth = 100 - copper - zinc;
+                check_matches_untraced::<BismuthBronze>(&[
+                    Copper(copper as f32 / 100.0),
+                    Zinc(zinc as f32 / 100.0),
+                    Bismuth(bismuth as f32 / 100.0),
+                ]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_explain_errors() {
+        assert_eq!(
+            Err(InvalidPercentages),
+            AlloyData::<TinBronze>::explain([Copper(0.5), Tin(0.5)], 1)
+        );
+        assert_eq!(
+            Err(TooFewIngots),
+            AlloyData::<TinBronze>::explain([Copper(0.92), Tin(0.08)], 0)
+        );
+    }
+}
+
+#[cfg(test)]
+mod shared_reorder_tests {
+    use crate::AlloyError::{self, *};
+    use crate::alloy::alloy_types::reorder_constituents;
+    use crate::prelude::*;
+
+    const VALUES: [f32; 6] = [0.0, 0.08, 0.12, 0.45, 0.55, 0.7];
+
+    fn every_input(len: usize) -> Vec<Vec<BaseMetal<f32>>> {
+        let items: Vec<BaseMetal<f32>> = Metal::ALL
+            .iter()
+            .flat_map(|m| VALUES.map(|v| m.with_value(v)))
+            .collect();
+        let mut inputs = vec![vec![]];
+        for _ in 0..len {
+            inputs = inputs
+                .into_iter()
+                .flat_map(|input| {
+                    items.iter().map(move |item| {
+                        let mut next = input.clone();
+                        next.push(*item);
+                        next
+                    })
+                })
+                .collect();
+        }
+        inputs
+    }
+
+    fn shared<T: AlloyType>(
+        percentages: &[BaseMetal<f32>],
+    ) -> Result<Box<[BaseMetal<f32>]>, AlloyError> {
+        let ranges = T::percentage_ranges();
+        reorder_constituents(percentages, T::constituent_metals(), |i, p| {
+            ranges[i].contains(p)
+        })
+    }
+
+    /// The per-alloy logic the shared function replaced: a seen flag per metal and a placeholder array filled in as metals are found
+    fn legacy<T: AlloyType>(
+        percentages: &[BaseMetal<f32>],
+    ) -> Result<Box<[BaseMetal<f32>]>, AlloyError> {
+        if percentages.len() != T::N_CONSTITUENTS {
+            return Err(InvalidPercentages);
+        }
+        let mut seen = vec![false; T::N_CONSTITUENTS];
+        let mut reorder = vec![Copper(0.0); T::N_CONSTITUENTS];
+        for p in percentages {
+            let index = T::constituent_metals()
+                .iter()
+                .position(|m| *m == p.metal())
+                .ok_or(UnknownConstituent(p.metal()))?;
+            if seen[index] {
+                return Err(DuplicateConstituent(p.metal()));
+            } else if !T::percentage_ranges()[index].contains(p) {
+                return Err(InvalidPercentages);
+            }
+            reorder[index] = *p;
+            seen[index] = true;
+        }
+        Ok(reorder.into())
+    }
+
+    fn check_matches_old<T: AlloyType>() {
+        for input in every_input(T::N_CONSTITUENTS) {
+            let expected = legacy::<T>(&input);
+            assert_eq!(expected, shared::<T>(&input), "{} {input:?}", T::name());
+            assert_eq!(
+                expected,
+                T::check_ranges_contains(&input).map(|p| p.as_ref().into()),
+                "{} {input:?}",
+                T::name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_shared_matches_per_alloy() {
+        for_each_alloy!(check_matches_old);
+    }
+    #[test]
+    fn test_symmetric_ranges_reorder() {
+        assert_eq!(
+            Ok([Gold(0.4), Silver(0.6)]),
+            Electrum::check_ranges_contains([Silver(0.6), Gold(0.4)])
+        );
+        assert_eq!(
+            Ok([Tin(0.45), Lead(0.55)]),
+            LeadSolder::check_ranges_contains([Lead(0.55), Tin(0.45)])
+        );
+        assert_eq!(
+            Ok([Tin(0.55), Lead(0.45)]),
+            LeadSolder::check_ranges_contains([Tin(0.55), Lead(0.45)])
+        );
+    }
+
+    #[test]
+    fn test_symmetric_ranges_reject_duplicates() {
+        assert_eq!(
+            Err(DuplicateConstituent(Metal::Gold)),
+            Electrum::check_ranges_contains([Gold(0.5), Gold(0.5)])
+        );
+        assert_eq!(
+            Err(DuplicateConstituent(Metal::Lead)),
+            LeadSolder::check_ranges_contains([Lead(0.5), Lead(0.5)])
+        );
+    }
+}
+
+#[cfg(test)]
+mod boundary_mix_tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_tin_bronze_corners() {
+        assert_eq!(
+            vec![
+                Box::from([Copper(0.92), Tin(0.08)]),
+                Box::from([Copper(0.88), Tin(0.12)]),
+            ],
+            Alloys::TinBronze.boundary_mixes()
+        );
+    }
+
+    #[test]
+    fn test_bismuth_bronze_corners() {
+        assert_eq!(
+            vec![
+                Box::from([Copper(0.7), Zinc(0.2), Bismuth(0.1)]),
+                Box::from([Copper(0.6), Zinc(0.3), Bismuth(0.1)]),
+                Box::from([Copper(0.6), Zinc(0.2), Bismuth(0.2)]),
+                Box::from([Copper(0.5), Zinc(0.3), Bismuth(0.2)]),
+            ],
+            Alloys::BismuthBronze.boundary_mixes()
+        );
+    }
+
+    #[test]
+    fn test_black_bronze_corners() {
+        assert_eq!(
+            vec![
+                Box::from([Copper(0.84), Gold(0.08), Silver(0.08)]),
+                Box::from([Copper(0.76), Gold(0.16), Silver(0.08)]),
+                Box::from([Copper(0.76), Gold(0.08), Silver(0.16)]),
+                Box::from([Copper(0.68), Gold(0.16), Silver(0.16)]),
+            ],
+            Alloys::BlackBronze.boundary_mixes()
+        );
+    }
+
+    #[test]
+    fn test_every_corner_validates() {
+        for alloy in Alloys::ALL {
+            let corners = alloy.boundary_mixes();
+            let expected = if alloy.constituent_metals().len() == 2 {
+                2
+            } else {
+                4
+            };
+            assert_eq!(expected, corners.len(), "{alloy:?}");
+            for corner in corners {
+                assert!(alloy.try_new(&corner, 1).is_ok(), "{alloy:?} {corner:?}");
+                assert_eq!(
+                    10_000,
+                    corner
+                        .iter()
+                        .map(|p| (**p * 10_000.0).round() as i32)
+                        .sum::<i32>()
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod lerp_tests {
+    use crate::AlloyError;
+    use crate::prelude::*;
+
+    fn check_lerp<T: AlloyType>(kind: Alloys) {
+        let corners = kind.boundary_mixes();
+        let start = AlloyData::<T>::from_lerp(0.0, 1).unwrap();
+        let end = AlloyData::<T>::from_lerp(1.0, 1).unwrap();
+        assert_eq!(&corners[0][..], start.percentages(), "{kind:?}");
+        assert_eq!(
+            &corners[corners.len() - 1][..],
+            end.percentages(),
+            "{kind:?}"
+        );
+
+        for step in 0..=1000 {
+            let t = step as f32 / 1000.0;
+            let alloy = AlloyData::<T>::from_lerp(t, 1)
+                .unwrap_or_else(|e| panic!("{kind:?} at {t} gave {e:?}"));
+            let whole: Vec<i32> = alloy
+                .percentages()
+                .iter()
+                .map(|p| (**p * 100.0).round() as i32)
+                .collect();
+            assert_eq!(100, whole.iter().sum::<i32>(), "{kind:?} at {t}");
+            assert!(
+                alloy
+                    .percentages()
+                    .iter()
+                    .zip(&whole)
+                    .all(|(p, w)| (**p * 100.0 - *w as f32).abs() < 1e-3),
+                "{kind:?} at {t} is not whole percents"
+            );
+        }
+    }
+
+    #[test]
+    fn test_every_alloy_over_grid() {
+        for_each_alloy!(check_lerp(Alloys));
+    }
+
+    #[test]
+    fn test_midpoints() {
+        let tin_bronze = AlloyData::<TinBronze>::from_lerp(0.5, 5).unwrap();
+        assert_eq!(&[Copper(0.90), Tin(0.10)], tin_bronze.percentages());
+        assert_eq!(&[Copper(90), Tin(10)], tin_bronze.nuggets());
+
+        let bismuth_bronze = AlloyData::<BismuthBronze>::from_lerp(0.5, 1).unwrap();
+        assert_eq!(
+            &[Copper(0.60), Zinc(0.25), Bismuth(0.15)],
+            bismuth_bronze.percentages()
+        );
+    }
+
+    #[test]
+    fn test_rejects_out_of_range_positions() {
+        for t in [-0.01, 1.01, f32::NAN, f32::INFINITY] {
+            assert_eq!(
+                Some(AlloyError::InvalidValues),
+                AlloyData::<TinBronze>::from_lerp(t, 1).err()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod panic_free_tests {
+    use crate::crucible;
+    use crate::format::FormatOptions;
+    use crate::help::ErrorContext;
+    use crate::inventory::Inventory;
+    use crate::planner::{MAX_PLAN_NUGGETS, PlanError};
+    use crate::prelude::*;
+    use crate::{AlloyError, ConstituentRange, CreateOptions, MetalTotals, normalize_sum};
+    use crate::{parse, planner, ranking, shopping};
+
+    const INGOTS: [i32; 9] = [i32::MIN, -1, 0, 1, 2, 25, 26, 10_000, i32::MAX];
+    const VALUES: [f32; 10] = [
+        f32::NAN,
+        f32::INFINITY,
+        f32::NEG_INFINITY,
+        f32::MAX,
+        f32::MIN,
+        f32::MIN_POSITIVE,
+        -0.0,
+        -1.0,
+        0.5,
+        1.0,
+    ];
+
+    /// Percentage lists that are the wrong length, repeat metals, or hold values that are not percentages
+    fn adversarial_percentages() -> Vec<Vec<BaseMetal<f32>>> {
+        let mut inputs = vec![
+            vec![],
+            vec![Copper(1.0)],
+            vec![Copper(0.5), Copper(0.5)],
+            vec![Copper(0.92), Tin(0.08), Tin(0.0)],
+            Metal::ALL.map(|m| m.with_value(0.125)).to_vec(),
+            [Metal::ALL, Metal::ALL]
+                .concat()
+                .into_iter()
+                .map(|m| m.with_value(0.0625))
+                .collect(),
+        ];
+        for value in VALUES {
+            inputs.push(vec![Copper(value), Tin(1.0 - value)]);
+            inputs.push(vec![Copper(value), Zinc(value), Bismuth(value)]);
+            inputs.push(vec![Copper(0.6), Zinc(value), Bismuth(0.2)]);
+            inputs.push(Metal::ALL.map(|m| m.with_value(value)).to_vec());
+        }
+        inputs
+    }
+
+    fn hammer<T: AlloyType + Default>(kind: Alloys) {
+        for percentages in adversarial_percentages() {
+            for num_ingots in INGOTS {
+                let _ = AlloyData::<T>::try_new(&percentages, num_ingots);
+                let _ = AlloyData::<T>::try_new_reporting(&percentages, num_ingots);
+                let _ = AlloyData::<T>::explain(&percentages, num_ingots);
+                let _ = AlloyData::<T>::nearest_achievable(&percentages, num_ingots);
+                let _ = AlloyData::<T>::try_new_with_options(
+                    &percentages,
+                    num_ingots,
+                    CreateOptions::default(),
+                );
+                let _ = kind.try_new(&percentages, num_ingots);
+                let context = ErrorContext::new(kind, &percentages, num_ingots);
+                for error in [
+                    AlloyError::InvalidPercentages,
+                    AlloyError::InvalidBaseMetals,
+                    AlloyError::TooManyIngots,
+                    AlloyError::TooFewIngots,
+                ] {
+                    let _ = error.help(&context);
+                }
+            }
+            let _ = AlloyData::<T>::check_valid_percentages(&percentages);
+            let _ = AlloyData::<T>::achievable_ingot_counts(&percentages);
+            let _ = AlloyData::<T>::efficiency_table(&percentages);
+            let _ = AlloyData::<T>::most_efficient_counts(&percentages);
+            let _ = AlloyData::<T>::min_ingots_for_exact(&percentages);
+            let _ = Alloys::infer(&percentages);
+            let _ = normalize_sum::<T>(&percentages);
+
+            let mut alloy = AlloyData::<T>::default();
+            let _ = alloy.set_percentages(&percentages);
+            let mut alloy = kind.get_default();
+            let _ = alloy.set_percentages(&percentages);
+            let _ = alloy.set_percentages_or_suggest(&percentages);
+        }
+
+        for num_ingots in INGOTS {
+            let mut alloy = AlloyData::<T>::default();
+            let _ = alloy.set_num_ingots(num_ingots);
+            let _ = kind.get_default().set_num_ingots(num_ingots);
+            let _ = AlloyData::<T>::from_lerp(0.5, num_ingots);
+            let _ = AlloyData::<T>::nearest_to_target(Metal::Copper, 1, Some(num_ingots));
+        }
+
+        // The solvers search every valid split, so only the extremes are tried here
+        for nuggets in [i32::MIN, 0, i32::MAX] {
+            for metal in [T::constituent_metals()[0], Metal::Nickel] {
+                let _ = AlloyData::<T>::solve_with_fixed(metal, nuggets);
+                let _ = AlloyData::<T>::nearest_to_target(metal, nuggets, None);
+                let _ = planner::consume_exact(kind, metal, nuggets);
+            }
+        }
+
+        for t in VALUES {
+            let _ = AlloyData::<T>::from_lerp(t, 1);
+        }
+
+        let mut alloy = AlloyData::<T>::default();
+        for metal in Metal::ALL {
+            for percent in [0, 1, 50, 99, 100, u8::MAX] {
+                let _ = alloy.set_percent(metal, percent);
+                let _ = alloy.set_percents(&[(metal, percent)]);
+                let _ = alloy.set_percents(&[(metal, percent), (metal, percent)]);
+            }
+        }
+        for parts in [0, 1, 2, usize::MAX] {
+            let _ = alloy.split(parts);
+            let _ = kind.get_default().split(parts);
+        }
+        let _ = alloy.split_overhead(&[]);
+        let _ = kind.get_default().split_overhead(&[]);
+        let _ = kind
+            .get_default()
+            .split_overhead(&[kind.get_default(), kind.get_default()]);
+        for other in Alloys::iter() {
+            let _ = kind.get_default().split_overhead(&[other.get_default()]);
+        }
+
+        let extremes: Vec<BaseMetal<i32>> = Metal::ALL
+            .into_iter()
+            .flat_map(|m| {
+                [
+                    m.with_value(i32::MIN),
+                    m.with_value(0),
+                    m.with_value(i32::MAX),
+                ]
+            })
+            .collect();
+        let _ = AlloyData::<T>::classify_contents(&extremes);
+        let _ = kind.classify_contents(&extremes);
+        let _ = Alloys::matching_contents(&extremes);
+        let _ = Inventory::from_iter(extremes.iter().copied()).shortfall(&extremes);
+    }
+
+    #[test]
+    fn test_every_alloy_rejects_adversarial_input() {
+        for_each_alloy!(hammer(Alloys));
+    }
+
+    #[cfg(feature = "rational")]
+    #[test]
+    fn test_raw_ratios_are_errors() {
+        use num_rational::Ratio;
+
+        let raw = [
+            Ratio::new_raw(1, 0),
+            Ratio::new_raw(0, 0),
+            Ratio::new_raw(i32::MIN, 0),
+            Ratio::new_raw(i32::MIN, -1),
+            Ratio::new_raw(i32::MAX, i32::MIN),
+            Ratio::new_raw(-9, -10),
+        ];
+        for value in raw {
+            for other in raw
+                .into_iter()
+                .chain([Ratio::new_raw(0, 1), Ratio::new(1, 10)])
+            {
+                let percentages = [Copper(value), Tin(other)];
+                let _ = AlloyData::<TinBronze>::check_valid_rational_percentages(percentages);
+                let _ = AlloyData::<TinBronze>::try_new_rational(percentages, 1);
+            }
+        }
+        assert_eq!(
+            Err(AlloyError::InvalidPercentages),
+            AlloyData::<TinBronze>::try_new_rational(
+                [Copper(Ratio::new_raw(1, 0)), Tin(Ratio::new_raw(0, 1))],
+                1
+            )
+        );
+        // 2^31 copper and 1 - 2^31 tin total exactly 1 without any reduced value fitting in an i32
+        assert_eq!(
+            Err(AlloyError::InvalidPercentages),
+            AlloyData::<TinBronze>::try_new_rational(
+                [
+                    Copper(Ratio::new_raw(i32::MIN, -1)),
+                    Tin(Ratio::from_integer(-i32::MAX))
+                ],
+                1
+            )
+        );
+        // Unreduced negative denominators are still valid percentages
+        assert_eq!(
+            &[Copper(18), Tin(2)],
+            AlloyData::<TinBronze>::try_new_rational(
+                [Copper(Ratio::new_raw(-9, -10)), Tin(Ratio::new_raw(1, 10))],
+                1
+            )
+            .unwrap()
+            .nuggets()
+        );
+    }
+
+    #[test]
+    fn test_extreme_ingots_are_errors() {
+        for num_ingots in [i32::MIN, -1, 0, 26, i32::MAX] {
+            assert!(
+                AlloyData::<TinBronze>::try_new([Copper(0.92), Tin(0.08)], num_ingots).is_err()
+            );
+        }
+        assert_eq!(
+            Some(AlloyError::InvalidPercentages),
+            AlloyData::<TinBronze>::try_new([Copper(f32::NAN), Tin(f32::NAN)], 1).err()
+        );
+        assert_eq!(
+            Some(AlloyError::InvalidPercentages),
+            AlloyData::<BismuthBronze>::try_new([Copper(0.6), Zinc(0.4)], 1).err()
+        );
+    }
+
+    #[test]
+    fn test_totals_saturate() {
+        let totals =
+            MetalTotals::from_iter([Copper(i32::MAX), Copper(i32::MAX), Tin(i32::MIN), Tin(-1)]);
+        assert_eq!(i32::MAX, totals.get(Metal::Copper));
+        assert_eq!(i32::MIN, totals.get(Metal::Tin));
+        let recipes = [(); 3].map(|_| Alloys::TinBronze.get_default());
+        let _ = shopping::aggregate(&recipes).stacks();
+    }
+
+    #[test]
+    fn test_free_functions() {
+        let _ = crucible::fits(&[(Metal::Copper, i32::MIN), (Metal::Tin, i32::MAX)]);
+        let _ = crucible::fits(&[(Metal::Copper, i32::MAX)]);
+        let options = FormatOptions::default();
+        for value in [i64::MIN, -1, 0, i64::MAX] {
+            let _ = options.integer(value);
+        }
+        for value in VALUES {
+            let _ = options.decimal(f64::from(value));
+            let _ = options.percent(value);
+            let _ = ConstituentRange::new(0.5_f32, 0.7).lerp(value);
+            let _ = ConstituentRange::new(i32::MIN, i32::MAX).lerp(value);
+            let _ = ConstituentRange::new(u8::MAX, 0).lerp(value);
+        }
+        for num_ingots in INGOTS {
+            let _ = options.ingots(num_ingots);
+            let _ = ranking::by_metal_efficiency(Metal::Tin, num_ingots);
+        }
+        assert_eq!(
+            Some(PlanError::TooManyNuggets),
+            planner::consume_exact(Alloys::TinBronze, Metal::Tin, MAX_PLAN_NUGGETS + 1).err()
+        );
+        let long = "9".repeat(parse::MAX_INPUT_LEN * 4);
+        for input in ["", "%", "nan", "inf", "-0%", "copper nan%", long.as_str()] {
+            let _ = parse::alloy(input);
+            let _ = parse::metal(input);
+            let _ = parse::percent(input);
+            let _ = parse::percentages(input);
+        }
+    }
+
+    #[cfg(feature = "rational")]
+    #[test]
+    fn test_rational_sum_overflow_is_an_error() {
+        use num_rational::Ratio;
+        let percentages = [
+            Copper(Ratio::new(1, i32::MAX)),
+            Zinc(Ratio::new(1, i32::MAX - 1)),
+            Bismuth(Ratio::new(1, i32::MAX - 2)),
+        ];
+        assert_eq!(
+            Some(AlloyError::InvalidPercentages),
+            AlloyData::<BismuthBronze>::try_new_rational(percentages, 1).err()
+        );
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_decimal_extremes_are_errors() {
+        use rust_decimal::Decimal;
+        for value in [Decimal::MAX, Decimal::MIN, Decimal::ZERO] {
+            let percentages = [Copper(value), Tin(value)];
+            for num_ingots in INGOTS {
+                assert!(AlloyData::<TinBronze>::try_new_decimal(percentages, num_ingots).is_err());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod nugget_invariant_tests {
+    use crate::prelude::*;
+
+    /// Every valid whole-percent mix of the alloy in canonical order
+    fn whole_percent_mixes(kind: Alloys) -> Vec<Vec<i32>> {
+        let ranges: Vec<(i32, i32)> = kind
+            .percentage_ranges()
+            .iter()
+            .map(|r| {
+                (
+                    (r.min * 100.0).round() as i32,
+                    (r.max * 100.0).round() as i32,
+                )
+            })
+            .collect();
+        let (last, rest) = ranges.split_last().unwrap();
+        let mut mixes = vec![vec![]];
+        for (min, max) in rest {
+            mixes = mixes
+                .into_iter()
+                .flat_map(|mix: Vec<i32>| {
+                    (*min..=*max).map(move |p| [mix.as_slice(), &[p]].concat())
+                })
+                .collect();
+        }
+        mixes
+            .into_iter()
+            .filter_map(|mut mix| {
+                let rest = 100 - mix.iter().sum::<i32>();
+                (last.0..=last.1).contains(&rest).then(|| {
+                    mix.push(rest);
+                    mix
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_black_bronze_stays_in_range() {
+        let alloy =
+            AlloyData::<BlackBronze>::try_new([Copper(0.68), Gold(0.16), Silver(0.16)], 3).unwrap();
+        assert_eq!(&[Copper(42), Gold(9), Silver(9)], alloy.nuggets());
+    }
+
+    #[test]
+    fn test_every_whole_percent_mix_and_ingot_count() {
+        for kind in Alloys::ALL {
+            let ranges = kind.percentage_ranges();
+            let mixes = whole_percent_mixes(kind);
+            assert!(!mixes.is_empty(), "{kind:?}");
+            for mix in mixes {
+                let percentages: Vec<BaseMetal<f32>> = kind
+                    .constituent_metals()
+                    .iter()
+                    .zip(&mix)
+                    .map(|(metal, p)| metal.with_value(*p as f32 / 100.0))
+                    .collect();
+                for num_ingots in 1..=25 {
+                    let Ok(alloy) = kind.try_new(&percentages, num_ingots) else {
+                        assert!(num_ingots > kind.try_new(&percentages, 1).unwrap().max_ingots());
+                        continue;
+                    };
+                    let total = 20 * num_ingots;
+                    let nuggets = alloy.nuggets();
+                    assert_eq!(
+                        total,
+                        nuggets.iter().map(|n| **n).sum::<i32>(),
+                        "{kind:?} {mix:?} {num_ingots}"
+                    );
+                    for (n, range) in nuggets.iter().zip(ranges) {
+                        // Compared in whole percents scaled by the total to avoid float rounding
+                        let (min, max) = ((range.min * 100.0).round(), (range.max * 100.0).round());
+                        let scaled = (**n * 100) as f32;
+                        assert!(
+                            (min * total as f32..=max * total as f32).contains(&scaled),
+                            "{kind:?} {mix:?} {num_ingots}: {nuggets:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod summary_tests {
+    use crate::prelude::*;
+    use crate::summaries;
+
+    /// Counts whole-percent mixes by trying every one against the alloy's own validation
+    fn brute_force_mix_count(kind: Alloys) -> usize {
+        let metals = kind.constituent_metals();
+        let mixes: Vec<Vec<u8>> = match metals.len() {
+            2 => (0..=100).map(|a| vec![a, 100 - a]).collect(),
+            _ => (0..=100)
+                .flat_map(|a| (0..=100 - a).map(move |b| vec![a, b, 100 - a - b]))
+                .collect(),
+        };
+        mixes
+            .into_iter()
+            .filter(|mix| {
+                let percentages: Vec<BaseMetal<f32>> = metals
+                    .iter()
+                    .zip(mix)
+                    .map(|(metal, p)| metal.with_value(f32::from(*p) / 100.0))
+                    .collect();
+                kind.try_new(percentages, 1).is_ok()
+            })
+            .count()
+    }
+
+    #[test]
+    fn test_fields_match_sources() {
+        let menu = summaries::all();
+        assert_eq!(Alloys::ALL.len(), menu.len());
+        for (kind, summary) in Alloys::ALL.into_iter().zip(menu) {
+            let default = kind.get_default();
+            assert_eq!(kind, summary.alloy);
+            assert_eq!(kind.summary(), summary);
+            assert_eq!(kind.name(), summary.name);
+            assert_eq!(kind.game_code(), summary.game_code);
+            assert_eq!(kind.percentage_ranges(), summary.ranges);
+            assert_eq!(kind.default_percentages(), summary.default_percentages);
+            assert_eq!(default.nuggets(), &*summary.default_nuggets);
+            assert_eq!(kind.default_ingots(), summary.default_ingots);
+            assert_eq!(default.max_ingots(), summary.max_ingots);
+            assert_eq!(kind.doc(), summary.doc);
+            assert_eq!(
+                brute_force_mix_count(kind),
+                summary.num_whole_percent_mixes,
+                "{kind:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_mix_counts() {
+        let counts: Vec<usize> = summaries::all()
+            .iter()
+            .map(|s| s.num_whole_percent_mixes)
+            .collect();
+        assert_eq!(vec![5, 121, 81, 11, 5, 11, 11, 21, 11], counts);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serializes() {
+        let json = serde_json::to_value(Alloys::Brass.summary()).unwrap();
+        assert_eq!("Brass", json["name"]);
+        assert_eq!("brass", json["game_code"]);
+        assert_eq!(11, json["num_whole_percent_mixes"]);
+        assert_eq!(2, json["ranges"].as_array().unwrap().len());
+        assert!(json["doc"]["description"].is_string());
+    }
+}
+
+#[cfg(test)]
+mod tooltip_parse_tests {
+    use crate::inventory::{Inventory, TooltipParseError};
+    use crate::prelude::*;
+
+    const PASTE: &str = "Copper nugget x128
+Nugget (Tin) 37
+
+  COPPER INGOT × 3
+12x Cassiterite nuggets
+Bone x4
+Nugget (Bismuth) x 19
+";
+
+    #[test]
+    fn test_realistic_paste() {
+        let TooltipParseError {
+            inventory,
+            unrecognized,
+        } = Inventory::parse_tooltip_text(PASTE).unwrap_err();
+        assert_eq!(128 + 60, inventory.nuggets(Metal::Copper));
+        assert_eq!(37 + 12, inventory.nuggets(Metal::Tin));
+        assert_eq!(19, inventory.nuggets(Metal::Bismuth));
+        assert_eq!(0, inventory.nuggets(Metal::Zinc));
+        assert_eq!(vec![(6, String::from("Bone x4"))], unrecognized);
+    }
+
+    #[test]
+    fn test_clean_paste() {
+        let inventory =
+            Inventory::parse_tooltip_text("Ingot (Gold) 2\r\nsilver nuggets 40\n").unwrap();
+        assert_eq!(Inventory::from_iter([Gold(40), Silver(40)]), inventory);
+        assert_eq!(
+            Ok(Inventory::new()),
+            Inventory::parse_tooltip_text("\n  \n")
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_lines_are_unrecognized() {
+        for line in [
+            "Copper x12",
+            "Copper nugget",
+            "Copper tin nugget x3",
+            "Copper nugget ingot x3",
+            "Copper nugget 3 4",
+            "Copper nugget x0",
+            "Copper nugget x-3",
+            "Copper ingot x2147483647",
+        ] {
+            let error = Inventory::parse_tooltip_text(line).unwrap_err();
+            assert_eq!(vec![(1, String::from(line))], error.unrecognized, "{line}");
+            assert_eq!(Inventory::new(), error.inventory);
+        }
+    }
+}
+
+#[cfg(test)]
+mod get_default_tests {
+    use crate::alloy_names::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_every_variant_wraps_its_default() {
+        let expected = [
+            (Alloy::TinBronze(AlloyData::default()), TIN_BRONZE),
+            (Alloy::BismuthBronze(AlloyData::default()), BISMUTH_BRONZE),
+            (Alloy::BlackBronze(AlloyData::default()), BLACK_BRONZE),
+            (Alloy::Brass(AlloyData::default()), BRASS),
+            (Alloy::Molybdochalkos(AlloyData::default()), MOLYBDOCHALKOS),
+            (Alloy::LeadSolder(AlloyData::default()), LEAD_SOLDER),
+            (Alloy::SilverSolder(AlloyData::default()), SILVER_SOLDER),
+            (Alloy::Electrum(AlloyData::default()), ELECTRUM),
+            (Alloy::Cupronickel(AlloyData::default()), CUPRONICKEL),
+        ];
+        for (kind, (alloy, name)) in Alloys::ALL.into_iter().zip(expected) {
+            let default = kind.get_default();
+            assert_eq!(alloy, default);
+            assert_eq!(name, default.name());
+            assert_eq!(kind.name(), default.name());
+        }
+    }
+
+    #[test]
+    fn test_brass_round_trips_name() {
+        assert_eq!("Brass", Alloys::Brass.get_default().name());
+        assert_eq!(
+            "Molybdochalkos",
+            Alloys::Molybdochalkos.get_default().name()
+        );
+        assert_eq!("Silver Solder", Alloys::SilverSolder.get_default().name());
+    }
+}
+
+#[cfg(test)]
+mod alloys_iter_tests {
+    use crate::prelude::*;
+
+    /// Position of the alloy in declaration order. Fails to compile when a variant is added without updating it.
+    fn declared_index(alloy: Alloys) -> usize {
+        match alloy {
+            Alloys::TinBronze => 0,
+            Alloys::BismuthBronze => 1,
+            Alloys::BlackBronze => 2,
+            Alloys::Brass => 3,
+            Alloys::Molybdochalkos => 4,
+            Alloys::LeadSolder => 5,
+            Alloys::SilverSolder => 6,
+            Alloys::Electrum => 7,
+            Alloys::Cupronickel => 8,
+        }
+    }
+
+    #[test]
+    fn test_all_in_sync_with_variants() {
+        assert_eq!(9, Alloys::ALL.len());
+        for (i, alloy) in Alloys::ALL.into_iter().enumerate() {
+            assert_eq!(i, declared_index(alloy));
+            assert_eq!(i, alloy as usize);
+        }
+    }
+
+    #[test]
+    fn test_iter_matches_all() {
+        assert_eq!(Alloys::ALL.to_vec(), Alloys::iter().collect::<Vec<_>>());
+        for alloy in Alloys::iter() {
+            assert!(!alloy.name().is_empty());
+            assert!(!alloy.percentage_ranges().is_empty());
+        }
+    }
+}
+
+#[cfg(test)]
+mod alloy_name_tests {
+    use crate::alloy_names::*;
+    use crate::prelude::*;
+
+    fn check<T: AlloyType>(kind: Alloys, constant: &str) {
+        assert_eq!(constant, kind.name());
+        assert_eq!(constant, T::name());
+        assert_eq!(constant, kind.to_string());
+        assert_eq!(constant, kind.get_default().name());
+    }
+
+    #[test]
+    fn test_name_sources_agree() {
+        check::<TinBronze>(Alloys::TinBronze, TIN_BRONZE);
+        check::<BismuthBronze>(Alloys::BismuthBronze, BISMUTH_BRONZE);
+        check::<BlackBronze>(Alloys::BlackBronze, BLACK_BRONZE);
+        check::<Brass>(Alloys::Brass, BRASS);
+        check::<Molybdochalkos>(Alloys::Molybdochalkos, MOLYBDOCHALKOS);
+        check::<LeadSolder>(Alloys::LeadSolder, LEAD_SOLDER);
+        check::<SilverSolder>(Alloys::SilverSolder, SILVER_SOLDER);
+        check::<Electrum>(Alloys::Electrum, ELECTRUM);
+        check::<Cupronickel>(Alloys::Cupronickel, CUPRONICKEL);
+    }
+
+    #[test]
+    fn test_display_in_format() {
+        assert_eq!("Make Black Bronze", format!("Make {}", Alloys::BlackBronze));
+    }
+}
+
+#[cfg(test)]
+mod runtime_ranges_tests {
+    use crate::prelude::*;
+
+    fn check<T: AlloyType>(kind: Alloys) {
+        assert_eq!(T::percentage_ranges(), kind.percentage_ranges(), "{kind}");
+        assert_eq!(
+            AlloyData::<T>::percentage_ranges(),
+            kind.percentage_ranges(),
+            "{kind}"
+        );
+    }
+
+    #[test]
+    fn test_dispatch_matches_static_types() {
+        for_each_alloy!(check(Alloys));
+    }
+}
+
+#[cfg(test)]
+mod alloy_kind_tests {
+    use crate::prelude::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_kind_round_trips_through_get_default() {
+        for kind in Alloys::ALL {
+            let alloy = kind.get_default();
+            assert_eq!(kind, alloy.kind());
+            assert_eq!(alloy.name(), alloy.kind().name());
+            assert_eq!(kind.percentage_ranges(), alloy.kind().percentage_ranges());
+        }
+    }
+
+    #[test]
+    fn test_kind_as_map_key() {
+        let recipes = [
+            Alloys::Brass
+                .try_new([Copper(0.65), Zinc(0.35)], 2)
+                .unwrap(),
+            Alloys::TinBronze.get_default(),
+            Alloys::Brass.get_default(),
+        ];
+        let mut counts: HashMap<Alloys, usize> = HashMap::new();
+        for recipe in &recipes {
+            *counts.entry(recipe.kind()).or_default() += 1;
+        }
+        assert_eq!(Some(&2), counts.get(&Alloys::Brass));
+        assert_eq!(Some(&1), counts.get(&Alloys::TinBronze));
+    }
+}
+
+#[cfg(test)]
+mod alloy_from_data_tests {
+    use crate::prelude::*;
+
+    fn check<T: AlloyType>(kind: Alloys)
+    where
+        Alloy: From<AlloyData<T>>,
+    {
+        let data = AlloyData::<T>::try_new(kind.default_percentages(), 3).unwrap();
+        let (nuggets, percentages) = (data.nuggets().to_vec(), data.percentages().to_vec());
+        let (num_ingots, max_ingots) = (data.num_ingots(), data.max_ingots());
+
+        let alloy: Alloy = data.into();
+        assert_eq!(kind, alloy.kind());
+        assert_eq!(nuggets, alloy.nuggets());
+        assert_eq!(percentages, alloy.percentages());
+        assert_eq!(num_ingots, alloy.num_ingots());
+        assert_eq!(max_ingots, alloy.max_ingots());
+        assert_eq!(kind.try_new(kind.default_percentages(), 3).unwrap(), alloy);
+    }
+
+    #[test]
+    fn test_every_alloy_type_converts() {
+        for_each_alloy!(check(Alloys));
+    }
+
+    #[test]
+    fn test_mixed_vec() {
+        let recipes: Vec<Alloy> = vec![
+            AlloyData::<TinBronze>::default().into(),
+            AlloyData::<Brass>::default().into(),
+        ];
+        assert_eq!(
+            vec![Alloys::TinBronze, Alloys::Brass],
+            recipes.iter().map(Alloy::kind).collect::<Vec<_>>()
+        );
+    }
+}
+
+#[cfg(test)]
+mod alloy_downcast_tests {
+    use crate::AlloyMismatch;
+    use crate::prelude::*;
+
+    fn check<T: AlloyType + Default + std::fmt::Debug>(
+        kind: Alloys,
+        borrow: fn(&Alloy) -> Option<&AlloyData<T>>,
+    ) where
+        AlloyData<T>: TryFrom<Alloy, Error = AlloyMismatch>,
+        for<'a> Alloys: From<&'a AlloyData<T>> + From<&'a T>,
+    {
+        let data = AlloyData::<T>::default();
+        assert_eq!(kind, Alloys::from(&data));
+        assert_eq!(kind, Alloys::from(data.alloy_type()));
+        for other in Alloys::ALL {
+            let alloy = other.get_default();
+            if other == kind {
+                assert_eq!(Some(&AlloyData::<T>::default()), borrow(&alloy));
+                assert_eq!(
+                    Ok(AlloyData::<T>::default()),
+                    AlloyData::<T>::try_from(alloy)
+                );
+            } else {
+                assert_eq!(None, borrow(&alloy));
+                let error = AlloyData::<T>::try_from(alloy).unwrap_err();
+                assert_eq!(kind, error.expected);
+                assert_eq!(other, error.actual);
+                assert_eq!(other.get_default(), error.alloy);
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_alloy_type_downcasts() {
+        check::<TinBronze>(Alloys::TinBronze, Alloy::as_tin_bronze);
+        check::<BismuthBronze>(Alloys::BismuthBronze, Alloy::as_bismuth_bronze);
+        check::<BlackBronze>(Alloys::BlackBronze, Alloy::as_black_bronze);
+        check::<Brass>(Alloys::Brass, Alloy::as_brass);
+        check::<Molybdochalkos>(Alloys::Molybdochalkos, Alloy::as_molybdochalkos);
+        check::<LeadSolder>(Alloys::LeadSolder, Alloy::as_lead_solder);
+        check::<SilverSolder>(Alloys::SilverSolder, Alloy::as_silver_solder);
+        check::<Electrum>(Alloys::Electrum, Alloy::as_electrum);
+        check::<Cupronickel>(Alloys::Cupronickel, Alloy::as_cupronickel);
+    }
+
+    #[test]
+    fn test_round_trip_keeps_recipe() {
+        let data = AlloyData::<BismuthBronze>::try_new([Copper(0.6), Zinc(0.25), Bismuth(0.15)], 4)
+            .unwrap();
+        let expected =
+            AlloyData::<BismuthBronze>::try_new([Copper(0.6), Zinc(0.25), Bismuth(0.15)], 4)
+                .unwrap();
+        let back = AlloyData::<BismuthBronze>::try_from(Alloy::from(data)).unwrap();
+        assert_eq!(expected, back);
+        assert_eq!(expected.nuggets(), back.nuggets());
+    }
+}
+
+#[cfg(test)]
+mod alloy_runtime_constructor_tests {
+    use crate::AlloyError::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_every_kind_succeeds_and_fails() {
+        for kind in Alloys::ALL {
+            let defaults = kind.default_percentages();
+            let alloy = Alloy::try_new(kind, defaults, 1).unwrap();
+            assert_eq!(kind, alloy.kind());
+            assert_eq!(kind.get_default(), alloy);
+
+            assert_eq!(Err(TooFewIngots), Alloy::try_new(kind, defaults, 0));
+            assert_eq!(Err(TooManyIngots), Alloy::try_new(kind, defaults, 26));
+            assert_eq!(
+                Err(TooManyIngots),
+                Alloy::try_new(kind, defaults, alloy.max_ingots() + 1)
+            );
+
+            let halved: Vec<BaseMetal<f32>> = defaults.iter().map(|p| p.map(|v| v / 2.0)).collect();
+            assert_eq!(Err(InvalidPercentages), Alloy::try_new(kind, &halved, 1));
+
+            let foreign = Metal::ALL
+                .into_iter()
+                .find(|m| !kind.constituent_metals().contains(m))
+                .unwrap();
+            let mut wrong_metals = defaults.to_vec();
+            wrong_metals[0] = foreign.with_value(*wrong_metals[0]);
+            assert_eq!(
+                Err(UnknownConstituent(foreign)),
+                Alloy::try_new(kind, &wrong_metals, 1),
+                "{kind}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_matches_typed_constructor() {
+        let percentages = [Copper(0.6), Zinc(0.25), Bismuth(0.15)];
+        assert_eq!(
+            Alloy::from(AlloyData::<BismuthBronze>::try_new(percentages, 9).unwrap()),
+            Alloy::try_new(Alloys::BismuthBronze, percentages, 9).unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod clone_tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_cloned_alloy_data_is_independent() {
+        let original =
+            AlloyData::<BlackBronze>::try_new([Copper(0.8), Gold(0.1), Silver(0.1)], 4).unwrap();
+        let mut copy = original.clone();
+        assert_eq!(original, copy);
+        assert_eq!(original.nuggets(), copy.nuggets());
+
+        copy.set_num_ingots(8).unwrap();
+        assert_eq!(4, original.num_ingots());
+        assert_eq!(&[Copper(64), Gold(8), Silver(8)], original.nuggets());
+        assert_eq!(8, copy.num_ingots());
+        assert_ne!(original, copy);
+    }
+
+    #[test]
+    fn test_cloned_alloy_restores_last_good() {
+        for kind in Alloys::ALL {
+            let mut plan = kind.get_default();
+            let last_good = plan.clone();
+            assert_eq!(last_good, plan);
+
+            assert!(plan.set_num_ingots(2).is_ok());
+            assert!(plan.set_num_ingots(0).is_err());
+            assert_eq!(1, last_good.num_ingots());
+            assert_eq!(kind.get_default(), last_good);
+
+            plan = last_good.clone();
+            assert_eq!(last_good, plan);
+        }
+    }
+}
+
+#[cfg(test)]
+mod recipe_display_tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_tin_bronze_snapshot() {
+        let alloy = AlloyData::<TinBronze>::try_new([Copper(0.92), Tin(0.08)], 7).unwrap();
+        let expected =
+            "Tin Bronze × 7 ingots (max 20 ingots): Copper 92% = 128 nuggets, Tin 8% = 12 nuggets";
+        assert_eq!(expected, alloy.to_string());
+        assert_eq!(expected, Alloy::from(alloy).to_string());
+    }
+
+    #[test]
+    fn test_bismuth_bronze_snapshot() {
+        let alloy = Alloys::BismuthBronze
+            .try_new([Copper(0.6), Zinc(0.2), Bismuth(0.2)], 13)
+            .unwrap();
+        assert_eq!(
+            "Bismuth Bronze × 13 ingots (max 21 ingots): Copper 60% = 156 nuggets, Zinc 20% = 52 nuggets, Bismuth 20% = 52 nuggets",
+            alloy.to_string()
+        );
+        assert_eq!(
+            "Bismuth Bronze × 13 ingots (max 21 ingots)
+- Copper 60% = 156 nuggets
+- Zinc 20% = 52 nuggets
+- Bismuth 20% = 52 nuggets",
+            format!("{alloy:#}")
+        );
+    }
+
+    #[test]
+    fn test_typed_and_unified_agree() {
+        for kind in Alloys::ALL {
+            let alloy = kind.get_default();
+            assert!(alloy.to_string().starts_with(kind.name()));
+            assert_eq!(
+                kind.num_constituents(),
+                format!("{alloy:#}").lines().count() - 1
+            );
+        }
+        let alloy = AlloyData::<Electrum>::default();
+        assert_eq!(
+            format!("{alloy:#}"),
+            format!("{:#}", Alloy::from(alloy.clone()))
+        );
+    }
+}
+
+#[cfg(test)]
+mod base_metal_display_tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_every_metal() {
+        let names = [
+            "Nickel", "Copper", "Zinc", "Silver", "Tin", "Gold", "Lead", "Bismuth",
+        ];
+        for (metal, name) in Metal::ALL.into_iter().zip(names) {
+            assert_eq!(format!("{name}: 128"), metal.with_value(128).to_string());
+            assert_eq!(
+                format!("{name}: 0.5"),
+                metal.with_value(0.5_f32).to_string()
+            );
+            assert_eq!(
+                format!("{name}: 50%"),
+                metal.with_value(0.5_f32).as_percent().to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_percent_rounding_and_formatting() {
+        assert_eq!("Tin: 8%", Tin(0.08).as_percent().to_string());
+        assert_eq!("Copper: 91.5%", Copper(0.915).as_percent().to_string());
+        assert_eq!("Zinc: 0.250", format!("{:.3}", Zinc(0.25_f32)));
+        assert_eq!("Lead:    12", format!("{:>5}", Lead(12)));
+    }
+
+    #[test]
+    fn test_nuggets_of_an_alloy() {
+        let alloy = AlloyData::<TinBronze>::try_new([Copper(0.92), Tin(0.08)], 7).unwrap();
+        let lines: Vec<String> = alloy.nuggets().iter().map(ToString::to_string).collect();
+        assert_eq!(vec!["Copper: 128", "Tin: 12"], lines);
+        let percents: Vec<String> = alloy
+            .percentages()
+            .iter()
+            .map(|p| p.as_percent().to_string())
+            .collect();
+        assert_eq!(vec!["Copper: 92%", "Tin: 8%"], percents);
+    }
+}
+
+#[cfg(test)]
+mod alloy_from_str_tests {
+    use crate::parse::ParseAlloyError;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_every_alloy_parses() {
+        for alloy in Alloys::ALL {
+            let name = alloy.name();
+            assert_eq!(Ok(alloy), name.parse());
+            assert_eq!(Ok(alloy), name.to_lowercase().parse());
+            assert_eq!(Ok(alloy), name.to_uppercase().parse());
+            assert_eq!(Ok(alloy), name.replace(' ', "").parse());
+            assert_eq!(Ok(alloy), name.replace(' ', "-").parse());
+            assert_eq!(Ok(alloy), format!("  {name}\t").parse());
+            assert_eq!(Ok(alloy), alloy.game_code().parse());
+        }
+    }
+
+    #[test]
+    fn test_misspellings_fail() {
+        for input in [
+            "",
+            "bronze",
+            "tin bronz",
+            "bismuth bronze alloy",
+            "molybdochalcos",
+            "lead soldier",
+            "t.i.n bronze",
+            "ingot-tinbronze",
+        ] {
+            let error = input.parse::<Alloys>().unwrap_err();
+            assert_eq!(
+                ParseAlloyError {
+                    input: input.to_string()
+                },
+                error
+            );
+            let message = error.to_string();
+            for alloy in Alloys::ALL {
+                assert!(message.contains(alloy.name()), "{message}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod metal_identity_tests {
+    use crate::prelude::*;
+    use std::collections::{BTreeSet, HashSet};
+
+    #[test]
+    fn test_round_trips() {
+        for metal in Metal::ALL {
+            let base_metal = BaseMetal::from_metal(metal, 7);
+            assert_eq!(metal, base_metal.metal());
+            assert_eq!(7, *base_metal);
+            assert_eq!(metal.with_value(7), base_metal);
+            assert_eq!(metal.name(), base_metal.name());
+            assert_eq!(
+                base_metal,
+                BaseMetal::from_metal(base_metal.metal(), *base_metal)
+            );
+        }
+        for base_metal in [
+            Nickel(1),
+            Copper(2),
+            Zinc(3),
+            Silver(4),
+            Tin(5),
+            Gold(6),
+            Lead(7),
+            Bismuth(8),
+        ] {
+            assert_eq!(
+                base_metal,
+                BaseMetal::from_metal(base_metal.metal(), *base_metal)
+            );
+        }
+    }
+
+    #[test]
+    fn test_metal_as_key_and_membership() {
+        let keys: HashSet<Metal> = Metal::ALL.into_iter().collect();
+        assert_eq!(8, keys.len());
+        let ordered: Vec<Metal> = Metal::ALL
+            .into_iter()
+            .rev()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        assert_eq!(Metal::ALL.to_vec(), ordered);
+        assert!(Alloys::Brass.constituent_metals().contains(&Metal::Zinc));
+        assert!(
+            !Alloys::TinBronze
+                .constituent_metals()
+                .contains(&Metal::Zinc)
+        );
+    }
+}
+
+#[cfg(test)]
+mod metal_from_str_tests {
+    use crate::parse::ParseMetalError;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_every_metal_parses() {
+        for metal in Metal::ALL {
+            let name = metal.name();
+            assert_eq!(Ok(metal), name.parse());
+            assert_eq!(Ok(metal), name.to_lowercase().parse());
+            assert_eq!(Ok(metal), name.to_uppercase().parse());
+            assert_eq!(Ok(metal.with_value(3)), BaseMetal::try_from_name(name, 3));
+            assert_eq!(
+                Ok(metal.with_value(0.5)),
+                BaseMetal::try_from_name(&format!(" {name} "), 0.5)
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_names() {
+        for input in [
+            "",
+            "iron",
+            "coper",
+            "copper nugget",
+            "nugget-cassiterite",
+            "Cu",
+        ] {
+            let expected = ParseMetalError {
+                input: input.to_string(),
+            };
+            assert_eq!(Err(expected.clone()), input.parse::<Metal>());
+            assert_eq!(Err(expected), BaseMetal::try_from_name(input, 1));
+            let message = input.parse::<Metal>().unwrap_err().to_string();
+            for metal in Metal::ALL {
+                assert!(message.contains(metal.name()), "{message}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod base_metal_value_tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_value_and_into_inner() {
+        for (i, metal) in Metal::ALL.into_iter().enumerate() {
+            let nuggets = metal.with_value(i as i32 * 3);
+            assert_eq!(i as i32 * 3, nuggets.value());
+            assert_eq!(i as i32 * 3, nuggets.into_inner());
+
+            let label = metal.with_value(metal.name().to_string());
+            assert_eq!(metal.name(), label.into_inner());
+        }
+    }
+
+    #[test]
+    fn test_values_on_slices() {
+        let nuggets: Vec<BaseMetal<i32>> = Metal::ALL
+            .into_iter()
+            .zip(1..)
+            .map(|(metal, n)| metal.with_value(n))
+            .collect();
+        assert_eq!(
+            (1..=8).collect::<Vec<_>>(),
+            nuggets.values().collect::<Vec<_>>()
+        );
+        assert_eq!(0, <[BaseMetal<f32>]>::values(&[]).count());
+
+        let alloy = Alloys::ALL[0].get_default();
+        let total: i32 = alloy.nuggets().values().sum();
+        assert_eq!(alloy.nuggets().iter().map(|n| **n).sum::<i32>(), total);
+    }
+}
+
+#[cfg(test)]
+mod base_metal_combine_tests {
+    use crate::MetalMismatch;
+    use crate::prelude::*;
+    use crate::unit_constants::NUGGET_UNIT_AMOUNT;
+
+    #[test]
+    fn test_map_keeps_metal() {
+        for metal in Metal::ALL {
+            let units = metal.with_value(4).map(|n| n * NUGGET_UNIT_AMOUNT);
+            assert_eq!(metal.with_value(20), units);
+            let basis_points = metal.with_value(0.25_f32).map(|p| (p * 10_000.0) as i32);
+            assert_eq!(metal.with_value(2500), basis_points);
+        }
+    }
+
+    #[test]
+    fn test_zip_and_combine_same_metal() {
+        for metal in Metal::ALL {
+            assert_eq!(
+                Ok(metal.with_value((3, "three"))),
+                metal.with_value(3).zip(metal.with_value("three"))
+            );
+            assert_eq!(
+                Ok(metal.with_value(7)),
+                metal
+                    .with_value(3)
+                    .try_combine(metal.with_value(4), |a, b| a + b)
+            );
+        }
+    }
+
+    #[test]
+    fn test_mismatched_metals() {
+        for expected in Metal::ALL {
+            for actual in Metal::ALL.into_iter().filter(|m| *m != expected) {
+                let error = MetalMismatch { expected, actual };
+                assert_eq!(Err(error), expected.with_value(1).zip(actual.with_value(2)));
+                assert_eq!(
+                    Err(error),
+                    expected
+                        .with_value(1)
+                        .try_combine(actual.with_value(2), |a, b| a + b)
+                );
+                assert_eq!(
+                    format!("expected {} but found {}", expected.name(), actual.name()),
+                    error.to_string()
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod base_metal_deref_mut_tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_mutation_keeps_metal() {
+        for metal in Metal::ALL {
+            let mut nuggets = metal.with_value(10);
+            *nuggets += 5;
+            *nuggets -= 1;
+            assert_eq!(metal.with_value(14), nuggets);
+            assert_eq!(metal, nuggets.metal());
+
+            let mut label = metal.with_value(String::from("ingot"));
+            label.push('s');
+            assert_eq!(metal.with_value(String::from("ingots")), label);
+        }
+    }
+
+    #[test]
+    fn test_mutation_through_slices() {
+        let mut nuggets: Vec<BaseMetal<i32>> = Metal::ALL.map(|m| m.with_value(1)).into();
+        for n in nuggets.iter_mut() {
+            **n *= 3;
+        }
+        assert_eq!(Metal::ALL.map(|m| m.with_value(3)).as_slice(), &nuggets[..]);
+    }
+}
+
+#[cfg(test)]
+mod base_metal_ops_tests {
+    use crate::MetalMismatch;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_same_metal() {
+        for metal in Metal::ALL {
+            let (a, b) = (metal.with_value(18), metal.with_value(92));
+            assert_eq!(Ok(metal.with_value(110)), a + b);
+            assert_eq!(Ok(metal.with_value(74)), b - a);
+            assert_eq!(
+                Ok(metal.with_value(0.5)),
+                metal.with_value(0.25) + metal.with_value(0.25)
+            );
+        }
+    }
+
+    #[test]
+    fn test_different_metals() {
+        for expected in Metal::ALL {
+            for actual in Metal::ALL.into_iter().filter(|m| *m != expected) {
+                let error = Err(MetalMismatch { expected, actual });
+                assert_eq!(error, expected.with_value(18) + actual.with_value(2));
+                assert_eq!(error, expected.with_value(18) - actual.with_value(2));
+            }
+        }
+    }
+
+    #[test]
+    fn test_scaling() {
+        for metal in Metal::ALL {
+            assert_eq!(metal.with_value(60), metal.with_value(12) * 5);
+            assert_eq!(metal.with_value(1.5), metal.with_value(0.5) * 3.0);
+        }
+        let alloy = Alloys::TinBronze.get_default();
+        let batches: Vec<_> = alloy.nuggets().iter().map(|&n| n * 3).collect();
+        for (scaled, n) in batches.iter().zip(alloy.nuggets()) {
+            assert_eq!(n.metal(), scaled.metal());
+            assert_eq!(**n * 3, **scaled);
+        }
+    }
+}
+
+#[cfg(test)]
+mod same_metal_tests {
+    use crate::prelude::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_same_metal_ignores_value() {
+        for a in Metal::ALL {
+            for b in Metal::ALL {
+                assert_eq!(a == b, a.with_value(18).same_metal(&b.with_value(92.0)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_metal_keys_collide() {
+        let mut inventory: HashMap<Metal, i32> = HashMap::new();
+        for n in [Copper(18), Tin(2), Copper(92)] {
+            *inventory.entry(n.metal()).or_default() += *n;
+        }
+        assert_eq!(2, inventory.len());
+        assert_eq!(Some(&110), inventory.get(&Metal::Copper));
+        assert_ne!(Copper(18), Copper(92));
+    }
+
+    #[test]
+    fn test_find_metal() {
+        for kind in Alloys::ALL {
+            let alloy = kind.get_default();
+            for metal in Metal::ALL {
+                let found = alloy.nuggets().find_metal(metal);
+                assert_eq!(kind.constituent_metals().contains(&metal), found.is_some());
+                assert!(found.is_none_or(|n| n.metal() == metal));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod lookup_by_metal_tests {
+    use crate::alloy_types::AlloyType;
+    use crate::prelude::*;
+
+    fn check<T: AlloyType + Default>(kind: Alloys) {
+        let data = AlloyData::<T>::default();
+        let alloy = kind.get_default();
+        for (i, metal) in kind.constituent_metals().iter().enumerate() {
+            assert_eq!(Some(*data.percentages()[i]), data.percentage_of(*metal));
+            assert_eq!(Some(*data.nuggets()[i]), data.nuggets_of(*metal));
+            assert_eq!(data.percentage_of(*metal), alloy.percentage_of(*metal));
+            assert_eq!(data.nuggets_of(*metal), alloy.nuggets_of(*metal));
+        }
+        let absent: Vec<Metal> = Metal::ALL
+            .into_iter()
+            .filter(|m| !kind.constituent_metals().contains(m))
+            .collect();
+        assert!(!absent.is_empty());
+        for metal in absent {
+            assert_eq!(None, data.percentage_of(metal));
+            assert_eq!(None, data.nuggets_of(metal));
+            assert_eq!(None, alloy.percentage_of(metal));
+            assert_eq!(None, alloy.nuggets_of(metal));
+        }
+    }
+
+    #[test]
+    fn test_every_alloy() {
+        for_each_alloy!(check(Alloys));
+    }
+}
+
+#[cfg(test)]
+mod set_percentage_of_tests {
+    use crate::AlloyError;
+    use crate::alloy_types::AlloyType;
+    use crate::prelude::*;
+
+    fn percents(alloy: &[BaseMetal<f32>]) -> Vec<i32> {
+        alloy.iter().map(|p| (**p * 100.0).round() as i32).collect()
+    }
+
+    #[test]
+    fn test_bismuth_bronze() {
+        let mut alloy = AlloyData::<BismuthBronze>::default();
+        // Zinc and Bismuth would share 30% evenly, but Zinc is held at its 20% minimum
+        alloy
+            .set_percentage_of(Metal::Copper, 0.7)
+            .expect("should be valid");
+        assert_eq!(vec![70, 20, 10], percents(alloy.percentages()));
+        // Zinc would drop below 20% again, so Copper gives up the difference
+        alloy
+            .set_percentage_of(Metal::Bismuth, 0.12)
+            .expect("should be valid");
+        assert_eq!(vec![68, 20, 12], percents(alloy.percentages()));
+        // Setting a value to what it already is changes nothing
+        alloy
+            .set_percentage_of(Metal::Zinc, 0.2)
+            .expect("should be valid");
+        assert_eq!(vec![68, 20, 12], percents(alloy.percentages()));
+        alloy
+            .set_percentage_of(Metal::Bismuth, 0.1)
+            .expect("should be valid");
+        assert_eq!(vec![70, 20, 10], percents(alloy.percentages()));
+        // Both others end at their maximums
+        alloy
+            .set_percentage_of(Metal::Copper, 0.5)
+            .expect("should be valid");
+        assert_eq!(vec![50, 30, 20], percents(alloy.percentages()));
+    }
+
+    #[test]
+    fn test_black_bronze() {
+        let mut alloy = AlloyData::<BlackBronze>::default();
+        assert_eq!(vec![84, 8, 8], percents(alloy.percentages()));
+        alloy
+            .set_percentage_of(Metal::Copper, 0.72)
+            .expect("should be valid");
+        assert_eq!(vec![72, 14, 14], percents(alloy.percentages()));
+        alloy
+            .set_percentage_of(Metal::Gold, 0.16)
+            .expect("should be valid");
+        assert_eq!(vec![70, 16, 14], percents(alloy.percentages()));
+        // Gold would pass its 16% maximum, so it is held there and Silver takes the rest
+        alloy
+            .set_percentage_of(Metal::Copper, 0.68)
+            .expect("should be valid");
+        assert_eq!(vec![68, 16, 16], percents(alloy.percentages()));
+        // Silver would pass its maximum in turn, leaving the rest to Copper
+        alloy
+            .set_percentage_of(Metal::Gold, 0.08)
+            .expect("should be valid");
+        assert_eq!(vec![76, 8, 16], percents(alloy.percentages()));
+
+        let before = alloy.clone();
+        assert_eq!(
+            Err(AlloyError::InvalidPercentages),
+            alloy.set_percentage_of(Metal::Copper, 0.9)
+        );
+        assert_eq!(
+            Err(AlloyError::InvalidPercentages),
+            alloy.set_percentage_of(Metal::Gold, 0.07)
+        );
+        assert_eq!(before, alloy);
+    }
+
+    #[test]
+    fn test_two_metal_alloys() {
+        let mut alloy = AlloyData::<TinBronze>::default();
+        alloy
+            .set_percentage_of(Metal::Tin, 0.1)
+            .expect("should be valid");
+        assert_eq!(&[Copper(0.9), Tin(0.1)], alloy.percentages());
+        alloy
+            .set_percentage_of(Metal::Copper, 0.88)
+            .expect("should be valid");
+        assert_eq!(&[Copper(0.88), Tin(0.12)], alloy.percentages());
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let mut alloy = AlloyData::<BismuthBronze>::default();
+        let before = alloy.clone();
+        assert_eq!(
+            Err(AlloyError::UnknownConstituent(Metal::Tin)),
+            alloy.set_percentage_of(Metal::Tin, 0.1)
+        );
+        for value in [f32::NAN, f32::INFINITY, -0.6, 0.8, 0.49] {
+            assert_eq!(
+                Err(AlloyError::InvalidPercentages),
+                alloy.set_percentage_of(Metal::Copper, value)
+            );
+        }
+        assert_eq!(before, alloy);
+    }
+
+    fn check<T: AlloyType + Default + Clone + std::fmt::Debug>() {
+        let alloy = AlloyData::<T>::default();
+        for binding in alloy.slider_bindings() {
+            for percent in 0..=100 {
+                let value = percent as f32 / 100.0;
+                let mut changed = alloy.clone();
+                let result = changed.set_percentage_of(binding.metal, value);
+                let reachable =
+                    value >= binding.reachable.min - 1e-6 && value <= binding.reachable.max + 1e-6;
+                assert_eq!(reachable, result.is_ok(), "{:?} {percent}%", binding.metal);
+                if result.is_ok() {
+                    assert_eq!(Some(value), changed.percentage_of(binding.metal));
+                    let total: f32 = changed.percentages().values().sum();
+                    assert!((total - 1.0).abs() < 1e-4, "{total}");
+                    assert!(AlloyData::<T>::check_valid_percentages(changed.percentages()).is_ok());
+                } else {
+                    assert_eq!(alloy, changed);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_reachable_values_always_succeed() {
+        for_each_alloy!(check);
+    }
+}
+
+#[cfg(test)]
+mod constituent_rows_tests {
+    use crate::alloy_types::AlloyType;
+    use crate::prelude::*;
+    use crate::unit_constants::NUGGET_UNIT_AMOUNT;
+
+    fn check<T: AlloyType + Default>(kind: Alloys) {
+        let data = AlloyData::<T>::default();
+        let alloy = kind.get_default();
+        assert_eq!(kind.num_constituents(), data.constituents().len());
+        assert_eq!(kind.num_constituents(), alloy.constituents().count());
+        for (i, (row, other)) in data.constituents().zip(alloy.constituents()).enumerate() {
+            assert_eq!(row, other);
+            assert_eq!(kind.constituent_metals()[i], row.metal);
+            assert_eq!(*kind.percentage_ranges()[i], row.range);
+            assert_eq!(*data.percentages()[i], row.percent);
+            assert_eq!(*data.nuggets()[i], row.nuggets);
+            assert_eq!(row.nuggets * NUGGET_UNIT_AMOUNT, row.units());
+        }
+    }
+
+    #[test]
+    fn test_every_alloy() {
+        for_each_alloy!(check(Alloys));
+    }
+}
+
+#[cfg(test)]
+mod builder_tests {
+    use crate::AlloyError;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_full_specification() {
+        let built = AlloyData::<BismuthBronze>::builder()
+            .percentages([Bismuth(0.15), Copper(0.6), Zinc(0.25)])
+            .ingots(4)
+            .build();
+        let expected =
+            AlloyData::<BismuthBronze>::try_new([Copper(0.6), Zinc(0.25), Bismuth(0.15)], 4);
+        assert_eq!(expected, built);
+    }
+
+    #[test]
+    fn test_defaults() {
+        for built in [
+            AlloyData::<Brass>::builder().build(),
+            AlloyData::<Brass>::builder()
+                .percentage(Metal::Zinc, 0.4)
+                .default_ratio()
+                .build(),
+        ] {
+            assert_eq!(Ok(AlloyData::<Brass>::default()), built);
+        }
+        let alloy = AlloyData::<Electrum>::builder()
+            .ingots(9)
+            .build()
+            .expect("should be valid");
+        assert_eq!(
+            AlloyData::<Electrum>::default().percentages(),
+            alloy.percentages()
+        );
+        assert_eq!(9, alloy.num_ingots());
+    }
+
+    #[test]
+    fn test_partial_overrides() {
+        let alloy = AlloyData::<TinBronze>::builder()
+            .percentage(Metal::Tin, 0.1)
+            .build()
+            .expect("should be valid");
+        assert_eq!(&[Copper(0.9), Tin(0.1)], alloy.percentages());
+
+        // The later value for the same metal wins
+        let alloy = AlloyData::<BlackBronze>::builder()
+            .percentage(Metal::Copper, 0.8)
+            .percentage(Metal::Copper, 0.72)
+            .build()
+            .expect("should be valid");
+        assert_eq!(
+            &[Copper(0.72), Gold(0.14), Silver(0.14)],
+            alloy.percentages()
+        );
+
+        let alloy = AlloyData::<BismuthBronze>::builder()
+            .percentage(Metal::Zinc, 0.3)
+            .percentage(Metal::Bismuth, 0.1)
+            .ingots(2)
+            .build()
+            .expect("should be valid");
+        assert_eq!(&[Copper(0.6), Zinc(0.3), Bismuth(0.1)], alloy.percentages());
+        assert_eq!(2, alloy.num_ingots());
+    }
+
+    #[test]
+    fn test_invalid_combinations() {
+        let bronze = AlloyData::<TinBronze>::builder;
+        assert_eq!(
+            Err(AlloyError::UnknownConstituent(Metal::Lead)),
+            bronze().percentage(Metal::Lead, 0.1).build()
+        );
+        assert_eq!(
+            Err(AlloyError::UnknownConstituent(Metal::Zinc)),
+            bronze()
+                .percentages([Copper(0.9), Tin(0.05), Zinc(0.05)])
+                .build()
+        );
+        assert_eq!(
+            Err(AlloyError::InvalidPercentages),
+            bronze().percentage(Metal::Tin, 0.2).build()
+        );
+        assert_eq!(
+            Err(AlloyError::InvalidPercentages),
+            bronze().percentage(Metal::Tin, f32::NAN).build()
+        );
+        assert_eq!(
+            Err(AlloyError::InvalidPercentages),
+            bronze().percentages([Copper(0.9), Tin(0.12)]).build()
+        );
+        assert_eq!(Err(AlloyError::TooFewIngots), bronze().ingots(0).build());
+        assert_eq!(Err(AlloyError::TooManyIngots), bronze().ingots(21).build());
+        // Bismuth Bronze cannot make up 45% from Zinc and Bismuth
+        assert_eq!(
+            Err(AlloyError::InvalidPercentages),
+            AlloyData::<BismuthBronze>::builder()
+                .percentage(Metal::Copper, 0.45)
+                .build()
+        );
+    }
+}
+
+#[cfg(test)]
+mod molybdochalkos_tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_default() {
+        let alloy = AlloyData::<Molybdochalkos>::try_new([Lead(0.92), Copper(0.08)], 1).unwrap();
+        assert_eq!(AlloyData::<Molybdochalkos>::default(), alloy);
+        assert_eq!(20, alloy.max_ingots());
+        assert_eq!(
+            alloy.max_ingots(),
+            AlloyData::<Molybdochalkos>::default().max_ingots()
+        );
+    }
+}
+
+#[cfg(test)]
+mod silver_solder_tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_default() {
+        let alloy = AlloyData::<SilverSolder>::try_new([Tin(0.50), Silver(0.50)], 1).unwrap();
+        assert_eq!(AlloyData::<SilverSolder>::default(), alloy);
+        assert_eq!(25, alloy.max_ingots());
+        assert_eq!(
+            alloy.max_ingots(),
+            AlloyData::<SilverSolder>::default().max_ingots()
+        );
+    }
+}
+
+#[cfg(test)]
+mod default_drift_tests {
+    use crate::alloy_types::AlloyType;
+    use crate::prelude::*;
+
+    fn check<T: AlloyType + Default + std::fmt::Debug>() {
+        let default = AlloyData::<T>::default();
+        let built = AlloyData::<T>::try_new(T::default_percentages(), 1).unwrap();
+        assert_eq!(built, default);
+        // Equality only compares the inputs, so check the calculated values too
+        assert_eq!(built.nuggets(), default.nuggets());
+        assert_eq!(built.max_ingots(), default.max_ingots());
+        assert_eq!(built.percentages(), default.percentages());
+    }
+
+    #[test]
+    fn test_defaults_match_try_new() {
+        for_each_alloy!(check);
+    }
+}
+
+#[cfg(test)]
+mod alloy_eq_tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_equal_plans() {
+        for kind in Alloys::ALL {
+            assert_eq!(kind.get_default(), kind.get_default());
+            let plan = kind.try_new(kind.default_percentages(), 2).unwrap();
+            assert_eq!(plan, plan.clone());
+        }
+    }
+
+    #[test]
+    fn test_different_ingot_counts() {
+        for kind in Alloys::ALL {
+            let one = kind.try_new(kind.default_percentages(), 1).unwrap();
+            let two = kind.try_new(kind.default_percentages(), 2).unwrap();
+            assert_ne!(one, two);
+        }
+    }
+
+    #[test]
+    fn test_different_alloys() {
+        for a in Alloys::ALL {
+            for b in Alloys::ALL.into_iter().filter(|b| *b != a) {
+                assert_ne!(a.get_default(), b.get_default());
+            }
+        }
+    }
+
+    #[test]
+    fn test_debug_names_alloy() {
+        for kind in Alloys::ALL {
+            let debug = format!("{:?}", kind.get_default());
+            assert!(debug.starts_with(&format!("{kind:?}(")), "{debug}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod dedup_tests {
+    use crate::prelude::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_hash_set_dedups_noisy_percentages() {
+        // Just above 0.92 would be outside Tin Bronze's copper range, so approach it from below
+        let noisy: f32 = "0.9199999".parse().unwrap();
+        assert_ne!(0.92, noisy);
+        let mut plans = HashSet::new();
+        for copper in [0.92, noisy, 0.92, noisy] {
+            let plan = AlloyData::<TinBronze>::try_new([Copper(copper), Tin(0.08)], 4);
+            plans.insert(plan.unwrap());
+        }
+        assert_eq!(1, plans.len());
+
+        let mut alloys: HashSet<Alloy> = HashSet::new();
+        for kind in Alloys::ALL {
+            alloys.insert(kind.get_default());
+            alloys.insert(kind.try_new(kind.default_percentages(), 1).unwrap());
+            alloys.insert(kind.try_new(kind.default_percentages(), 2).unwrap());
+        }
+        assert_eq!(2 * Alloys::ALL.len(), alloys.len());
+        let noisy_bronze = Alloys::TinBronze.try_new([Tin(0.08), Copper(noisy)], 1);
+        assert!(alloys.contains(&noisy_bronze.unwrap()));
+    }
+}
+
+#[cfg(test)]
+mod ingot_count_tests {
+    use crate::AlloyError::*;
+    use crate::IngotCount;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_boundaries() {
+        assert_eq!(Err(TooFewIngots), IngotCount::new(i32::MIN));
+        assert_eq!(Err(TooFewIngots), IngotCount::new(0));
+        assert_eq!(Ok(IngotCount::MIN), IngotCount::new(1));
+        assert_eq!(Ok(IngotCount::MAX), IngotCount::new(25));
+        assert_eq!(Err(TooManyIngots), IngotCount::new(26));
+        assert_eq!(Err(TooManyIngots), IngotCount::try_from(i32::MAX));
+        assert_eq!(1, IngotCount::MIN.get());
+        assert_eq!(25, i32::from(IngotCount::MAX));
+    }
+
+    #[test]
+    fn test_i32_and_newtype_inputs_agree() {
+        let percentages = [Tin(0.5), Silver(0.5)];
+        for num_ingots in [0, 1, 25, 26] {
+            let plain = AlloyData::<SilverSolder>::try_new(percentages, num_ingots);
+            match IngotCount::new(num_ingots) {
+                Ok(count) => {
+                    let typed = AlloyData::<SilverSolder>::try_new(percentages, count);
+                    assert_eq!(plain, typed);
+                    let typed = typed.unwrap();
+                    assert_eq!(count, typed.ingot_count());
+                    assert_eq!(num_ingots, typed.ingot_count().get());
+                    let alloy = Alloys::SilverSolder.try_new(percentages, count).unwrap();
+                    assert_eq!(count, alloy.ingot_count());
+                }
+                Err(e) => {
+                    assert_eq!(Err(e), plain);
+                    assert_eq!(
+                        Err(e),
+                        Alloys::SilverSolder.try_new(percentages, num_ingots)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_num_ingots() {
+        let mut alloy = AlloyData::<SilverSolder>::default();
+        let mut runtime = Alloys::SilverSolder.get_default();
+        for (num_ingots, expected) in [
+            (0, Err(TooFewIngots)),
+            (1, Ok(())),
+            (25, Ok(())),
+            (26, Err(TooManyIngots)),
+        ] {
+            assert_eq!(expected, alloy.set_num_ingots(num_ingots));
+            assert_eq!(expected, runtime.set_num_ingots(num_ingots));
+        }
+        assert_eq!(IngotCount::MAX, alloy.ingot_count());
+        alloy.set_num_ingots(IngotCount::MIN).unwrap();
+        runtime.set_num_ingots(IngotCount::MIN).unwrap();
+        assert_eq!(1, alloy.num_ingots());
+        assert_eq!(1, runtime.num_ingots());
+    }
+
+    #[test]
+    fn test_with_options_takes_ingot_count() {
+        let options = crate::CreateOptions::default();
+        let percentages = [Tin(0.5), Silver(0.5)];
+        let count = IngotCount::new(4).unwrap();
+        let typed = AlloyData::<SilverSolder>::try_new_with_options(percentages, count, options);
+        assert_eq!(
+            AlloyData::<SilverSolder>::try_new_with_options(percentages, 4, options),
+            typed
+        );
+        assert_eq!(count, typed.unwrap().alloy.ingot_count());
+        let runtime = Alloys::SilverSolder.try_new_with_options(percentages, count, options);
+        assert_eq!(count, runtime.unwrap().alloy.ingot_count());
+        // The number of ingots is checked before the percentages
+        assert_eq!(
+            Err(TooFewIngots),
+            AlloyData::<SilverSolder>::try_new_with_options([Tin(2.0)], 0, options)
+        );
+        assert_eq!(
+            Err(TooManyIngots),
+            Alloys::SilverSolder.try_new_with_options([Tin(2.0)], 26, options)
+        );
+    }
+}
+
+#[cfg(test)]
+mod percent_type_tests {
+    use crate::prelude::*;
+    use crate::{AlloyError, Percent, PercentError, Percentages};
+
+    fn whole(value: f32) -> Percent {
+        Percent::from_whole(value).unwrap()
+    }
+
+    #[test]
+    fn test_constructors() {
+        assert_eq!(Percent::from_fraction(0.92), Percent::from_whole(92.0));
+        assert_eq!(Ok(0.0), Percent::from_fraction(0.0).map(f32::from));
+        assert_eq!(Ok(1.0), Percent::from_whole(100.0).map(Percent::fraction));
+        assert_eq!(Ok(50.0), Percent::try_from(0.5).map(Percent::whole));
+        for bad in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            assert_eq!(Err(PercentError::NotFinite), Percent::from_fraction(bad));
+            assert_eq!(Err(PercentError::NotFinite), Percent::from_whole(bad));
+        }
+        for bad in [-0.01, 1.01, 92.0] {
+            assert_eq!(Err(PercentError::OutOfBounds), Percent::from_fraction(bad));
+        }
+        for bad in [-1.0, 100.5, f32::MAX] {
+            assert_eq!(Err(PercentError::OutOfBounds), Percent::from_whole(bad));
+        }
+        assert_eq!(
+            AlloyError::InvalidPercentages,
+            AlloyError::from(PercentError::NotFinite)
+        );
+    }
+
+    #[test]
+    fn test_typed_inputs_match_floats() {
+        for kind in Alloys::ALL {
+            let floats = kind.default_percentages();
+            let typed: Percentages = floats
+                .iter()
+                .map(|p| p.map(|v| Percent::from_fraction(v).unwrap()))
+                .collect();
+            assert_eq!(kind.try_new(floats, 2), kind.try_new(typed, 2));
+            let alloy = kind.get_default();
+            let round_trip: Vec<f32> = alloy
+                .percentages_typed()
+                .iter()
+                .map(|p| p.fraction())
+                .collect();
+            assert_eq!(alloy.percentages().values().collect::<Vec<_>>(), round_trip);
+        }
+    }
+
+    #[test]
+    fn test_set_percentages_with_percents() {
+        let mut alloy = AlloyData::<Brass>::default();
+        alloy
+            .set_percentages(Percentages::from([Zinc(whole(35.0)), Copper(whole(65.0))]))
+            .unwrap();
+        assert_eq!(&[Copper(0.65), Zinc(0.35)], alloy.percentages());
+        assert_eq!(
+            &[Copper(whole(65.0)), Zinc(whole(35.0))],
+            &alloy.percentages_typed()[..]
+        );
+        // A valid percent can still be outside the alloy's range
+        assert_eq!(
+            Err(AlloyError::InvalidPercentages),
+            alloy.set_percentages(Percentages::from([Copper(whole(50.0)), Zinc(whole(50.0))]))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_as_fraction() {
+        let json = serde_json::to_string(&whole(8.0)).unwrap();
+        assert_eq!("0.08", json);
+        assert_eq!(whole(8.0), serde_json::from_str::<Percent>(&json).unwrap());
+        assert!(serde_json::from_str::<Percent>("92.0").is_err());
+    }
+}
+
+#[cfg(test)]
+mod whole_percent_input_tests {
+    use crate::AlloyError::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_both_styles_agree() {
+        for kind in Alloys::ALL {
+            let fractions = kind.default_percentages();
+            let wholes: Vec<_> = fractions.iter().map(|p| p.map(|v| v * 100.0)).collect();
+            for num_ingots in [1, 3] {
+                assert_eq!(
+                    kind.try_new(fractions, num_ingots),
+                    kind.try_new_whole_percent(&wholes, num_ingots),
+                    "{kind}"
+                );
+            }
+        }
+        let two = AlloyData::<Brass>::try_new_whole_percent([Zinc(35.0), Copper(65.0)], 4);
+        assert_eq!(
+            AlloyData::<Brass>::try_new([Copper(0.65), Zinc(0.35)], 4),
+            two
+        );
+        let three = AlloyData::<BlackBronze>::try_new_whole_percent(
+            [Copper(76.0), Gold(12.0), Silver(12.0)],
+            2,
+        );
+        assert_eq!(
+            AlloyData::<BlackBronze>::try_new([Copper(0.76), Gold(0.12), Silver(0.12)], 2),
+            three
+        );
+    }
+
+    #[test]
+    fn test_fractions_are_never_rescaled() {
+        // Fractions still go through try_new untouched, and are far too small as whole percents
+        let fractions = [Copper(0.6), Zinc(0.2), Bismuth(0.2)];
+        assert!(AlloyData::<BismuthBronze>::try_new(fractions, 1).is_ok());
+        assert_eq!(
+            Err(InvalidPercentages),
+            AlloyData::<BismuthBronze>::try_new_whole_percent(fractions, 1)
+        );
+        assert_eq!(
+            Err(InvalidPercentages),
+            AlloyData::<BismuthBronze>::try_new([Copper(60.0), Zinc(20.0), Bismuth(20.0)], 1)
+        );
+    }
+
+    #[test]
+    fn test_mixed_scales() {
+        assert_eq!(
+            Err(MixedPercentScales),
+            AlloyData::<TinBronze>::try_new_whole_percent([Copper(0.92), Tin(8.0)], 1)
+        );
+        assert_eq!(
+            Err(MixedPercentScales),
+            Alloys::BismuthBronze
+                .try_new_whole_percent([Copper(60.0), Zinc(0.2), Bismuth(20.0)], 1)
+        );
+        // Other errors are still reported normally
+        assert_eq!(
+            Err(InvalidPercentages),
+            AlloyData::<TinBronze>::try_new_whole_percent([Copper(80.0), Tin(20.0)], 1)
+        );
+        assert_eq!(
+            Err(UnknownConstituent(Metal::Zinc)),
+            AlloyData::<TinBronze>::try_new_whole_percent([Copper(92.0), Zinc(8.0)], 1)
+        );
+        assert_eq!(
+            Err(TooFewIngots),
+            AlloyData::<TinBronze>::try_new_whole_percent([Copper(92.0), Tin(8.0)], 0)
+        );
+        assert_eq!(
+            Err(InvalidPercentages),
+            AlloyData::<TinBronze>::try_new_whole_percent([Copper(f32::NAN), Tin(8.0)], 1)
+        );
+    }
+}
+
+#[cfg(test)]
+mod normalized_input_tests {
+    use crate::AlloyError;
+    use crate::prelude::*;
+
+    fn total(percentages: &[BaseMetal<f32>]) -> f32 {
+        percentages.values().sum()
+    }
+
+    #[test]
+    fn test_try_new_normalized_totals() {
+        for percentages in [
+            [Copper(0.6), Zinc(0.25), Bismuth(0.14)],
+            [Copper(0.6), Zinc(0.25), Bismuth(0.15)],
+            [Copper(0.609), Zinc(0.25), Bismuth(0.15)],
+        ] {
+            let alloy = AlloyData::<BismuthBronze>::try_new_normalized(percentages, 3)
+                .expect("should be valid");
+            assert!((total(alloy.percentages()) - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_near_miss_totals() {
+        let near_miss = [Copper(0.915), Tin(0.08)];
+        assert_eq!(
+            Err(AlloyError::InvalidPercentages),
+            AlloyData::<TinBronze>::try_new(near_miss, 1)
+        );
+        let mut alloy = AlloyData::<TinBronze>::default();
+        assert_eq!(
+            Err(AlloyError::InvalidPercentages),
+            alloy.set_percentages(near_miss)
+        );
+        assert_eq!(AlloyData::<TinBronze>::default(), alloy);
+        let mut alloy = AlloyData::<TinBronze>::try_new_normalized(near_miss, 1).unwrap();
+        assert!((total(alloy.percentages()) - 1.0).abs() < 1e-6);
+        assert_eq!(20, alloy.nuggets().iter().map(|n| **n).sum::<i32>());
+        alloy.set_percentages_normalized(near_miss).unwrap();
+    }
+
+    #[test]
+    fn test_set_percentages_normalized_totals() {
+        let mut alloy = AlloyData::<BismuthBronze>::default();
+        for percentages in [
+            [Copper(0.6), Zinc(0.25), Bismuth(0.14)],
+            [Copper(0.6), Zinc(0.25), Bismuth(0.15)],
+            [Copper(0.609), Zinc(0.25), Bismuth(0.15)],
+        ] {
+            alloy
+                .set_percentages_normalized(percentages)
+                .expect("should be valid");
+            assert!((total(alloy.percentages()) - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_normalized_out_of_range() {
+        // Tin bronze at its copper maximum rescales past it
+        assert_eq!(
+            Err(AlloyError::InvalidPercentages),
+            AlloyData::<TinBronze>::try_new_normalized([Copper(0.92), Tin(0.075)], 1)
+        );
+
+        let mut alloy = AlloyData::<TinBronze>::default();
+        alloy
+            .set_percentages([Copper(0.9), Tin(0.1)])
+            .expect("should be valid");
+        assert_eq!(
+            Err(AlloyError::InvalidPercentages),
+            alloy.set_percentages_normalized([Copper(0.92), Tin(0.075)])
+        );
+        assert_eq!(&[Copper(0.9), Tin(0.1)], alloy.percentages());
+    }
+
+    #[test]
+    fn test_alloys_normalized() {
+        let alloy = Alloys::Brass
+            .try_new_normalized([Copper(0.655), Zinc(0.35)], 2)
+            .expect("should be valid");
+        assert!((total(alloy.percentages()) - 1.0).abs() < 1e-6);
+
+        let mut alloy = Alloys::Brass.get_default();
+        alloy
+            .set_percentages_normalized([Copper(0.645), Zinc(0.35)])
+            .expect("should be valid");
+        assert!((total(alloy.percentages()) - 1.0).abs() < 1e-6);
+    }
+}
+
+#[cfg(test)]
+mod clamped_percentages_tests {
+    use crate::prelude::*;
+    use crate::{AlloyError, ClampedValue};
+
+    fn clamped(metal: Metal, requested: f32, value: f32, clamped: bool) -> ClampedValue {
+        ClampedValue {
+            metal,
+            requested,
+            value,
+            clamped,
+        }
+    }
+
+    #[test]
+    fn test_clamp_cascades_to_lower_bound() {
+        // Zinc would drop below 20% when sharing the excess, so Bismuth takes the rest
+        let mut alloy = AlloyData::<BismuthBronze>::default();
+        let report = alloy
+            .set_percentages_clamped([Copper(0.8), Zinc(0.25), Bismuth(0.15)])
+            .expect("should be valid");
+        assert_eq!(&[Copper(0.7), Zinc(0.2), Bismuth(0.1)], alloy.percentages());
+        assert_eq!(
+            vec![
+                clamped(Metal::Copper, 0.8, 0.7, true),
+                clamped(Metal::Zinc, 0.25, 0.2, false),
+                clamped(Metal::Bismuth, 0.15, 0.1, false),
+            ],
+            report.adjustments
+        );
+    }
+
+    #[test]
+    fn test_clamp_cascades_to_upper_bound() {
+        // Zinc would rise above 30% when sharing the shortfall, so Bismuth takes the rest
+        let mut alloy = AlloyData::<BismuthBronze>::default();
+        let report = alloy
+            .set_percentages_clamped([Copper(0.45), Zinc(0.28), Bismuth(0.18)])
+            .expect("should be valid");
+        assert_eq!(&[Copper(0.5), Zinc(0.3), Bismuth(0.2)], alloy.percentages());
+        assert_eq!(3, report.adjustments.len());
+        assert!(report.adjustments[0].clamped);
+        assert!((report.adjustments[0].change() - 0.05).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_clamp_two_onto_one() {
+        let mut alloy = AlloyData::<BismuthBronze>::default();
+        let report = alloy
+            .set_percentages_clamped([Bismuth(0.25), Copper(0.65), Zinc(0.4)])
+            .expect("should be valid");
+        assert_eq!(&[Copper(0.5), Zinc(0.3), Bismuth(0.2)], alloy.percentages());
+        assert_eq!(
+            vec![
+                clamped(Metal::Copper, 0.65, 0.5, false),
+                clamped(Metal::Zinc, 0.4, 0.3, true),
+                clamped(Metal::Bismuth, 0.25, 0.2, true),
+            ],
+            report.adjustments
+        );
+    }
+
+    #[test]
+    fn test_clamp_valid_input_unchanged() {
+        let mut alloy = AlloyData::<BismuthBronze>::default();
+        let report = alloy
+            .set_percentages_clamped([Copper(0.55), Zinc(0.3), Bismuth(0.15)])
+            .expect("should be valid");
+        assert!(report.is_empty());
+        assert_eq!(
+            &[Copper(0.55), Zinc(0.3), Bismuth(0.15)],
+            alloy.percentages()
+        );
+        assert_eq!(
+            AlloyData::<BismuthBronze>::try_new([Copper(0.55), Zinc(0.3), Bismuth(0.15)], 1)
+                .unwrap()
+                .nuggets(),
+            alloy.nuggets()
+        );
+    }
+
+    #[test]
+    fn test_clamp_inconsistent() {
+        let mut alloy = AlloyData::<BismuthBronze>::default();
+        for percentages in [
+            // Every value pinned at its maximum, totalling more than 100%
+            [Copper(0.75), Zinc(0.35), Bismuth(0.25)],
+            // Every value pinned at its minimum, totalling less than 100%
+            [Copper(0.45), Zinc(0.15), Bismuth(0.05)],
+            // Copper and Zinc pinned at their maximums leave Bismuth below its minimum
+            [Copper(0.8), Zinc(0.4), Bismuth(0.1)],
+        ] {
+            assert_eq!(
+                Err(AlloyError::InvalidPercentages),
+                alloy.set_percentages_clamped(percentages)
+            );
+            assert_eq!(&[Copper(0.6), Zinc(0.2), Bismuth(0.2)], alloy.percentages());
+        }
+    }
+
+    #[test]
+    fn test_clamp_invalid_inputs() {
+        let mut alloy = AlloyData::<BismuthBronze>::default();
+        assert_eq!(
+            Err(AlloyError::MissingConstituent(Metal::Bismuth)),
+            alloy.set_percentages_clamped([Copper(0.6), Zinc(0.4)])
+        );
+        assert_eq!(
+            Err(AlloyError::UnknownConstituent(Metal::Tin)),
+            alloy.set_percentages_clamped([Copper(0.6), Zinc(0.2), Tin(0.2)])
+        );
+        assert_eq!(
+            Err(AlloyError::InvalidPercentages),
+            alloy.set_percentages_clamped([Copper(f32::NAN), Zinc(0.2), Bismuth(0.2)])
+        );
+        assert_eq!(&[Copper(0.6), Zinc(0.2), Bismuth(0.2)], alloy.percentages());
+    }
+}
+
+#[cfg(test)]
+mod adjusting_ingots_tests {
+    use crate::prelude::*;
+    use crate::{AdjustOutcome, AlloyError};
+
+    #[test]
+    fn test_clamps_to_new_max() {
+        let mut alloy = AlloyData::<TinBronze>::try_new([Copper(0.88), Tin(0.12)], 21).unwrap();
+        assert_eq!(21, alloy.max_ingots());
+
+        // The strict setter still refuses
+        let mut strict = alloy.clone();
+        assert_eq!(
+            Err(AlloyError::TooManyIngots),
+            strict.set_percentages([Copper(0.92), Tin(0.08)])
+        );
+        assert_eq!(alloy, strict);
+
+        let outcome = alloy
+            .set_percentages_adjusting_ingots([Copper(0.92), Tin(0.08)])
+            .expect("should be valid");
+        assert_eq!(
+            AdjustOutcome {
+                previous_ingots: 21,
+                num_ingots: 20
+            },
+            outcome
+        );
+        assert!(outcome.clamped());
+        assert_eq!(
+            AlloyData::<TinBronze>::try_new([Copper(0.92), Tin(0.08)], 20).unwrap(),
+            alloy
+        );
+        assert_eq!(&[Copper(0.92), Tin(0.08)], alloy.percentages());
+        assert_eq!(20, alloy.max_ingots());
+    }
+
+    #[test]
+    fn test_no_clamp_needed() {
+        let mut alloy = AlloyData::<TinBronze>::try_new([Copper(0.88), Tin(0.12)], 10).unwrap();
+        let outcome = alloy
+            .set_percentages_adjusting_ingots([Copper(0.92), Tin(0.08)])
+            .expect("should be valid");
+        assert!(!outcome.clamped());
+        assert_eq!(10, outcome.num_ingots);
+        assert_eq!(10, alloy.num_ingots());
+        assert_eq!(&[Copper(0.92), Tin(0.08)], alloy.percentages());
+    }
+
+    #[test]
+    fn test_invalid_percentages_not_applied() {
+        let mut alloy = AlloyData::<TinBronze>::try_new([Copper(0.88), Tin(0.12)], 21).unwrap();
+        let before = alloy.clone();
+        assert_eq!(
+            Err(AlloyError::InvalidPercentages),
+            alloy.set_percentages_adjusting_ingots([Copper(0.8), Tin(0.2)])
+        );
+        assert_eq!(before, alloy);
+        assert_eq!(21, alloy.num_ingots());
+    }
+
+    #[test]
+    fn test_alloy_dispatch() {
+        let mut alloy = Alloys::TinBronze
+            .try_new([Copper(0.88), Tin(0.12)], 21)
+            .unwrap();
+        let outcome = alloy
+            .set_percentages_adjusting_ingots([Copper(0.92), Tin(0.08)])
+            .expect("should be valid");
+        assert_eq!(20, outcome.num_ingots);
+        assert_eq!(20, alloy.num_ingots());
+    }
+}
+
+#[cfg(test)]
+mod ingot_step_tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_increment_past_max() {
+        let mut alloy = AlloyData::<TinBronze>::try_new([Copper(0.92), Tin(0.08)], 19).unwrap();
+        assert_eq!(20, alloy.increment_ingots(1));
+        assert_eq!(20, alloy.increment_ingots(1));
+        assert_eq!(20, alloy.num_ingots());
+        assert_eq!(
+            AlloyData::<TinBronze>::try_new([Copper(0.92), Tin(0.08)], 20).unwrap(),
+            alloy
+        );
+    }
+
+    #[test]
+    fn test_decrement_past_min() {
+        let mut alloy = AlloyData::<TinBronze>::try_new([Copper(0.92), Tin(0.08)], 2).unwrap();
+        assert_eq!(1, alloy.decrement_ingots(1));
+        assert_eq!(1, alloy.decrement_ingots(1));
+        assert_eq!(
+            AlloyData::<TinBronze>::try_new([Copper(0.92), Tin(0.08)], 1).unwrap(),
+            alloy
+        );
+    }
+
+    #[test]
+    fn test_large_steps() {
+        let mut alloy = AlloyData::<TinBronze>::default();
+        assert_eq!(20, alloy.increment_ingots(i32::MAX));
+        assert_eq!(1, alloy.decrement_ingots(i32::MAX));
+        assert_eq!(20, alloy.decrement_ingots(i32::MIN));
+        assert_eq!(1, alloy.increment_ingots(i32::MIN));
+        assert_eq!(8, alloy.increment_ingots(7));
+        assert_eq!(3, alloy.decrement_ingots(5));
+        assert_eq!(3, alloy.increment_ingots(0));
+        assert_eq!(
+            AlloyData::<TinBronze>::try_new([Copper(0.92), Tin(0.08)], 3).unwrap(),
+            alloy
+        );
+    }
+
+    #[test]
+    fn test_alloy_steps() {
+        let mut alloy = Alloys::BismuthBronze.get_default();
+        let max = alloy.max_ingots();
+        assert_eq!(max, alloy.increment_ingots(1000));
+        assert_eq!(max, alloy.num_ingots());
+        assert_eq!(1, alloy.decrement_ingots(1000));
+        assert_eq!(1, alloy.num_ingots());
+    }
+}
+
+#[cfg(test)]
+mod reset_to_default_tests {
+    use crate::alloy::alloy_types::AlloyType;
+    use crate::prelude::*;
+
+    fn check<T: AlloyType + Default + Clone + std::fmt::Debug>() {
+        let default = AlloyData::<T>::default();
+        let mut alloy = default.clone();
+        // Move every constituent away from its default so the reset has something to undo
+        let percentages: Vec<BaseMetal<f32>> = T::percentage_ranges()
+            .iter()
+            .map(|r| r.metal().with_value(r.lerp(0.5)))
+            .collect();
+        alloy.set_percentages_normalized(&percentages).unwrap();
+        alloy.increment_ingots(i32::MAX);
+
+        alloy.reset_to_default();
+        assert_eq!(default, alloy);
+        assert_eq!(default.percentages(), alloy.percentages());
+        assert_eq!(default.nuggets(), alloy.nuggets());
+        assert_eq!(default.max_ingots(), alloy.max_ingots());
+    }
+
+    #[test]
+    fn test_reset_every_alloy_data() {
+        for_each_alloy!(check);
+    }
+
+    #[test]
+    fn test_reset_every_alloy() {
+        for kind in Alloys::iter() {
+            let mut alloy = kind.get_default();
+            alloy.increment_ingots(i32::MAX);
+            alloy.reset_to_default();
+            assert_eq!(kind.get_default(), alloy);
+        }
+    }
+}
+
+#[cfg(test)]
+mod max_ingots_for_tests {
+    use crate::alloy::alloy_types::AlloyType;
+    use crate::prelude::*;
+
+    fn check<T: AlloyType + Default>() {
+        let mut compared = 0;
+        for t in [0.0, 0.25, 0.5, 0.75, 1.0] {
+            // Move the first constituent across its range and let the builder rebalance the rest
+            let first = T::percentage_ranges()[0];
+            let Ok(alloy) = AlloyData::<T>::builder()
+                .percentage(first.metal(), first.lerp(t))
+                .build()
+            else {
+                continue;
+            };
+            assert_eq!(
+                Ok(alloy.max_ingots()),
+                AlloyData::<T>::max_ingots_for(alloy.percentages())
+            );
+            let created = AlloyData::<T>::try_new(alloy.percentages(), 1).unwrap();
+            assert_eq!(
+                Ok(created.max_ingots()),
+                AlloyData::<T>::max_ingots_for(alloy.percentages())
+            );
+            compared += 1;
+        }
+        assert!(compared >= 3);
+        let default = AlloyData::<T>::default();
+        assert_eq!(
+            Ok(default.max_ingots()),
+            AlloyData::<T>::max_ingots_for(T::default_percentages())
+        );
+    }
+
+    #[test]
+    fn test_matches_created_alloys() {
+        for_each_alloy!(check);
+    }
+
+    #[test]
+    fn test_invalid_percentages() {
+        assert_eq!(
+            Err(crate::AlloyError::UnknownConstituent(Metal::Zinc)),
+            AlloyData::<TinBronze>::max_ingots_for([Copper(0.92), Zinc(0.08)])
+        );
+        assert_eq!(
+            Err(crate::AlloyError::InvalidPercentages),
+            AlloyData::<TinBronze>::max_ingots_for([Copper(0.92), Tin(0.2)])
+        );
+    }
+
+    fn check_never_zero<T: AlloyType>() {
+        let defaults = T::default_percentages();
+        for nudge in [-2.0, -1.0, 0.0, 1.0, 2.0] {
+            let mut percentages = defaults.as_ref().to_vec();
+            let last = percentages.last_mut().unwrap();
+            *last = last.map(|p| p + nudge * f32::EPSILON);
+            match AlloyData::<T>::max_ingots_for(&percentages) {
+                Ok(max) => {
+                    assert!(max >= 1);
+                    assert!(
+                        !AlloyData::<T>::achievable_ingot_counts(&percentages)
+                            .unwrap()
+                            .is_empty()
+                    );
+                }
+                Err(error) => {
+                    assert_eq!(
+                        Err(error),
+                        AlloyData::<T>::try_new(&percentages, 1).map(|_| ())
+                    );
+                    assert!(AlloyData::<T>::achievable_ingot_counts(&percentages).is_err());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_accepted_percentages_make_an_ingot() {
+        for_each_alloy!(check_never_zero);
+    }
+}
+
+#[cfg(test)]
+mod nuggets_for_tests {
+    use crate::AlloyError;
+    use crate::alloy::alloy_types::AlloyType;
+    use crate::prelude::*;
+
+    fn check<T: AlloyType>() {
+        let percentages = T::default_percentages();
+        let max = AlloyData::<T>::max_ingots_for(percentages).unwrap();
+        for num_ingots in 1..=max {
+            let alloy = AlloyData::<T>::try_new(percentages, num_ingots).unwrap();
+            assert_eq!(
+                alloy.nuggets(),
+                AlloyData::<T>::nuggets_for(percentages, num_ingots)
+                    .unwrap()
+                    .nuggets()
+            );
+        }
+        for num_ingots in [0, max + 1] {
+            assert_eq!(
+                AlloyData::<T>::try_new(percentages, num_ingots).map(|a| a.nuggets().to_vec()),
+                AlloyData::<T>::nuggets_for(percentages, num_ingots).map(|n| n.nuggets().to_vec())
+            );
+        }
+    }
+
+    #[test]
+    fn test_matches_try_new() {
+        for_each_alloy!(check);
+    }
+
+    #[test]
+    fn test_alloys_matches_try_new() {
+        for kind in Alloys::iter() {
+            for percentages in kind.boundary_mixes() {
+                let Ok(alloy) = kind.try_new(&percentages, 3) else {
+                    continue;
+                };
+                assert_eq!(
+                    alloy.nuggets(),
+                    &*kind.nuggets_for(&percentages, 3).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        for kind in Alloys::iter() {
+            let percentages = kind.default_percentages();
+            assert_eq!(
+                Err(AlloyError::TooFewIngots),
+                kind.nuggets_for(percentages, 0)
+            );
+            assert_eq!(
+                Err(AlloyError::TooManyIngots),
+                kind.nuggets_for(percentages, 1000)
+            );
+            let mut wrong = percentages.to_vec();
+            wrong.reverse();
+            *wrong[0] = 2.0;
+            assert_eq!(
+                Err(AlloyError::InvalidPercentages),
+                kind.nuggets_for(&wrong, 1)
+            );
+            let outsider = Metal::ALL
+                .into_iter()
+                .find(|m| !kind.constituent_metals().contains(m))
+                .unwrap();
+            let mut foreign = percentages.to_vec();
+            foreign[0] = outsider.with_value(*foreign[0]);
+            assert_eq!(
+                Err(AlloyError::UnknownConstituent(outsider)),
+                kind.nuggets_for(&foreign, 1)
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod validation_report_tests {
+    use crate::alloy::alloy_types::AlloyType;
+    use crate::prelude::*;
+    use crate::{ConstituentRange, ValidationIssue::*};
+
+    #[test]
+    fn test_valid_input() {
+        let report = AlloyData::<BismuthBronze>::validate_percentages([
+            Bismuth(0.2),
+            Copper(0.6),
+            Zinc(0.2),
+        ]);
+        assert!(report.is_valid());
+    }
+
+    #[test]
+    fn test_every_problem_reported() {
+        let report = AlloyData::<BismuthBronze>::validate_percentages([
+            Copper(0.75),
+            Tin(0.1),
+            Copper(0.6),
+            Zinc(0.1),
+        ]);
+        assert!(!report.is_valid());
+        assert_eq!(
+            vec![
+                OutOfRange {
+                    metal: Metal::Copper,
+                    value: 0.75,
+                    range: ConstituentRange::new(0.5, 0.7)
+                },
+                UnknownMetal { metal: Metal::Tin },
+                DuplicateMetal {
+                    metal: Metal::Copper
+                },
+                OutOfRange {
+                    metal: Metal::Zinc,
+                    value: 0.1,
+                    range: ConstituentRange::new(0.2, 0.3)
+                },
+                MissingMetal {
+                    metal: Metal::Bismuth
+                },
+            ],
+            report.issues[..5]
+        );
+        let BadTotal { sum } = report.issues[5] else {
+            panic!("expected a bad total, got {:?}", report.issues[5]);
+        };
+        assert!((sum - 1.55).abs() < 1e-6);
+        assert_eq!(6, report.issues.len());
+    }
+
+    #[test]
+    fn test_in_range_but_bad_total() {
+        let report = Alloys::TinBronze.validate_percentages([Copper(0.9), Tin(0.12)]);
+        assert_eq!(1, report.issues.len());
+        assert!(matches!(report.issues[0], BadTotal { sum } if (sum - 1.02).abs() < 1e-6));
+    }
+
+    fn check<T: AlloyType>() {
+        let defaults = T::default_percentages();
+        let mut inputs: Vec<Vec<BaseMetal<f32>>> = vec![
+            defaults.to_vec(),
+            defaults[1..].to_vec(),
+            defaults.iter().map(|p| p.map(|v| v * 1.5)).collect(),
+            [defaults, &defaults[..1]].concat(),
+        ];
+        for range in T::percentage_ranges() {
+            for value in [range.min - 0.01, range.min, range.max, range.max + 0.01] {
+                let mut input = defaults.to_vec();
+                *input
+                    .iter_mut()
+                    .find(|p| p.metal() == range.metal())
+                    .unwrap() = range.metal().with_value(value);
+                inputs.push(input);
+            }
+        }
+        for input in inputs {
+            assert_eq!(
+                AlloyData::<T>::check_valid_percentages(&input).is_ok(),
+                AlloyData::<T>::validate_percentages(&input).is_valid(),
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_agrees_with_check_valid_percentages() {
+        for_each_alloy!(check);
+    }
+}
+
+#[cfg(test)]
+mod constituent_error_tests {
+    use crate::AlloyError::*;
+    use crate::alloy::alloy_types::AlloyType;
+    use crate::help::ErrorContext;
+    use crate::prelude::*;
+
+    fn check<T: AlloyType + Default + std::fmt::Debug>() {
+        let defaults = T::default_percentages();
+        let metals = T::constituent_metals();
+        let n = metals.len();
+        let foreign = Metal::ALL
+            .into_iter()
+            .find(|m| !metals.contains(m))
+            .unwrap();
+
+        // Each position can hold the unknown metal
+        for i in 0..n {
+            let mut input = defaults.to_vec();
+            input[i] = foreign.with_value(*input[i]);
+            assert_eq!(
+                UnknownConstituent(foreign),
+                T::check_ranges_contains(&input).unwrap_err()
+            );
+            assert_eq!(
+                Err(UnknownConstituent(foreign)),
+                AlloyData::<T>::try_new(&input, 1)
+            );
+        }
+
+        // Each constituent can be repeated in place of another, checked on its own since the repeat changes the total
+        for (i, metal) in metals.iter().enumerate() {
+            let mut input = defaults.to_vec();
+            let other = (i + 1) % n;
+            input[other] = metal.with_value(*defaults[i]);
+            assert_eq!(
+                DuplicateConstituent(*metal),
+                T::check_ranges_contains(&input).unwrap_err()
+            );
+        }
+
+        // Each constituent can be left out, in any order
+        for (i, metal) in metals.iter().enumerate() {
+            let mut input: Vec<_> = defaults.to_vec();
+            input.remove(i);
+            assert_eq!(
+                MissingConstituent(*metal),
+                T::check_ranges_contains(&input).unwrap_err()
+            );
+            input.reverse();
+            assert_eq!(
+                MissingConstituent(*metal),
+                T::check_ranges_contains(&input).unwrap_err()
+            );
+        }
+        assert_eq!(
+            MissingConstituent(metals[0]),
+            T::check_ranges_contains([]).unwrap_err()
+        );
+
+        // Too many entries is reported before anything else
+        let mut input = defaults.to_vec();
+        input.push(foreign.with_value(0.0));
+        assert_eq!(
+            WrongConstituentCount {
+                expected: n,
+                got: n + 1
+            },
+            T::check_ranges_contains(&input).unwrap_err()
+        );
+        assert_eq!(
+            Err(WrongConstituentCount {
+                expected: n,
+                got: n + 1
+            }),
+            AlloyData::<T>::try_new(&input, 1)
+        );
+        let mut input = defaults.to_vec();
+        input.extend_from_slice(defaults);
+        assert_eq!(
+            WrongConstituentCount {
+                expected: n,
+                got: 2 * n
+            },
+            T::check_ranges_contains(&input).unwrap_err()
+        );
+
+        // Nothing changed when setting invalid metals
+        let mut alloy = AlloyData::<T>::default();
+        let mut input = defaults.to_vec();
+        input[0] = foreign.with_value(*input[0]);
+        assert_eq!(
+            Err(UnknownConstituent(foreign)),
+            alloy.set_percentages(&input)
+        );
+        assert_eq!(AlloyData::<T>::default(), alloy);
+    }
+
+    #[test]
+    fn test_every_alloy_type() {
+        for_each_alloy!(check);
+    }
+
+    #[test]
+    fn test_missing_metal_with_full_total() {
+        // Two of three metals totalling 1.0 used to be reported as a percentage problem
+        assert_eq!(
+            Err(MissingConstituent(Metal::Bismuth)),
+            AlloyData::<BismuthBronze>::try_new([Copper(0.7), Zinc(0.3)], 1)
+        );
+        assert_eq!(
+            Err(MissingConstituent(Metal::Silver)),
+            Alloys::BlackBronze.try_new([Gold(0.16), Copper(0.84)], 1)
+        );
+    }
+
+    #[test]
+    fn test_display_and_codes() {
+        assert_eq!(
+            "Lead is not part of the alloy",
+            UnknownConstituent(Metal::Lead).to_string()
+        );
+        assert_eq!(
+            "Copper is supplied more than once",
+            DuplicateConstituent(Metal::Copper).to_string()
+        );
+        assert_eq!(
+            "Tin is missing from the alloy",
+            MissingConstituent(Metal::Tin).to_string()
+        );
+        assert_eq!(
+            "expected at most 2 metals but found 5",
+            WrongConstituentCount {
+                expected: 2,
+                got: 5
+            }
+            .to_string()
+        );
+        assert_eq!("missing_constituent", MissingConstituent(Metal::Tin).code());
+    }
+
+    #[test]
+    fn test_help() {
+        let percentages = [Copper(0.7), Zinc(0.3)];
+        let context = ErrorContext::new(Alloys::BismuthBronze, &percentages, 1);
+        let help = MissingConstituent(Metal::Bismuth).help(&context);
+        assert_eq!("A metal is missing", help.headline);
+        assert_eq!("add Bismuth between 10% and 20%", help.suggestions[0]);
+
+        let percentages = [Copper(0.7), Tin(0.3)];
+        let context = ErrorContext::new(Alloys::Brass, &percentages, 1);
+        let help = UnknownConstituent(Metal::Tin).help(&context);
+        assert_eq!("A metal is not part of the alloy", help.headline);
+        assert_eq!(vec![String::from("remove Tin")], help.suggestions);
+
+        let percentages = [Copper(0.7), Copper(0.3)];
+        let context = ErrorContext::new(Alloys::Brass, &percentages, 1);
+        let help = DuplicateConstituent(Metal::Copper).help(&context);
+        assert_eq!("combine the Copper entries into one", help.suggestions[0]);
+    }
+}
+
+#[cfg(test)]
+mod range_step_tests {
+    use crate::ConstituentRange;
+    use crate::prelude::*;
+
+    fn assert_close(expected: &[f32], actual: &[f32]) {
+        assert_eq!(expected.len(), actual.len(), "{actual:?}");
+        for (e, a) in expected.iter().zip(actual) {
+            assert!((e - a).abs() < 1e-6, "{expected:?} {actual:?}");
+        }
+    }
+
+    #[test]
+    fn test_clamp_edge_cases() {
+        let range = ConstituentRange::new(0.08_f32, 0.12);
+        assert_eq!(0.08, range.clamp(f32::NAN));
+        assert_eq!(0.12, range.clamp(f32::INFINITY));
+        assert_eq!(0.08, range.clamp(f32::NEG_INFINITY));
+        assert_eq!(0.4, ConstituentRange::new(0.4_f32, 0.4).clamp(0.9));
+        assert_eq!(10, ConstituentRange::new(8, 12).clamp(10));
+    }
+
+    #[test]
+    fn test_midpoint_and_span() {
+        let range = ConstituentRange::new(0.4_f32, 0.6);
+        assert!((range.midpoint() - 0.5).abs() < 1e-6);
+        assert!((range.span() - 0.2).abs() < 1e-6);
+        let fixed = ConstituentRange::new(0.4_f32, 0.4);
+        assert_eq!(0.4, fixed.midpoint());
+        assert_eq!(0.0, fixed.span());
+    }
+
+    #[test]
+    fn test_even_steps() {
+        let steps: Vec<f32> = ConstituentRange::new(0.08_f32, 0.12)
+            .iter_steps(0.01)
+            .collect();
+        assert_close(&[0.08, 0.09, 0.10, 0.11, 0.12], &steps);
+        assert_eq!(0.12, *steps.last().unwrap());
+    }
+
+    #[test]
+    fn test_uneven_steps_end_on_max() {
+        let steps: Vec<f32> = ConstituentRange::new(0.5_f32, 0.7)
+            .iter_steps(0.03)
+            .collect();
+        assert_close(&[0.5, 0.53, 0.56, 0.59, 0.62, 0.65, 0.68, 0.7], &steps);
+        // A step larger than the span gives just the bounds
+        let steps: Vec<f32> = ConstituentRange::new(0.4_f32, 0.6)
+            .iter_steps(0.5)
+            .collect();
+        assert_eq!(vec![0.4, 0.6], steps);
+    }
+
+    #[test]
+    fn test_degenerate_and_invalid() {
+        let fixed = ConstituentRange::new(0.4_f32, 0.4);
+        assert_eq!(vec![0.4], fixed.iter_steps(0.01).collect::<Vec<_>>());
+        let range = ConstituentRange::new(0.4_f32, 0.6);
+        for step in [0.0, -0.01, f32::NAN, f32::INFINITY] {
+            assert_eq!(0, range.iter_steps(step).count(), "{step}");
+        }
+        let inverted = ConstituentRange::new(0.6_f32, 0.4);
+        assert_eq!(0, inverted.iter_steps(0.01).count());
+    }
+
+    #[test]
+    fn test_whole_percent_steps_for_every_alloy() {
+        for kind in Alloys::iter() {
+            for range in kind.percentage_ranges() {
+                let steps: Vec<f32> = range.iter_steps(0.01).collect();
+                let whole = ((range.max - range.min) * 100.0).round() as usize;
+                assert_eq!(whole + 1, steps.len(), "{kind} {range:?}");
+                assert_eq!(range.min, steps[0]);
+                assert_eq!(range.max, *steps.last().unwrap());
+                assert!(steps.iter().all(|s| range.contains(s)));
+            }
+        }
+        let electrum = Alloys::Electrum.percentage_ranges();
+        assert_eq!(21, electrum[0].iter_steps(0.01).count());
+    }
+
+    #[test]
+    fn test_f64_steps() {
+        let steps: Vec<f64> = ConstituentRange::new(0.0_f64, 1.0)
+            .iter_steps(0.25)
+            .collect();
+        assert_eq!(vec![0.0, 0.25, 0.5, 0.75, 1.0], steps);
+    }
+}
+
+#[cfg(test)]
+mod any_alloy_tests {
+    use crate::AnyAlloy;
+    use crate::prelude::*;
+
+    fn assert_agrees(alloy: &Alloy, any: &dyn AnyAlloy) {
+        assert_eq!(alloy.name(), any.name());
+        assert_eq!(alloy.kind().percentage_ranges(), any.percentage_ranges());
+        assert_eq!(alloy.nuggets(), any.nuggets());
+        assert_eq!(alloy.percentages(), any.percentages());
+        assert_eq!(alloy.num_ingots(), any.num_ingots());
+        assert_eq!(alloy.max_ingots(), any.max_ingots());
+    }
+
+    #[test]
+    fn test_as_dyn_matches_enum() {
+        for kind in Alloys::ALL {
+            let alloy = kind.get_default();
+            assert_agrees(&alloy, alloy.as_dyn());
+        }
+    }
+
+    #[test]
+    fn test_as_dyn_mut_set_num_ingots_matches_enum() {
+        for kind in Alloys::ALL {
+            let mut expected = kind.get_default();
+            let mut actual = kind.get_default();
+            let max = expected.max_ingots();
+            for num_ingots in [0, 1, 2, max, max + 1] {
+                assert_eq!(
+                    expected.set_num_ingots(num_ingots),
+                    actual.as_dyn_mut().set_num_ingots(num_ingots),
+                    "{kind} {num_ingots}"
+                );
+                assert_eq!(expected, actual);
+            }
+        }
+    }
+
+    #[test]
+    fn test_as_dyn_mut_set_percentages_matches_enum() {
+        for kind in Alloys::ALL {
+            let mut expected = kind.get_default();
+            let mut actual = kind.get_default();
+            let mut reversed = kind.default_percentages().to_vec();
+            reversed.reverse();
+            let midpoints: Vec<BaseMetal<f32>> = kind
+                .percentage_ranges()
+                .iter()
+                .map(|r| r.metal().with_value(r.midpoint()))
+                .collect();
+            for percentages in [reversed, midpoints, vec![Lead(1.0)]] {
+                assert_eq!(
+                    expected.set_percentages(&percentages),
+                    actual.as_dyn_mut().set_percentages(&percentages),
+                    "{kind} {percentages:?}"
+                );
+                assert_eq!(expected, actual);
+                assert_agrees(&expected, actual.as_dyn());
+            }
+        }
+    }
+
+    #[test]
+    fn test_boxed_data_matches_static() {
+        let mut data = AlloyData::<BismuthBronze>::default();
+        let mut boxed: Box<dyn AnyAlloy> = Box::new(data.clone());
+        data.set_num_ingots(3).unwrap();
+        boxed.set_num_ingots(3).unwrap();
+
+        assert_eq!(BismuthBronze::name(), boxed.name());
+        assert_eq!(
+            AlloyData::<BismuthBronze>::percentage_ranges(),
+            boxed.percentage_ranges()
+        );
+        assert_eq!(data.nuggets(), boxed.nuggets());
+        assert_eq!(data.percentages(), boxed.percentages());
+        assert_eq!(data.num_ingots(), boxed.num_ingots());
+        assert_eq!(data.max_ingots(), boxed.max_ingots());
+    }
+}
+
+#[cfg(test)]
+mod define_alloy_tests {
+    use crate::AlloyError;
+    use crate::ConstituentRange;
+    use crate::prelude::*;
+
+    crate::define_alloy! {
+        /// Made-up alloy used only to exercise the macro
+        pub struct Experimental = "Experimental";
+        Copper: 0.60..=0.80, default 0.70 with 14 nuggets;
+        Gold: 0.10..=0.20, default 0.15 with 3 nuggets;
+        Silver: 0.10..=0.20, default 0.15 with 3 nuggets;
+    }
+
+    #[test]
+    fn test_generated_constants() {
+        assert_eq!("Experimental", Experimental::name());
+        assert_eq!(3, Experimental::N_CONSTITUENTS);
+        assert_eq!(
+            &[Metal::Copper, Metal::Gold, Metal::Silver],
+            Experimental::constituent_metals()
+        );
+        assert_eq!(
+            &[
+                Copper(ConstituentRange::new(0.60, 0.80)),
+                Gold(ConstituentRange::new(0.10, 0.20)),
+                Silver(ConstituentRange::new(0.10, 0.20)),
+            ],
+            Experimental::percentage_ranges()
+        );
+        assert_eq!(
+            &[Copper(0.70), Gold(0.15), Silver(0.15)],
+            Experimental::default_percentages()
+        );
+    }
+
+    #[test]
+    fn test_default_matches_default_percentages() {
+        let alloy = AlloyData::<Experimental>::default();
+        assert_eq!(&[Copper(14), Gold(3), Silver(3)], alloy.nuggets());
+        assert_eq!(1, alloy.num_ingots());
+
+        let recalculated =
+            AlloyData::<Experimental>::try_new(Experimental::default_percentages(), 1).unwrap();
+        assert_eq!(alloy, recalculated);
+    }
+
+    #[test]
+    fn test_generated_alloy_uses_shared_machinery() {
+        let mut alloy =
+            AlloyData::<Experimental>::try_new([Silver(0.2), Copper(0.6), Gold(0.2)], 5).unwrap();
+        assert_eq!(&[Copper(0.6), Gold(0.2), Silver(0.2)], alloy.percentages());
+        assert_eq!(&[Copper(60), Gold(20), Silver(20)], alloy.nuggets());
+
+        assert_eq!(
+            Err(AlloyError::MissingConstituent(Metal::Silver)),
+            alloy.set_percentages([Copper(0.8), Gold(0.2)])
+        );
+        assert_eq!(
+            Err(AlloyError::UnknownConstituent(Metal::Tin)),
+            alloy.set_percentages([Copper(0.7), Gold(0.15), Tin(0.15)])
+        );
+        alloy.set_num_ingots(alloy.max_ingots()).unwrap();
+        let total: i32 = alloy.nuggets().iter().map(|n| **n).sum();
+        assert_eq!(alloy.max_ingots() * 20, total);
+    }
+
+    #[test]
+    fn test_ported_alloys_keep_their_definitions() {
+        assert_eq!(&[Copper(18), Tin(2)], TinBronze::default().nuggets());
+        assert_eq!(
+            &[Copper(0.84), Gold(0.08), Silver(0.08)],
+            BlackBronze::default_percentages()
+        );
+        assert_eq!(
+            &[
+                Lead(ConstituentRange::new(0.88, 0.92)),
+                Copper(ConstituentRange::new(0.08, 0.12)),
+            ],
+            Molybdochalkos::percentage_ranges()
+        );
+        assert_eq!("Cupronickel", Cupronickel::name());
+    }
+}
+
+#[cfg(test)]
+mod f64_percentage_tests {
+    use crate::AlloyError;
+    use crate::prelude::*;
+
+    /// Every whole percent mix of the alloy's first two constituents with the rest at their defaults, as basis points
+    fn whole_percent_mixes<T: AlloyType>() -> Vec<Vec<BaseMetal<i64>>> {
+        let bp = |value: f32| (f64::from(value) * 10_000.0).round() as i64;
+        let ranges = T::percentage_ranges();
+        let defaults: Vec<BaseMetal<i64>> = T::default_percentages()
+            .iter()
+            .map(|p| p.update_inner_value(bp(**p)))
+            .collect();
+        let rest: i64 = defaults[2..].iter().map(|p| **p).sum();
+        (bp(ranges[0].min)..=bp(ranges[0].max))
+            .step_by(100)
+            .map(|first| {
+                let mut mix = defaults.clone();
+                mix[0] = mix[0].update_inner_value(first);
+                mix[1] = mix[1].update_inner_value(10_000 - rest - first);
+                mix
+            })
+            .collect()
+    }
+
+    fn scaled<F>(mix: &[BaseMetal<i64>], scale: impl Fn(i64) -> F) -> Vec<BaseMetal<F>> {
+        mix.iter()
+            .map(|p| p.update_inner_value(scale(**p)))
+            .collect()
+    }
+
+    fn check_mirrors_f32<T: AlloyType + Default + std::fmt::Debug>() {
+        for basis_points in whole_percent_mixes::<T>() {
+            let mix = scaled(&basis_points, |bp| bp as f32 / 10_000.0);
+            let wide = scaled(&basis_points, |bp| bp as f64 / 10_000.0);
+            for num_ingots in [1, 3, 20, 21, 40] {
+                let expected = AlloyData::<T>::try_new(&mix, num_ingots);
+                let actual = AlloyData::<T>::try_new_f64(&wide, num_ingots);
+                match (&expected, &actual) {
+                    (Ok(expected), Ok(actual)) => {
+                        assert_eq!(expected, actual, "{mix:?} {num_ingots}");
+                        assert_eq!(expected.nuggets(), actual.nuggets());
+                        assert_eq!(expected.max_ingots(), actual.max_ingots());
+                        assert_eq!(&wide[..], actual.percentages_f64());
+                    }
+                    _ => assert_eq!(
+                        expected.as_ref().err(),
+                        actual.as_ref().err(),
+                        "{mix:?} {num_ingots}"
+                    ),
+                }
+            }
+
+            let mut expected = AlloyData::<T>::default();
+            let mut actual = AlloyData::<T>::default();
+            assert_eq!(
+                expected.set_percentages(&mix),
+                actual.set_percentages_f64(&wide)
+            );
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    fn test_f64_mirrors_f32() {
+        for_each_alloy!(check_mirrors_f32);
+    }
+
+    fn check_ranges<T: AlloyType>() {
+        let ranges = AlloyData::<T>::f64_percentage_ranges();
+        assert_eq!(T::percentage_ranges().len(), ranges.len());
+        for (narrow, wide) in T::percentage_ranges().iter().zip(&ranges) {
+            assert_eq!(narrow.metal(), wide.metal());
+            assert_eq!(narrow.min, wide.min as f32);
+            assert_eq!(narrow.max, wide.max as f32);
+        }
+    }
+
+    #[test]
+    fn test_f64_ranges_match_f32_ranges() {
+        for_each_alloy!(check_ranges);
+    }
+
+    #[test]
+    fn test_f64_errors_match_f32() {
+        type Data = AlloyData<BismuthBronze>;
+        assert_eq!(
+            Err(AlloyError::MissingConstituent(Metal::Bismuth)),
+            Data::check_valid_f64_percentages([Copper(0.7), Zinc(0.3)])
+        );
+        assert_eq!(
+            Err(AlloyError::UnknownConstituent(Metal::Tin)),
+            Data::check_valid_f64_percentages([Copper(0.6), Zinc(0.2), Tin(0.2)])
+        );
+        assert_eq!(
+            Err(AlloyError::DuplicateConstituent(Metal::Zinc)),
+            Data::check_valid_f64_percentages([Zinc(0.2), Copper(0.6), Zinc(0.2)])
+        );
+        assert_eq!(
+            Err(AlloyError::InvalidPercentages),
+            Data::check_valid_f64_percentages([Copper(0.75), Zinc(0.15), Bismuth(0.1)])
+        );
+        assert_eq!(
+            Err(AlloyError::InvalidPercentages),
+            Data::check_valid_f64_percentages([Copper(f64::NAN), Zinc(0.2), Bismuth(0.2)])
+        );
+        assert_eq!(
+            Err(AlloyError::TooFewIngots),
+            Data::try_new_f64([Copper(0.6), Zinc(0.2), Bismuth(0.2)], 0)
+        );
+    }
+
+    #[test]
+    fn test_total_tolerance_is_f64_rounding() {
+        type Data = AlloyData<TinBronze>;
+        // Off by f32 rounding, which only the f32 methods allow
+        assert!(Data::check_valid_percentages([Copper(0.9), Tin(0.1000002)]).is_ok());
+        assert_eq!(
+            Err(AlloyError::InvalidPercentages),
+            Data::check_valid_f64_percentages([Copper(0.9), Tin(0.1000002)])
+        );
+        assert_eq!(
+            Err(AlloyError::InvalidPercentages),
+            Data::check_valid_f64_percentages([Copper(0.9), Tin(0.105)])
+        );
+        // f64 rounding in the total is accepted
+        assert_ne!(1.0, 0.6 + 0.3 + 0.1);
+        assert!(
+            AlloyData::<BismuthBronze>::check_valid_f64_percentages([
+                Copper(0.6),
+                Zinc(0.3),
+                Bismuth(0.1)
+            ])
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_range_bounds_allow_f64_rounding_only() {
+        type Data = AlloyData<TinBronze>;
+        let below = 0.88 - f64::EPSILON;
+        assert!(Data::check_valid_f64_percentages([Copper(below), Tin(1.0 - below)]).is_ok());
+        assert_eq!(
+            Err(AlloyError::InvalidPercentages),
+            Data::check_valid_f64_percentages([Copper(0.88 - 1e-9), Tin(0.12 + 1e-9)])
+        );
+    }
+
+    #[test]
+    fn test_f64_accepts_rounding_from_many_adjustments() {
+        let step = |k: u32| 0.0001 * f64::from(k % 7 + 1);
+        let (mut copper, mut tin) = (0.88_f64, 0.12_f64);
+        for k in 0..301 {
+            copper += step(k);
+            tin -= step(k);
+        }
+        for k in 0..301 {
+            copper -= step(k * 37 % 301);
+            tin += step(k * 37 % 301);
+        }
+        // Back where it started apart from rounding, which leaves it just under the minimum
+        assert!(copper < 0.88 && 0.88 - copper < 1e-15);
+
+        let mut alloy = AlloyData::<TinBronze>::default();
+        alloy
+            .set_percentages_f64([Copper(copper), Tin(tin)])
+            .expect("should allow rounding error");
+        assert_eq!(&[Copper(copper), Tin(tin)], alloy.percentages_f64());
+        assert_eq!(&[Copper(0.88), Tin(0.12)], alloy.percentages());
+    }
+
+    fn check_round_trip<T: AlloyType>() {
+        for mix in whole_percent_mixes::<T>() {
+            // Mixes that leave the second constituent out of range have nothing to round trip
+            let Ok(mut alloy) = AlloyData::<T>::try_new(scaled(&mix, |bp| bp as f32 / 10_000.0), 1)
+            else {
+                continue;
+            };
+            let wide = alloy.percentages_f64().to_vec();
+            alloy.set_percentages_f64(&wide).unwrap();
+            assert_eq!(wide, alloy.percentages_f64());
+        }
+    }
+
+    #[test]
+    fn test_f32_built_alloys_round_trip_through_f64() {
+        for_each_alloy!(check_round_trip);
+
+        // Totals short of 1.0 by f32 rounding are made up by the last constituent when widened
+        let mut alloy = AlloyData::<TinBronze>::try_new([Copper(0.9199998), Tin(0.08)], 1).unwrap();
+        let wide = alloy.percentages_f64().to_vec();
+        assert_eq!([Copper(0.9199998), Tin(0.0800002)], *wide);
+        assert_eq!(Ok(()), alloy.set_percentages_f64(&wide));
+        assert_eq!(
+            Ok(alloy.clone()),
+            AlloyData::<TinBronze>::try_new_f64(&wide, 1)
+        );
+    }
+
+    #[test]
+    fn test_f32_setters_refresh_f64_copy() {
+        let mut alloy = AlloyData::<TinBronze>::try_new_f64([Copper(0.9), Tin(0.1)], 2).unwrap();
+        assert_eq!(&[Copper(0.9), Tin(0.1)], alloy.percentages_f64());
+
+        alloy.set_percentages([Copper(0.92), Tin(0.08)]).unwrap();
+        assert_eq!(&[Copper(0.92), Tin(0.08)], alloy.percentages_f64());
+        let round_trip = alloy.percentages_f64().to_vec();
+        alloy.set_percentages_f64(round_trip).unwrap();
+
+        alloy.set_percentages_f64([Copper(0.9), Tin(0.1)]).unwrap();
+        alloy.set_num_ingots(3).unwrap();
+        assert_eq!(&[Copper(0.9), Tin(0.1)], alloy.percentages_f64());
+
+        alloy.reset_to_default();
+        assert_eq!(
+            AlloyData::<TinBronze>::default().percentages_f64(),
+            alloy.percentages_f64()
+        );
+    }
+}
@@ -0,0 +1,29 @@
+//! Helpers shared by the unit tests in `src/tests.rs` and the integration tests, which include this file by path
+
+/// Runs a generic check once for every built-in alloy type, which must be in scope from the prelude along with `Alloys` \
+/// `for_each_alloy!(check)` calls `check::<T>()` and `for_each_alloy!(check(Alloys))` calls `check::<T>(Alloys::T)`.
+macro_rules! for_each_alloy {
+    ($check:ident) => {
+        for_each_alloy!(@each $check ())
+    };
+    ($check:ident(Alloys)) => {
+        for_each_alloy!(@each $check (Alloys))
+    };
+    (@each $check:ident $args:tt) => {
+        for_each_alloy!(@call $check $args TinBronze);
+        for_each_alloy!(@call $check $args BismuthBronze);
+        for_each_alloy!(@call $check $args BlackBronze);
+        for_each_alloy!(@call $check $args Brass);
+        for_each_alloy!(@call $check $args Molybdochalkos);
+        for_each_alloy!(@call $check $args LeadSolder);
+        for_each_alloy!(@call $check $args SilverSolder);
+        for_each_alloy!(@call $check $args Electrum);
+        for_each_alloy!(@call $check $args Cupronickel);
+    };
+    (@call $check:ident () $t:ident) => {
+        $check::<$t>()
+    };
+    (@call $check:ident (Alloys) $t:ident) => {
+        $check::<$t>(Alloys::$t)
+    };
+}
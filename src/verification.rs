@@ -0,0 +1,149 @@
+//! # Verification
+//! Cases for checking the calculator against the in-game crucible by hand.
+//!
+//! Each case is a recipe with the nuggets and max ingots the library expects, so it can be printed, tested in game,
+//! and later pinned as a regression fixture once the game agrees.
+use crate::format::FormatOptions;
+use crate::prelude::*;
+use std::fmt::{Display, Formatter};
+
+/// Why a recipe was picked for verification
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CaseKind {
+    /// Every constituent but one pinned to the edge of its range
+    Corner,
+    /// The alloy's default percentages
+    Default,
+    /// The default percentages at the most ingots that fit in the crucible
+    MaxBatch,
+}
+
+impl CaseKind {
+    /// Gets the lowercase label used in the checklist and CSV output
+    pub fn label(&self) -> &'static str {
+        match self {
+            CaseKind::Corner => "corner",
+            CaseKind::Default => "default",
+            CaseKind::MaxBatch => "max batch",
+        }
+    }
+}
+
+/// A recipe to try at a crucible along with the results the library expects
+#[derive(Clone, PartialEq, Debug)]
+pub struct VerificationCase {
+    pub alloy: Alloys,
+    pub kind: CaseKind,
+    pub percentages: Box<[BaseMetal<f32>]>,
+    pub num_ingots: i32,
+    pub nuggets: Box<[BaseMetal<i32>]>,
+    pub max_ingots: i32,
+}
+
+/// Header row matching [`VerificationCase::to_csv_row`]
+pub const CSV_HEADER: &str = "alloy,case,percentages,ingots,nuggets,max_ingots";
+
+/// Lists the verification cases for every alloy: each corner mix and the defaults at one ingot, then the defaults at the max batch
+/// ### Example
+/// ```rust
+/// use vs_alloy_calculator::prelude::*;
+/// use vs_alloy_calculator::verification::{self, CaseKind};
+///
+/// let cases = verification::cases();
+/// let tin_bronze: Vec<_> = cases.iter().filter(|c| c.alloy == Alloys::TinBronze).collect();
+///
+/// assert_eq!(4, tin_bronze.len());
+/// assert_eq!(CaseKind::MaxBatch, tin_bronze[3].kind);
+/// assert_eq!(&[Copper(368), Tin(32)], &tin_bronze[3].nuggets[..]);
+/// ```
+pub fn cases() -> Vec<VerificationCase> {
+    Alloys::ALL
+        .into_iter()
+        .flat_map(|alloy| {
+            let defaults = alloy.default_percentages();
+            let max_ingots = alloy
+                .try_new(defaults, 1)
+                .map_or(1, |default| default.max_ingots());
+            alloy
+                .boundary_mixes()
+                .into_iter()
+                .map(|mix| (CaseKind::Corner, mix, 1))
+                .chain([
+                    (CaseKind::Default, Box::from(defaults), 1),
+                    (CaseKind::MaxBatch, Box::from(defaults), max_ingots),
+                ])
+                .filter_map(move |(kind, percentages, num_ingots)| {
+                    let recipe = alloy.try_new(&percentages, num_ingots).ok()?;
+                    Some(VerificationCase {
+                        alloy,
+                        kind,
+                        nuggets: recipe.nuggets().into(),
+                        max_ingots: recipe.max_ingots(),
+                        percentages,
+                        num_ingots,
+                    })
+                })
+        })
+        .collect()
+}
+
+impl VerificationCase {
+    /// Writes the case as a CSV row, with constituents separated by spaces within a field
+    /// ### Example
+    /// ```rust
+    /// use vs_alloy_calculator::verification;
+    ///
+    /// let case = &verification::cases()[0];
+    ///
+    /// assert_eq!("Tin Bronze,corner,Copper 92% Tin 8%,1,Copper 18 Tin 2,20", case.to_csv_row());
+    /// ```
+    pub fn to_csv_row(&self) -> String {
+        let options = FormatOptions::default();
+        let percentages: Vec<String> = self
+            .percentages
+            .iter()
+            .map(|p| format!("{} {}", p.name(), options.percent(**p)))
+            .collect();
+        let nuggets: Vec<String> = self
+            .nuggets
+            .iter()
+            .map(|n| format!("{} {}", n.name(), **n))
+            .collect();
+        format!(
+            "{},{},{},{},{},{}",
+            self.alloy.name(),
+            self.kind.label(),
+            percentages.join(" "),
+            self.num_ingots,
+            nuggets.join(" "),
+            self.max_ingots
+        )
+    }
+}
+
+impl Display for VerificationCase {
+    /// Writes the case as an unchecked checklist item
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let options = FormatOptions::default();
+        let percentages: Vec<String> = self
+            .percentages
+            .iter()
+            .map(|p| format!("{} {}", options.percent(**p), p.name()))
+            .collect();
+        let nuggets: Vec<String> = self
+            .nuggets
+            .iter()
+            .map(|n| format!("{} {}", **n, n.name()))
+            .collect();
+        write!(
+            f,
+            "[ ] {} ({}): {} for {} -> {} nuggets, max {}",
+            self.alloy.name(),
+            self.kind.label(),
+            percentages.join(", "),
+            options.ingots(self.num_ingots),
+            nuggets.join(", "),
+            options.ingots(self.max_ingots)
+        )
+    }
+}
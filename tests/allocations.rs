@@ -0,0 +1,111 @@
+//! Creating, updating, and validating alloys without touching the heap
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use vs_alloy_calculator::normalize_sum;
+use vs_alloy_calculator::prelude::*;
+
+#[macro_use]
+#[path = "../src/tests/common.rs"]
+mod common;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Counts the allocations made on this thread while running `f`
+fn allocations<R>(f: impl FnOnce() -> R) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    std::hint::black_box(f());
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+fn check<T: AlloyType + Default + std::fmt::Debug>() {
+    let defaults = T::default_percentages();
+    let mut reversed = [defaults[0]; 3];
+    for (slot, p) in reversed.iter_mut().zip(defaults.iter().rev()) {
+        *slot = *p;
+    }
+    let reversed = &reversed[..defaults.len()];
+
+    assert_eq!(0, allocations(AlloyData::<T>::default), "{}", T::name());
+    assert_eq!(
+        0,
+        allocations(|| AlloyData::<T>::try_new(reversed, 3).unwrap()),
+        "{}",
+        T::name()
+    );
+
+    let mut alloy = AlloyData::<T>::default();
+    assert_eq!(0, allocations(|| alloy.set_percentages(reversed).unwrap()));
+    assert_eq!(0, allocations(|| alloy.set_num_ingots(2).unwrap()));
+    let wide: Vec<BaseMetal<f64>> = alloy.percentages_f64().iter().rev().copied().collect();
+    assert_eq!(0, allocations(|| alloy.set_percentages_f64(&wide).unwrap()));
+    assert_eq!(
+        0,
+        allocations(|| AlloyData::<T>::try_new_f64(&wide, 2).unwrap())
+    );
+    assert_eq!(
+        0,
+        allocations(|| alloy.set_percentages([Lead(1.0)]).unwrap_err())
+    );
+    assert_eq!(0, allocations(|| alloy.set_num_ingots(0).unwrap_err()));
+    assert_eq!(0, allocations(|| T::check_percentages_total(reversed)));
+    assert_eq!(0, allocations(|| T::check_valid_percentages(reversed)));
+    assert_eq!(0, allocations(|| T::check_ranges_contains(reversed)));
+    assert_eq!(
+        0,
+        allocations(|| AlloyData::<T>::check_valid_percentages(reversed))
+    );
+    assert_eq!(
+        0,
+        allocations(|| AlloyData::<T>::check_valid_percentages([Lead(1.0)]).unwrap_err())
+    );
+    assert_eq!(0, allocations(|| AlloyData::<T>::max_ingots_for(reversed)));
+    assert_eq!(
+        0,
+        allocations(|| AlloyData::<T>::nuggets_for(reversed, 2).unwrap())
+    );
+    assert_eq!(0, allocations(|| normalize_sum::<T>(reversed).unwrap()));
+    assert_eq!(
+        0,
+        allocations(|| AlloyData::<T>::try_new_normalized(reversed, 2))
+    );
+}
+
+#[test]
+fn counter_sees_allocations() {
+    assert_eq!(1, allocations(|| Box::new(1)));
+}
+
+#[test]
+fn construction_and_updates_do_not_allocate() {
+    for_each_alloy!(check);
+}
+
+#[test]
+fn runtime_nuggets_do_not_allocate() {
+    for kind in Alloys::iter() {
+        let percentages = kind.default_percentages();
+        assert_eq!(
+            0,
+            allocations(|| kind.nuggets_for(percentages, 2).unwrap()),
+            "{kind}"
+        );
+    }
+}
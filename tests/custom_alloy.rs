@@ -0,0 +1,206 @@
+//! Alloys declared outside the crate through the `unstable-custom-alloys` feature
+#![cfg(feature = "unstable-custom-alloys")]
+use vs_alloy_calculator::alloy_types::AlloyTypeDefinition;
+use vs_alloy_calculator::definition::{AlloyDefinitionError, validate_alloy_type};
+use vs_alloy_calculator::prelude::*;
+use vs_alloy_calculator::{AlloyError, ConstituentRange, define_alloy};
+
+define_alloy! {
+    /// Two constituent alloy from a hypothetical mod
+    pub struct RoseGold = "Rose Gold";
+    Gold: 0.70..=0.80, default 0.75 with 15 nuggets;
+    Copper: 0.20..=0.30, default 0.25 with 5 nuggets;
+}
+
+define_alloy! {
+    /// Three constituent alloy from a hypothetical mod
+    pub struct Pewter = "Pewter";
+    Tin: 0.80..=0.90, default 0.85 with 17 nuggets;
+    Copper: 0.05..=0.10, default 0.10 with 2 nuggets;
+    Bismuth: 0.05..=0.10, default 0.05 with 1 nuggets;
+}
+
+/// Written by hand instead of with the macro
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Billon([BaseMetal<i32>; 2]);
+
+impl Default for Billon {
+    fn default() -> Self {
+        // Nuggets for `DEFAULT_INGOTS` of `DEFAULT_PERCENTAGES`
+        Self([Silver(12), Copper(28)])
+    }
+}
+
+impl AlloyType for Billon {
+    fn nuggets(&self) -> &[BaseMetal<i32>] {
+        &self.0
+    }
+}
+
+impl AsRef<[BaseMetal<i32>]> for Billon {
+    fn as_ref(&self) -> &[BaseMetal<i32>] {
+        &self.0
+    }
+}
+
+impl AlloyTypeDefinition for Billon {
+    const NAME: &str = "Billon";
+    const RANGES: &[BaseMetal<ConstituentRange<f32>>] = &[
+        Silver(ConstituentRange::new(0.20, 0.40)),
+        Copper(ConstituentRange::new(0.60, 0.80)),
+    ];
+    const METALS: &[Metal] = &[Metal::Silver, Metal::Copper];
+    const DEFAULT_PERCENTAGES: &[BaseMetal<f32>] = &[Silver(0.30), Copper(0.70)];
+    const DEFAULT_INGOTS: i32 = 2;
+
+    type Percentages<F: Copy + std::fmt::Debug> = [BaseMetal<F>; 2];
+
+    fn percentages_from_fn<F: Copy + std::fmt::Debug>(
+        mut value: impl FnMut(usize) -> F,
+    ) -> Self::Percentages<F> {
+        [Silver(value(0)), Copper(value(1))]
+    }
+
+    fn try_from_amounts(value: &[i32]) -> Result<Self, AlloyError> {
+        match *value {
+            [silver, copper, ..] => Ok(Self([Silver(silver), Copper(copper)])),
+            _ => Err(AlloyError::InvalidConstituentAmounts),
+        }
+    }
+}
+
+define_alloy! {
+    /// Nuggets that were not updated when the default percentages changed
+    pub struct Drifted = "Drifted";
+    Gold: 0.70..=0.80, default 0.70 with 15 nuggets;
+    Copper: 0.20..=0.30, default 0.30 with 5 nuggets;
+}
+
+define_alloy! {
+    /// Default percentage outside of its own range
+    pub struct Lopsided = "Lopsided";
+    Gold: 0.70..=0.80, default 0.85 with 17 nuggets;
+    Copper: 0.20..=0.30, default 0.15 with 3 nuggets;
+}
+
+#[test]
+fn definitions_are_consistent() {
+    assert_eq!(Ok(()), validate_alloy_type::<RoseGold>());
+    assert_eq!(Ok(()), validate_alloy_type::<Pewter>());
+    assert_eq!(Ok(()), validate_alloy_type::<Billon>());
+}
+
+#[test]
+fn inconsistent_definitions_are_reported() {
+    assert_eq!(
+        Err(AlloyDefinitionError::DefaultNuggetsMismatch),
+        validate_alloy_type::<Drifted>()
+    );
+    assert_eq!(
+        Err(AlloyDefinitionError::DefaultOutOfRange(Metal::Gold)),
+        validate_alloy_type::<Lopsided>()
+    );
+}
+
+#[test]
+fn default_does_not_check_definition() {
+    let default = AlloyData::<Drifted>::default();
+    assert_eq!(&[Gold(15), Copper(5)], default.nuggets());
+}
+
+#[test]
+fn bad_range_is_an_error() {
+    let error = AlloyError::InvalidDefinition(AlloyDefinitionError::DefaultOutOfRange(Metal::Gold));
+    assert_eq!(
+        Err(error),
+        AlloyData::<Lopsided>::try_new([Gold(0.75), Copper(0.25)], 1)
+    );
+    assert_eq!(
+        Err(error),
+        AlloyData::<Lopsided>::try_new_f64([Gold(0.75), Copper(0.25)], 1)
+    );
+    assert_eq!("invalid_definition", error.code());
+    // The cached result is reused on later uses
+    assert_eq!(
+        Err(AlloyDefinitionError::DefaultOutOfRange(Metal::Gold)),
+        Lopsided::checked_definition()
+    );
+    assert_eq!(Ok(()), RoseGold::checked_definition());
+    assert_eq!(Ok(()), Billon::checked_definition());
+}
+
+#[test]
+fn two_constituent_alloy() {
+    let default = AlloyData::<RoseGold>::default();
+    assert_eq!("Rose Gold", RoseGold::name());
+    assert_eq!(&[Gold(15), Copper(5)], default.nuggets());
+
+    let mut alloy = AlloyData::<RoseGold>::try_new([Copper(0.25), Gold(0.75)], 4).unwrap();
+    assert_eq!(default.percentages(), alloy.percentages());
+    assert_eq!(&[Gold(60), Copper(20)], alloy.nuggets());
+
+    alloy.set_percentages([Gold(0.8), Copper(0.2)]).unwrap();
+    assert_eq!(&[Gold(0.8), Copper(0.2)], alloy.percentages());
+    assert_eq!(&[Gold(64), Copper(16)], alloy.nuggets());
+
+    assert_eq!(
+        Err(AlloyError::InvalidPercentages),
+        alloy.set_percentages([Gold(0.9), Copper(0.1)])
+    );
+    assert_eq!(
+        Err(AlloyError::UnknownConstituent(Metal::Tin)),
+        alloy.set_percentages([Gold(0.8), Tin(0.2)])
+    );
+    assert_eq!(&[Gold(0.8), Copper(0.2)], alloy.percentages());
+
+    let max_ingots = alloy.max_ingots();
+    assert!(max_ingots > 4);
+    assert_eq!(
+        Err(AlloyError::TooManyIngots),
+        alloy.set_num_ingots(max_ingots + 1)
+    );
+    alloy.set_num_ingots(max_ingots).unwrap();
+    let total: i32 = alloy.nuggets().iter().map(|n| **n).sum();
+    assert_eq!(max_ingots * 20, total);
+}
+
+#[test]
+fn three_constituent_alloy() {
+    let default = AlloyData::<Pewter>::default();
+    assert_eq!(&[Tin(17), Copper(2), Bismuth(1)], default.nuggets());
+
+    let mut alloy =
+        AlloyData::<Pewter>::try_new([Bismuth(0.1), Tin(0.8), Copper(0.1)], 10).unwrap();
+    assert_eq!(&[Tin(0.8), Copper(0.1), Bismuth(0.1)], alloy.percentages());
+    assert_eq!(&[Tin(160), Copper(20), Bismuth(20)], alloy.nuggets());
+
+    alloy
+        .set_percentages([Tin(0.9), Copper(0.05), Bismuth(0.05)])
+        .unwrap();
+    assert_eq!(&[Tin(180), Copper(10), Bismuth(10)], alloy.nuggets());
+    assert_eq!(
+        Err(AlloyError::MissingConstituent(Metal::Bismuth)),
+        alloy.set_percentages([Tin(0.9), Copper(0.1)])
+    );
+
+    assert_eq!(
+        AlloyData::<Pewter>::max_ingots_for([Tin(0.9), Copper(0.05), Bismuth(0.05)]),
+        Ok(alloy.max_ingots())
+    );
+    assert_eq!(
+        Err(AlloyError::InvalidPercentages),
+        AlloyData::<Pewter>::try_new([Tin(0.7), Copper(0.2), Bismuth(0.1)], 1)
+    );
+}
+
+#[test]
+fn hand_written_alloy() {
+    let default = AlloyData::<Billon>::default();
+    assert_eq!(2, default.num_ingots());
+    assert_eq!(&[Silver(12), Copper(28)], default.nuggets());
+
+    let mut alloy = AlloyData::<Billon>::try_new([Silver(0.4), Copper(0.6)], 1).unwrap();
+    assert_eq!(&[Silver(8), Copper(12)], alloy.nuggets());
+    alloy.set_num_ingots(3).unwrap();
+    assert_eq!(&[Silver(24), Copper(36)], alloy.nuggets());
+}